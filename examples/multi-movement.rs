@@ -47,7 +47,7 @@ fn main() {
                 .all(|b| b.pos.x % isize::try_from(view.width).expect("Wrapped usize") == 0)
             {
                 thread::sleep(Duration::from_secs(2));
-            };
+            }
         },
         200.0
     );
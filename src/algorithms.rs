@@ -0,0 +1,19 @@
+//! General purpose algorithms for games, such as pathfinding and decision making for game AI
+//! - [`dijkstra_map()`] builds a [`DijkstraMap`] of distances to a set of goals, for approach and flee behaviours
+//! - [`NavMesh`] finds smooth paths over walkable polygons for free-moving agents
+//! - [`steering`] holds steering behaviours for smooth movement, such as seeking, arriving, wandering and flocking
+//! - [`StateMachine`] and [`BehaviourTree`] give structure to AI decisions
+
+mod behaviour_tree;
+pub use behaviour_tree::{ActionFn, BehaviourTree, Node, Status};
+
+mod dijkstra_map;
+pub use dijkstra_map::{dijkstra_map, DijkstraMap};
+
+mod nav_mesh;
+pub use nav_mesh::NavMesh;
+
+mod state_machine;
+pub use state_machine::StateMachine;
+
+pub mod steering;
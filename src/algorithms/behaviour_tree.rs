@@ -0,0 +1,201 @@
+use std::{fmt, time::Duration};
+
+/// The result of ticking a [`Node`] of a [`BehaviourTree`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The node finished successfully
+    Success,
+    /// The node couldn't do what it was trying to do
+    Failure,
+    /// The node is still working and should be ticked again next frame
+    Running,
+}
+
+impl From<bool> for Status {
+    fn from(value: bool) -> Self {
+        if value {
+            Self::Success
+        } else {
+            Self::Failure
+        }
+    }
+}
+
+/// The function run by a [`Node::Action`]
+pub type ActionFn<C> = dyn FnMut(&mut C, Duration) -> Status;
+
+/// A node of a [`BehaviourTree`], acting on a context of type `C` such as an enemy's state
+pub enum Node<C> {
+    /// Ticks its children in order until one doesn't succeed, returning that child's status. Succeeds if every child succeeds
+    Sequence(Vec<Self>),
+    /// Ticks its children in order until one doesn't fail, returning that child's status. Fails if every child fails
+    Selector(Vec<Self>),
+    /// Succeeds if the check returns true, and fails otherwise
+    Condition(Box<dyn Fn(&C) -> bool>),
+    /// Does something to the context, returning whether it succeeded, failed or is still running. It's passed the time since the last tick
+    Action(Box<ActionFn<C>>),
+    /// Swaps its child's success and failure
+    Invert(Box<Self>),
+}
+
+impl<C> Node<C> {
+    /// Create a [`Node::Sequence`]
+    #[must_use]
+    pub fn sequence(children: impl IntoIterator<Item = Self>) -> Self {
+        Self::Sequence(children.into_iter().collect())
+    }
+
+    /// Create a [`Node::Selector`]
+    #[must_use]
+    pub fn selector(children: impl IntoIterator<Item = Self>) -> Self {
+        Self::Selector(children.into_iter().collect())
+    }
+
+    /// Create a [`Node::Condition`]
+    #[must_use]
+    pub fn condition(check: impl Fn(&C) -> bool + 'static) -> Self {
+        Self::Condition(Box::new(check))
+    }
+
+    /// Create a [`Node::Action`]
+    #[must_use]
+    pub fn action(action: impl FnMut(&mut C, Duration) -> Status + 'static) -> Self {
+        Self::Action(Box::new(action))
+    }
+
+    /// Create a [`Node::Invert`]
+    #[must_use]
+    pub fn invert(child: Self) -> Self {
+        Self::Invert(Box::new(child))
+    }
+
+    /// Tick the node and return its status
+    pub fn tick(&mut self, context: &mut C, delta: Duration) -> Status {
+        match self {
+            Self::Sequence(children) => children
+                .iter_mut()
+                .map(|child| child.tick(context, delta))
+                .find(|status| *status != Status::Success)
+                .unwrap_or(Status::Success),
+            Self::Selector(children) => children
+                .iter_mut()
+                .map(|child| child.tick(context, delta))
+                .find(|status| *status != Status::Failure)
+                .unwrap_or(Status::Failure),
+            Self::Condition(check) => check(context).into(),
+            Self::Action(action) => action(context, delta),
+            Self::Invert(child) => match child.tick(context, delta) {
+                Status::Success => Status::Failure,
+                Status::Failure => Status::Success,
+                Status::Running => Status::Running,
+            },
+        }
+    }
+}
+
+impl<C> fmt::Debug for Node<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sequence(children) => f.debug_tuple("Sequence").field(children).finish(),
+            Self::Selector(children) => f.debug_tuple("Selector").field(children).finish(),
+            Self::Condition(_) => f.write_str("Condition"),
+            Self::Action(_) => f.write_str("Action"),
+            Self::Invert(child) => f.debug_tuple("Invert").field(child).finish(),
+        }
+    }
+}
+
+/// A behaviour tree decides what an AI should do by ticking a tree of [`Node`]s every frame: selectors try alternatives in order of priority, sequences run steps in order, conditions check the world and actions change it. The whole tree is ticked from the root every frame, so higher priority behaviours take over as soon as their conditions are met
+/// ```
+/// use gemini_engine::algorithms::{BehaviourTree, Node, Status};
+/// use std::time::Duration;
+///
+/// struct Enemy { health: u32, distance_to_player: u32 }
+///
+/// let mut tree = BehaviourTree::new(Node::selector([
+///     // Run away when hurt
+///     Node::sequence([
+///         Node::condition(|enemy: &Enemy| enemy.health < 20),
+///         Node::action(|enemy: &mut Enemy, _| { enemy.distance_to_player += 1; Status::Running }),
+///     ]),
+///     // Otherwise chase the player
+///     Node::action(|enemy: &mut Enemy, _| {
+///         enemy.distance_to_player = enemy.distance_to_player.saturating_sub(1);
+///         (enemy.distance_to_player == 0).then_some(Status::Success).unwrap_or(Status::Running)
+///     }),
+/// ]));
+///
+/// let mut enemy = Enemy { health: 100, distance_to_player: 5 };
+/// tree.tick(&mut enemy, Duration::from_millis(33));
+/// assert_eq!(enemy.distance_to_player, 4);
+///
+/// enemy.health = 10;
+/// tree.tick(&mut enemy, Duration::from_millis(33));
+/// assert_eq!(enemy.distance_to_player, 5);
+/// ```
+#[derive(Debug)]
+pub struct BehaviourTree<C> {
+    root: Node<C>,
+    status: Option<Status>,
+}
+
+impl<C> BehaviourTree<C> {
+    /// Create a new `BehaviourTree` from its root node
+    #[must_use]
+    pub const fn new(root: Node<C>) -> Self {
+        Self { root, status: None }
+    }
+
+    /// Tick the tree once with the time since the last tick, returning the root node's status. Call this every frame from the entity's update
+    pub fn tick(&mut self, context: &mut C, delta: Duration) -> Status {
+        let status = self.root.tick(context, delta);
+        self.status = Some(status);
+        status
+    }
+
+    /// Return the status from the last tick, or `None` if the tree hasn't been ticked yet
+    #[must_use]
+    pub const fn status(&self) -> Option<Status> {
+        self.status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequences_stop_at_first_unfinished_child() {
+        let mut tree = BehaviourTree::new(Node::sequence([
+            Node::action(|log: &mut Vec<u8>, _| {
+                log.push(1);
+                Status::Success
+            }),
+            Node::invert(Node::condition(|log: &Vec<u8>| log.len() > 3)),
+            Node::action(|log: &mut Vec<u8>, _| {
+                log.push(2);
+                Status::Running
+            }),
+        ]));
+        let mut log = vec![];
+
+        assert_eq!(tree.status(), None);
+        assert_eq!(tree.tick(&mut log, Duration::ZERO), Status::Running);
+        assert_eq!(tree.tick(&mut log, Duration::ZERO), Status::Running);
+        assert_eq!(tree.tick(&mut log, Duration::ZERO), Status::Failure);
+        assert_eq!(log, vec![1, 2, 1, 2, 1]);
+    }
+
+    #[test]
+    fn selectors_fall_back() {
+        let mut selector: Node<()> = Node::selector([
+            Node::condition(|()| false),
+            Node::action(|(), _| Status::Failure),
+        ]);
+        assert_eq!(selector.tick(&mut (), Duration::ZERO), Status::Failure);
+
+        let mut selector: Node<()> =
+            Node::selector([Node::condition(|()| false), Node::condition(|()| true)]);
+        assert_eq!(selector.tick(&mut (), Duration::ZERO), Status::Success);
+    }
+}
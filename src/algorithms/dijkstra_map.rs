@@ -0,0 +1,236 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use crate::elements::{
+    tilemap::{TileMap, ORTHOGONAL},
+    Vec2D,
+};
+
+/// A cell waiting to be visited by the search, ordered so that the nearest cell is popped from the heap first
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    distance: f64,
+    pos: Vec2D,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.total_cmp(&self.distance)
+    }
+}
+
+/// A grid holding the cost to reach the nearest goal from every cell, made with [`dijkstra_map()`]. An AI approaches the goals by always stepping [`downhill()`](DijkstraMap::downhill()), to the neighbour with the lowest value. Maps can be combined, for example to weigh chasing the player against picking up items, and [`inverted()`](DijkstraMap::inverted()) to make AI flee instead
+/// ```
+/// use gemini_engine::{algorithms::dijkstra_map, elements::Vec2D};
+///
+/// let player = Vec2D::new(0, 0);
+/// // A 5x1 corridor with a wall at x = 3
+/// let approach = dijkstra_map(Vec2D::new(5, 1), &[player], |pos| (pos.x != 3).then_some(1.0));
+///
+/// assert_eq!(approach.get(Vec2D::new(2, 0)), Some(2.0));
+/// assert_eq!(approach.get(Vec2D::new(4, 0)), None);
+/// assert_eq!(approach.downhill(Vec2D::new(2, 0)), Some(Vec2D::new(1, 0)));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DijkstraMap {
+    distances: TileMap<f64>,
+    /// The cost of entering every cell, or `None` if it's impassable. Kept so the map can be rescanned
+    costs: TileMap<Option<f64>>,
+}
+
+/// Build a [`DijkstraMap`] of the given size, holding the cheapest cost to reach any of the sources from every cell. `cost` returns the cost of stepping into a cell, or `None` if it can't be entered, such as a wall. Movement is orthogonal only
+#[must_use]
+pub fn dijkstra_map(
+    size: Vec2D,
+    sources: &[Vec2D],
+    cost: impl Fn(Vec2D) -> Option<f64>,
+) -> DijkstraMap {
+    let (width, height) = (size.x.max(0) as usize, size.y.max(0) as usize);
+    let costs = TileMap::from_fn(Vec2D::ZERO, width, height, cost);
+    let mut distances = TileMap::new(Vec2D::ZERO, width, height, f64::INFINITY);
+    for source in sources {
+        if costs.get(*source).is_some_and(Option::is_some) {
+            distances.set(*source, 0.0);
+        }
+    }
+
+    let mut map = DijkstraMap { distances, costs };
+    map.scan();
+    map
+}
+
+impl DijkstraMap {
+    /// Spread the current values outwards until every cell holds the cheapest cost reachable from any other cell's value. Impassable cells are left at infinity
+    fn scan(&mut self) {
+        let mut heap: BinaryHeap<Entry> = self
+            .distances
+            .iter()
+            .filter(|(_, distance)| distance.is_finite())
+            .map(|(pos, distance)| Entry {
+                distance: *distance,
+                pos,
+            })
+            .collect();
+
+        while let Some(Entry { distance, pos }) = heap.pop() {
+            if self.distances.get(pos).is_some_and(|d| distance > *d) {
+                continue;
+            }
+
+            for offset in ORTHOGONAL {
+                let neighbour = pos + offset;
+                let Some(Some(cost)) = self.costs.get(neighbour).copied() else {
+                    continue;
+                };
+                let next = distance + cost;
+                if self.distances.get(neighbour).is_some_and(|d| next < *d) {
+                    self.distances.set(neighbour, next);
+                    heap.push(Entry {
+                        distance: next,
+                        pos: neighbour,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Return the value at the position, or `None` if it's outside the map or can't reach any goal
+    #[must_use]
+    pub fn get(&self, pos: Vec2D) -> Option<f64> {
+        self.distances.get(pos).copied().filter(|d| d.is_finite())
+    }
+
+    /// Return every value in the map. Cells that can't reach any goal are `f64::INFINITY`
+    #[must_use]
+    pub const fn distances(&self) -> &TileMap<f64> {
+        &self.distances
+    }
+
+    /// Return the position to step to from `pos` to get closer to a goal: the passable neighbour with the lowest value, if it's lower than the value at `pos`. Returns `None` if there is nowhere better to go, such as at a goal
+    #[must_use]
+    pub fn downhill(&self, pos: Vec2D) -> Option<Vec2D> {
+        let current = self.distances.get(pos).copied()?;
+        self.distances
+            .neighbours(pos)
+            .filter_map(|neighbour| self.get(neighbour).map(|d| (neighbour, d)))
+            .filter(|(_, d)| *d < current)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(neighbour, _)| neighbour)
+    }
+
+    /// Return a map holding the lower of the two maps' values in every cell, as if both maps' goals had been passed to the same [`dijkstra_map()`]. The maps should be the same size
+    #[must_use]
+    pub fn min(&self, other: &Self) -> Self {
+        self.zip_with(other, f64::min)
+    }
+
+    /// Return a map where every cell is the sum of each map's value multiplied by its weight, for AI with several goals at once. A negative weight makes the AI avoid that map's goals. Cells that can't reach a goal in any of the maps stay unreachable. Returns `None` if no maps are given
+    #[must_use]
+    pub fn weighted_sum(maps: &[(&Self, f64)]) -> Option<Self> {
+        let (first, _) = maps.first()?;
+        let mut sum = first.map_values(|_, _| 0.0);
+
+        for (map, weight) in maps {
+            sum = sum.zip_with(map, |total, distance| {
+                if total.is_finite() && distance.is_finite() {
+                    distance.mul_add(*weight, total)
+                } else {
+                    f64::INFINITY
+                }
+            });
+        }
+
+        Some(sum)
+    }
+
+    /// Return a map for fleeing from the goals. Every value is multiplied by `-coefficient` and the map is rescanned, so that stepping downhill leads away from the goals, but towards open space and around obstacles rather than into the nearest dead end. A coefficient of about 1.2 works well
+    #[must_use]
+    pub fn inverted(&self, coefficient: f64) -> Self {
+        let mut map = self.map_values(|_, distance| {
+            if distance.is_finite() {
+                -coefficient * distance
+            } else {
+                distance
+            }
+        });
+        map.scan();
+        map
+    }
+
+    /// Return a copy of the map with every value converted by the function
+    fn map_values(&self, mut f: impl FnMut(Vec2D, f64) -> f64) -> Self {
+        let distances = TileMap::from_fn(
+            Vec2D::ZERO,
+            self.distances.size().x as usize,
+            self.distances.size().y as usize,
+            |pos| {
+                f(
+                    pos,
+                    self.distances.get(pos).copied().unwrap_or(f64::INFINITY),
+                )
+            },
+        );
+
+        Self {
+            distances,
+            costs: self.costs.clone(),
+        }
+    }
+
+    /// Return a copy of the map with every value combined with the other map's value at the same position. Positions outside the other map are treated as unreachable
+    fn zip_with(&self, other: &Self, f: impl Fn(f64, f64) -> f64) -> Self {
+        self.map_values(|pos, distance| {
+            f(
+                distance,
+                other.distances.get(pos).copied().unwrap_or(f64::INFINITY),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every cell costs 1 to enter
+    const OPEN: fn(Vec2D) -> Option<f64> = |_| Some(1.0);
+
+    #[test]
+    fn combines_maps() {
+        let size = Vec2D::new(7, 1);
+        let left = dijkstra_map(size, &[Vec2D::new(0, 0)], OPEN);
+        let right = dijkstra_map(size, &[Vec2D::new(6, 0)], OPEN);
+
+        let nearest = left.min(&right);
+        assert_eq!(nearest.get(Vec2D::new(2, 0)), Some(2.0));
+        assert_eq!(nearest.get(Vec2D::new(5, 0)), Some(1.0));
+
+        let sum = DijkstraMap::weighted_sum(&[(&left, 1.0), (&right, 0.5)]).expect("two maps");
+        assert_eq!(sum.get(Vec2D::new(2, 0)), Some(4.0));
+        assert!(DijkstraMap::weighted_sum(&[]).is_none());
+    }
+
+    #[test]
+    fn fleeing_moves_away() {
+        let size = Vec2D::new(9, 1);
+        let threat = Vec2D::new(3, 0);
+        let flee = dijkstra_map(size, &[threat], OPEN).inverted(1.2);
+
+        assert_eq!(flee.downhill(Vec2D::new(4, 0)), Some(Vec2D::new(5, 0)));
+        assert_eq!(flee.downhill(Vec2D::new(2, 0)), Some(Vec2D::new(1, 0)));
+        assert_eq!(flee.downhill(Vec2D::new(8, 0)), None);
+    }
+}
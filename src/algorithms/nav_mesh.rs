@@ -0,0 +1,378 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use crate::{
+    elements::{tilemap::TileMap, view::Vec2F, Vec2D},
+    EngineError,
+};
+
+/// How close two points must be to count as the same, to allow for rounding errors
+const EPSILON: f64 = 1e-9;
+
+/// A polygon waiting to be searched by [`NavMesh::find_path()`], ordered so that the lowest estimated cost is popped from the heap first
+struct Entry {
+    estimate: f64,
+    polygon: usize,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+/// The edge shared by two polygons of a [`NavMesh`], through which an agent can move from one to the other
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Portal {
+    to: usize,
+    start: Vec2F,
+    end: Vec2F,
+}
+
+/// Twice the signed area of the triangle, positive if `c` is clockwise from `b` around `a` on screen, where y points down
+fn triangle_area(a: Vec2F, b: Vec2F, c: Vec2F) -> f64 {
+    let (ab, ac) = (b - a, c - a);
+    ab.x.mul_add(ac.y, -(ab.y * ac.x))
+}
+
+/// A navigation mesh of convex walkable polygons, for finding smooth paths for free-moving agents such as [`Steerable`](super::steering::Steerable)s instead of stepping from cell to cell. Paths are found through the polygons with A* and then pulled tight around corners with the funnel algorithm, giving the fewest waypoints to [`seek()`](super::steering::seek()) or [`arrive()`](super::steering::arrive()) towards
+/// ```
+/// use gemini_engine::{algorithms::NavMesh, elements::{tilemap::TileMap, view::Vec2F, Vec2D}};
+///
+/// // An L-shaped corridor
+/// let map = TileMap::from_fn(Vec2D::ZERO, 6, 6, |pos| pos.x < 2 || pos.y >= 4);
+/// let mesh = NavMesh::from_tilemap(&map, |walkable| *walkable);
+///
+/// let path = mesh.find_path(Vec2F::new(0.5, 0.0), Vec2F::new(5.0, 4.5)).expect("both ends are walkable");
+///
+/// // The path turns once, at the inside corner
+/// assert_eq!(path, vec![Vec2F::new(0.5, 0.0), Vec2F::new(1.5, 3.5), Vec2F::new(5.0, 4.5)]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavMesh {
+    polygons: Vec<Vec<Vec2F>>,
+    portals: Vec<Vec<Portal>>,
+}
+
+impl NavMesh {
+    /// Create a `NavMesh` from convex walkable polygons. Polygons are connected wherever their edges overlap, so neighbouring polygons don't need to share vertices
+    ///
+    /// # Errors
+    /// Returns an error if a polygon has fewer than three vertices or isn't convex
+    pub fn from_polygons(polygons: Vec<Vec<Vec2F>>) -> Result<Self, EngineError> {
+        for (i, polygon) in polygons.iter().enumerate() {
+            if polygon.len() < 3 {
+                return Err(EngineError::InvalidData(format!(
+                    "Polygon {i} has fewer than three vertices"
+                )));
+            }
+
+            let turns: Vec<f64> = (0..polygon.len())
+                .map(|j| {
+                    let [a, b, c] = [0, 1, 2].map(|k| polygon[(j + k) % polygon.len()]);
+                    triangle_area(a, b, c)
+                })
+                .collect();
+            if turns.iter().any(|t| *t > EPSILON) && turns.iter().any(|t| *t < -EPSILON) {
+                return Err(EngineError::InvalidData(format!(
+                    "Polygon {i} isn't convex"
+                )));
+            }
+        }
+
+        let portals = (0..polygons.len())
+            .map(|i| {
+                (0..polygons.len())
+                    .filter(|j| *j != i)
+                    .filter_map(|j| {
+                        shared_edge(&polygons[i], &polygons[j]).map(|(start, end)| Portal {
+                            to: j,
+                            start,
+                            end,
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self { polygons, portals })
+    }
+
+    /// Create a `NavMesh` covering the tiles of the [`TileMap`] the function returns true for, by merging them into as few rectangles as it can. Each tile is a 1x1 square centred on its position, so that [`Vec2F::round()`] gives the tile a point is on. Positions are relative to the top left tile, like the `TileMap`'s methods
+    #[must_use]
+    pub fn from_tilemap<T>(map: &TileMap<T>, is_walkable: impl Fn(&T) -> bool) -> Self {
+        let mut covered = map.map(|tile| !is_walkable(tile));
+        let size = map.size();
+        let mut rects = vec![];
+
+        for pos in map.positions() {
+            if covered.get(pos) != Some(&false) {
+                continue;
+            }
+
+            let mut end = pos;
+            while covered.get(end + Vec2D::new(1, 0)) == Some(&false) {
+                end.x += 1;
+            }
+            while end.y + 1 < size.y
+                && (pos.x..=end.x).all(|x| covered.get(Vec2D::new(x, end.y + 1)) == Some(&false))
+            {
+                end.y += 1;
+            }
+
+            for y in pos.y..=end.y {
+                for x in pos.x..=end.x {
+                    covered.set(Vec2D::new(x, y), true);
+                }
+            }
+
+            let half = Vec2F::new(0.5, 0.5);
+            let (min, max) = (Vec2F::from(pos) - half, Vec2F::from(end) + half);
+            rects.push(vec![
+                min,
+                Vec2F::new(max.x, min.y),
+                max,
+                Vec2F::new(min.x, max.y),
+            ]);
+        }
+
+        Self::from_polygons(rects).unwrap_or_else(|_| unreachable!("rectangles are convex"))
+    }
+
+    /// Return the polygons of the `NavMesh`
+    #[must_use]
+    pub fn polygons(&self) -> &[Vec<Vec2F>] {
+        &self.polygons
+    }
+
+    /// Return the index of a polygon containing the point, or `None` if the point isn't on the `NavMesh`. Points on the edge of a polygon count as inside it
+    #[must_use]
+    pub fn polygon_at(&self, point: Vec2F) -> Option<usize> {
+        self.polygons.iter().position(|polygon| {
+            let sides: Vec<f64> = (0..polygon.len())
+                .map(|i| triangle_area(polygon[i], polygon[(i + 1) % polygon.len()], point))
+                .collect();
+            sides.iter().all(|s| *s >= -EPSILON) || sides.iter().all(|s| *s <= EPSILON)
+        })
+    }
+
+    /// Find the shortest path between two points on the `NavMesh`, returned as a list of waypoints starting with `start` and ending with `end`. Returns `None` if either point is off the `NavMesh` or there is no way between them
+    #[must_use]
+    pub fn find_path(&self, start: Vec2F, end: Vec2F) -> Option<Vec<Vec2F>> {
+        let corridor = self.find_corridor(start, end)?;
+
+        // Each portal is oriented as (left, right) when facing the way the corridor goes through it
+        let mut portals = vec![(start, start)];
+        for pair in corridor.windows(2) {
+            let portal = self.portals[pair[0]].iter().find(|p| p.to == pair[1])?;
+            let centre = centroid(&self.polygons[pair[0]]);
+            if triangle_area(centre, portal.start, portal.end) > 0.0 {
+                portals.push((portal.start, portal.end));
+            } else {
+                portals.push((portal.end, portal.start));
+            }
+        }
+        portals.push((end, end));
+
+        Some(pull_string(&portals))
+    }
+
+    /// Find the polygons an agent passes through on the way from the start to the end with A*, using the distances between portal midpoints as costs
+    fn find_corridor(&self, start: Vec2F, end: Vec2F) -> Option<Vec<usize>> {
+        let (first, last) = (self.polygon_at(start)?, self.polygon_at(end)?);
+
+        let mut costs = vec![f64::INFINITY; self.polygons.len()];
+        let mut points = vec![start; self.polygons.len()];
+        let mut came_from = vec![None; self.polygons.len()];
+        costs[first] = 0.0;
+
+        let mut heap = BinaryHeap::from([Entry {
+            estimate: (end - start).magnitude(),
+            polygon: first,
+        }]);
+        while let Some(Entry { polygon, .. }) = heap.pop() {
+            if polygon == last {
+                break;
+            }
+
+            for portal in &self.portals[polygon] {
+                let midpoint = (portal.start + portal.end) / 2.0;
+                let cost = costs[polygon] + (midpoint - points[polygon]).magnitude();
+                if cost < costs[portal.to] {
+                    costs[portal.to] = cost;
+                    points[portal.to] = midpoint;
+                    came_from[portal.to] = Some(polygon);
+                    heap.push(Entry {
+                        estimate: cost + (end - midpoint).magnitude(),
+                        polygon: portal.to,
+                    });
+                }
+            }
+        }
+
+        let mut corridor = vec![last];
+        while let Some(previous) = came_from[*corridor.last()?] {
+            corridor.push(previous);
+        }
+
+        (corridor.last() == Some(&first)).then(|| corridor.into_iter().rev().collect())
+    }
+}
+
+/// Return the average of the polygon's vertices
+fn centroid(polygon: &[Vec2F]) -> Vec2F {
+    polygon.iter().fold(Vec2F::ZERO, |sum, v| sum + *v) / polygon.len() as f64
+}
+
+/// Return the overlapping part of two polygons' edges, if any edges overlap by more than a point
+fn shared_edge(a: &[Vec2F], b: &[Vec2F]) -> Option<(Vec2F, Vec2F)> {
+    let edges = |polygon: &[Vec2F]| {
+        (0..polygon.len())
+            .map(|i| (polygon[i], polygon[(i + 1) % polygon.len()]))
+            .collect::<Vec<_>>()
+    };
+
+    edges(a).into_iter().find_map(|(a0, a1)| {
+        let direction = a1 - a0;
+        let length = direction.dot(direction);
+        edges(b).into_iter().find_map(|(b0, b1)| {
+            let collinear = triangle_area(a0, a1, b0).abs() < EPSILON
+                && triangle_area(a0, a1, b1).abs() < EPSILON;
+            if !collinear || length < EPSILON {
+                return None;
+            }
+
+            let (t0, t1) = (
+                (b0 - a0).dot(direction) / length,
+                (b1 - a0).dot(direction) / length,
+            );
+            let (from, to) = (t0.min(t1).max(0.0), t0.max(t1).min(1.0));
+            (to - from > EPSILON).then(|| (a0 + direction * from, a0 + direction * to))
+        })
+    })
+}
+
+/// Pull a path tight through a corridor of (left, right) portals with the simple stupid funnel algorithm, returning the corners it bends around
+fn pull_string(portals: &[(Vec2F, Vec2F)]) -> Vec<Vec2F> {
+    let (start, end) = (portals[0].0, portals[portals.len() - 1].0);
+    let mut path = vec![start];
+    let (mut apex, mut left, mut right) = (start, start, start);
+    let (mut left_index, mut right_index) = (0, 0);
+
+    let mut i = 1;
+    while i < portals.len() {
+        let (new_left, new_right) = portals[i];
+
+        // Try to narrow the funnel from the right
+        if triangle_area(apex, right, new_right) <= 0.0 {
+            if apex == right || triangle_area(apex, left, new_right) > 0.0 {
+                right = new_right;
+                right_index = i;
+            } else {
+                // The right side crossed the left, so the left is a corner
+                path.push(left);
+                apex = left;
+                (right, right_index) = (apex, left_index);
+                i = left_index + 1;
+                continue;
+            }
+        }
+
+        // Try to narrow the funnel from the left
+        if triangle_area(apex, left, new_left) >= 0.0 {
+            if apex == left || triangle_area(apex, right, new_left) < 0.0 {
+                left = new_left;
+                left_index = i;
+            } else {
+                // The left side crossed the right, so the right is a corner
+                path.push(right);
+                apex = right;
+                (left, left_index) = (apex, right_index);
+                i = right_index + 1;
+                continue;
+            }
+        }
+
+        i += 1;
+    }
+
+    if path.last() != Some(&end) {
+        path.push(end);
+    }
+
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_tiles_into_rectangles() {
+        let open = TileMap::new(Vec2D::ZERO, 4, 3, true);
+        let mesh = NavMesh::from_tilemap(&open, |walkable| *walkable);
+        assert_eq!(mesh.polygons().len(), 1);
+        assert_eq!(
+            mesh.find_path(Vec2F::new(0.0, 0.0), Vec2F::new(3.0, 2.0)),
+            Some(vec![Vec2F::new(0.0, 0.0), Vec2F::new(3.0, 2.0)])
+        );
+
+        let split = TileMap::from_fn(Vec2D::ZERO, 5, 3, |pos| pos.x != 2);
+        let mesh = NavMesh::from_tilemap(&split, |walkable| *walkable);
+        assert_eq!(mesh.polygon_at(Vec2F::new(2.0, 1.0)), None);
+        assert_eq!(mesh.find_path(Vec2F::ZERO, Vec2F::new(4.0, 0.0)), None);
+    }
+
+    #[test]
+    fn paths_bend_around_both_sides() {
+        // A wall in the middle, with gaps above and below
+        let map = TileMap::from_fn(Vec2D::ZERO, 7, 7, |pos| {
+            pos.x != 3 || pos.y == 0 || pos.y == 6
+        });
+        let mesh = NavMesh::from_tilemap(&map, |walkable| *walkable);
+
+        assert_eq!(
+            mesh.find_path(Vec2F::new(0.0, 2.0), Vec2F::new(6.0, 2.0)),
+            Some(vec![
+                Vec2F::new(0.0, 2.0),
+                Vec2F::new(2.5, 0.5),
+                Vec2F::new(3.5, 0.5),
+                Vec2F::new(6.0, 2.0),
+            ])
+        );
+        assert_eq!(
+            mesh.find_path(Vec2F::new(0.0, 4.0), Vec2F::new(6.0, 4.0)),
+            Some(vec![
+                Vec2F::new(0.0, 4.0),
+                Vec2F::new(2.5, 5.5),
+                Vec2F::new(3.5, 5.5),
+                Vec2F::new(6.0, 4.0),
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_bad_polygons() {
+        let concave = vec![
+            Vec2F::new(0.0, 0.0),
+            Vec2F::new(4.0, 0.0),
+            Vec2F::new(1.0, 1.0),
+            Vec2F::new(0.0, 4.0),
+        ];
+        assert!(NavMesh::from_polygons(vec![concave]).is_err());
+        assert!(NavMesh::from_polygons(vec![vec![Vec2F::ZERO, Vec2F::ZERO]]).is_err());
+    }
+}
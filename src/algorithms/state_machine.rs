@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+/// A finite state machine moving between states of type `S` in response to events of type `E`, using a table of allowed transitions. Gives enemy AI and game modes structure beyond a large `match` statement, and keeps track of how long it has been in the current state for timed behaviour. Call [`update()`](StateMachine::update()) every frame with the time since the last frame
+/// ```
+/// use gemini_engine::algorithms::StateMachine;
+/// use std::time::Duration;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum Guard { Patrol, Chase, Search }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Sight { Spotted, Lost }
+///
+/// let mut guard = StateMachine::new(Guard::Patrol)
+///     .with_transition(Guard::Patrol, Sight::Spotted, Guard::Chase)
+///     .with_transition(Guard::Chase, Sight::Lost, Guard::Search)
+///     .with_transition(Guard::Search, Sight::Spotted, Guard::Chase);
+///
+/// assert!(!guard.handle(&Sight::Lost));
+/// assert!(guard.handle(&Sight::Spotted));
+/// assert_eq!(guard.state(), &Guard::Chase);
+///
+/// guard.handle(&Sight::Lost);
+/// guard.update(Duration::from_secs(6));
+/// if guard.time_in_state() > Duration::from_secs(5) {
+///     guard.set_state(Guard::Patrol);
+/// }
+/// assert_eq!(guard.previous(), Some(&Guard::Search));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateMachine<S, E> {
+    state: S,
+    previous: Option<S>,
+    /// Transitions as `(from, event, to)`. A `from` of `None` allows the transition from any state
+    transitions: Vec<(Option<S>, E, S)>,
+    time_in_state: Duration,
+    just_changed: bool,
+}
+
+impl<S: Clone + PartialEq, E: PartialEq> StateMachine<S, E> {
+    /// Create a new `StateMachine` in the initial state, with no transitions
+    #[must_use]
+    pub const fn new(initial: S) -> Self {
+        Self {
+            state: initial,
+            previous: None,
+            transitions: vec![],
+            time_in_state: Duration::ZERO,
+            just_changed: true,
+        }
+    }
+
+    /// Return the `StateMachine` with a transition from one state to another when the event is handled
+    #[must_use]
+    pub fn with_transition(mut self, from: S, event: E, to: S) -> Self {
+        self.transitions.push((Some(from), event, to));
+        self
+    }
+
+    /// Return the `StateMachine` with a transition from any state to `to` when the event is handled. Transitions from a specific state are checked first
+    #[must_use]
+    pub fn with_any_transition(mut self, event: E, to: S) -> Self {
+        self.transitions.push((None, event, to));
+        self
+    }
+
+    /// Return the current state
+    #[must_use]
+    pub const fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Return the state before the current one, if the state has ever changed
+    #[must_use]
+    pub const fn previous(&self) -> Option<&S> {
+        self.previous.as_ref()
+    }
+
+    /// Return how long the `StateMachine` has been in the current state, as counted by [`update()`](StateMachine::update())
+    #[must_use]
+    pub const fn time_in_state(&self) -> Duration {
+        self.time_in_state
+    }
+
+    /// Returns true if the state changed since the last [`update()`](StateMachine::update()), or the `StateMachine` was just created. Useful for running something once on entering a state
+    #[must_use]
+    pub const fn just_changed(&self) -> bool {
+        self.just_changed
+    }
+
+    /// Change state if there is a transition for the event from the current state. Returns true if the state changed
+    pub fn handle(&mut self, event: &E) -> bool {
+        let to = self
+            .transitions
+            .iter()
+            .find(|(from, e, _)| from.as_ref() == Some(&self.state) && e == event)
+            .or_else(|| {
+                self.transitions
+                    .iter()
+                    .find(|(from, e, _)| from.is_none() && e == event)
+            })
+            .map(|(_, _, to)| to.clone());
+
+        to.is_some_and(|to| {
+            self.set_state(to);
+            true
+        })
+    }
+
+    /// Change to the state, whether or not there is a transition to it. Changing to the current state restarts it
+    pub fn set_state(&mut self, state: S) {
+        self.previous = Some(std::mem::replace(&mut self.state, state));
+        self.time_in_state = Duration::ZERO;
+        self.just_changed = true;
+    }
+
+    /// Advance the time spent in the current state. Call this once per frame after acting on [`just_changed()`](StateMachine::just_changed())
+    pub fn update(&mut self, delta: Duration) {
+        self.time_in_state += delta;
+        self.just_changed = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn specific_transitions_before_any() {
+        let mut machine = StateMachine::new('a')
+            .with_any_transition("reset", 'a')
+            .with_transition('b', "reset", 'c')
+            .with_transition('a', "next", 'b');
+
+        assert!(machine.just_changed());
+        machine.update(Duration::from_secs(1));
+        assert!(!machine.just_changed());
+
+        assert!(machine.handle(&"next"));
+        assert_eq!(machine.time_in_state(), Duration::ZERO);
+        assert!(machine.handle(&"reset"));
+        assert_eq!(machine.state(), &'c');
+        assert!(machine.handle(&"reset"));
+        assert_eq!(machine.state(), &'a');
+        assert!(!machine.handle(&"unknown"));
+    }
+}
@@ -0,0 +1,312 @@
+//! Steering behaviours for smooth, non-grid movement, such as enemies that chase the player, slow down as they arrive, wander around and move in flocks. Every behaviour returns a steering force for a [`Steerable`], and forces can be weighted and added together before being [`apply()`](Steerable::apply())d
+//! ```
+//! use gemini_engine::{
+//!     algorithms::steering::{self, Steerable},
+//!     elements::view::Vec2F,
+//! };
+//!
+//! let mut enemy = Steerable::new(Vec2F::new(0.0, 0.0), 10.0, 20.0);
+//! let player = Vec2F::new(30.0, 0.0);
+//!
+//! for _ in 0..60 {
+//!     let force = steering::arrive(&enemy, player, 8.0);
+//!     enemy.apply(force, 1.0 / 30.0);
+//! }
+//!
+//! assert!(enemy.pos.x > 10.0);
+//! ```
+//!
+//! Positions are measured in cells. Obstacles are found with a query for whether a cell is blocked, such as [`CollisionContainer::contains()`](crate::elements::containers::CollisionContainer::contains())
+
+use crate::{
+    elements::view::{Vec2D, Vec2F},
+    procgen::Rng,
+};
+
+/// How long, in seconds, steering forces aim to take to reach the velocity they want, if `max_force` allows it
+const RESPONSE_TIME: f64 = 0.1;
+
+/// Something that moves by steering: a position and velocity, with a limited speed and turning force
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Steerable {
+    /// The current position, in cells
+    pub pos: Vec2F,
+    /// The current velocity, in cells per second
+    pub velocity: Vec2F,
+    /// The fastest the `Steerable` can move, in cells per second
+    pub max_speed: f64,
+    /// The strongest steering force that can be applied, in cells per second per second
+    pub max_force: f64,
+}
+
+impl Steerable {
+    /// Create a new, stationary `Steerable`
+    #[must_use]
+    pub const fn new(pos: Vec2F, max_speed: f64, max_force: f64) -> Self {
+        Self {
+            pos,
+            velocity: Vec2F::ZERO,
+            max_speed,
+            max_force,
+        }
+    }
+
+    /// Return the `Steerable` with the given velocity
+    #[must_use]
+    pub const fn with_velocity(mut self, velocity: Vec2F) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    /// Apply the steering force for `delta` seconds, limiting it to [`max_force`](Steerable::max_force) and the resulting velocity to [`max_speed`](Steerable::max_speed), then move
+    pub fn apply(&mut self, force: Vec2F, delta: f64) {
+        let force = force.clamp_magnitude(self.max_force);
+        self.velocity = (self.velocity + force * delta).clamp_magnitude(self.max_speed);
+        self.pos += self.velocity * delta;
+    }
+
+    /// Return the cell the `Steerable` is in
+    #[must_use]
+    pub const fn cell(&self) -> Vec2D {
+        self.pos.round()
+    }
+
+    /// Return the force needed to change the current velocity to the desired one within [`RESPONSE_TIME`], limited to the `max_force`
+    fn steer_towards(&self, desired: Vec2F) -> Vec2F {
+        ((desired - self.velocity) / RESPONSE_TIME).clamp_magnitude(self.max_force)
+    }
+}
+
+/// Steer straight towards the target at full speed
+#[must_use]
+pub fn seek(agent: &Steerable, target: Vec2F) -> Vec2F {
+    agent.steer_towards((target - agent.pos).normal() * agent.max_speed)
+}
+
+/// Steer straight away from the threat at full speed
+#[must_use]
+pub fn flee(agent: &Steerable, threat: Vec2F) -> Vec2F {
+    agent.steer_towards((agent.pos - threat).normal() * agent.max_speed)
+}
+
+/// Steer towards the target, slowing down within `slowing_radius` so as to stop on the target rather than overshoot it
+#[must_use]
+pub fn arrive(agent: &Steerable, target: Vec2F, slowing_radius: f64) -> Vec2F {
+    let offset = target - agent.pos;
+    let distance = offset.magnitude();
+    let speed = if distance < slowing_radius {
+        agent.max_speed * distance / slowing_radius
+    } else {
+        agent.max_speed
+    };
+
+    agent.steer_towards(offset.normal() * speed)
+}
+
+/// Steer towards where a moving target will be, predicting its position from its velocity and how long it would take to reach it
+#[must_use]
+pub fn pursuit(agent: &Steerable, target: &Steerable) -> Vec2F {
+    let prediction = if agent.max_speed > 0.0 {
+        (target.pos - agent.pos).magnitude() / agent.max_speed
+    } else {
+        0.0
+    };
+
+    seek(agent, target.pos + target.velocity * prediction)
+}
+
+/// Steer away from the cells the `is_blocked` query returns true for, looking up to `look_ahead` cells ahead in the direction of movement. Returns a force to the side of the first blocked cell ahead, stronger the closer it is, or no force if the way ahead is clear
+/// ```
+/// use gemini_engine::{
+///     algorithms::steering::{avoid_obstacles, Steerable},
+///     elements::{containers::CollisionContainer, view::{ColChar, Vec2F}, Rect, Vec2D},
+/// };
+///
+/// let wall = Rect::new(Vec2D::new(5, -3), Vec2D::new(1, 7), ColChar::SOLID);
+/// let mut collision = CollisionContainer::new();
+/// collision.push(&wall);
+///
+/// let agent = Steerable::new(Vec2F::new(2.0, 0.0), 5.0, 10.0).with_velocity(Vec2F::new(5.0, 0.0));
+/// let force = avoid_obstacles(&agent, 4.0, |cell| collision.contains(cell));
+///
+/// assert!(force.magnitude() > 0.0);
+/// assert_eq!(force.x, 0.0);
+/// ```
+#[must_use]
+pub fn avoid_obstacles(
+    agent: &Steerable,
+    look_ahead: f64,
+    is_blocked: impl Fn(Vec2D) -> bool,
+) -> Vec2F {
+    let heading = agent.velocity.normal();
+    if heading == Vec2F::ZERO {
+        return Vec2F::ZERO;
+    }
+
+    let steps = (look_ahead * 2.0).ceil() as usize;
+    for step in 1..=steps {
+        let distance = step as f64 / 2.0;
+        let probe = agent.pos + heading * distance;
+        if is_blocked(probe.round()) {
+            let left = heading.perpendicular();
+            let side = if is_blocked((probe + left).round()) {
+                -left
+            } else {
+                left
+            };
+            let urgency = 1.0 - (distance - 0.5) / look_ahead;
+            return side * agent.max_force * urgency;
+        }
+    }
+
+    Vec2F::ZERO
+}
+
+/// Return the neighbours within the radius of the agent, leaving out any at exactly the agent's position, such as the agent itself
+fn nearby<'a>(
+    agent: &'a Steerable,
+    neighbours: &'a [Steerable],
+    radius: f64,
+) -> impl Iterator<Item = &'a Steerable> {
+    neighbours.iter().filter(move |other| {
+        let distance = (other.pos - agent.pos).magnitude();
+        distance > 0.0 && distance <= radius
+    })
+}
+
+/// Steer away from neighbours within the radius, more strongly from closer ones, to stop a flock from bunching up. The neighbours may include the agent itself
+#[must_use]
+pub fn separation(agent: &Steerable, neighbours: &[Steerable], radius: f64) -> Vec2F {
+    let away = nearby(agent, neighbours, radius).fold(Vec2F::ZERO, |away, other| {
+        let offset = agent.pos - other.pos;
+        away + offset.normal() / offset.magnitude()
+    });
+
+    if away == Vec2F::ZERO {
+        Vec2F::ZERO
+    } else {
+        agent.steer_towards(away.normal() * agent.max_speed)
+    }
+}
+
+/// Steer to match the average heading of neighbours within the radius, so a flock moves together. The neighbours may include the agent itself
+#[must_use]
+pub fn alignment(agent: &Steerable, neighbours: &[Steerable], radius: f64) -> Vec2F {
+    let heading =
+        nearby(agent, neighbours, radius).fold(Vec2F::ZERO, |sum, other| sum + other.velocity);
+
+    if heading == Vec2F::ZERO {
+        Vec2F::ZERO
+    } else {
+        agent.steer_towards(heading.normal() * agent.max_speed)
+    }
+}
+
+/// Steer towards the centre of neighbours within the radius, so a flock stays together. The neighbours may include the agent itself
+#[must_use]
+pub fn cohesion(agent: &Steerable, neighbours: &[Steerable], radius: f64) -> Vec2F {
+    let (sum, count) = nearby(agent, neighbours, radius)
+        .fold((Vec2F::ZERO, 0), |(sum, count), other| {
+            (sum + other.pos, count + 1)
+        });
+
+    if count == 0 {
+        Vec2F::ZERO
+    } else {
+        seek(agent, sum / f64::from(count))
+    }
+}
+
+/// Wandering steers towards a target that drifts randomly around a circle ahead of the agent, so that it turns gently rather than jittering. Keep one `Wander` per agent, as it remembers where its target is
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wander {
+    /// How far ahead of the agent the circle is
+    pub distance: f64,
+    /// The radius of the circle
+    pub radius: f64,
+    /// The most the target can move around the circle each call, in radians
+    pub jitter: f64,
+    angle: f64,
+    rng: Rng,
+}
+
+impl Wander {
+    /// Create a new `Wander` with the given circle distance and radius and a jitter of 0.5 radians
+    #[must_use]
+    pub const fn new(distance: f64, radius: f64, seed: u64) -> Self {
+        Self {
+            distance,
+            radius,
+            jitter: 0.5,
+            angle: 0.0,
+            rng: Rng::new(seed),
+        }
+    }
+
+    /// Return the `Wander` with a different jitter
+    #[must_use]
+    pub const fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Move the target randomly and return the force steering the agent towards it
+    pub fn force(&mut self, agent: &Steerable) -> Vec2F {
+        self.angle += self.rng.next_f64().mul_add(2.0, -1.0) * self.jitter;
+
+        let heading = match agent.velocity.normal() {
+            Vec2F::ZERO => Vec2F::new(1.0, 0.0),
+            heading => heading,
+        };
+        let centre = agent.pos + heading * self.distance;
+        let target = centre + Vec2F::new(self.angle.cos(), self.angle.sin()) * self.radius;
+
+        seek(agent, target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrives_without_overshooting() {
+        let mut agent = Steerable::new(Vec2F::ZERO, 10.0, 30.0);
+        let target = Vec2F::new(20.0, 0.0);
+
+        let mut furthest: f64 = 0.0;
+        for _ in 0..300 {
+            agent.apply(arrive(&agent, target, 10.0), 1.0 / 30.0);
+            furthest = furthest.max(agent.pos.x);
+        }
+
+        assert!((agent.pos - target).magnitude() < 0.5);
+        assert!(furthest < 20.5);
+    }
+
+    #[test]
+    fn flocking_forces() {
+        let agent = Steerable::new(Vec2F::ZERO, 4.0, 8.0);
+        let flock = [
+            agent,
+            Steerable::new(Vec2F::new(2.0, 0.0), 4.0, 8.0).with_velocity(Vec2F::new(0.0, 3.0)),
+            Steerable::new(Vec2F::new(50.0, 0.0), 4.0, 8.0),
+        ];
+
+        assert!(separation(&agent, &flock, 5.0).x < 0.0);
+        assert!(cohesion(&agent, &flock, 5.0).x > 0.0);
+        assert!(alignment(&agent, &flock, 5.0).y > 0.0);
+        assert_eq!(cohesion(&agent, &flock[..1], 5.0), Vec2F::ZERO);
+    }
+
+    #[test]
+    fn flee_and_pursuit() {
+        let agent = Steerable::new(Vec2F::ZERO, 4.0, 8.0);
+        let target =
+            Steerable::new(Vec2F::new(4.0, 0.0), 4.0, 8.0).with_velocity(Vec2F::new(0.0, 4.0));
+
+        assert!(flee(&agent, target.pos).x < 0.0);
+        // The target will have moved down by the time the agent gets there
+        assert!(pursuit(&agent, &target).y > 0.0);
+    }
+}
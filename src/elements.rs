@@ -25,12 +25,34 @@
 pub mod ascii;
 pub use ascii::{AnimatedSprite, Sprite, Text};
 
+pub mod backgrounds;
+
+pub mod charts;
+
+pub mod codes;
+
 pub mod containers;
 pub use containers::PixelContainer;
 
+pub mod editor;
+
 pub mod geometry;
 pub use geometry::{Line, Polygon, Rect, Triangle};
 
+#[cfg(feature = "pty")]
+pub mod pty_view;
+#[cfg(feature = "pty")]
+pub use pty_view::PtyView;
+
+pub mod tilemap;
+pub use tilemap::TileMap;
+
+pub mod transition;
+
+pub mod ui;
+
 pub mod view;
 #[allow(deprecated)]
 pub use view::{Pixel, Point, Vec2D, View};
+
+pub mod weather;
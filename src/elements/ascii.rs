@@ -9,9 +9,31 @@ pub use sprite::Sprite;
 mod text;
 pub use text::Text;
 
+mod path_text;
+pub use path_text::{PathText, TextPath};
+
+mod text_effects;
+pub use text_effects::{TextEffect, TextEffects};
+
+mod floating_text;
+pub use floating_text::FloatingText;
+
+mod ansi;
+pub use ansi::{AnsiSpan, AnsiTokeniser};
+
 mod alignment;
 pub use alignment::{TextAlign, TextAlign2D};
 
+#[cfg(feature = "ttf")]
+mod big_text;
+#[cfg(feature = "ttf")]
+pub use big_text::{BigText, OutlineFont};
+
+use crate::{elements::Vec2D, utils::is_zero_width};
+
+/// The default distance between tab stops, used by [`Text`] and [`Sprite`] when expanding tab characters
+pub const DEFAULT_TAB_WIDTH: usize = 4;
+
 /// Remove all leading newlines from the string
 #[must_use]
 pub fn remove_leading_newlines(texture: &str) -> String {
@@ -23,3 +45,67 @@ pub fn remove_leading_newlines(texture: &str) -> String {
 
     texture.iter().rev().collect()
 }
+
+/// Prepare a single line of text for display: tabs are expanded with spaces up to the next tab stop (every `tab_width` columns) and all other control or zero-width characters are removed, so that every `char` in the returned `String` takes up exactly one cell
+#[must_use]
+pub fn sanitise_line(line: &str, tab_width: usize) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut column = 0;
+
+    for c in line.chars() {
+        if c == '\t' {
+            let tab_width = tab_width.max(1);
+            let stop = (column / tab_width + 1) * tab_width;
+            output.extend(std::iter::repeat_n(' ', stop - column));
+            column = stop;
+        } else if !c.is_control() && !is_zero_width(c) {
+            output.push(c);
+            column += 1;
+        }
+    }
+
+    output
+}
+
+/// Split the text into rows on every newline (`\n` or `\r\n`) and sanitise each row with [`sanitise_line()`]
+#[must_use]
+pub fn split_rows(text: &str, tab_width: usize) -> Vec<String> {
+    text.split('\n')
+        .map(|line| sanitise_line(line, tab_width))
+        .collect()
+}
+
+/// Return the size of the block of text once split into rows, as a [`Vec2D`] of the widest row's width and the number of rows
+#[must_use]
+pub fn measure(text: &str, tab_width: usize) -> Vec2D {
+    let rows = split_rows(text, tab_width);
+
+    Vec2D::new(
+        rows.iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0) as isize,
+        rows.len() as isize,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tabs_expand_to_next_stop() {
+        assert_eq!(sanitise_line("a\tbc\td", 4), "a   bc  d");
+        assert_eq!(sanitise_line("\tx", 2), "  x");
+    }
+
+    #[test]
+    fn control_and_zero_width_chars_are_removed() {
+        assert_eq!(sanitise_line("a\u{7}b\u{200B}c\r", 4), "abc");
+    }
+
+    #[test]
+    fn measure_multiline() {
+        assert_eq!(measure("ab\r\n\tcd\nx", 4), Vec2D::new(6, 3));
+    }
+}
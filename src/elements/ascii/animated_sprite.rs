@@ -37,14 +37,14 @@ impl AnimatedSprite {
     }
 
     /// Go to the next frame of the `AnimatedSprite`'s frames. Will automatically wrap around at the end of the list
-    pub fn next_frame(&mut self) {
+    pub const fn next_frame(&mut self) {
         self.current_frame += 1;
         self.current_frame %= self.frames.len();
     }
 
     /// Returns true if the `current_frame` property is within range of the list of frames. Also returns false if the list of frames is empty
     #[must_use]
-    pub fn is_within_frame_range(&self) -> bool {
+    pub const fn is_within_frame_range(&self) -> bool {
         self.current_frame < self.frames.len()
     }
 }
@@ -0,0 +1,201 @@
+use crate::{
+    elements::{
+        view::{ColChar, Modifier},
+        Pixel, Vec2D,
+    },
+    utils::is_zero_width,
+};
+
+use super::DEFAULT_TAB_WIDTH;
+
+/// A run of text sharing the same [`Modifier`], produced by an [`AnsiTokeniser`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnsiSpan {
+    /// The `Modifier` applied to the text
+    pub modifier: Modifier,
+    /// The text of the span, with all escape sequences removed
+    pub text: String,
+}
+
+/// Splits text containing ANSI escape sequences, such as the coloured output of another program, into [`AnsiSpan`]s. SGR sequences (`\x1b[...m`) change the [`Modifier`] of the following text and every other escape sequence is left out
+///
+/// The tokeniser can be fed text a chunk at a time with [`feed()`](AnsiTokeniser::feed()), for example as it is read from a pipe. Escape sequences split between chunks are kept until the rest of the sequence arrives, and the current `Modifier` carries over from one chunk to the next
+#[derive(Debug, Clone, Default)]
+pub struct AnsiTokeniser {
+    /// The `Modifier` applied to the next text
+    pub modifier: Modifier,
+    pending: String,
+}
+
+impl AnsiTokeniser {
+    /// Create a new `AnsiTokeniser`, starting with no `Modifier`
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            modifier: Modifier::None,
+            pending: String::new(),
+        }
+    }
+
+    /// Split the whole text into spans
+    #[must_use]
+    pub fn tokenise(text: &str) -> Vec<AnsiSpan> {
+        Self::new().feed(text)
+    }
+
+    /// Feed the next chunk of text to the tokeniser, returning the spans completed by it. An unfinished escape sequence at the end of the chunk is held back until the next call
+    pub fn feed(&mut self, chunk: &str) -> Vec<AnsiSpan> {
+        let mut input = std::mem::take(&mut self.pending);
+        input.push_str(chunk);
+
+        let mut spans = vec![];
+        let mut text = String::new();
+        let mut rest = input.as_str();
+
+        while let Some(escape) = rest.find('\x1b') {
+            text.push_str(&rest[..escape]);
+            let sequence = &rest[escape..];
+
+            let Some(length) = Self::sequence_length(sequence) else {
+                // The sequence is incomplete, so wait for the next chunk
+                self.pending = String::from(sequence);
+                rest = "";
+                break;
+            };
+
+            if sequence[..length].ends_with('m') && sequence.starts_with("\x1b[") {
+                if let Ok(modifier) = Modifier::parse_ansi(&sequence[..length]) {
+                    if modifier != self.modifier && !text.is_empty() {
+                        spans.push(AnsiSpan {
+                            modifier: self.modifier,
+                            text: std::mem::take(&mut text),
+                        });
+                    }
+                    self.modifier = modifier;
+                }
+            }
+            rest = &sequence[length..];
+        }
+        text.push_str(rest);
+
+        if !text.is_empty() {
+            spans.push(AnsiSpan {
+                modifier: self.modifier,
+                text,
+            });
+        }
+
+        spans
+    }
+
+    /// Return the length in bytes of the escape sequence at the start of the string, or `None` if the sequence is incomplete
+    pub(crate) fn sequence_length(sequence: &str) -> Option<usize> {
+        let bytes = sequence.as_bytes();
+        match bytes.get(1)? {
+            // CSI sequences end with a byte in the range 0x40 to 0x7E
+            b'[' => bytes[2..]
+                .iter()
+                .position(|b| (0x40..=0x7E).contains(b))
+                .map(|end| end + 3),
+            // OSC sequences end with whichever comes first of a BEL, an ST (`\x1b\\`) or a C1 ST
+            b']' => sequence.char_indices().skip(2).find_map(|(i, c)| match c {
+                '\x07' | '\u{9c}' => Some(i + c.len_utf8()),
+                '\x1b' if sequence[i + 1..].starts_with('\\') => Some(i + 2),
+                _ => None,
+            }),
+            // Any other escape is two bytes long, as long as the second is a complete character
+            _ => sequence
+                .char_indices()
+                .nth(1)
+                .map(|(i, c)| i + c.len_utf8()),
+        }
+    }
+
+    /// Return a vector of Pixels to display the spans, starting at the given position. Newlines start a new row, tabs are expanded with the [`DEFAULT_TAB_WIDTH`] and whitespaces are left transparent
+    #[must_use]
+    pub fn draw(pos: Vec2D, spans: &[AnsiSpan]) -> Vec<Pixel> {
+        let mut pixels = vec![];
+        let mut cursor = Vec2D::ZERO;
+
+        for span in spans {
+            for text_char in span.text.chars() {
+                match text_char {
+                    '\n' => cursor = Vec2D::new(0, cursor.y + 1),
+                    '\t' => {
+                        let tab_width = DEFAULT_TAB_WIDTH as isize;
+                        cursor.x = (cursor.x / tab_width + 1) * tab_width;
+                    }
+                    ' ' => cursor.x += 1,
+                    c if c.is_control() || is_zero_width(c) => (),
+                    c => {
+                        pixels.push(Pixel::new(pos + cursor, ColChar::new(c, span.modifier)));
+                        cursor.x += 1;
+                    }
+                }
+            }
+        }
+
+        pixels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenise_split_across_chunks() {
+        let mut tokeniser = AnsiTokeniser::new();
+        let mut spans = tokeniser.feed("ab\x1b[3");
+        spans.extend(tokeniser.feed("2mcd\x1b[0m\x1b[2Ke"));
+
+        assert_eq!(
+            spans,
+            vec![
+                AnsiSpan {
+                    modifier: Modifier::None,
+                    text: String::from("ab")
+                },
+                AnsiSpan {
+                    modifier: Modifier::GREEN,
+                    text: String::from("cd")
+                },
+                AnsiSpan {
+                    modifier: Modifier::None,
+                    text: String::from("e")
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn draw_handles_newlines() {
+        let spans = AnsiTokeniser::tokenise("a\x1b[31mb\nc d");
+        let pixels = AnsiTokeniser::draw(Vec2D::ZERO, &spans);
+
+        assert_eq!(pixels.len(), 4);
+        assert_eq!(
+            pixels[1],
+            Pixel::new(Vec2D::new(1, 0), ColChar::new('b', Modifier::RED))
+        );
+        assert_eq!(pixels[3].pos, Vec2D::new(2, 1));
+    }
+
+    #[test]
+    fn osc_sequences_end_at_the_first_terminator() {
+        assert_eq!(
+            AnsiTokeniser::tokenise("a\x1b]0;t\u{9c}x"),
+            vec![AnsiSpan {
+                modifier: Modifier::None,
+                text: String::from("ax")
+            }]
+        );
+        assert_eq!(
+            AnsiTokeniser::tokenise("\x1b]0;t\x1b\\hello\x07world"),
+            vec![AnsiSpan {
+                modifier: Modifier::None,
+                text: String::from("hello\x07world")
+            }]
+        );
+    }
+}
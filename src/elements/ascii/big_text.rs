@@ -0,0 +1,264 @@
+use ab_glyph::{point, Font, FontArc, GlyphId, PxScale, ScaleFont};
+use std::{fs, path::Path};
+
+use crate::{
+    elements::{
+        view::{ColChar, Modifier, ViewElement},
+        Pixel, Vec2D,
+    },
+    utils::cell_aspect_ratio,
+    EngineError,
+};
+
+/// A TTF or OTF font used to draw [`BigText`]. Cloning an `OutlineFont` is cheap, as the font data is shared. Only available with the `ttf` feature
+#[derive(Debug, Clone)]
+pub struct OutlineFont {
+    font: FontArc,
+}
+
+impl OutlineFont {
+    /// Load an `OutlineFont` from the bytes of a TTF or OTF file
+    ///
+    /// # Errors
+    /// Returns [`EngineError::InvalidData`] if the data isn't a valid font
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, EngineError> {
+        FontArc::try_from_vec(data)
+            .map(|font| Self { font })
+            .map_err(|err| EngineError::InvalidData(err.to_string()))
+    }
+
+    /// Load an `OutlineFont` from a TTF or OTF file
+    ///
+    /// # Errors
+    /// Returns [`EngineError::Io`] if the file couldn't be read or [`EngineError::InvalidData`] if it isn't a valid font
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, EngineError> {
+        Self::from_bytes(fs::read(path)?)
+    }
+
+    /// Rasterise the text so that each line is `height` cells tall, returning the coverage of every cell the glyphs touch, from 0.0 to 1.0. Glyphs are stretched horizontally by the engine-wide [`cell_aspect_ratio()`] so they keep their proportions. Positions are relative to the top left of the first line
+    #[must_use]
+    pub fn rasterise(&self, text: &str, height: usize) -> Vec<(Vec2D, f32)> {
+        let height = height as f32;
+        let font = self.font.as_scaled(PxScale {
+            x: height * cell_aspect_ratio() as f32,
+            y: height,
+        });
+        let line_height = font.height() + font.line_gap();
+
+        let mut coverage = vec![];
+        for (row, line) in text.lines().enumerate() {
+            let baseline = line_height.mul_add(row as f32, font.ascent());
+            let mut caret = 0.0;
+            let mut previous: Option<GlyphId> = None;
+
+            for c in line.chars() {
+                let id = font.glyph_id(c);
+                if let Some(previous) = previous {
+                    caret += font.kern(previous, id);
+                }
+                previous = Some(id);
+
+                let glyph = id.with_scale_and_position(font.scale(), point(caret, baseline));
+                caret += font.h_advance(id);
+
+                if let Some(outlined) = font.outline_glyph(glyph) {
+                    let bounds = outlined.px_bounds();
+                    let origin = Vec2D::new(bounds.min.x as isize, bounds.min.y as isize);
+                    outlined.draw(|x, y, amount| {
+                        if amount > 0.0 {
+                            coverage.push((origin + Vec2D::new(x as isize, y as isize), amount));
+                        }
+                    });
+                }
+            }
+        }
+
+        coverage
+    }
+}
+
+/// Large text drawn with any TTF or OTF [`OutlineFont`] at any size, for titles and logos. Cells are filled with characters from a brightness ramp depending on how much of the cell the glyph covers, which smooths out curves and diagonals. Only available with the `ttf` feature
+/// ```no_run
+/// use gemini_engine::elements::{ascii::{BigText, OutlineFont}, view::Modifier, Vec2D};
+///
+/// let font = OutlineFont::from_file("assets/title.ttf").expect("a valid font file");
+/// let title = BigText::new(Vec2D::new(2, 1), "GEMINI", font, 8, Modifier::None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BigText {
+    /// The position of the top left of the text
+    pub pos: Vec2D,
+    /// The text to draw. Newlines start a new line below the previous one
+    pub content: String,
+    /// The font to draw the text with
+    pub font: OutlineFont,
+    /// How many cells tall each line of text is
+    pub height: usize,
+    /// The characters used for increasing amounts of coverage, from faintest to fullest. Defaults to [`BigText::DEFAULT_RAMP`]
+    pub ramp: Vec<char>,
+    /// The modifier applied to every character of the text
+    pub modifier: Modifier,
+}
+
+impl BigText {
+    /// The default brightness ramp, going from a faint dot to a full block
+    pub const DEFAULT_RAMP: &'static str = ".:-=+*#%@█";
+
+    /// Create a new `BigText` with the given font and a line height in cells
+    #[must_use]
+    pub fn new(
+        pos: Vec2D,
+        content: &str,
+        font: OutlineFont,
+        height: usize,
+        modifier: Modifier,
+    ) -> Self {
+        Self {
+            pos,
+            content: String::from(content),
+            font,
+            height,
+            ramp: Self::DEFAULT_RAMP.chars().collect(),
+            modifier,
+        }
+    }
+
+    /// Return the `BigText` with a different brightness ramp, from faintest to fullest. Passing a single character draws every covered cell with it
+    #[must_use]
+    pub fn with_ramp(mut self, ramp: &str) -> Self {
+        self.ramp = ramp.chars().collect();
+        self
+    }
+
+    /// Return the character from the ramp for the amount of coverage, from 0.0 to 1.0
+    fn ramp_char(&self, coverage: f32) -> Option<char> {
+        let last = self.ramp.len().checked_sub(1)?;
+        let index = (coverage.clamp(0.0, 1.0) * last as f32).round() as usize;
+        self.ramp.get(index).copied()
+    }
+}
+
+impl ViewElement for BigText {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        self.font
+            .rasterise(&self.content, self.height)
+            .into_iter()
+            .filter_map(|(pos, coverage)| {
+                let text_char = self.ramp_char(coverage)?;
+                Some(Pixel::new(
+                    self.pos + pos,
+                    ColChar::new(text_char, self.modifier),
+                ))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_invalid_fonts() {
+        assert!(matches!(
+            OutlineFont::from_bytes(b"not a font".to_vec()),
+            Err(EngineError::InvalidData(_))
+        ));
+        assert!(matches!(
+            OutlineFont::from_file("missing-font.ttf"),
+            Err(EngineError::Io(_))
+        ));
+    }
+
+    /// Build a minimal TrueType font whose only glyph, mapped to `'A'`, is a box 500 units wide and 800 units tall, sitting on the baseline. The font is 1000 units from descender to ascender
+    fn box_font() -> OutlineFont {
+        fn be16(values: &[i32]) -> Vec<u8> {
+            values
+                .iter()
+                .flat_map(|v| (*v as u16).to_be_bytes())
+                .collect()
+        }
+
+        // An empty glyph 0, then the box as one contour of four on-curve points
+        let glyf = [
+            be16(&[1, 0, 0, 500, 800, 3, 0]),
+            vec![1; 4],
+            be16(&[0, 500, 0, -500]),
+            be16(&[0, 0, 800, 0]),
+        ]
+        .concat();
+        // A single byte encoding subtable, mapping every character below 256
+        let mut cmap = be16(&[0, 1, 0, 0, 0, 12, 0, 262, 0]);
+        cmap.extend((0..=255u8).map(|c| u8::from(c == b'A')));
+
+        let tables = [
+            (*b"cmap", cmap),
+            (*b"glyf", glyf.clone()),
+            (
+                *b"head",
+                [
+                    be16(&[1, 0, 0, 0, 0, 0, 0x5F0F, 0x3CF5, 0, 1000]),
+                    vec![0; 16],
+                    be16(&[0, 0, 500, 800, 0, 8, 2, 0, 0]),
+                ]
+                .concat(),
+            ),
+            (
+                *b"hhea",
+                be16(&[
+                    1, 0, 800, -200, 0, 500, 0, 0, 500, 1, 0, 0, 0, 0, 0, 0, 0, 2,
+                ]),
+            ),
+            (*b"hmtx", be16(&[0, 0, 500, 0])),
+            (*b"loca", be16(&[0, 0, glyf.len() as i32 / 2])),
+            (*b"maxp", be16(&[0, 0x5000, 2])),
+        ];
+
+        let mut font = be16(&[1, 0, tables.len() as i32, 0, 0, 0]);
+        let mut offset = font.len() + tables.len() * 16;
+        let mut data = vec![];
+        for (tag, table) in &tables {
+            font.extend_from_slice(tag);
+            font.extend([0; 4]);
+            font.extend((offset as u32).to_be_bytes());
+            font.extend((table.len() as u32).to_be_bytes());
+            offset += table.len();
+            data.extend_from_slice(table);
+        }
+        font.extend(data);
+
+        OutlineFont::from_bytes(font).expect("a valid font")
+    }
+
+    #[test]
+    fn rasterises_glyph_coverage() {
+        // The box is 3.2 cells tall and 500 / 1000 * 4 cells wide, stretched by the cell aspect ratio
+        let coverage = box_font().rasterise("A", 4);
+        let width = 2.0 * cell_aspect_ratio() as f32;
+        let max = coverage.iter().fold(Vec2D::ZERO, |max, (pos, _)| {
+            Vec2D::new(max.x.max(pos.x), max.y.max(pos.y))
+        });
+
+        assert_eq!(max, Vec2D::new(width.ceil() as isize - 1, 3));
+        for (pos, amount) in coverage {
+            assert!(amount > 0.0 && amount <= 1.0);
+            if pos.y < 3 && (pos.x as f32) < width.floor() {
+                assert!(amount > 0.99, "{pos} should be fully covered");
+            }
+        }
+        assert!(box_font().rasterise("B", 4).is_empty());
+    }
+
+    #[test]
+    fn ramp_chars_follow_coverage() {
+        let text = BigText::new(Vec2D::ZERO, "A", box_font(), 4, Modifier::None).with_ramp(" .#");
+        assert_eq!(text.ramp_char(0.0), Some(' '));
+        assert_eq!(text.ramp_char(0.4), Some('.'));
+        assert_eq!(text.ramp_char(0.8), Some('#'));
+        assert_eq!(text.ramp_char(1.5), Some('#'));
+        assert_eq!(text.clone().with_ramp("").ramp_char(1.0), None);
+
+        let pixels = text.active_pixels();
+        assert!(pixels.iter().any(|p| p.fill_char.text_char == '#'));
+    }
+}
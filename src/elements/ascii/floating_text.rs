@@ -0,0 +1,232 @@
+use super::{sanitise_line, DEFAULT_TAB_WIDTH};
+use crate::{
+    elements::{
+        view::{utils::Easing, ColChar, Colour, Modifier, ViewElement},
+        Pixel, Vec2D,
+    },
+    gameloop::CanAnimate,
+};
+use std::time::Duration;
+
+/// A single piece of text in a [`FloatingText`]'s pool. Expired labels are kept to be reused by the next spawn
+#[derive(Debug, Clone)]
+struct Label {
+    text: String,
+    pos: Vec2D,
+    colour: Colour,
+    age: Duration,
+    alive: bool,
+}
+
+/// `FloatingText` spawns short pieces of text at positions in the world, such as damage numbers and `"+10 gold"`, which rise and fade out before disappearing. Every label is drawn by the one element, centred on the position it was spawned at
+///
+/// Labels are positioned in world space, so blit the `FloatingText` with [`View::blit_world()`](crate::elements::View::blit_world()) to move them with the camera. Call [`update()`](FloatingText::update()) every frame to animate them. Expired labels are pooled and reused by later spawns, and once [`capacity`](FloatingText::capacity) labels are showing, the oldest is replaced
+/// ```
+/// use gemini_engine::elements::{ascii::FloatingText, view::{ColChar, Colour, Wrapping}, Vec2D, View};
+/// use std::time::Duration;
+///
+/// let mut view = View::new(20, 10, ColChar::EMPTY);
+/// view.camera = Vec2D::new(100, 0);
+///
+/// let mut damage = FloatingText::new();
+/// damage.spawn(Vec2D::new(110, 8), "-12", Colour::rgb(255, 0, 0));
+/// damage.update(Duration::from_millis(500));
+///
+/// view.blit_world(&damage, Wrapping::Ignore);
+/// assert_eq!(view.get(Vec2D::new(10, 6)).map(|c| c.text_char), Some('1'));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FloatingText {
+    /// How long each label is shown for. Defaults to 1 second
+    pub lifetime: Duration,
+    /// How many cells each label rises over its lifetime. Defaults to 3.0
+    pub rise: f64,
+    /// The colour labels fade to by the end of their lifetime, usually the colour of the background. Defaults to [`Colour::BLACK`]
+    pub fade_to: Colour,
+    /// The most labels shown at once. Defaults to 64
+    pub capacity: usize,
+    labels: Vec<Label>,
+}
+
+impl Default for FloatingText {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FloatingText {
+    /// Create a new, empty `FloatingText`
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            lifetime: Duration::from_secs(1),
+            rise: 3.0,
+            fade_to: Colour::BLACK,
+            capacity: 64,
+            labels: vec![],
+        }
+    }
+
+    /// Return the `FloatingText` with a different [`lifetime`](FloatingText::lifetime)
+    #[must_use]
+    pub const fn with_lifetime(mut self, lifetime: Duration) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// Return the `FloatingText` with a different [`rise`](FloatingText::rise)
+    #[must_use]
+    pub const fn with_rise(mut self, rise: f64) -> Self {
+        self.rise = rise;
+        self
+    }
+
+    /// Return the `FloatingText` with a different [`fade_to`](FloatingText::fade_to) colour
+    #[must_use]
+    pub const fn with_fade_to(mut self, colour: Colour) -> Self {
+        self.fade_to = colour;
+        self
+    }
+
+    /// Return the `FloatingText` with a different [`capacity`](FloatingText::capacity)
+    #[must_use]
+    pub const fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Spawn a label centred on a position in world space. Only the first line of the text is shown
+    pub fn spawn(&mut self, pos: Vec2D, text: &str, colour: Colour) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let slot = if let Some(i) = self.labels.iter().position(|label| !label.alive) {
+            i
+        } else if self.labels.len() < self.capacity {
+            self.labels.push(Label {
+                text: String::new(),
+                pos,
+                colour,
+                age: Duration::ZERO,
+                alive: false,
+            });
+            self.labels.len() - 1
+        } else {
+            self.labels
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, label)| label.age)
+                .map_or(0, |(i, _)| i)
+        };
+
+        let label = &mut self.labels[slot];
+        label.text.clear();
+        label.text.push_str(&sanitise_line(
+            text.lines().next().unwrap_or_default(),
+            DEFAULT_TAB_WIDTH,
+        ));
+        label.pos = pos;
+        label.colour = colour;
+        label.age = Duration::ZERO;
+        label.alive = true;
+    }
+
+    /// Age every label by `delta`, usually the time since the last frame, expiring those that have outlived the [`lifetime`](FloatingText::lifetime)
+    pub fn update(&mut self, delta: Duration) {
+        for label in self.labels.iter_mut().filter(|label| label.alive) {
+            label.age += delta;
+            if label.age >= self.lifetime {
+                label.alive = false;
+            }
+        }
+    }
+
+    /// Return the number of labels currently showing
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.labels.iter().filter(|label| label.alive).count()
+    }
+
+    /// Returns true if no labels are showing
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove every label
+    pub fn clear(&mut self) {
+        for label in &mut self.labels {
+            label.alive = false;
+        }
+    }
+
+    /// Return how far through its lifetime the label is, from 0.0 to 1.0
+    fn progress(&self, label: &Label) -> f64 {
+        if self.lifetime.is_zero() {
+            1.0
+        } else {
+            (label.age.as_secs_f64() / self.lifetime.as_secs_f64()).min(1.0)
+        }
+    }
+}
+
+impl ViewElement for FloatingText {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let mut pixels = vec![];
+        for label in self.labels.iter().filter(|label| label.alive) {
+            let progress = self.progress(label);
+            let rise = (Easing::EaseOut.apply(progress) * self.rise).round() as isize;
+            let colour = label.colour.lerp(self.fade_to, progress);
+
+            let width = label.text.chars().count() as isize;
+            let start = label.pos - Vec2D::new(width / 2, rise);
+            pixels.extend(label.text.chars().enumerate().map(|(x, c)| {
+                Pixel::new(
+                    start + Vec2D::new(x as isize, 0),
+                    ColChar::new(c, Modifier::Colour(colour)),
+                )
+            }));
+        }
+
+        pixels
+    }
+}
+
+impl CanAnimate for FloatingText {
+    fn is_animating(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_rise_fade_and_are_reused() {
+        let mut text = FloatingText::new()
+            .with_lifetime(Duration::from_secs(2))
+            .with_rise(4.0)
+            .with_capacity(2);
+        text.spawn(Vec2D::new(10, 10), "+10", Colour::WHITE);
+        text.update(Duration::from_secs(1));
+
+        let pixels = text.active_pixels();
+        assert_eq!(pixels[0].pos, Vec2D::new(9, 7));
+        assert_eq!(
+            pixels[0].fill_char.modifier.colour(),
+            Some(Colour::rgb(128, 128, 128))
+        );
+
+        text.spawn(Vec2D::ZERO, "a", Colour::WHITE);
+        text.spawn(Vec2D::ZERO, "b", Colour::WHITE);
+        assert_eq!(text.len(), 2);
+        assert!(text.active_pixels().iter().all(|p| p.pos == Vec2D::ZERO));
+
+        text.update(Duration::from_secs(2));
+        assert!(text.is_empty());
+        text.spawn(Vec2D::ZERO, "c", Colour::WHITE);
+        assert_eq!((text.len(), text.labels.len()), (1, 2));
+    }
+}
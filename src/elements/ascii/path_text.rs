@@ -0,0 +1,216 @@
+use crate::elements::{
+    view::{ColChar, Modifier, ViewElement},
+    Line, Pixel, Vec2D,
+};
+
+use super::{sanitise_line, DEFAULT_TAB_WIDTH};
+
+/// The path along which a [`PathText`] lays out its characters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextPath {
+    /// A chain of straight lines joining each point to the next
+    Polyline(Vec<Vec2D>),
+    /// A Bezier curve of any degree, defined by its control points. The curve starts at the first point and ends at the last
+    Bezier(Vec<Vec2D>),
+}
+
+impl TextPath {
+    /// Return every cell along the path in order, from the start of the path to its end. Each cell appears directly next to (or diagonal to) the previous one, so that consecutive characters placed on the path never leave gaps
+    #[must_use]
+    pub fn cells(&self) -> Vec<Vec2D> {
+        match self {
+            Self::Polyline(points) => Self::join_points(points),
+            Self::Bezier(control_points) => Self::join_points(&Self::sample_bezier(control_points)),
+        }
+    }
+
+    /// Join the points with lines, leaving out the cells shared by the end of one line and the start of the next
+    fn join_points(points: &[Vec2D]) -> Vec<Vec2D> {
+        let mut cells: Vec<Vec2D> = points.first().copied().into_iter().collect();
+
+        for pair in points.windows(2) {
+            for cell in Line::draw(pair[0], pair[1]) {
+                if cells.last() != Some(&cell) {
+                    cells.push(cell);
+                }
+            }
+        }
+
+        cells
+    }
+
+    /// Sample points along the Bezier curve using De Casteljau's algorithm, with enough samples that consecutive points are no more than a cell apart
+    fn sample_bezier(control_points: &[Vec2D]) -> Vec<Vec2D> {
+        if control_points.len() < 2 {
+            return control_points.to_vec();
+        }
+
+        let hull_length: f64 = control_points
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]).magnitude())
+            .sum();
+        let steps = (hull_length.ceil() as usize).max(1);
+
+        let mut samples = Vec::with_capacity(steps + 1);
+        for step in 0..=steps {
+            let t = step as f64 / steps as f64;
+
+            let mut points: Vec<(f64, f64)> = control_points
+                .iter()
+                .map(|p| (p.x as f64, p.y as f64))
+                .collect();
+            while points.len() > 1 {
+                points = points
+                    .windows(2)
+                    .map(|pair| {
+                        (
+                            (pair[1].0 - pair[0].0).mul_add(t, pair[0].0),
+                            (pair[1].1 - pair[0].1).mul_add(t, pair[0].1),
+                        )
+                    })
+                    .collect();
+            }
+
+            let (x, y) = points[0];
+            samples.push(Vec2D::new(x.round() as isize, y.round() as isize));
+        }
+
+        samples
+    }
+}
+
+/// Displays text laid out along a [`TextPath`], one character per cell of the path. Useful for circular HUD labels or stylised titles. Characters that don't fit on the path are left out
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct PathText {
+    /// The path along which the text is laid out
+    pub path: TextPath,
+    /// The actual text content of the element. Tabs are expanded and newlines and other control characters are left out
+    pub content: String,
+    /// A raw [`Modifier`], determining the appearance of the `PathText`
+    pub modifier: Modifier,
+    /// The number of cells along the path to skip before the first character
+    pub offset: usize,
+    /// The number of cells along the path between the start of one character and the next. Defaults to 1
+    pub spacing: usize,
+}
+
+impl PathText {
+    /// Create a new `PathText` element with a path, content and modifier
+    #[must_use]
+    pub fn new(path: TextPath, content: &str, modifier: Modifier) -> Self {
+        Self {
+            path,
+            content: String::from(content),
+            modifier,
+            offset: 0,
+            spacing: 1,
+        }
+    }
+
+    /// Return the `PathText` with the modified offset property
+    #[must_use]
+    pub const fn with_offset(self, offset: usize) -> Self {
+        let mut tmp = self;
+        tmp.offset = offset;
+        tmp
+    }
+
+    /// Return the `PathText` with the modified spacing property
+    #[must_use]
+    pub const fn with_spacing(self, spacing: usize) -> Self {
+        let mut tmp = self;
+        tmp.spacing = spacing;
+        tmp
+    }
+
+    /// Return a vector of Pixels to display the given content along the path, starting `offset` cells along the path and placing each character `spacing` cells after the previous one
+    #[must_use]
+    pub fn draw(
+        path: &TextPath,
+        content: &str,
+        offset: usize,
+        spacing: usize,
+        modifier: Modifier,
+    ) -> Vec<Pixel> {
+        let row = sanitise_line(content, DEFAULT_TAB_WIDTH);
+
+        path.cells()
+            .into_iter()
+            .skip(offset)
+            .step_by(spacing.max(1))
+            .zip(row.chars())
+            .filter(|(_, text_char)| *text_char != ' ')
+            .map(|(pos, text_char)| {
+                Pixel::new(
+                    pos,
+                    ColChar {
+                        text_char,
+                        modifier,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+impl ViewElement for PathText {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        Self::draw(
+            &self.path,
+            &self.content,
+            self.offset,
+            self.spacing,
+            self.modifier,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polyline_cells_are_continuous() {
+        let path = TextPath::Polyline(vec![Vec2D::new(0, 0), Vec2D::new(2, 0), Vec2D::new(2, 2)]);
+
+        assert_eq!(
+            path.cells(),
+            vec![
+                Vec2D::new(0, 0),
+                Vec2D::new(1, 0),
+                Vec2D::new(2, 0),
+                Vec2D::new(2, 1),
+                Vec2D::new(2, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn bezier_cells_have_no_gaps() {
+        let path = TextPath::Bezier(vec![
+            Vec2D::new(0, 10),
+            Vec2D::new(10, -10),
+            Vec2D::new(20, 10),
+        ]);
+        let cells = path.cells();
+
+        assert_eq!(cells.first(), Some(&Vec2D::new(0, 10)));
+        assert_eq!(cells.last(), Some(&Vec2D::new(20, 10)));
+        assert!(cells.windows(2).all(|pair| {
+            let step = pair[1] - pair[0];
+            step != Vec2D::ZERO && step.x.abs() <= 1 && step.y.abs() <= 1
+        }));
+    }
+
+    #[test]
+    fn draw_skips_offset_and_spaces() {
+        let path = TextPath::Polyline(vec![Vec2D::new(0, 0), Vec2D::new(9, 0)]);
+        let positions: Vec<Vec2D> = PathText::draw(&path, "a b", 2, 2, Modifier::None)
+            .iter()
+            .map(|pixel| pixel.pos)
+            .collect();
+
+        assert_eq!(positions, vec![Vec2D::new(2, 0), Vec2D::new(6, 0)]);
+    }
+}
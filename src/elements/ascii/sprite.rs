@@ -1,4 +1,4 @@
-use super::{remove_leading_newlines, Text, TextAlign2D};
+use super::{measure, remove_leading_newlines, Text, TextAlign, TextAlign2D, DEFAULT_TAB_WIDTH};
 use crate::elements::{
     view::{Modifier, ViewElement},
     Pixel, Vec2D,
@@ -38,17 +38,10 @@ impl Sprite {
         tmp
     }
 
-    /// Render a string texture at a given position in a [`ViewElement::active_pixels()`]-readable format
+    /// Render a string texture at a given position in a [`ViewElement::active_pixels()`]-readable format. Tabs are expanded with the [`DEFAULT_TAB_WIDTH`]
     #[must_use]
     pub fn draw(pos: Vec2D, texture: &str, modifier: Modifier) -> Vec<Pixel> {
-        let mut pixels = vec![];
-
-        let lines = texture.split('\n');
-        for (y, line) in (0isize..).zip(lines) {
-            pixels.extend(Text::draw(pos + Vec2D::new(0, y), line, modifier));
-        }
-
-        pixels
+        Text::draw_with_tab_width(pos, texture, TextAlign::Begin, DEFAULT_TAB_WIDTH, modifier)
     }
 
     /// Return a vector of Pixels to display the given content, aligning the content to the position as directed by the `align` attribute
@@ -59,10 +52,7 @@ impl Sprite {
         align: TextAlign2D,
         modifier: Modifier,
     ) -> Vec<Pixel> {
-        let content_size = Vec2D::new(
-            texture.lines().count() as isize,
-            texture.lines().map(str::len).max().unwrap_or(0) as isize,
-        );
+        let content_size = measure(texture, DEFAULT_TAB_WIDTH);
         let pos = align.apply_to(pos, content_size);
 
         Self::draw(pos, texture, modifier)
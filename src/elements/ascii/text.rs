@@ -3,9 +3,9 @@ use crate::elements::{
     Pixel, Vec2D,
 };
 
-use super::TextAlign;
+use super::{split_rows, TextAlign, DEFAULT_TAB_WIDTH};
 
-/// Displays text at the given position
+/// Displays text at the given position. Tabs are expanded to the next tab stop, newlines start a new row and any other control characters are left out
 #[non_exhaustive]
 #[derive(Debug, Clone)]
 pub struct Text {
@@ -17,25 +17,20 @@ pub struct Text {
     pub align: TextAlign,
     /// A raw [`Modifier`], determining the appearance of the `Text`
     pub modifier: Modifier,
+    /// The distance between tab stops when expanding tab characters. Defaults to [`DEFAULT_TAB_WIDTH`]
+    pub tab_width: usize,
 }
 
 impl Text {
-    /// Create a new Text element with a position, content and modifier
-    ///
-    /// # Panics
-    /// This function will panic if the content contains a newline, as Text only works with single lines. For multi-line strings, see [Sprite](super::Sprite)
+    /// Create a new Text element with a position, content and modifier. If the content contains newlines, each line will be displayed on its own row below the previous one
     #[must_use]
     pub fn new(pos: Vec2D, content: &str, modifier: Modifier) -> Self {
-        assert!(
-            !content.contains('\n'),
-            "Text was created with a content string containing a \n character"
-        );
-
         Self {
             pos,
             content: String::from(content),
             align: TextAlign::Begin,
             modifier,
+            tab_width: DEFAULT_TAB_WIDTH,
         }
     }
 
@@ -47,11 +42,18 @@ impl Text {
         tmp
     }
 
-    /// Return a vector of Pixels to display the given content
+    /// Return the `Text` with the modified tab width property
     #[must_use]
-    pub fn draw(pos: Vec2D, content: &str, modifier: Modifier) -> Vec<Pixel> {
+    pub const fn with_tab_width(self, tab_width: usize) -> Self {
+        let mut tmp = self;
+        tmp.tab_width = tab_width;
+        tmp
+    }
+
+    /// Return a vector of Pixels to display a single, already sanitised row of text
+    fn draw_row(pos: Vec2D, row: &str, modifier: Modifier) -> Vec<Pixel> {
         let mut pixels = vec![];
-        for (x, text_char) in (0isize..).zip(content.chars()) {
+        for (x, text_char) in (0isize..).zip(row.chars()) {
             if text_char != ' ' {
                 pixels.push(Pixel::new(
                     pos + Vec2D::new(x, 0),
@@ -66,7 +68,13 @@ impl Text {
         pixels
     }
 
-    /// Return a vector of Pixels to display the given content, aligning the content to the position as directed by the `align` attribute
+    /// Return a vector of Pixels to display the given content, expanding tabs with the [`DEFAULT_TAB_WIDTH`]
+    #[must_use]
+    pub fn draw(pos: Vec2D, content: &str, modifier: Modifier) -> Vec<Pixel> {
+        Self::draw_with_align(pos, content, TextAlign::Begin, modifier)
+    }
+
+    /// Return a vector of Pixels to display the given content, aligning the content to the position as directed by the `align` attribute. Each row is aligned separately
     #[must_use]
     pub fn draw_with_align(
         pos: Vec2D,
@@ -74,14 +82,40 @@ impl Text {
         align: TextAlign,
         modifier: Modifier,
     ) -> Vec<Pixel> {
-        let pos = Vec2D::new(align.apply_to(pos.x, content.len() as isize), pos.y);
+        Self::draw_with_tab_width(pos, content, align, DEFAULT_TAB_WIDTH, modifier)
+    }
+
+    /// Return a vector of Pixels to display the given content, aligning each row to the position as directed by the `align` attribute and expanding tabs to every `tab_width` columns
+    #[must_use]
+    pub fn draw_with_tab_width(
+        pos: Vec2D,
+        content: &str,
+        align: TextAlign,
+        tab_width: usize,
+        modifier: Modifier,
+    ) -> Vec<Pixel> {
+        let mut pixels = vec![];
 
-        Self::draw(pos, content, modifier)
+        for (y, row) in (0isize..).zip(split_rows(content, tab_width)) {
+            let row_pos = Vec2D::new(
+                align.apply_to(pos.x, row.chars().count() as isize),
+                pos.y + y,
+            );
+            pixels.extend(Self::draw_row(row_pos, &row, modifier));
+        }
+
+        pixels
     }
 }
 
 impl ViewElement for Text {
     fn active_pixels(&self) -> Vec<Pixel> {
-        Self::draw_with_align(self.pos, &self.content, self.align, self.modifier)
+        Self::draw_with_tab_width(
+            self.pos,
+            &self.content,
+            self.align,
+            self.tab_width,
+            self.modifier,
+        )
     }
 }
@@ -0,0 +1,233 @@
+use std::{f64::consts::TAU, time::Duration};
+
+use crate::{
+    elements::{
+        view::{Colour, Modifier, ViewElement},
+        Pixel, Vec2D,
+    },
+    gameloop::CanAnimate,
+    procgen::hash_cell,
+};
+
+/// An animated effect applied to every character of a text element by [`TextEffects`]. Each character is identified by its index in the element's [`active_pixels()`](ViewElement::active_pixels())
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextEffect {
+    /// Reveal the characters one at a time, as if being typed out
+    Typewriter {
+        /// How many characters are revealed every second
+        chars_per_second: f64,
+    },
+    /// Offset each character vertically along a sine wave that moves over time
+    Wave {
+        /// The maximum vertical offset of a character, in cells
+        amplitude: f64,
+        /// The number of characters between one peak of the wave and the next
+        wavelength: f64,
+        /// How many times the wave passes every character each second
+        speed: f64,
+    },
+    /// Colour each character with a hue that cycles over time, with neighbouring characters a little further along the cycle
+    Rainbow {
+        /// How far the hue (from 0 to 255) moves every second
+        speed: f64,
+        /// How far apart the hues of neighbouring characters are
+        spread: f64,
+    },
+    /// Jitter each character by a random offset every update
+    Shake {
+        /// The maximum offset of a character in each direction, in cells
+        intensity: isize,
+    },
+}
+
+impl TextEffect {
+    /// Create a [`TextEffect::Typewriter`] effect
+    #[must_use]
+    pub const fn typewriter(chars_per_second: f64) -> Self {
+        Self::Typewriter { chars_per_second }
+    }
+
+    /// Create a [`TextEffect::Wave`] effect
+    #[must_use]
+    pub const fn wave(amplitude: f64, wavelength: f64, speed: f64) -> Self {
+        Self::Wave {
+            amplitude,
+            wavelength,
+            speed,
+        }
+    }
+
+    /// Create a [`TextEffect::Rainbow`] effect
+    #[must_use]
+    pub const fn rainbow(speed: f64, spread: f64) -> Self {
+        Self::Rainbow { speed, spread }
+    }
+
+    /// Create a [`TextEffect::Shake`] effect
+    #[must_use]
+    pub const fn shake(intensity: isize) -> Self {
+        Self::Shake { intensity }
+    }
+
+    /// Apply the effect to the characters of an element, `elapsed` time after the effect started. `tick` should change every time the effect is updated, and is used to pick the offsets for [`TextEffect::Shake`]
+    #[must_use]
+    pub fn apply(self, pixels: Vec<Pixel>, elapsed: Duration, tick: u64) -> Vec<Pixel> {
+        let seconds = elapsed.as_secs_f64();
+
+        match self {
+            Self::Typewriter { chars_per_second } => {
+                let revealed = (seconds * chars_per_second).floor() as usize;
+                pixels.into_iter().take(revealed).collect()
+            }
+            Self::Wave {
+                amplitude,
+                wavelength,
+                speed,
+            } => (0..)
+                .zip(pixels)
+                .map(|(i, mut pixel)| {
+                    let phase =
+                        seconds.mul_add(-speed, f64::from(i) / wavelength.max(f64::EPSILON));
+                    let phase = phase * TAU;
+                    pixel.pos.y += (amplitude * phase.sin()).round() as isize;
+                    pixel
+                })
+                .collect(),
+            Self::Rainbow { speed, spread } => (0..)
+                .zip(pixels)
+                .map(|(i, mut pixel)| {
+                    let hue = f64::from(i)
+                        .mul_add(spread, seconds * speed)
+                        .rem_euclid(256.0);
+                    pixel.fill_char.modifier = Modifier::Colour(Colour::hsv(hue as u8, 255, 255));
+                    pixel
+                })
+                .collect(),
+            Self::Shake { intensity } => {
+                let range = intensity.unsigned_abs() * 2 + 1;
+                (0..)
+                    .zip(pixels)
+                    .map(|(i, mut pixel)| {
+                        let noise = hash_cell(tick, i, 0);
+                        let jitter = |n: u64| (n % range as u64) as isize - intensity.abs();
+                        pixel.pos += Vec2D::new(jitter(noise), jitter(noise >> 32));
+                        pixel
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// `TextEffects` is a container for a [`ViewElement`] (usually a [`Text`](super::Text) or [`PathText`](super::PathText)) that animates each of its characters with a list of [`TextEffect`]s. The effects are applied in order, so they can be freely combined. Call [`update()`](TextEffects::update()) every frame to move the animation forward
+#[derive(Debug, Clone)]
+pub struct TextEffects<E: ViewElement> {
+    /// The element held by the `TextEffects`. Must implement [`ViewElement`]
+    pub element: E,
+    /// The effects applied to the element's characters, in order
+    pub effects: Vec<TextEffect>,
+    /// How long the effects have been running for
+    pub elapsed: Duration,
+    tick: u64,
+}
+
+impl<E: ViewElement> TextEffects<E> {
+    /// Create a new `TextEffects` with no effects
+    pub const fn new(element: E) -> Self {
+        Self {
+            element,
+            effects: vec![],
+            elapsed: Duration::ZERO,
+            tick: 0,
+        }
+    }
+
+    /// Return the `TextEffects` with another effect added to the end of the list
+    #[must_use]
+    pub fn with_effect(mut self, effect: TextEffect) -> Self {
+        self.effects.push(effect);
+        self
+    }
+
+    /// Move the animation forward by `delta`, usually the time since the last frame
+    pub fn update(&mut self, delta: Duration) {
+        self.elapsed += delta;
+        self.tick = self.tick.wrapping_add(1);
+    }
+
+    /// Restart the animation from the beginning
+    pub const fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+        self.tick = 0;
+    }
+
+    /// Returns true once every [`TextEffect::Typewriter`] effect has revealed all of the element's characters. Always true if there are no typewriter effects
+    #[must_use]
+    pub fn is_fully_revealed(&self) -> bool {
+        let char_count = self.element.active_pixels().len() as f64;
+
+        self.effects.iter().all(|effect| match effect {
+            TextEffect::Typewriter { chars_per_second } => {
+                self.elapsed.as_secs_f64() * chars_per_second >= char_count
+            }
+            _ => true,
+        })
+    }
+}
+
+impl<E: ViewElement> ViewElement for TextEffects<E> {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        self.effects
+            .iter()
+            .fold(self.element.active_pixels(), |pixels, effect| {
+                effect.apply(pixels, self.elapsed, self.tick)
+            })
+    }
+}
+
+impl<E: ViewElement> CanAnimate for TextEffects<E> {
+    /// A `TextEffects` is animating while a typewriter effect is still revealing characters, or while it has any wave, rainbow or shake effect
+    fn is_animating(&self) -> bool {
+        let continuous = self
+            .effects
+            .iter()
+            .any(|effect| !matches!(effect, TextEffect::Typewriter { .. }));
+
+        continuous || !self.is_fully_revealed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::Text;
+
+    #[test]
+    fn typewriter_reveals_over_time() {
+        let mut text = TextEffects::new(Text::new(Vec2D::ZERO, "abcd", Modifier::None))
+            .with_effect(TextEffect::typewriter(2.0));
+        assert!(text.active_pixels().is_empty());
+
+        text.update(Duration::from_millis(1500));
+        assert_eq!(text.active_pixels().len(), 3);
+        assert!(!text.is_fully_revealed());
+
+        text.update(Duration::from_millis(500));
+        assert!(text.is_fully_revealed());
+    }
+
+    #[test]
+    fn shake_stays_within_intensity() {
+        let mut text = TextEffects::new(Text::new(Vec2D::ZERO, "shake it", Modifier::None))
+            .with_effect(TextEffect::shake(1));
+
+        for _ in 0..10 {
+            text.update(Duration::from_millis(16));
+            let original = text.element.active_pixels();
+            for (shaken, pixel) in text.active_pixels().iter().zip(original) {
+                let offset = shaken.pos - pixel.pos;
+                assert!(offset.x.abs() <= 1 && offset.y.abs() <= 1);
+            }
+        }
+    }
+}
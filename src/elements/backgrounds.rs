@@ -0,0 +1,49 @@
+//! This module holds procedural background elements such as [`Starfield`] and [`NoiseBackground`]. They are generated from a seed rather than stored, so they can scroll forever and follow the camera without using more memory
+
+mod noise_background;
+pub use noise_background::NoiseBackground;
+
+mod starfield;
+pub use starfield::{StarLayer, Starfield};
+
+pub use crate::procgen::{hash_cell, random_unit};
+
+/// Return smooth value noise between 0.0 and 1.0 at the position, interpolating between random values at each whole coordinate
+#[must_use]
+pub fn value_noise(seed: u64, x: f64, y: f64) -> f64 {
+    let (cell_x, cell_y) = (x.floor(), y.floor());
+    let smooth = |t: f64| t * t * 2.0f64.mul_add(-t, 3.0);
+    let (tx, ty) = (smooth(x - cell_x), smooth(y - cell_y));
+    let (cell_x, cell_y) = (cell_x as i64, cell_y as i64);
+
+    let lerp = |a: f64, b: f64, t: f64| (b - a).mul_add(t, a);
+    let top = lerp(
+        random_unit(seed, cell_x, cell_y),
+        random_unit(seed, cell_x + 1, cell_y),
+        tx,
+    );
+    let bottom = lerp(
+        random_unit(seed, cell_x, cell_y + 1),
+        random_unit(seed, cell_x + 1, cell_y + 1),
+        tx,
+    );
+
+    lerp(top, bottom, ty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn noise_is_deterministic_and_bounded() {
+        assert_eq!(hash_cell(1, 5, -3), hash_cell(1, 5, -3));
+        assert_ne!(hash_cell(1, 5, -3), hash_cell(2, 5, -3));
+
+        for i in 0..100 {
+            let value = value_noise(7, f64::from(i) * 0.37, f64::from(i) * -0.61);
+            assert!((0.0..=1.0).contains(&value));
+        }
+        assert!((value_noise(7, 3.0, 4.0) - random_unit(7, 3, 4)).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,155 @@
+use super::value_noise;
+use crate::{
+    elements::{
+        view::{ColChar, ViewElement},
+        Pixel, Vec2D,
+    },
+    gameloop::CanAnimate,
+};
+use std::time::Duration;
+
+/// A `NoiseBackground` fills an area with smooth, cloud-like noise, picking each cell's [`ColChar`] from a ramp of levels. It can scroll with a camera or on its own, for nebulae, clouds or water. Levels drawn with a space are left empty so that elements behind can show through
+#[derive(Debug, Clone)]
+pub struct NoiseBackground {
+    /// The position of the top left corner of the `NoiseBackground`
+    pub pos: Vec2D,
+    /// The width and height of the `NoiseBackground`
+    pub size: Vec2D,
+    /// The `ColChar`s used for each level of noise, from lowest to highest
+    pub levels: Vec<ColChar>,
+    /// The size of the noise's features in cells. Larger values give larger, smoother blobs
+    pub scale: f64,
+    /// The number of layers of finer noise added on top for detail
+    pub octaves: u32,
+    /// How far the noise moves relative to the camera. 1.0 moves with the camera, 0.0 stays still
+    pub parallax: f64,
+    /// The position of the camera, as an `(x, y)` pair which can move by fractions of a cell
+    pub camera: (f64, f64),
+    /// How far the camera moves every second when [`update()`](NoiseBackground::update()) is called, in cells
+    pub velocity: (f64, f64),
+    /// The seed the noise is generated with
+    pub seed: u64,
+}
+
+impl NoiseBackground {
+    /// Create a new `NoiseBackground` with a subtle ramp of shading characters
+    #[must_use]
+    pub fn new(pos: Vec2D, size: Vec2D, seed: u64) -> Self {
+        Self {
+            pos,
+            size,
+            levels: [' ', ' ', '.', ':', '░']
+                .map(|c| ColChar::EMPTY.with_char(c))
+                .to_vec(),
+            scale: 8.0,
+            octaves: 3,
+            parallax: 1.0,
+            camera: (0.0, 0.0),
+            velocity: (0.0, 0.0),
+            seed,
+        }
+    }
+
+    /// Return the `NoiseBackground` with the modified levels
+    #[must_use]
+    pub fn with_levels(mut self, levels: Vec<ColChar>) -> Self {
+        self.levels = levels;
+        self
+    }
+
+    /// Return the `NoiseBackground` with the modified scale and octaves
+    #[must_use]
+    pub const fn with_scale(mut self, scale: f64, octaves: u32) -> Self {
+        self.scale = scale;
+        self.octaves = octaves;
+        self
+    }
+
+    /// Return the `NoiseBackground` with the modified parallax
+    #[must_use]
+    pub const fn with_parallax(mut self, parallax: f64) -> Self {
+        self.parallax = parallax;
+        self
+    }
+
+    /// Return the `NoiseBackground` with the modified velocity
+    #[must_use]
+    pub const fn with_velocity(mut self, x: f64, y: f64) -> Self {
+        self.velocity = (x, y);
+        self
+    }
+
+    /// Move the camera to the given offset, such as the position of the player
+    pub const fn set_camera(&mut self, offset: Vec2D) {
+        self.camera = (offset.x as f64, offset.y as f64);
+    }
+
+    /// Scroll the camera by the `NoiseBackground`'s velocity for `delta`, usually the time since the last frame
+    pub const fn update(&mut self, delta: Duration) {
+        let seconds = delta.as_secs_f64();
+        self.camera.0 = self.velocity.0.mul_add(seconds, self.camera.0);
+        self.camera.1 = self.velocity.1.mul_add(seconds, self.camera.1);
+    }
+
+    /// Return the noise value between 0.0 and 1.0 at a position in the world
+    #[must_use]
+    pub fn sample(&self, x: f64, y: f64) -> f64 {
+        let (mut total, mut amplitude, mut frequency, mut max) = (0.0, 1.0, 1.0, 0.0);
+
+        for octave in 0..self.octaves.max(1) {
+            let seed = self.seed.wrapping_add(u64::from(octave));
+            let scale = self.scale.max(f64::EPSILON) / frequency;
+            total += value_noise(seed, x / scale, y / scale) * amplitude;
+            max += amplitude;
+            amplitude /= 2.0;
+            frequency *= 2.0;
+        }
+
+        total / max
+    }
+}
+
+impl ViewElement for NoiseBackground {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        if self.levels.is_empty() {
+            return vec![];
+        }
+        let shift_x = self.camera.0 * self.parallax;
+        let shift_y = self.camera.1 * self.parallax;
+        let mut pixels = vec![];
+
+        for y in 0..self.size.y {
+            for x in 0..self.size.x {
+                let value = self.sample(x as f64 + shift_x, y as f64 + shift_y);
+                let level =
+                    ((value * self.levels.len() as f64) as usize).min(self.levels.len() - 1);
+                let fill_char = self.levels[level];
+
+                if fill_char.text_char != ' ' {
+                    pixels.push(Pixel::new(self.pos + Vec2D::new(x, y), fill_char));
+                }
+            }
+        }
+
+        pixels
+    }
+}
+
+impl CanAnimate for NoiseBackground {
+    fn is_animating(&self) -> bool {
+        self.velocity != (0.0, 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_with_every_level_drawn() {
+        let background = NoiseBackground::new(Vec2D::ZERO, Vec2D::new(30, 10), 1)
+            .with_levels(vec![ColChar::SOLID, ColChar::BACKGROUND]);
+
+        assert_eq!(background.active_pixels().len(), 300);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{
+    elements::{
+        view::{ColChar, ViewElement},
+        Pixel, Vec2D,
+    },
+    gameloop::CanAnimate,
+    procgen::{hash_cell, random_unit},
+};
+use std::time::Duration;
+
+/// A single layer of stars in a [`Starfield`]. Layers with a lower `parallax` move more slowly, making them appear further away
+#[derive(Debug, Clone, PartialEq)]
+pub struct StarLayer {
+    /// The chance of any one cell holding a star, between 0.0 and 1.0
+    pub density: f64,
+    /// How far the layer moves relative to the camera. 1.0 moves with the camera, 0.0 stays still
+    pub parallax: f64,
+    /// The characters stars on this layer can be drawn with, picked at random for each star
+    pub stars: Vec<ColChar>,
+}
+
+impl StarLayer {
+    /// Create a new `StarLayer` drawn with a single `ColChar`
+    #[must_use]
+    pub fn new(density: f64, parallax: f64, star: ColChar) -> Self {
+        Self {
+            density,
+            parallax,
+            stars: vec![star],
+        }
+    }
+
+    /// Return the `StarLayer` with the modified star characters
+    #[must_use]
+    pub fn with_stars(mut self, stars: Vec<ColChar>) -> Self {
+        self.stars = stars;
+        self
+    }
+}
+
+/// A `Starfield` fills an area with procedurally placed stars across any number of [`StarLayer`]s, each scrolling at its own speed for a parallax effect. Set the camera with [`Starfield::set_camera()`] to follow the player, or give the `Starfield` a velocity and call [`update()`](Starfield::update()) every frame to scroll it on its own
+#[derive(Debug, Clone)]
+pub struct Starfield {
+    /// The position of the top left corner of the `Starfield`
+    pub pos: Vec2D,
+    /// The width and height of the `Starfield`
+    pub size: Vec2D,
+    /// The layers of stars, drawn in order so later layers appear on top
+    pub layers: Vec<StarLayer>,
+    /// The position of the camera, as an `(x, y)` pair which can move by fractions of a cell
+    pub camera: (f64, f64),
+    /// How far the camera moves every second when [`update()`](Starfield::update()) is called, in cells
+    pub velocity: (f64, f64),
+    /// The seed stars are placed with. Different seeds give different starfields
+    pub seed: u64,
+}
+
+impl Starfield {
+    /// Create a new `Starfield` with no layers
+    #[must_use]
+    pub const fn new(pos: Vec2D, size: Vec2D, seed: u64) -> Self {
+        Self {
+            pos,
+            size,
+            layers: vec![],
+            camera: (0.0, 0.0),
+            velocity: (0.0, 0.0),
+            seed,
+        }
+    }
+
+    /// Return the `Starfield` with an added layer
+    #[must_use]
+    pub fn with_layer(mut self, layer: StarLayer) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    /// Return the `Starfield` with the modified velocity
+    #[must_use]
+    pub const fn with_velocity(mut self, x: f64, y: f64) -> Self {
+        self.velocity = (x, y);
+        self
+    }
+
+    /// Move the camera to the given offset, such as the position of the player
+    pub const fn set_camera(&mut self, offset: Vec2D) {
+        self.camera = (offset.x as f64, offset.y as f64);
+    }
+
+    /// Scroll the camera by the `Starfield`'s velocity for `delta`, usually the time since the last frame
+    pub const fn update(&mut self, delta: Duration) {
+        let seconds = delta.as_secs_f64();
+        self.camera.0 = self.velocity.0.mul_add(seconds, self.camera.0);
+        self.camera.1 = self.velocity.1.mul_add(seconds, self.camera.1);
+    }
+}
+
+impl ViewElement for Starfield {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let mut pixels = vec![];
+
+        for (layer, layer_seed) in self
+            .layers
+            .iter()
+            .zip((0..).map(|i| self.seed.wrapping_add(i)))
+        {
+            if layer.stars.is_empty() {
+                continue;
+            }
+            let shift_x = (self.camera.0 * layer.parallax).round() as i64;
+            let shift_y = (self.camera.1 * layer.parallax).round() as i64;
+
+            for y in 0..self.size.y {
+                for x in 0..self.size.x {
+                    let (world_x, world_y) = (x as i64 + shift_x, y as i64 + shift_y);
+                    if random_unit(layer_seed, world_x, world_y) >= layer.density {
+                        continue;
+                    }
+
+                    let star_index = hash_cell(!layer_seed, world_x, world_y) as usize;
+                    pixels.push(Pixel::new(
+                        self.pos + Vec2D::new(x, y),
+                        layer.stars[star_index % layer.stars.len()],
+                    ));
+                }
+            }
+        }
+
+        pixels
+    }
+}
+
+impl CanAnimate for Starfield {
+    fn is_animating(&self) -> bool {
+        self.velocity != (0.0, 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stars_scroll_with_parallax() {
+        let mut starfield = Starfield::new(Vec2D::ZERO, Vec2D::new(40, 20), 3)
+            .with_layer(StarLayer::new(0.1, 1.0, ColChar::SOLID));
+        let before = starfield.active_pixels();
+        assert!(!before.is_empty());
+
+        starfield.set_camera(Vec2D::new(1, 0));
+        let after = starfield.active_pixels();
+
+        // Every star still on screen should have moved one cell to the left
+        for pixel in after.iter().filter(|p| p.pos.x < 39) {
+            assert!(before.iter().any(|p| p.pos == pixel.pos + Vec2D::new(1, 0)));
+        }
+    }
+
+    #[test]
+    fn layers_past_the_largest_seed_wrap() {
+        let starfield = Starfield::new(Vec2D::ZERO, Vec2D::new(10, 5), u64::MAX)
+            .with_layer(StarLayer::new(0.5, 1.0, ColChar::SOLID))
+            .with_layer(StarLayer::new(0.5, 0.5, ColChar::SOLID));
+        assert!(!starfield.active_pixels().is_empty());
+    }
+}
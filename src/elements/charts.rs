@@ -0,0 +1,136 @@
+//! This module holds data visualisation elements for dashboards and monitoring tools, such as [`Sparkline`], [`BarChart`], [`LineGraph`] and [`Heatmap`]. Every chart scales its values to fit automatically unless given a fixed range, and can colour values with [`ColourThresholds`]. Record live data such as frame times with a [`Trace`] to plot it
+
+mod bar_chart;
+pub use bar_chart::BarChart;
+
+mod heatmap;
+pub use heatmap::{ColourMap, Heatmap};
+
+mod line_graph;
+pub use line_graph::LineGraph;
+
+mod sparkline;
+pub use sparkline::Sparkline;
+
+mod trace;
+pub use trace::Trace;
+
+use super::view::Modifier;
+
+/// Block characters filling the bottom of a cell in eighths, from empty to full
+pub const EIGHTH_BLOCKS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Picks the [`Modifier`] for a value in a chart, for example to draw values above a warning level in yellow and above a critical level in red
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ColourThresholds {
+    /// The `Modifier` used for values below every threshold
+    pub default: Modifier,
+    /// The thresholds, as pairs of the lowest value and the `Modifier` to use from that value upwards
+    pub thresholds: Vec<(f64, Modifier)>,
+}
+
+impl ColourThresholds {
+    /// Create a new `ColourThresholds` with no thresholds, using the `default` for every value
+    #[must_use]
+    pub const fn new(default: Modifier) -> Self {
+        Self {
+            default,
+            thresholds: vec![],
+        }
+    }
+
+    /// Return the `ColourThresholds` with another threshold, above which values use the `modifier`
+    #[must_use]
+    pub fn with_threshold(mut self, value: f64, modifier: Modifier) -> Self {
+        self.thresholds.push((value, modifier));
+        self
+    }
+
+    /// Return the `Modifier` for the value: that of the highest threshold the value reaches, or the default if it doesn't reach any
+    #[must_use]
+    pub fn modifier_for(&self, value: f64) -> Modifier {
+        self.thresholds
+            .iter()
+            .filter(|(threshold, _)| value >= *threshold)
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map_or(self.default, |(_, modifier)| *modifier)
+    }
+}
+
+impl From<Modifier> for ColourThresholds {
+    fn from(value: Modifier) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Return the range a chart should scale its data to: the fixed `range` if given, otherwise the lowest and highest finite values in the data. A range with no size is widened so that values can still be scaled
+#[must_use]
+pub fn data_range(data: &[f64], range: Option<(f64, f64)>) -> (f64, f64) {
+    let (min, max) = range.unwrap_or_else(|| {
+        data.iter()
+            .filter(|value| value.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                (min.min(*value), max.max(*value))
+            })
+    });
+
+    if !min.is_finite() || !max.is_finite() {
+        (0.0, 1.0)
+    } else if (max - min).abs() < f64::EPSILON {
+        (min - 0.5, max + 0.5)
+    } else {
+        (min, max)
+    }
+}
+
+/// Scale the value to between 0.0 and 1.0 within the range, clamping values outside it
+#[must_use]
+pub fn normalise(value: f64, (min, max): (f64, f64)) -> f64 {
+    ((value - min) / (max - min)).clamp(0.0, 1.0)
+}
+
+/// Return the braille character with the given dots raised, where `dots` holds a row of two bits for each of the four rows of the cell, top to bottom, each with the left dot in the lowest bit
+#[must_use]
+pub fn braille_char(dots: [[bool; 2]; 4]) -> char {
+    const BITS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+    let mut code = 0x2800;
+    for (row, row_bits) in dots.iter().zip(BITS) {
+        for (dot, bit) in row.iter().zip(row_bits) {
+            if *dot {
+                code |= bit;
+            }
+        }
+    }
+
+    char::from_u32(code).unwrap_or(' ')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thresholds_pick_highest_reached() {
+        let thresholds = ColourThresholds::new(Modifier::GREEN)
+            .with_threshold(90.0, Modifier::RED)
+            .with_threshold(70.0, Modifier::YELLOW);
+
+        assert_eq!(thresholds.modifier_for(10.0), Modifier::GREEN);
+        assert_eq!(thresholds.modifier_for(75.0), Modifier::YELLOW);
+        assert_eq!(thresholds.modifier_for(95.0), Modifier::RED);
+    }
+
+    #[test]
+    fn range_and_braille() {
+        assert_eq!(data_range(&[3.0, f64::NAN, -1.0], None), (-1.0, 3.0));
+        assert_eq!(data_range(&[2.0, 2.0], None), (1.5, 2.5));
+        assert_eq!(data_range(&[], None), (0.0, 1.0));
+
+        assert_eq!(braille_char([[false; 2]; 4]), '⠀');
+        assert_eq!(
+            braille_char([[true, false], [false, false], [false, false], [false, true]]),
+            '⢁'
+        );
+    }
+}
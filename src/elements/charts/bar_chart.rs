@@ -0,0 +1,119 @@
+use super::{data_range, normalise, ColourThresholds, EIGHTH_BLOCKS};
+use crate::elements::{
+    view::{ColChar, ViewElement},
+    Pixel, Vec2D,
+};
+
+/// A `BarChart` draws each value as a vertical bar growing up from the bottom of the chart. The top of each bar is drawn with a partial block character, giving eight steps of resolution per cell
+#[derive(Debug, Clone)]
+pub struct BarChart {
+    /// The position of the top left corner of the `BarChart`
+    pub pos: Vec2D,
+    /// The height of the `BarChart` in cells
+    pub height: usize,
+    /// The values to draw, one bar each from left to right
+    pub data: Vec<f64>,
+    /// The width of each bar in cells
+    pub bar_width: usize,
+    /// The number of empty cells between bars
+    pub gap: usize,
+    /// The range the values are scaled to. If `None`, the bars are scaled from 0 (or the lowest value, if it's negative) up to the highest value
+    pub range: Option<(f64, f64)>,
+    /// The colours of the bars
+    pub colours: ColourThresholds,
+}
+
+impl BarChart {
+    /// Create a new `BarChart` with a position, height and data. Bars are one cell wide with a one cell gap
+    #[must_use]
+    pub fn new(pos: Vec2D, height: usize, data: Vec<f64>) -> Self {
+        Self {
+            pos,
+            height,
+            data,
+            bar_width: 1,
+            gap: 1,
+            range: None,
+            colours: ColourThresholds::default(),
+        }
+    }
+
+    /// Return the `BarChart` with the modified bar width and gap
+    #[must_use]
+    pub const fn with_bar_width(mut self, bar_width: usize, gap: usize) -> Self {
+        self.bar_width = bar_width;
+        self.gap = gap;
+        self
+    }
+
+    /// Return the `BarChart` with a fixed range
+    #[must_use]
+    pub const fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Return the `BarChart` with the modified colours
+    #[must_use]
+    pub fn with_colours(mut self, colours: impl Into<ColourThresholds>) -> Self {
+        self.colours = colours.into();
+        self
+    }
+
+    /// Return the width of the whole `BarChart` in cells
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        (self.bar_width + self.gap) * self.data.len().saturating_sub(1) + self.bar_width
+    }
+}
+
+impl ViewElement for BarChart {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let range = self.range.unwrap_or_else(|| {
+            let (min, max) = data_range(&self.data, None);
+            (min.min(0.0), max)
+        });
+        let mut pixels = vec![];
+
+        for (i, value) in self.data.iter().enumerate() {
+            if !value.is_finite() {
+                continue;
+            }
+            let eighths = (normalise(*value, range) * (self.height * 8) as f64).round() as usize;
+            let modifier = self.colours.modifier_for(*value);
+            let left = (i * (self.bar_width + self.gap)) as isize;
+
+            for row in 0..self.height {
+                let level = eighths.saturating_sub(row * 8).min(8);
+                if level == 0 {
+                    break;
+                }
+                let y = (self.height - 1 - row) as isize;
+                for x in 0..self.bar_width as isize {
+                    pixels.push(Pixel::new(
+                        self.pos + Vec2D::new(left + x, y),
+                        ColChar::new(EIGHTH_BLOCKS[level], modifier),
+                    ));
+                }
+            }
+        }
+
+        pixels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bars_scale_from_zero() {
+        let chart = BarChart::new(Vec2D::ZERO, 2, vec![10.0, 5.0, 0.0]);
+        let pixels = chart.active_pixels();
+
+        assert_eq!(chart.width(), 5);
+        assert_eq!(pixels.len(), 3);
+        assert_eq!(pixels[2].pos, Vec2D::new(2, 1));
+        assert_eq!(pixels[2].fill_char.text_char, '█');
+    }
+}
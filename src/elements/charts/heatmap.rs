@@ -0,0 +1,167 @@
+use super::{data_range, normalise};
+use crate::elements::{
+    view::{ColChar, Colour, Gradient, Modifier, ViewElement},
+    Pixel, Vec2D,
+};
+
+/// The viridis colour map, sampled at evenly spaced stops
+const VIRIDIS: [Colour; 9] = [
+    Colour::rgb(68, 1, 84),
+    Colour::rgb(70, 50, 126),
+    Colour::rgb(54, 92, 141),
+    Colour::rgb(39, 127, 142),
+    Colour::rgb(31, 161, 135),
+    Colour::rgb(74, 193, 109),
+    Colour::rgb(160, 218, 57),
+    Colour::rgb(208, 225, 28),
+    Colour::rgb(253, 231, 37),
+];
+
+/// The inferno colour map, sampled at evenly spaced stops
+const INFERNO: [Colour; 9] = [
+    Colour::rgb(0, 0, 4),
+    Colour::rgb(31, 12, 72),
+    Colour::rgb(85, 15, 109),
+    Colour::rgb(136, 34, 106),
+    Colour::rgb(186, 54, 85),
+    Colour::rgb(227, 89, 51),
+    Colour::rgb(249, 140, 10),
+    Colour::rgb(249, 201, 50),
+    Colour::rgb(252, 255, 164),
+];
+
+/// A colour map turns a value between 0.0 and 1.0 into a [`Colour`] by sampling a [`Gradient`]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ColourMap {
+    /// The perceptually uniform viridis colour map, running from dark purple through teal to yellow
+    #[default]
+    Viridis,
+    /// The perceptually uniform inferno colour map, running from black through red and orange to pale yellow
+    Inferno,
+    /// A custom [`Gradient`]
+    Gradient(Gradient),
+}
+
+impl ColourMap {
+    /// Return the `ColourMap` as a [`Gradient`], with the viridis and inferno colours spread evenly from 0.0 to 1.0
+    #[must_use]
+    pub fn gradient(&self) -> Gradient {
+        match self {
+            Self::Viridis => Gradient::even(&VIRIDIS),
+            Self::Inferno => Gradient::even(&INFERNO),
+            Self::Gradient(gradient) => gradient.clone(),
+        }
+    }
+
+    /// Return the colour at `t`, which is clamped to between 0.0 and 1.0. A `Gradient` with no colours always returns black
+    #[must_use]
+    pub fn colour_at(&self, t: f64) -> Colour {
+        self.gradient().sample(t.clamp(0.0, 1.0))
+    }
+}
+
+/// A `Heatmap` draws a 2D grid of values as solid cells, coloured by passing each value through a [`ColourMap`]. Useful for visualising noise, terrain heights or any other grid of measurements
+#[derive(Debug, Clone)]
+pub struct Heatmap {
+    /// The position of the top left cell of the `Heatmap`
+    pub pos: Vec2D,
+    /// The values to draw, as a list of rows from top to bottom
+    pub values: Vec<Vec<f64>>,
+    /// The range the values are scaled to before being passed through the colour map. If `None`, the range is taken from the values
+    pub range: Option<(f64, f64)>,
+    /// The colour map the values are passed through
+    pub colour_map: ColourMap,
+    /// The character each cell is drawn with. Defaults to a full block so that the colour fills the whole cell
+    pub fill_char: char,
+}
+
+impl Heatmap {
+    /// Create a new `Heatmap` with a position and a grid of values, using the viridis colour map
+    #[must_use]
+    pub fn new(pos: Vec2D, values: Vec<Vec<f64>>) -> Self {
+        Self {
+            pos,
+            values,
+            range: None,
+            colour_map: ColourMap::default(),
+            fill_char: ColChar::SOLID.text_char,
+        }
+    }
+
+    /// Return the `Heatmap` with a fixed range
+    #[must_use]
+    pub const fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Return the `Heatmap` with the modified colour map
+    #[must_use]
+    pub fn with_colour_map(mut self, colour_map: ColourMap) -> Self {
+        self.colour_map = colour_map;
+        self
+    }
+
+    /// Return the `Heatmap` with the modified fill character
+    #[must_use]
+    pub const fn with_fill_char(mut self, fill_char: char) -> Self {
+        self.fill_char = fill_char;
+        self
+    }
+}
+
+impl ViewElement for Heatmap {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let all_values: Vec<f64> = self.values.iter().flatten().copied().collect();
+        let range = data_range(&all_values, self.range);
+        let gradient = self.colour_map.gradient();
+
+        (0..)
+            .zip(&self.values)
+            .flat_map(|(y, row)| {
+                (0..)
+                    .zip(row)
+                    .map(move |(x, value)| (Vec2D::new(x, y), value))
+            })
+            .filter(|(_, value)| value.is_finite())
+            .map(|(offset, value)| {
+                let colour = gradient.sample(normalise(*value, range).clamp(0.0, 1.0));
+                Pixel::new(
+                    self.pos + offset,
+                    ColChar::new(self.fill_char, Modifier::Colour(colour)),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colour_map_interpolates_stops() {
+        let map = ColourMap::Gradient(Gradient::even(&[Colour::BLACK, Colour::rgb(200, 100, 0)]));
+
+        assert_eq!(map.colour_at(0.5), Colour::rgb(100, 50, 0));
+        assert_eq!(map.colour_at(2.0), Colour::rgb(200, 100, 0));
+        assert_eq!(ColourMap::Viridis.colour_at(0.0), VIRIDIS[0]);
+        assert_eq!(ColourMap::Inferno.colour_at(0.5), INFERNO[4]);
+        assert_eq!(
+            ColourMap::Gradient(Gradient::even(&[])).colour_at(0.3),
+            Colour::BLACK
+        );
+    }
+
+    #[test]
+    fn heatmap_maps_extremes_to_ends() {
+        let heatmap = Heatmap::new(Vec2D::ZERO, vec![vec![0.0, 1.0], vec![f64::NAN, 0.5]])
+            .with_colour_map(ColourMap::Inferno);
+        let pixels = heatmap.active_pixels();
+
+        assert_eq!(pixels.len(), 3);
+        assert_eq!(pixels[0].fill_char.modifier, Modifier::Colour(INFERNO[0]));
+        assert_eq!(pixels[1].fill_char.modifier, Modifier::Colour(INFERNO[8]));
+        assert_eq!(pixels[2].pos, Vec2D::new(1, 1));
+    }
+}
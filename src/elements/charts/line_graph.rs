@@ -0,0 +1,162 @@
+use super::{braille_char, data_range, normalise, ColourThresholds};
+use crate::elements::{
+    view::{ColChar, ViewElement},
+    Line, Pixel, Vec2D,
+};
+
+/// A `LineGraph` plots values as a line using braille characters, which split every cell into a 2x4 grid of dots for a much higher resolution than whole cells. The values are spread evenly across the width of the graph, with an optional axis along the left and bottom edges
+#[derive(Debug, Clone)]
+pub struct LineGraph {
+    /// The position of the top left corner of the plotting area. Axes are drawn outside of it
+    pub pos: Vec2D,
+    /// The width and height of the plotting area in cells
+    pub size: Vec2D,
+    /// The values to plot, from left to right
+    pub data: Vec<f64>,
+    /// The range the values are scaled to. If `None`, the range is taken from the data
+    pub range: Option<(f64, f64)>,
+    /// The colours of the line. Each cell is coloured by the highest value plotted in it
+    pub colours: ColourThresholds,
+    /// Whether to draw an axis along the left and bottom edges of the plotting area
+    pub show_axes: bool,
+}
+
+impl LineGraph {
+    /// Create a new `LineGraph` with a position, size and data. The range is scaled to fit the data
+    #[must_use]
+    pub fn new(pos: Vec2D, size: Vec2D, data: Vec<f64>) -> Self {
+        Self {
+            pos,
+            size,
+            data,
+            range: None,
+            colours: ColourThresholds::default(),
+            show_axes: false,
+        }
+    }
+
+    /// Return the `LineGraph` with a fixed range
+    #[must_use]
+    pub const fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Return the `LineGraph` with the modified colours
+    #[must_use]
+    pub fn with_colours(mut self, colours: impl Into<ColourThresholds>) -> Self {
+        self.colours = colours.into();
+        self
+    }
+
+    /// Return the `LineGraph` with the modified `show_axes` property
+    #[must_use]
+    pub const fn with_axes(mut self, show_axes: bool) -> Self {
+        self.show_axes = show_axes;
+        self
+    }
+
+    /// Return the positions of the plotted values on the grid of braille dots, along with the values themselves
+    fn dot_positions(&self) -> Vec<(Vec2D, f64)> {
+        let range = data_range(&self.data, self.range);
+        let dots = self.size * Vec2D::new(2, 4);
+        let last_index = self.data.len().saturating_sub(1).max(1) as f64;
+
+        self.data
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| value.is_finite())
+            .map(|(i, value)| {
+                let x = (i as f64 / last_index * (dots.x - 1) as f64).round() as isize;
+                let y = ((1.0 - normalise(*value, range)) * (dots.y - 1) as f64).round() as isize;
+                (Vec2D::new(x, y), *value)
+            })
+            .collect()
+    }
+
+    /// Return the axes drawn along the left and bottom edges of the plotting area
+    fn axes(&self) -> Vec<Pixel> {
+        let fill_char = ColChar::new('│', self.colours.default);
+        let mut pixels: Vec<Pixel> = (0..self.size.y)
+            .map(|y| Pixel::new(self.pos + Vec2D::new(-1, y), fill_char))
+            .collect();
+        pixels.push(Pixel::new(
+            self.pos + Vec2D::new(-1, self.size.y),
+            fill_char.with_char('└'),
+        ));
+        pixels.extend((0..self.size.x).map(|x| {
+            Pixel::new(
+                self.pos + Vec2D::new(x, self.size.y),
+                fill_char.with_char('─'),
+            )
+        }));
+
+        pixels
+    }
+}
+
+impl ViewElement for LineGraph {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let width = self.size.x.max(0) as usize;
+        let height = self.size.y.max(0) as usize;
+        let mut cells: Vec<Option<([[bool; 2]; 4], f64)>> = vec![None; width * height];
+
+        let points = self.dot_positions();
+        let segments: Vec<(Vec<Vec2D>, f64)> = if points.len() == 1 {
+            vec![(vec![points[0].0], points[0].1)]
+        } else {
+            points
+                .windows(2)
+                .map(|pair| (Line::draw(pair[0].0, pair[1].0), pair[0].1.max(pair[1].1)))
+                .collect()
+        };
+
+        for (dots, value) in segments {
+            for dot in dots {
+                let (cell_x, cell_y) = (dot.x / 2, dot.y / 4);
+                if dot.x < 0 || dot.y < 0 || cell_x >= self.size.x || cell_y >= self.size.y {
+                    continue;
+                }
+
+                let cell = &mut cells[(cell_y * self.size.x + cell_x) as usize];
+                let (cell_dots, cell_value) = cell.get_or_insert(([[false; 2]; 4], value));
+                cell_dots[(dot.y % 4) as usize][(dot.x % 2) as usize] = true;
+                *cell_value = cell_value.max(value);
+            }
+        }
+
+        let mut pixels: Vec<Pixel> = (0..)
+            .zip(cells)
+            .filter_map(|(i, cell)| {
+                cell.map(|(dots, value)| {
+                    Pixel::new(
+                        self.pos + Vec2D::new(i % self.size.x, i / self.size.x),
+                        ColChar::new(braille_char(dots), self.colours.modifier_for(value)),
+                    )
+                })
+            })
+            .collect();
+
+        if self.show_axes {
+            pixels.extend(self.axes());
+        }
+
+        pixels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plots_rising_line_across_graph() {
+        let graph = LineGraph::new(Vec2D::ZERO, Vec2D::new(2, 1), vec![0.0, 1.0]);
+        let pixels = graph.active_pixels();
+
+        assert_eq!(pixels.len(), 2);
+        assert_eq!(pixels[0].pos, Vec2D::new(0, 0));
+        assert_eq!(pixels[0].fill_char.text_char, '⡠');
+        assert_eq!(pixels[1].fill_char.text_char, '⠊');
+    }
+}
@@ -0,0 +1,86 @@
+use super::{data_range, normalise, ColourThresholds, EIGHTH_BLOCKS};
+use crate::elements::{
+    view::{ColChar, ViewElement},
+    Pixel, Vec2D,
+};
+
+/// A `Sparkline` is a compact, single row chart of the most recent values, drawing each value as a block character filled to its height. Useful for showing trends such as frame times or CPU usage in very little space
+#[derive(Debug, Clone)]
+pub struct Sparkline {
+    /// The position of the leftmost cell of the `Sparkline`
+    pub pos: Vec2D,
+    /// The maximum number of values shown. Only the most recent `width` values are drawn
+    pub width: usize,
+    /// The values to draw, oldest first
+    pub data: Vec<f64>,
+    /// The range the values are scaled to. If `None`, the range is taken from the visible values
+    pub range: Option<(f64, f64)>,
+    /// The colours of the values
+    pub colours: ColourThresholds,
+}
+
+impl Sparkline {
+    /// Create a new `Sparkline` with a position, width and data. The range is scaled to fit the data
+    #[must_use]
+    pub fn new(pos: Vec2D, width: usize, data: Vec<f64>) -> Self {
+        Self {
+            pos,
+            width,
+            data,
+            range: None,
+            colours: ColourThresholds::default(),
+        }
+    }
+
+    /// Return the `Sparkline` with a fixed range
+    #[must_use]
+    pub const fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Return the `Sparkline` with the modified colours
+    #[must_use]
+    pub fn with_colours(mut self, colours: impl Into<ColourThresholds>) -> Self {
+        self.colours = colours.into();
+        self
+    }
+}
+
+impl ViewElement for Sparkline {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let visible = &self.data[self.data.len().saturating_sub(self.width)..];
+        let range = data_range(visible, self.range);
+
+        (0..)
+            .zip(visible)
+            .filter(|(_, value)| value.is_finite())
+            .map(|(x, value)| {
+                // Always draw at least the lowest block so that the line stays continuous
+                let level = (normalise(*value, range) * 7.0).round() as usize + 1;
+
+                Pixel::new(
+                    self.pos + Vec2D::new(x, 0),
+                    ColChar::new(EIGHTH_BLOCKS[level], self.colours.modifier_for(*value)),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draws_most_recent_values() {
+        let sparkline = Sparkline::new(Vec2D::ZERO, 3, vec![100.0, 0.0, 5.0, 10.0]);
+        let chars: String = sparkline
+            .active_pixels()
+            .iter()
+            .map(|p| p.fill_char.text_char)
+            .collect();
+
+        assert_eq!(chars, "▁▅█");
+    }
+}
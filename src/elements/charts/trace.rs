@@ -0,0 +1,147 @@
+use super::{LineGraph, Sparkline};
+use crate::elements::Vec2D;
+use std::{collections::VecDeque, time::Duration};
+
+/// A `Trace` is a fixed-capacity ring buffer of values, such as one reading per frame. Once full, pushing a new value drops the oldest. Use it to record telemetry like frame times and plot it with [`Trace::sparkline`] or [`Trace::line_graph`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trace {
+    values: VecDeque<f64>,
+    capacity: usize,
+}
+
+impl Trace {
+    /// Create a new, empty `Trace` holding at most `capacity` values
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            values: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Return the maximum number of values the `Trace` holds
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Return the number of values currently in the `Trace`
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Return true if the `Trace` holds no values
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Add a value to the end of the `Trace`, dropping the oldest value if it's full
+    pub fn push(&mut self, value: f64) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.values.len() == self.capacity {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+    }
+
+    /// Add a `Duration` to the `Trace` in milliseconds. Pass the time taken by each frame to record frame times
+    pub fn push_duration(&mut self, duration: Duration) {
+        self.push(duration.as_secs_f64() * 1000.0);
+    }
+
+    /// Remove every value from the `Trace`
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    /// Return the most recently pushed value
+    #[must_use]
+    pub fn latest(&self) -> Option<f64> {
+        self.values.back().copied()
+    }
+
+    /// Return an iterator over the values, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = f64> + '_ {
+        self.values.iter().copied()
+    }
+
+    /// Return an iterator over the most recent `window` values, oldest first
+    fn window(&self, window: usize) -> impl Iterator<Item = f64> + '_ {
+        self.values
+            .iter()
+            .skip(self.values.len().saturating_sub(window))
+            .copied()
+    }
+
+    /// Return the lowest of the most recent `window` values, or `None` if the `Trace` is empty
+    #[must_use]
+    pub fn min(&self, window: usize) -> Option<f64> {
+        self.window(window).reduce(f64::min)
+    }
+
+    /// Return the highest of the most recent `window` values, or `None` if the `Trace` is empty
+    #[must_use]
+    pub fn max(&self, window: usize) -> Option<f64> {
+        self.window(window).reduce(f64::max)
+    }
+
+    /// Return the mean of the most recent `window` values, or `None` if the `Trace` is empty
+    #[must_use]
+    pub fn avg(&self, window: usize) -> Option<f64> {
+        let (sum, count) = self
+            .window(window)
+            .fold((0.0, 0_u32), |(sum, count), value| (sum + value, count + 1));
+
+        (count > 0).then(|| sum / f64::from(count))
+    }
+
+    /// Return a [`Sparkline`] of the most recent `width` values
+    #[must_use]
+    pub fn sparkline(&self, pos: Vec2D, width: usize) -> Sparkline {
+        Sparkline::new(pos, width, self.window(width).collect())
+    }
+
+    /// Return a [`LineGraph`] of every value in the `Trace`
+    #[must_use]
+    pub fn line_graph(&self, pos: Vec2D, size: Vec2D) -> LineGraph {
+        LineGraph::new(pos, size, self.iter().collect())
+    }
+}
+
+impl Extend<f64> for Trace {
+    fn extend<T: IntoIterator<Item = f64>>(&mut self, iter: T) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_oldest_when_full() {
+        let mut trace = Trace::new(3);
+        trace.extend([1.0, 2.0, 3.0, 4.0]);
+
+        assert_eq!(trace.iter().collect::<Vec<_>>(), vec![2.0, 3.0, 4.0]);
+        assert_eq!(trace.latest(), Some(4.0));
+    }
+
+    #[test]
+    fn windowed_statistics() {
+        let mut trace = Trace::new(10);
+        trace.extend([9.0, 1.0, 2.0, 6.0]);
+
+        assert_eq!(trace.min(3), Some(1.0));
+        assert_eq!(trace.max(3), Some(6.0));
+        assert_eq!(trace.avg(3), Some(3.0));
+        assert_eq!(trace.max(100), Some(9.0));
+        assert_eq!(Trace::new(5).avg(5), None);
+    }
+}
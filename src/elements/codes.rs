@@ -0,0 +1,71 @@
+//! This module holds scannable code elements for linking players to leaderboards, downloads and other URLs from the terminal: [`Barcode`], and `QrCode` with the `qr` feature
+//!
+//! Scanners expect dark modules on a light background, so these elements draw the light modules (including the quiet zone around the code) with their [`Modifier`] and leave the dark modules as the terminal's background. This is right for dark terminal themes. On light terminal themes, set `inverted` to draw the dark modules instead
+
+mod barcode;
+pub use barcode::Barcode;
+
+#[cfg(feature = "qr")]
+mod qr_code;
+#[cfg(feature = "qr")]
+pub use qr_code::{QrCode, QrErrorCorrection};
+
+use super::{
+    view::{ColChar, Modifier},
+    Pixel, Vec2D,
+};
+
+/// Draw a grid of `width` by `height` modules, packing two rows of modules into each row of cells with half blocks so that every module is square. `is_dark` is called for every module, and only the light modules are drawn unless `inverted` is true
+fn draw_modules(
+    pos: Vec2D,
+    width: usize,
+    height: usize,
+    is_dark: impl Fn(usize, usize) -> bool,
+    modifier: Modifier,
+    inverted: bool,
+) -> Vec<Pixel> {
+    let is_drawn = |x: usize, y: usize| y < height && is_dark(x, y) == inverted;
+
+    let mut pixels = vec![];
+    for row in 0..height.div_ceil(2) {
+        for x in 0..width {
+            let text_char = match (is_drawn(x, row * 2), is_drawn(x, row * 2 + 1)) {
+                (true, true) => '█',
+                (true, false) => '▀',
+                (false, true) => '▄',
+                (false, false) => continue,
+            };
+            pixels.push(Pixel::new(
+                pos + Vec2D::new(x as isize, row as isize),
+                ColChar::new(text_char, modifier),
+            ));
+        }
+    }
+
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packs_module_rows_into_half_blocks() {
+        // Dark on the diagonal of a 2x3 grid
+        let pixels = draw_modules(Vec2D::ZERO, 2, 3, |x, y| x == y, Modifier::None, false);
+        let chars: Vec<(Vec2D, char)> = pixels
+            .iter()
+            .map(|pixel| (pixel.pos, pixel.fill_char.text_char))
+            .collect();
+
+        assert_eq!(
+            chars,
+            vec![
+                (Vec2D::new(0, 0), '▄'),
+                (Vec2D::new(1, 0), '▀'),
+                (Vec2D::new(0, 1), '▀'),
+                (Vec2D::new(1, 1), '▀'),
+            ]
+        );
+    }
+}
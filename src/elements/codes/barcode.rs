@@ -0,0 +1,167 @@
+use super::draw_modules;
+use crate::{
+    elements::{
+        view::{Modifier, ViewElement},
+        Pixel, Vec2D,
+    },
+    EngineError,
+};
+
+/// The bar and space widths of every Code 128 symbol, alternating bar, space, bar and so on. 103 to 105 are the start symbols for code sets A, B and C, and 106 is the stop symbol
+const PATTERNS: [&str; 107] = [
+    "212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312", "132212",
+    "221213", "221312", "231212", "112232", "122132", "122231", "113222", "123122", "123221",
+    "223211", "221132", "221231", "213212", "223112", "312131", "311222", "321122", "321221",
+    "312212", "322112", "322211", "212123", "212321", "232121", "111323", "131123", "131321",
+    "112313", "132113", "132311", "211313", "231113", "231311", "112133", "112331", "132131",
+    "113123", "113321", "133121", "313121", "211331", "231131", "213113", "213311", "213131",
+    "311123", "311321", "331121", "312113", "312311", "332111", "314111", "221411", "431111",
+    "111224", "111422", "121124", "121421", "141122", "141221", "112214", "112412", "122114",
+    "122411", "142112", "142211", "241211", "221114", "413111", "241112", "134111", "111242",
+    "121142", "121241", "114212", "124112", "124211", "411212", "421112", "421211", "212141",
+    "214121", "412121", "111143", "111341", "131141", "114113", "114311", "411113", "411311",
+    "113141", "114131", "311141", "411131", "211412", "211214", "211232", "2331112",
+];
+
+/// The symbol starting code set B, which covers printable ASCII
+const START_B: usize = 104;
+/// The symbol ending every barcode
+const STOP: usize = 106;
+
+/// A scannable Code 128 barcode, drawn as one column per module. The text can be any printable ASCII. A quiet zone of 10 modules is drawn on either side, as scanners need it to find the code. Read the [`codes`](super) module documentation for how the colours are drawn
+/// ```
+/// use gemini_engine::elements::{codes::Barcode, Vec2D};
+///
+/// let barcode = Barcode::new(Vec2D::ZERO, "GEMINI", 4).expect("GEMINI is printable ASCII");
+///
+/// // 6 characters plus the start, checksum and stop symbols, and the quiet zones
+/// assert_eq!(barcode.size(), Vec2D::new(8 * 11 + 13 + 2 * 10, 4));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Barcode {
+    /// The position of the top left of the barcode, including its quiet zone
+    pub pos: Vec2D,
+    /// The height of the bars in cells
+    pub height: usize,
+    /// The `Modifier` the light modules are drawn with
+    pub modifier: Modifier,
+    /// Whether to draw the dark modules instead of the light ones, for terminals with a light background
+    pub inverted: bool,
+    modules: Vec<bool>,
+}
+
+impl Barcode {
+    /// The width of the quiet zone on either side of the barcode, in modules
+    pub const QUIET_ZONE: usize = 10;
+
+    /// Encode the text as a new `Barcode` with bars `height` cells tall
+    ///
+    /// # Errors
+    /// Returns an error if the text contains anything other than printable ASCII
+    pub fn new(pos: Vec2D, text: &str, height: usize) -> Result<Self, EngineError> {
+        let mut symbols = vec![START_B];
+        for c in text.chars() {
+            if !(' '..='~').contains(&c) {
+                return Err(EngineError::InvalidData(format!(
+                    "Code 128 can't encode {c:?}"
+                )));
+            }
+            symbols.push(c as usize - ' ' as usize);
+        }
+
+        let checksum = symbols
+            .iter()
+            .enumerate()
+            .map(|(i, symbol)| i.max(1) * symbol)
+            .sum::<usize>()
+            % 103;
+        symbols.push(checksum);
+        symbols.push(STOP);
+
+        let mut modules = vec![false; Self::QUIET_ZONE];
+        for symbol in symbols {
+            for (i, width) in PATTERNS[symbol].bytes().enumerate() {
+                let is_bar = i % 2 == 0;
+                modules.extend(std::iter::repeat_n(is_bar, usize::from(width - b'0')));
+            }
+        }
+        modules.extend([false; Self::QUIET_ZONE]);
+
+        Ok(Self {
+            pos,
+            height,
+            modifier: Modifier::None,
+            inverted: false,
+            modules,
+        })
+    }
+
+    /// Return the `Barcode` with the light modules drawn with a different `Modifier`
+    #[must_use]
+    pub const fn with_modifier(mut self, modifier: Modifier) -> Self {
+        self.modifier = modifier;
+        self
+    }
+
+    /// Return the `Barcode` drawing its dark modules instead of its light ones
+    #[must_use]
+    pub const fn with_inverted(mut self, inverted: bool) -> Self {
+        self.inverted = inverted;
+        self
+    }
+
+    /// Return the size of the barcode in cells, including its quiet zone
+    #[must_use]
+    pub const fn size(&self) -> Vec2D {
+        Vec2D::new(self.modules.len() as isize, self.height as isize)
+    }
+
+    /// Return the modules of the barcode from left to right, including its quiet zone. `true` is a dark bar
+    #[must_use]
+    pub fn modules(&self) -> &[bool] {
+        &self.modules
+    }
+}
+
+impl ViewElement for Barcode {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        draw_modules(
+            self.pos,
+            self.modules.len(),
+            self.height * 2,
+            |x, _| self.modules[x],
+            self.modifier,
+            self.inverted,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_pattern_is_eleven_modules() {
+        for pattern in &PATTERNS[..STOP] {
+            let width: u32 = pattern.bytes().map(|width| u32::from(width - b'0')).sum();
+            assert_eq!(width, 11, "{pattern}");
+        }
+    }
+
+    #[test]
+    fn encodes_checksum_and_rejects_non_ascii() {
+        // Start B (104) + 1 * 'A' (33) = 137, which is 34 mod 103
+        let barcode = Barcode::new(Vec2D::ZERO, "A", 1).expect("A is printable ASCII");
+        let checksum_start = Barcode::QUIET_ZONE + 22;
+        let mut expected = vec![];
+        for (i, width) in PATTERNS[34].bytes().enumerate() {
+            expected.extend(std::iter::repeat_n(i % 2 == 0, usize::from(width - b'0')));
+        }
+        assert_eq!(
+            &barcode.modules()[checksum_start..checksum_start + 11],
+            expected.as_slice()
+        );
+
+        assert!(Barcode::new(Vec2D::ZERO, "café", 1).is_err());
+    }
+}
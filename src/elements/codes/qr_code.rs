@@ -0,0 +1,154 @@
+use qrcodegen::QrCodeEcc;
+
+use super::draw_modules;
+use crate::{
+    elements::{
+        view::{Modifier, ViewElement},
+        Pixel, Vec2D,
+    },
+    EngineError,
+};
+
+/// How much of a [`QrCode`] can be damaged or covered while staying readable. Higher levels make the code larger
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QrErrorCorrection {
+    /// About 7% can be recovered
+    Low,
+    /// About 15% can be recovered
+    #[default]
+    Medium,
+    /// About 25% can be recovered
+    Quartile,
+    /// About 30% can be recovered
+    High,
+}
+
+impl From<QrErrorCorrection> for QrCodeEcc {
+    fn from(value: QrErrorCorrection) -> Self {
+        match value {
+            QrErrorCorrection::Low => Self::Low,
+            QrErrorCorrection::Medium => Self::Medium,
+            QrErrorCorrection::Quartile => Self::Quartile,
+            QrErrorCorrection::High => Self::High,
+        }
+    }
+}
+
+/// A scannable QR code, drawn with half blocks so that every module is one cell wide and half a cell tall, and so square. The smallest version that fits the text is used, surrounded by a quiet zone of 4 modules. Read the [`codes`](super) module documentation for how the colours are drawn. Only available with the `qr` feature
+/// ```
+/// use gemini_engine::elements::{codes::{QrCode, QrErrorCorrection}, Vec2D};
+///
+/// let qr = QrCode::new(Vec2D::ZERO, "https://example.com", QrErrorCorrection::Medium)
+///     .expect("the URL fits in a QR code");
+///
+/// // A version 2 code is 25 modules across, plus the quiet zones
+/// assert_eq!(qr.size(), Vec2D::new(33, 17));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrCode {
+    /// The position of the top left of the QR code, including its quiet zone
+    pub pos: Vec2D,
+    /// The `Modifier` the light modules are drawn with
+    pub modifier: Modifier,
+    /// Whether to draw the dark modules instead of the light ones, for terminals with a light background
+    pub inverted: bool,
+    modules: Vec<Vec<bool>>,
+}
+
+impl QrCode {
+    /// The width of the quiet zone around the QR code, in modules
+    pub const QUIET_ZONE: usize = 4;
+
+    /// Encode the text as a new `QrCode`
+    ///
+    /// # Errors
+    /// Returns an error if the text is too long to fit in a QR code with the error correction level
+    pub fn new(
+        pos: Vec2D,
+        text: &str,
+        error_correction: QrErrorCorrection,
+    ) -> Result<Self, EngineError> {
+        let qr = qrcodegen::QrCode::encode_text(text, error_correction.into())
+            .map_err(|err| EngineError::InvalidData(err.to_string()))?;
+
+        let size = qr.size();
+        let quiet_zone = Self::QUIET_ZONE as i32;
+        let modules = (-quiet_zone..size + quiet_zone)
+            .map(|y| {
+                (-quiet_zone..size + quiet_zone)
+                    .map(|x| qr.get_module(x, y))
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self {
+            pos,
+            modifier: Modifier::None,
+            inverted: false,
+            modules,
+        })
+    }
+
+    /// Return the `QrCode` with the light modules drawn with a different `Modifier`
+    #[must_use]
+    pub const fn with_modifier(mut self, modifier: Modifier) -> Self {
+        self.modifier = modifier;
+        self
+    }
+
+    /// Return the `QrCode` drawing its dark modules instead of its light ones
+    #[must_use]
+    pub const fn with_inverted(mut self, inverted: bool) -> Self {
+        self.inverted = inverted;
+        self
+    }
+
+    /// Return the size of the QR code in cells, including its quiet zone
+    #[must_use]
+    pub const fn size(&self) -> Vec2D {
+        let modules = self.modules.len();
+        Vec2D::new(modules as isize, modules.div_ceil(2) as isize)
+    }
+
+    /// Return the rows of modules of the QR code, including its quiet zone. `true` is a dark module
+    #[must_use]
+    pub fn modules(&self) -> &[Vec<bool>] {
+        &self.modules
+    }
+}
+
+impl ViewElement for QrCode {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let modules = self.modules.len();
+        draw_modules(
+            self.pos,
+            modules,
+            modules,
+            |x, y| self.modules[y][x],
+            self.modifier,
+            self.inverted,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn has_quiet_zone_and_finder_patterns() {
+        let qr = QrCode::new(Vec2D::ZERO, "GEMINI", QrErrorCorrection::Low).expect("fits");
+        let modules = qr.modules();
+        let quiet = QrCode::QUIET_ZONE;
+
+        // Version 1 is 21 modules across
+        assert_eq!(modules.len(), 21 + quiet * 2);
+        assert!(modules[quiet - 1].iter().all(|dark| !dark));
+        // The top left finder pattern's outer ring and centre are dark, with a light ring between
+        assert!(modules[quiet][quiet]);
+        assert!(!modules[quiet + 1][quiet + 1]);
+        assert!(modules[quiet + 3][quiet + 3]);
+
+        assert!(QrCode::new(Vec2D::ZERO, &"x".repeat(4000), QrErrorCorrection::High).is_err());
+    }
+}
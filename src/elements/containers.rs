@@ -6,8 +6,35 @@ pub use visibility_toggle::VisibilityToggle;
 mod pixel_container;
 pub use pixel_container::PixelContainer;
 
+mod braille_canvas;
+pub use braille_canvas::BrailleCanvas;
+
 mod shader;
 pub use shader::CanShade;
 
+mod colour_cycle;
+pub use colour_cycle::ColourCycle;
+
+mod gradient_fill;
+pub use gradient_fill::GradientFill;
+
 mod collision_container;
 pub use collision_container::CollisionContainer;
+
+mod masked;
+pub use masked::{Mask, Masked};
+
+mod opacity;
+pub use opacity::Opacity;
+
+mod pixel_collider;
+pub use pixel_collider::PixelCollider;
+
+mod trail;
+pub use trail::Trail;
+
+mod interpolated;
+pub use interpolated::Interpolated;
+
+mod sub_cell;
+pub use sub_cell::SubCell;
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+
+use crate::elements::{
+    charts::braille_char,
+    view::{ColChar, Modifier, ViewElement},
+    Pixel, Vec2D,
+};
+
+/// `BrailleCanvas` packs a 2x4 grid of dots into every cell using braille characters, for 8 times the resolution of whole cells in plots, particles and wireframes. Dots are plotted in dot coordinates, where every cell of the canvas is 2 dots wide and 4 dots tall, and any [`ViewElement`] can be blit to the canvas to draw it with its pixels as dots. Each cell still only has one colour, which is the [`Modifier`] of the last dot plotted in it
+/// ```
+/// use gemini_engine::elements::{containers::BrailleCanvas, view::{ColChar, Modifier, ViewElement}, Line, Vec2D};
+///
+/// let mut canvas = BrailleCanvas::new(Vec2D::new(10, 5));
+/// canvas.blit(&Line::new(Vec2D::new(0, 0), Vec2D::new(3, 3), ColChar::SOLID));
+///
+/// // The diagonal line spans two cells
+/// let pixels = canvas.active_pixels();
+/// assert_eq!(pixels.len(), 2);
+/// assert_eq!(pixels[0].pos, Vec2D::new(10, 5));
+/// assert_eq!(pixels[0].fill_char.text_char, '⠑');
+/// ```
+#[derive(Debug, Clone)]
+pub struct BrailleCanvas {
+    /// The position of the canvas' top left cell on the `View`
+    pub pos: Vec2D,
+    /// The raised dots and colour of every cell with at least one dot raised, by row and column
+    cells: BTreeMap<(isize, isize), (u8, Modifier)>,
+}
+
+impl BrailleCanvas {
+    /// The width of each cell in dots
+    pub const DOTS_WIDE: isize = 2;
+    /// The height of each cell in dots
+    pub const DOTS_TALL: isize = 4;
+
+    /// Create a new, empty `BrailleCanvas` with its top left cell at the position
+    #[must_use]
+    pub const fn new(pos: Vec2D) -> Self {
+        Self {
+            pos,
+            cells: BTreeMap::new(),
+        }
+    }
+
+    /// Return the cell the dot is in, as a row and column, and the index of the dot within that cell
+    const fn locate(dot: Vec2D) -> ((isize, isize), usize) {
+        let cell = (
+            dot.y.div_euclid(Self::DOTS_TALL),
+            dot.x.div_euclid(Self::DOTS_WIDE),
+        );
+        let index =
+            dot.y.rem_euclid(Self::DOTS_TALL) * Self::DOTS_WIDE + dot.x.rem_euclid(Self::DOTS_WIDE);
+
+        (cell, index as usize)
+    }
+
+    /// Raise the dot at the position, and colour its cell with the `Modifier`
+    pub fn plot(&mut self, dot: Vec2D, modifier: Modifier) {
+        let (cell, index) = Self::locate(dot);
+        let (dots, cell_modifier) = self.cells.entry(cell).or_insert((0, modifier));
+        *dots |= 1 << index;
+        *cell_modifier = modifier;
+    }
+
+    /// Lower the dot at the position
+    pub fn unplot(&mut self, dot: Vec2D) {
+        let (cell, index) = Self::locate(dot);
+        if let Some((dots, _)) = self.cells.get_mut(&cell) {
+            *dots &= !(1 << index);
+            if *dots == 0 {
+                self.cells.remove(&cell);
+            }
+        }
+    }
+
+    /// Returns true if the dot at the position is raised
+    #[must_use]
+    pub fn get(&self, dot: Vec2D) -> bool {
+        let (cell, index) = Self::locate(dot);
+        self.cells
+            .get(&cell)
+            .is_some_and(|(dots, _)| dots & 1 << index != 0)
+    }
+
+    /// Blit a [`ViewElement`] to the `BrailleCanvas`, raising a dot for each of its pixels coloured with the pixel's [`Modifier`]. The pixels' characters are ignored
+    pub fn blit(&mut self, element: &impl ViewElement) {
+        for pixel in element.active_pixels() {
+            self.plot(pixel.pos, pixel.fill_char.modifier);
+        }
+    }
+
+    /// Lower every dot
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+}
+
+impl ViewElement for BrailleCanvas {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        self.cells
+            .iter()
+            .map(|(&(row, column), &(dots, modifier))| {
+                let mut grid = [[false; 2]; 4];
+                for (i, dot) in grid.iter_mut().flatten().enumerate() {
+                    *dot = dots & 1 << i != 0;
+                }
+
+                Pixel::new(
+                    self.pos + Vec2D::new(column, row),
+                    ColChar::new(braille_char(grid), modifier),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packs_dots_into_cells() {
+        let mut canvas = BrailleCanvas::new(Vec2D::ZERO);
+        canvas.plot(Vec2D::new(-1, -1), Modifier::RED);
+        canvas.plot(Vec2D::new(0, 0), Modifier::None);
+        canvas.plot(Vec2D::new(1, 3), Modifier::BLUE);
+        assert!(canvas.get(Vec2D::new(-1, -1)));
+        assert!(!canvas.get(Vec2D::new(1, 0)));
+
+        let pixels = canvas.active_pixels();
+        assert_eq!(
+            pixels,
+            vec![
+                Pixel::new(Vec2D::new(-1, -1), ColChar::new('⢀', Modifier::RED)),
+                Pixel::new(Vec2D::new(0, 0), ColChar::new('⢁', Modifier::BLUE)),
+            ]
+        );
+
+        canvas.unplot(Vec2D::new(-1, -1));
+        canvas.unplot(Vec2D::new(5, 5));
+        assert_eq!(canvas.active_pixels().len(), 1);
+        canvas.clear();
+        assert!(canvas.active_pixels().is_empty());
+    }
+}
@@ -7,7 +7,7 @@ pub struct CollisionContainer<'a> {
     pub elements: Vec<&'a dyn ViewElement>,
 }
 
-impl<'a> Default for CollisionContainer<'a> {
+impl Default for CollisionContainer<'_> {
     fn default() -> Self {
         Self::new()
     }
@@ -65,7 +65,7 @@ impl<'a> From<Vec<&'a dyn ViewElement>> for CollisionContainer<'a> {
     }
 }
 
-impl<'a> ViewElement for CollisionContainer<'a> {
+impl ViewElement for CollisionContainer<'_> {
     fn active_pixels(&self) -> Vec<Pixel> {
         self.elements
             .iter()
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use crate::{
+    elements::{
+        view::{Modifier, ViewElement},
+        Pixel,
+    },
+    gameloop::CanAnimate,
+};
+
+/// `ColourCycle` is a container for a [`ViewElement`] that rotates the hue of every [`Modifier::Colour`] pixel over time, for classic plasma and rainbow effects. Pixels with any other [`Modifier`] are left as they are. Call [`update()`](ColourCycle::update()) every frame to move the animation forward
+#[derive(Debug, Clone)]
+pub struct ColourCycle<E: ViewElement> {
+    /// The element held by the `ColourCycle`. Must implement [`ViewElement`]
+    pub element: E,
+    /// How many degrees the hue rotates every second
+    pub degrees_per_second: f64,
+    /// How many degrees further along the cycle each column of pixels is, for a rainbow that sweeps across the element
+    pub spread: f64,
+    /// How long the cycle has been running for
+    pub elapsed: Duration,
+}
+
+impl<E: ViewElement> ColourCycle<E> {
+    /// Create a new `ColourCycle` rotating the element's hues by `degrees_per_second`
+    pub const fn new(element: E, degrees_per_second: f64) -> Self {
+        Self {
+            element,
+            degrees_per_second,
+            spread: 0.0,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Return the `ColourCycle` with the modified spread property
+    #[must_use]
+    pub const fn with_spread(mut self, spread: f64) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Move the animation forward by `delta`, usually the time since the last frame
+    pub fn update(&mut self, delta: Duration) {
+        self.elapsed += delta;
+    }
+}
+
+impl<E: ViewElement> ViewElement for ColourCycle<E> {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let shift = self.elapsed.as_secs_f64() * self.degrees_per_second;
+
+        self.element
+            .active_pixels()
+            .into_iter()
+            .map(|mut pixel| {
+                if let Modifier::Colour(colour) = pixel.fill_char.modifier {
+                    let degrees = (pixel.pos.x as f64).mul_add(self.spread, shift);
+                    pixel.fill_char.modifier = Modifier::Colour(colour.shift_hue(degrees));
+                }
+                pixel
+            })
+            .collect()
+    }
+}
+
+impl<E: ViewElement> CanAnimate for ColourCycle<E> {
+    fn is_animating(&self) -> bool {
+        self.degrees_per_second != 0.0
+    }
+}
@@ -0,0 +1,121 @@
+use crate::elements::{
+    view::{Gradient, Modifier, ViewElement},
+    Pixel, Vec2D,
+};
+
+use super::CanShade;
+
+/// `GradientFill` is a container that colours every pixel of a [`ViewElement`] with a [`Gradient`], running from the `start` point at 0.0 to the `end` point at 1.0. Pixels are coloured by how far along that line they are, so a vertical line makes a sky or a fire and a horizontal one makes a health bar. It also implements [`CanShade`], so it can colour a [`PixelContainer`](super::PixelContainer), like a rendered 3D scene, with [`shade_with()`](super::PixelContainer::shade_with())
+/// ```
+/// use gemini_engine::elements::{containers::GradientFill, view::{ColChar, Colour, Gradient, Modifier, ViewElement}, Text, Vec2D};
+///
+/// let title = GradientFill::new(
+///     Text::new(Vec2D::ZERO, "FIRE", Modifier::None),
+///     Gradient::even(&[Colour::rgb(255, 0, 0), Colour::rgb(255, 255, 0)]),
+///     Vec2D::new(0, 0),
+///     Vec2D::new(3, 0),
+/// );
+///
+/// let pixels = title.active_pixels();
+/// assert_eq!(pixels[0].fill_char, ColChar::new('F', Modifier::from_rgb(255, 0, 0)));
+/// assert_eq!(pixels[3].fill_char, ColChar::new('E', Modifier::from_rgb(255, 255, 0)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct GradientFill<E: ViewElement> {
+    /// The element held by the `GradientFill`. Must implement [`ViewElement`]
+    pub element: E,
+    /// The `Gradient` the element is filled with
+    pub gradient: Gradient,
+    /// The point where the gradient starts
+    pub start: Vec2D,
+    /// The point where the gradient ends
+    pub end: Vec2D,
+}
+
+impl<E: ViewElement> GradientFill<E> {
+    /// Create a new `GradientFill` colouring the element with the gradient from `start` to `end`
+    pub const fn new(element: E, gradient: Gradient, start: Vec2D, end: Vec2D) -> Self {
+        Self {
+            element,
+            gradient,
+            start,
+            end,
+        }
+    }
+
+    /// Return how far along the gradient the position is, from 0.0 at the start to 1.0 at the end
+    fn progress(start: Vec2D, end: Vec2D, pos: Vec2D) -> f64 {
+        let line = end - start;
+        let length_squared = line.x * line.x + line.y * line.y;
+        if length_squared == 0 {
+            return 0.0;
+        }
+        let along = pos - start;
+
+        (along.x * line.x + along.y * line.y) as f64 / length_squared as f64
+    }
+
+    /// Return the pixel coloured by the gradient at its position
+    fn fill(&self, mut pixel: Pixel) -> Pixel {
+        let colour = self
+            .gradient
+            .sample(Self::progress(self.start, self.end, pixel.pos));
+        pixel.fill_char.modifier = Modifier::Colour(colour);
+        pixel
+    }
+}
+
+impl<E: ViewElement> ViewElement for GradientFill<E> {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        self.element
+            .active_pixels()
+            .into_iter()
+            .map(|pixel| self.fill(pixel))
+            .collect()
+    }
+
+    fn active_points(&self) -> Vec<Vec2D> {
+        self.element.active_points()
+    }
+}
+
+impl<E: ViewElement> CanShade for GradientFill<E> {
+    fn shade(&mut self, pixel: Pixel) -> Pixel {
+        self.fill(pixel)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{
+        containers::PixelContainer,
+        view::{ColChar, Colour},
+        Rect,
+    };
+
+    #[test]
+    fn vertical_fill_and_shader() {
+        let gradient = Gradient::even(&[Colour::BLACK, Colour::WHITE]);
+        let sky = GradientFill::new(
+            Rect::new(Vec2D::ZERO, Vec2D::new(2, 3), ColChar::SOLID),
+            gradient,
+            Vec2D::new(0, 0),
+            Vec2D::new(0, 2),
+        );
+        let pixels = sky.active_pixels();
+        assert_eq!(pixels.len(), 6);
+        for pixel in &pixels {
+            let expected = [Colour::BLACK, Colour::greyscale(128), Colour::WHITE];
+            assert_eq!(
+                pixel.fill_char.modifier,
+                Modifier::Colour(expected[pixel.pos.y as usize])
+            );
+        }
+
+        let unfilled = sky.element.active_pixels();
+        let mut shader: Box<dyn CanShade> = Box::new(sky);
+        let container = PixelContainer::from(unfilled.as_slice()).shade_with(&mut shader);
+        assert_eq!(container.active_pixels(), pixels);
+    }
+}
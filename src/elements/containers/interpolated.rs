@@ -0,0 +1,108 @@
+use crate::elements::{
+    view::{Vec2F, ViewElement},
+    Pixel, Vec2D,
+};
+
+/// `Interpolated` is a container that smooths the movement of an element between simulation ticks, so that game logic can run at 10-20 ticks per second while rendering at a much higher frame rate. The element itself is kept at its latest simulated position, and `Interpolated` remembers the position from the tick before. Every frame, set [`alpha`](Interpolated::alpha) to how far the simulation is into the next tick, such as from [`FixedTimestep::alpha()`](crate::gameloop::FixedTimestep::alpha()), and the element is drawn that far between the two positions
+/// ```
+/// use gemini_engine::elements::{containers::Interpolated, view::{ColChar, Vec2F, ViewElement}, Pixel, Vec2D};
+///
+/// let mut ball = Interpolated::new(Pixel::new(Vec2D::new(0, 0), ColChar::SOLID), Vec2F::new(0.0, 0.0));
+///
+/// // A simulation tick moves the ball 10 cells to the right
+/// ball.element.pos = Vec2D::new(10, 0);
+/// ball.record(Vec2F::new(10.0, 0.0));
+///
+/// // Halfway to the next tick, the ball is drawn halfway between the two positions
+/// ball.alpha = 0.5;
+/// assert_eq!(ball.active_points(), vec![Vec2D::new(5, 0)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Interpolated<E: ViewElement> {
+    /// The element held by the `Interpolated`, at its latest simulated position. Must implement [`ViewElement`]
+    pub element: E,
+    /// How far between the previous and latest simulation ticks to draw the element, from 0.0 at the previous tick to 1.0 at the latest
+    pub alpha: f64,
+    previous: Vec2F,
+    current: Vec2F,
+}
+
+impl<E: ViewElement> Interpolated<E> {
+    /// Create a new `Interpolated` holding the element at the position, with no movement to smooth yet
+    pub const fn new(element: E, pos: Vec2F) -> Self {
+        Self {
+            element,
+            alpha: 1.0,
+            previous: pos,
+            current: pos,
+        }
+    }
+
+    /// Record the element's position after a simulation tick. The element should already have been moved there
+    pub const fn record(&mut self, pos: Vec2F) {
+        self.previous = self.current;
+        self.current = pos;
+    }
+
+    /// Move to the position without smoothing, for teleports and respawns that shouldn't be drawn sliding across the screen
+    pub const fn snap(&mut self, pos: Vec2F) {
+        self.previous = pos;
+        self.current = pos;
+    }
+
+    /// Return the position the element is drawn at for the current [`alpha`](Interpolated::alpha)
+    #[must_use]
+    pub fn position(&self) -> Vec2F {
+        let alpha = self.alpha.clamp(0.0, 1.0);
+        self.previous + (self.current - self.previous) * alpha
+    }
+
+    /// Return how far the element is drawn from its latest position
+    fn offset(&self) -> Vec2D {
+        self.position().round() - self.current.round()
+    }
+}
+
+impl<E: ViewElement> ViewElement for Interpolated<E> {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let offset = self.offset();
+        self.element
+            .active_pixels()
+            .into_iter()
+            .map(|pixel| Pixel::new(pixel.pos + offset, pixel.fill_char))
+            .collect()
+    }
+
+    fn active_points(&self) -> Vec<Vec2D> {
+        let offset = self.offset();
+        self.element
+            .active_points()
+            .into_iter()
+            .map(|pos| pos + offset)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::view::ColChar;
+
+    #[test]
+    fn smooths_between_ticks_and_snaps() {
+        let mut pixel = Interpolated::new(
+            Pixel::new(Vec2D::new(4, 2), ColChar::SOLID),
+            Vec2F::new(4.0, 2.0),
+        );
+        assert_eq!(pixel.active_points(), vec![Vec2D::new(4, 2)]);
+
+        pixel.element.pos = Vec2D::new(8, 6);
+        pixel.record(Vec2F::new(8.0, 6.0));
+        pixel.alpha = 0.25;
+        assert_eq!(pixel.position(), Vec2F::new(5.0, 3.0));
+        assert_eq!(pixel.active_pixels()[0].pos, Vec2D::new(5, 3));
+
+        pixel.snap(Vec2F::new(8.0, 6.0));
+        assert_eq!(pixel.active_points(), vec![Vec2D::new(8, 6)]);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::elements::{view::ViewElement, Pixel, Vec2D};
+use std::{collections::HashSet, fmt, rc::Rc};
+
+/// A `Mask` decides which positions an element may be drawn at, for clipping to non-rectangular shapes or revealing part of the screen, such as a map seen through a circle of torchlight. Masks are made from the shape of another element with [`from_element()`](Mask::from_element()) or from any function of the position with [`from_fn()`](Mask::from_fn()), and can be [`inverted()`](Mask::inverted())
+/// ```
+/// use gemini_engine::elements::{containers::Mask, Vec2D};
+///
+/// let torch = Vec2D::new(10, 5);
+/// let torchlight = Mask::from_fn(move |pos| {
+///     let offset = pos - torch;
+///     offset.x * offset.x + offset.y * offset.y <= 9
+/// });
+///
+/// assert!(torchlight.allows(Vec2D::new(12, 5)));
+/// assert!(!torchlight.allows(Vec2D::new(14, 5)));
+/// assert!(torchlight.inverted().allows(Vec2D::new(14, 5)));
+/// ```
+#[derive(Clone)]
+pub struct Mask {
+    allows: Rc<dyn Fn(Vec2D) -> bool>,
+}
+
+impl Mask {
+    /// Create a `Mask` that allows the positions for which the function returns true
+    #[must_use]
+    pub fn from_fn(allows: impl Fn(Vec2D) -> bool + 'static) -> Self {
+        Self {
+            allows: Rc::new(allows),
+        }
+    }
+
+    /// Create a `Mask` that allows only the positions the element currently has pixels at, such as a [`PixelContainer`](super::PixelContainer) or a filled [`Polygon`](crate::elements::Polygon). Later changes to the element don't affect the `Mask`
+    #[must_use]
+    pub fn from_element(element: &impl ViewElement) -> Self {
+        let points: HashSet<Vec2D> = element.active_points().into_iter().collect();
+        Self::from_fn(move |pos| points.contains(&pos))
+    }
+
+    /// Return a `Mask` that allows every position this one doesn't, and none that it does
+    #[must_use]
+    pub fn inverted(&self) -> Self {
+        let allows = Rc::clone(&self.allows);
+        Self::from_fn(move |pos| !allows(pos))
+    }
+
+    /// Returns true if elements may be drawn at the position
+    #[must_use]
+    pub fn allows(&self, pos: Vec2D) -> bool {
+        (self.allows)(pos)
+    }
+}
+
+impl fmt::Debug for Mask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mask").finish_non_exhaustive()
+    }
+}
+
+/// `Masked` is a container for a [`ViewElement`] that only shows the pixels a [`Mask`] allows
+/// ```
+/// use gemini_engine::elements::{containers::{Mask, Masked}, view::{ColChar, ViewElement}, Rect, Vec2D};
+///
+/// let map = Rect::new(Vec2D::ZERO, Vec2D::new(20, 10), ColChar::SOLID);
+/// let window = Mask::from_fn(|pos| pos.x >= 5 && pos.x < 10);
+///
+/// assert_eq!(Masked::new(map, window).active_pixels().len(), 50);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Masked<E: ViewElement> {
+    /// The element held by the `Masked`. Must implement [`ViewElement`]
+    pub element: E,
+    /// The mask deciding which of the element's pixels are shown
+    pub mask: Mask,
+}
+
+impl<E: ViewElement> Masked<E> {
+    /// Create a new `Masked` showing only the parts of the element the mask allows
+    pub const fn new(element: E, mask: Mask) -> Self {
+        Self { element, mask }
+    }
+}
+
+impl<E: ViewElement> ViewElement for Masked<E> {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        self.element
+            .active_pixels()
+            .into_iter()
+            .filter(|pixel| self.mask.allows(pixel.pos))
+            .collect()
+    }
+
+    fn active_points(&self) -> Vec<Vec2D> {
+        self.element
+            .active_points()
+            .into_iter()
+            .filter(|pos| self.mask.allows(*pos))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{view::ColChar, PixelContainer, Text};
+
+    #[test]
+    fn masks_from_elements_and_inverted() {
+        let stencil = PixelContainer::from((
+            [Vec2D::new(0, 0), Vec2D::new(2, 0)].as_slice(),
+            ColChar::SOLID,
+        ));
+        let mask = Mask::from_element(&stencil);
+        let text = Text::new(Vec2D::ZERO, "abc", ColChar::SOLID.modifier);
+
+        let shown: String = Masked::new(text.clone(), mask.clone())
+            .active_pixels()
+            .iter()
+            .map(|pixel| pixel.fill_char.text_char)
+            .collect();
+        assert_eq!(shown, "ac");
+        assert_eq!(
+            Masked::new(text, mask.inverted()).active_points(),
+            vec![Vec2D::new(1, 0)]
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use crate::elements::{
+    view::{Dithering, ViewElement},
+    Pixel, Vec2D,
+};
+
+/// `Opacity` is a container for a [`ViewElement`] that only shows a fraction of its pixels, picked by a repeating Bayer dither pattern, so that whatever is behind it shows through. This gives ghost and fade effects in terminals without colour blending, including plain characters. The pattern is fixed to positions on the `View`, so it stays stable as the element moves
+/// ```
+/// use gemini_engine::elements::{containers::Opacity, view::{ColChar, ViewElement}, Rect, Vec2D};
+///
+/// let ghost = || Rect::new(Vec2D::ZERO, Vec2D::new(8, 8), ColChar::SOLID);
+///
+/// assert_eq!(Opacity::new(ghost(), 0.25).active_pixels().len(), 16);
+/// assert_eq!(Opacity::new(ghost(), 1.0).active_pixels().len(), 64);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Opacity<E: ViewElement> {
+    /// The element held by the `Opacity`. Must implement [`ViewElement`]
+    pub element: E,
+    /// How much of the element is shown, from 0.0 for invisible to 1.0 for fully opaque. Steps of 1/16 each show one more pixel in every 4x4 block
+    pub opacity: f64,
+}
+
+impl<E: ViewElement> Opacity<E> {
+    /// Create a new `Opacity` showing the element at the given opacity
+    pub const fn new(element: E, opacity: f64) -> Self {
+        Self { element, opacity }
+    }
+
+    /// Returns true if the pixel at the position is shown at the current opacity
+    #[must_use]
+    pub fn shows(&self, pos: Vec2D) -> bool {
+        Dithering::bayer_threshold(pos) < self.opacity
+    }
+}
+
+impl<E: ViewElement> ViewElement for Opacity<E> {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        self.element
+            .active_pixels()
+            .into_iter()
+            .filter(|pixel| self.shows(pixel.pos))
+            .collect()
+    }
+
+    fn active_points(&self) -> Vec<Vec2D> {
+        self.element
+            .active_points()
+            .into_iter()
+            .filter(|pos| self.shows(*pos))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{view::ColChar, Rect};
+
+    #[test]
+    fn shows_more_pixels_as_opacity_rises() {
+        let rect = || Rect::new(Vec2D::new(-3, 5), Vec2D::new(4, 4), ColChar::SOLID);
+
+        let counts: Vec<usize> = (0..=16)
+            .map(|step| {
+                Opacity::new(rect(), f64::from(step) / 16.0)
+                    .active_points()
+                    .len()
+            })
+            .collect();
+        assert_eq!(counts, (0..=16).collect::<Vec<usize>>());
+
+        let half = Opacity::new(rect(), 0.5);
+        let shown = half.active_points();
+        assert!(shown.iter().all(|pos| half.shows(*pos)));
+    }
+}
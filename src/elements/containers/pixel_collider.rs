@@ -0,0 +1,184 @@
+use std::collections::HashSet;
+
+use crate::elements::{
+    view::{ColChar, ViewElement},
+    Pixel, Sprite, Vec2D,
+};
+
+/// A collision mask generated from the shape of an element, so hitboxes stay in sync with the art without being defined twice. The mask is worked out once and stored relative to [`pos`](PixelCollider::pos), so moving the collider along with its sprite is cheap. Being a [`ViewElement`], it can be added to a [`CollisionContainer`](super::CollisionContainer) or blit to the `View` to see the hitbox
+/// ```
+/// use gemini_engine::elements::{containers::PixelCollider, view::Modifier, Sprite, Vec2D};
+///
+/// let ship = Sprite::new(Vec2D::new(10, 5), " /\\\n/__\\", Modifier::None);
+/// let mut hitbox = PixelCollider::from_sprite(&ship);
+///
+/// assert!(hitbox.contains(Vec2D::new(11, 5)));
+/// assert!(!hitbox.contains(Vec2D::new(10, 5)));
+///
+/// hitbox.pos = Vec2D::new(0, 0);
+/// assert!(hitbox.contains(Vec2D::new(0, 1)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PixelCollider {
+    /// The position the collider's mask is relative to
+    pub pos: Vec2D,
+    offsets: HashSet<Vec2D>,
+}
+
+impl PixelCollider {
+    /// Create a `PixelCollider` covering the element's current points, relative to `origin`
+    #[must_use]
+    pub fn from_element(element: &impl ViewElement, origin: Vec2D) -> Self {
+        Self {
+            pos: origin,
+            offsets: element
+                .active_points()
+                .into_iter()
+                .map(|point| point - origin)
+                .collect(),
+        }
+    }
+
+    /// Create a `PixelCollider` covering every visible character of the [`Sprite`], relative to its position. Whitespace and [`ColChar::VOID`] characters are left out
+    #[must_use]
+    pub fn from_sprite(sprite: &Sprite) -> Self {
+        Self {
+            pos: sprite.pos,
+            offsets: sprite
+                .active_pixels()
+                .into_iter()
+                .filter(|pixel| pixel.fill_char.text_char != ColChar::VOID.text_char)
+                .map(|pixel| pixel.pos - sprite.pos)
+                .collect(),
+        }
+    }
+
+    /// Return the collider grown by `radius` cells in every direction, including diagonally, for forgiving pickups
+    #[must_use]
+    pub fn dilated(&self, radius: usize) -> Self {
+        let mut offsets = self.offsets.clone();
+        for _ in 0..radius {
+            offsets = offsets
+                .iter()
+                .flat_map(|offset| neighbourhood(*offset))
+                .collect();
+        }
+
+        Self {
+            pos: self.pos,
+            offsets,
+        }
+    }
+
+    /// Return the collider shrunk by `radius` cells from every edge, for forgiving hitboxes on enemies and bullets. Parts thinner than the erosion disappear
+    #[must_use]
+    pub fn eroded(&self, radius: usize) -> Self {
+        let mut offsets = self.offsets.clone();
+        for _ in 0..radius {
+            offsets = offsets
+                .iter()
+                .filter(|offset| neighbourhood(**offset).all(|n| offsets.contains(&n)))
+                .copied()
+                .collect();
+        }
+
+        Self {
+            pos: self.pos,
+            offsets,
+        }
+    }
+
+    /// Return the number of cells in the collider
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Returns true if the collider has no cells
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Returns true if the collider covers the position
+    #[must_use]
+    pub fn contains(&self, pos: Vec2D) -> bool {
+        self.offsets.contains(&(pos - self.pos))
+    }
+
+    /// Returns true if any cell of the collider is also covered by the other collider
+    #[must_use]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        let (smaller, larger) = if self.len() <= other.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        smaller
+            .offsets
+            .iter()
+            .any(|offset| larger.contains(smaller.pos + *offset))
+    }
+}
+
+/// The cell and the eight cells around it
+fn neighbourhood(pos: Vec2D) -> impl Iterator<Item = Vec2D> {
+    (-1..=1).flat_map(move |y| (-1..=1).map(move |x| pos + Vec2D::new(x, y)))
+}
+
+impl From<&Sprite> for PixelCollider {
+    fn from(sprite: &Sprite) -> Self {
+        Self::from_sprite(sprite)
+    }
+}
+
+impl ViewElement for PixelCollider {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        self.active_points()
+            .into_iter()
+            .map(|pos| Pixel::new(pos, ColChar::SOLID))
+            .collect()
+    }
+
+    fn active_points(&self) -> Vec<Vec2D> {
+        self.offsets
+            .iter()
+            .map(|offset| self.pos + *offset)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::view::Modifier;
+
+    #[test]
+    fn erodes_and_dilates() {
+        let block = Sprite::new(Vec2D::ZERO, "###\n###\n###", Modifier::None);
+        let collider = PixelCollider::from_sprite(&block);
+        assert_eq!(collider.len(), 9);
+
+        let core = collider.eroded(1);
+        assert_eq!(core.active_points(), vec![Vec2D::new(1, 1)]);
+        assert!(collider.eroded(2).is_empty());
+        assert_eq!(core.dilated(1), collider);
+        assert_eq!(collider.dilated(1).len(), 25);
+    }
+
+    #[test]
+    fn overlaps_and_skips_void() {
+        let void = ColChar::VOID.text_char;
+        let sprite = Sprite::new(Vec2D::new(2, 2), &format!("#{void}#"), Modifier::None);
+        let mut a = PixelCollider::from(&sprite);
+        assert_eq!(a.len(), 2);
+
+        let b =
+            PixelCollider::from_element(&Pixel::new(Vec2D::new(3, 2), ColChar::SOLID), Vec2D::ZERO);
+        assert!(!a.overlaps(&b));
+        a.pos.x += 1;
+        assert!(a.overlaps(&b));
+        assert!(b.overlaps(&a));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::elements::{
-    view::{utils, ColChar, ViewElement},
+    view::{utils, Bitmap, ColChar, ImageQuality, ViewElement},
     Pixel, Vec2D,
 };
 
@@ -25,6 +25,22 @@ impl PixelContainer {
         Self { pixels: vec![] }
     }
 
+    /// Create a `PixelContainer` of coloured block art converted from an image, with its top left corner at the position. Read [`Bitmap::to_block_art()`] for how each [`ImageQuality`] draws the image
+    /// ```
+    /// use gemini_engine::elements::{containers::PixelContainer, view::{Bitmap, Colour, ImageQuality, ViewElement}, Vec2D};
+    ///
+    /// let image = Bitmap { width: 4, height: 4, pixels: vec![Colour::rgb(255, 128, 0); 16] };
+    ///
+    /// let art = PixelContainer::from_image(Vec2D::new(10, 5), &image, ImageQuality::QuarterBlock);
+    /// assert_eq!(art.active_pixels().len(), 4);
+    /// ```
+    #[must_use]
+    pub fn from_image(pos: Vec2D, image: &Bitmap, quality: ImageQuality) -> Self {
+        Self {
+            pixels: image.to_block_art(pos, quality),
+        }
+    }
+
     /// Add a single pixel to the `PixelContainer`
     pub fn push(&mut self, pixel: Pixel) {
         self.pixels.push(pixel);
@@ -0,0 +1,94 @@
+use crate::elements::{
+    view::{Rounding, Vec2F, ViewElement},
+    Pixel, Vec2D,
+};
+
+/// `SubCell` is a container that positions an element with a [`Vec2F`], so it can move by fractions of a cell, and picks the cell it's drawn in with a [`Rounding`] strategy as it's blit. The element should be built around [`Vec2D::ZERO`], and is moved to the position. With [`Rounding::Stochastic`] and a new seed every frame, slow-moving objects glide between cells rather than stuttering from one to the next
+/// ```
+/// use gemini_engine::elements::{containers::SubCell, view::{ColChar, Rounding, Vec2F, ViewElement}, Pixel, Vec2D};
+///
+/// let mut cloud = SubCell::new(Pixel::new(Vec2D::ZERO, ColChar::SOLID), Vec2F::new(3.0, 1.0))
+///     .with_rounding(Rounding::Floor);
+///
+/// // Drift a tenth of a cell every frame
+/// for _ in 0..9 {
+///     cloud.pos.x += 0.1;
+/// }
+/// assert_eq!(cloud.active_points(), vec![Vec2D::new(3, 1)]);
+///
+/// cloud.pos.x += 0.2;
+/// assert_eq!(cloud.active_points(), vec![Vec2D::new(4, 1)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SubCell<E: ViewElement> {
+    /// The element held by the `SubCell`, built around [`Vec2D::ZERO`]. Must implement [`ViewElement`]
+    pub element: E,
+    /// The position the element is drawn at
+    pub pos: Vec2F,
+    /// How the position is rounded to a cell. Defaults to [`Rounding::Round`]
+    pub rounding: Rounding,
+}
+
+impl<E: ViewElement> SubCell<E> {
+    /// Create a new `SubCell` drawing the element at the position
+    pub const fn new(element: E, pos: Vec2F) -> Self {
+        Self {
+            element,
+            pos,
+            rounding: Rounding::Round,
+        }
+    }
+
+    /// Return the `SubCell` with a different rounding strategy
+    #[must_use]
+    pub const fn with_rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Return the cell the element's origin is drawn in
+    #[must_use]
+    pub fn cell(&self) -> Vec2D {
+        self.pos.round_with(self.rounding)
+    }
+}
+
+impl<E: ViewElement> ViewElement for SubCell<E> {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let offset = self.cell();
+        self.element
+            .active_pixels()
+            .into_iter()
+            .map(|pixel| Pixel::new(pixel.pos + offset, pixel.fill_char))
+            .collect()
+    }
+
+    fn active_points(&self) -> Vec<Vec2D> {
+        let offset = self.cell();
+        self.element
+            .active_points()
+            .into_iter()
+            .map(|pos| pos + offset)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{view::ColChar, Rect};
+
+    #[test]
+    fn moves_whole_element_to_rounded_cell() {
+        let rect = Rect::new(Vec2D::ZERO, Vec2D::new(2, 1), ColChar::SOLID);
+        let mut block = SubCell::new(rect, Vec2F::new(-1.5, 2.4));
+        assert_eq!(block.cell(), Vec2D::new(-2, 2));
+        assert_eq!(
+            block.active_points(),
+            vec![Vec2D::new(-2, 2), Vec2D::new(-1, 2)]
+        );
+
+        block.rounding = Rounding::Floor;
+        assert_eq!(block.active_pixels()[0].pos, Vec2D::new(-2, 2));
+    }
+}
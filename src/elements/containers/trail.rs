@@ -0,0 +1,151 @@
+use std::collections::VecDeque;
+
+use crate::{
+    elements::{
+        view::{Modifier, ViewElement},
+        Pixel,
+    },
+    gameloop::CanAnimate,
+};
+
+/// `Trail` is a container for a [`ViewElement`] that leaves behind where the element was in previous frames, fading out over [`length`](Trail::length) frames, for motion trails behind fast-moving objects. [`Modifier::Colour`] pixels in the trail darken with age, and [`with_chars()`](Trail::with_chars()) sets characters for older frames so trails show up without colour too. Call [`update()`](Trail::update()) every frame after moving the element
+/// ```
+/// use gemini_engine::elements::{containers::Trail, view::{ColChar, ViewElement}, Pixel, Vec2D};
+///
+/// let mut comet = Trail::new(Pixel::new(Vec2D::new(0, 0), ColChar::SOLID), 3).with_chars(['*', '.']);
+///
+/// for _ in 0..5 {
+///     comet.element.pos.x += 1;
+///     comet.update();
+/// }
+///
+/// let chars: String = comet.active_pixels().iter().map(|p| p.fill_char.text_char).collect();
+/// assert_eq!(chars, "..*█");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Trail<E: ViewElement> {
+    /// The element held by the `Trail`. Must implement [`ViewElement`]
+    pub element: E,
+    /// How many previous frames are shown in the trail
+    pub length: usize,
+    /// The characters used for the trail, from the most recent frame to the oldest. Frames older than the list use the last character. If empty, the trail keeps the element's characters
+    pub chars: Vec<char>,
+    /// The element's pixels as of each recent [`update()`](Trail::update()), newest first
+    history: VecDeque<Vec<Pixel>>,
+}
+
+impl<E: ViewElement> Trail<E> {
+    /// Create a new `Trail` that shows the element's last `length` frames
+    pub const fn new(element: E, length: usize) -> Self {
+        Self {
+            element,
+            length,
+            chars: vec![],
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Return the `Trail` with the given characters for its frames, from the most recent to the oldest
+    #[must_use]
+    pub fn with_chars(mut self, chars: impl IntoIterator<Item = char>) -> Self {
+        self.chars = chars.into_iter().collect();
+        self
+    }
+
+    /// Record where the element is this frame, dropping frames too old to be in the trail
+    pub fn update(&mut self) {
+        self.history.push_front(self.element.active_pixels());
+        self.history.truncate(self.length + 1);
+    }
+
+    /// Forget every previous frame, such as when the element teleports
+    pub fn clear(&mut self) {
+        self.history.clear();
+    }
+
+    /// Return the pixel as it appears `age` frames into the trail
+    fn faded(&self, mut pixel: Pixel, age: usize) -> Pixel {
+        if let Some(&c) = self.chars.get(age - 1).or_else(|| self.chars.last()) {
+            pixel.fill_char.text_char = c;
+        }
+        if let Modifier::Colour(colour) = pixel.fill_char.modifier {
+            let intensity = 1.0 - age as f64 / (self.length + 1) as f64;
+            pixel.fill_char.modifier = Modifier::Colour(colour * intensity);
+        }
+
+        pixel
+    }
+}
+
+impl<E: ViewElement> ViewElement for Trail<E> {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        // The newest recorded frame is where the element is now, so the trail starts after it. The oldest frames come first so newer ones are drawn over them
+        let mut pixels: Vec<Pixel> = self
+            .history
+            .iter()
+            .enumerate()
+            .skip(1)
+            .rev()
+            .flat_map(|(age, frame)| frame.iter().map(move |pixel| self.faded(*pixel, age)))
+            .collect();
+        pixels.extend(self.element.active_pixels());
+
+        pixels
+    }
+}
+
+impl<E: ViewElement> CanAnimate for Trail<E> {
+    fn is_animating(&self) -> bool {
+        self.history
+            .iter()
+            .skip(1)
+            .any(|frame| Some(frame) != self.history.front())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{
+        view::{ColChar, Colour},
+        Vec2D,
+    };
+
+    #[test]
+    fn fades_colours_with_age() {
+        let pixel = Pixel::new(
+            Vec2D::ZERO,
+            ColChar::SOLID.with_colour(Colour::greyscale(200)),
+        );
+        let mut trail = Trail::new(pixel, 3);
+
+        for _ in 0..4 {
+            trail.update();
+            trail.element.pos.x += 1;
+        }
+        trail.update();
+
+        let greys: Vec<u8> = trail
+            .active_pixels()
+            .iter()
+            .map(|pixel| pixel.fill_char.modifier.colour().map_or(0, |c| c.r))
+            .collect();
+        assert_eq!(greys, vec![50, 100, 150, 200]);
+    }
+
+    #[test]
+    fn stops_animating_once_still() {
+        let mut trail = Trail::new(Pixel::new(Vec2D::ZERO, ColChar::SOLID), 2);
+        trail.update();
+        trail.element.pos.y += 1;
+        trail.update();
+        assert!(trail.is_animating());
+
+        trail.update();
+        trail.update();
+        assert!(!trail.is_animating());
+
+        trail.clear();
+        assert_eq!(trail.active_pixels().len(), 1);
+    }
+}
@@ -0,0 +1,54 @@
+//! This module holds the building blocks for in-terminal sprite and ANSI art editors: a [`Canvas`] to paint on with undo and redo, the [`ColourPicker`] and [`CharPalette`] widgets to choose what to paint with, and a [`MapEditor`] for painting layered tile maps. Every widget is a [`ViewElement`](super::view::ViewElement), so they can be blit to a [`View`](super::View) like any other element
+
+mod canvas;
+pub use canvas::Canvas;
+
+mod colour_picker;
+pub use colour_picker::ColourPicker;
+
+mod char_palette;
+pub use char_palette::CharPalette;
+
+mod map_editor;
+pub use map_editor::{Brush, MapEditor};
+
+use super::Vec2D;
+
+/// Move a selection through a grid of `len` items laid out in rows of `columns`, clamping the selection to the grid. Used by the [`ColourPicker`] and [`CharPalette`] to handle cursor movement
+#[must_use]
+pub fn move_grid_selection(selected: usize, len: usize, columns: usize, direction: Vec2D) -> usize {
+    if len == 0 {
+        return 0;
+    }
+
+    let columns = columns.max(1) as isize;
+    let rows = (len as isize + columns - 1) / columns;
+    let x = (selected as isize % columns + direction.x).clamp(0, columns - 1);
+    let y = (selected as isize / columns + direction.y).clamp(0, rows - 1);
+
+    ((y * columns + x) as usize).min(len - 1)
+}
+
+/// Return the position of the item at `index` in a grid laid out in rows of `columns`, where each item takes up `cell_width` cells
+#[must_use]
+pub const fn grid_position(index: usize, columns: usize, cell_width: usize) -> Vec2D {
+    let columns = if columns == 0 { 1 } else { columns };
+
+    Vec2D::new(
+        ((index % columns) * cell_width) as isize,
+        (index / columns) as isize,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_selection_is_clamped() {
+        assert_eq!(move_grid_selection(0, 10, 4, Vec2D::new(-1, -1)), 0);
+        assert_eq!(move_grid_selection(3, 10, 4, Vec2D::new(1, 0)), 3);
+        assert_eq!(move_grid_selection(5, 10, 4, Vec2D::new(0, 1)), 9);
+        assert_eq!(move_grid_selection(7, 10, 4, Vec2D::new(0, 1)), 9);
+    }
+}
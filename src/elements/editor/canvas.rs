@@ -0,0 +1,243 @@
+use crate::elements::{
+    ascii::{split_rows, DEFAULT_TAB_WIDTH},
+    view::{ColChar, Modifier, ViewElement},
+    Pixel, Sprite, Vec2D,
+};
+
+/// A single undoable change to the [`Canvas`]: the index of every changed cell with its value before and after the change
+type Edit = Vec<(usize, Option<ColChar>, Option<ColChar>)>;
+
+/// A `Canvas` is a fixed size grid of cells that can be painted on and erased, with every change recorded so that it can be undone and redone. Empty cells are transparent when blit to a [`View`](crate::elements::View)
+#[derive(Debug, Clone)]
+pub struct Canvas {
+    /// The position of the top left corner of the `Canvas`
+    pub pos: Vec2D,
+    /// The width of the `Canvas`
+    width: usize,
+    /// The height of the `Canvas`
+    height: usize,
+    cells: Vec<Option<ColChar>>,
+    undo_stack: Vec<Edit>,
+    redo_stack: Vec<Edit>,
+}
+
+impl Canvas {
+    /// Create a new, empty `Canvas` with the given size
+    #[must_use]
+    pub fn new(pos: Vec2D, width: usize, height: usize) -> Self {
+        Self {
+            pos,
+            width,
+            height,
+            cells: vec![None; width * height],
+            undo_stack: vec![],
+            redo_stack: vec![],
+        }
+    }
+
+    /// Create a `Canvas` from a [`Sprite`]-style texture, sized to fit the texture. Whitespaces are left empty and every other character is painted with the given `Modifier`
+    #[must_use]
+    pub fn from_texture(pos: Vec2D, texture: &str, modifier: Modifier) -> Self {
+        let rows = split_rows(texture, DEFAULT_TAB_WIDTH);
+        let width = rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut canvas = Self::new(pos, width, rows.len());
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, text_char) in row.chars().enumerate() {
+                if text_char != ' ' {
+                    canvas.cells[y * width + x] = Some(ColChar::new(text_char, modifier));
+                }
+            }
+        }
+
+        canvas
+    }
+
+    /// Return the width and height of the `Canvas` as a [`Vec2D`]
+    #[must_use]
+    pub const fn size(&self) -> Vec2D {
+        Vec2D::new(self.width as isize, self.height as isize)
+    }
+
+    /// Return the index of the cell at the position relative to the top left corner of the `Canvas`, or `None` if the position is outside the `Canvas`
+    fn index_of(&self, pos: Vec2D) -> Option<usize> {
+        let x = usize::try_from(pos.x).ok()?;
+        let y = usize::try_from(pos.y).ok()?;
+
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    /// Return the cell at the position relative to the top left corner of the `Canvas`. Returns `None` if the cell is empty or outside the `Canvas`
+    #[must_use]
+    pub fn get(&self, pos: Vec2D) -> Option<ColChar> {
+        self.index_of(pos).and_then(|i| self.cells[i])
+    }
+
+    /// Set every given position to the same value as a single undoable change. Positions outside the `Canvas` and cells that already have the value are skipped. Returns true if anything changed
+    fn apply(&mut self, positions: &[Vec2D], value: Option<ColChar>) -> bool {
+        let mut edit: Edit = vec![];
+        for pos in positions {
+            if let Some(i) = self.index_of(*pos) {
+                // Repeated positions already hold the value by their second visit, so they're skipped here too
+                if self.cells[i] != value {
+                    edit.push((i, self.cells[i], value));
+                    self.cells[i] = value;
+                }
+            }
+        }
+
+        if edit.is_empty() {
+            false
+        } else {
+            self.undo_stack.push(edit);
+            self.redo_stack.clear();
+            true
+        }
+    }
+
+    /// Paint the cell at the position (relative to the top left corner of the `Canvas`). Returns true if the cell changed
+    pub fn paint(&mut self, pos: Vec2D, fill_char: ColChar) -> bool {
+        self.apply(&[pos], Some(fill_char))
+    }
+
+    /// Paint every given position as a single undoable change, for example a whole brush stroke or a line. Returns true if any cell changed
+    pub fn paint_many(&mut self, positions: &[Vec2D], fill_char: ColChar) -> bool {
+        self.apply(positions, Some(fill_char))
+    }
+
+    /// Empty the cell at the position (relative to the top left corner of the `Canvas`). Returns true if the cell changed
+    pub fn erase(&mut self, pos: Vec2D) -> bool {
+        self.apply(&[pos], None)
+    }
+
+    /// Empty every cell of the `Canvas` as a single undoable change. Returns true if any cell changed
+    pub fn clear(&mut self) -> bool {
+        let positions: Vec<Vec2D> = (0..self.cells.len())
+            .map(|i| Vec2D::new((i % self.width) as isize, (i / self.width) as isize))
+            .collect();
+
+        self.apply(&positions, None)
+    }
+
+    /// Returns true if there is a change that can be undone
+    #[must_use]
+    pub const fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    /// Returns true if there is an undone change that can be redone
+    #[must_use]
+    pub const fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Undo the last change. Returns false if there was nothing to undo
+    pub fn undo(&mut self) -> bool {
+        let Some(edit) = self.undo_stack.pop() else {
+            return false;
+        };
+
+        for (i, before, _) in &edit {
+            self.cells[*i] = *before;
+        }
+        self.redo_stack.push(edit);
+
+        true
+    }
+
+    /// Redo the last undone change. Returns false if there was nothing to redo
+    pub fn redo(&mut self) -> bool {
+        let Some(edit) = self.redo_stack.pop() else {
+            return false;
+        };
+
+        for (i, _, after) in &edit {
+            self.cells[*i] = *after;
+        }
+        self.undo_stack.push(edit);
+
+        true
+    }
+
+    /// Return the `Canvas` as a [`Sprite`] texture. Empty cells become whitespaces and trailing whitespaces are left out of each row. Cell colours are not included, as a `Sprite` only has one [`Modifier`]
+    #[must_use]
+    pub fn to_texture(&self) -> String {
+        self.cells
+            .chunks(self.width.max(1))
+            .map(|row| {
+                let row: String = row
+                    .iter()
+                    .map(|cell| cell.map_or(' ', |c| c.text_char))
+                    .collect();
+                String::from(row.trim_end())
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Return the `Canvas` as a [`Sprite`] at its position, with the given `Modifier`. Empty rows at the top of the `Canvas` are kept, unlike with [`Sprite::new()`]
+    #[must_use]
+    pub fn to_sprite(&self, modifier: Modifier) -> Sprite {
+        let mut sprite = Sprite::new(self.pos, "", modifier);
+        sprite.texture = self.to_texture();
+        sprite
+    }
+}
+
+impl ViewElement for Canvas {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(|(i, cell)| {
+                cell.map(|fill_char| {
+                    let pos = Vec2D::new((i % self.width) as isize, (i / self.width) as isize);
+                    Pixel::new(self.pos + pos, fill_char)
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_and_redo_edits() {
+        let mut canvas = Canvas::new(Vec2D::ZERO, 3, 2);
+        canvas.paint(Vec2D::new(0, 0), ColChar::SOLID);
+        canvas.paint_many(&[Vec2D::new(1, 1), Vec2D::new(2, 1)], ColChar::SOLID);
+
+        assert!(canvas.undo());
+        assert_eq!(canvas.get(Vec2D::new(1, 1)), None);
+        assert_eq!(canvas.get(Vec2D::new(0, 0)), Some(ColChar::SOLID));
+
+        assert!(canvas.redo());
+        assert_eq!(canvas.get(Vec2D::new(2, 1)), Some(ColChar::SOLID));
+        assert!(!canvas.redo());
+
+        canvas.undo();
+        canvas.erase(Vec2D::new(0, 0));
+        assert!(!canvas.can_redo());
+
+        // A stroke crossing the same cell twice is still undone in one step
+        let stroke = [Vec2D::new(2, 0), Vec2D::new(2, 0), Vec2D::new(2, 1)];
+        assert!(canvas.paint_many(&stroke, ColChar::BACKGROUND));
+        assert!(canvas.undo());
+        assert_eq!(canvas.get(Vec2D::new(2, 0)), None);
+    }
+
+    #[test]
+    fn texture_round_trip() {
+        let texture = "/\\\n\\ /";
+        let canvas = Canvas::from_texture(Vec2D::ZERO, texture, Modifier::None);
+
+        assert_eq!(canvas.size(), Vec2D::new(3, 2));
+        assert_eq!(canvas.to_texture(), texture);
+    }
+}
@@ -0,0 +1,79 @@
+use super::{grid_position, move_grid_selection};
+use crate::elements::{
+    view::{ColChar, Modifier, ViewElement},
+    Pixel, Vec2D,
+};
+
+/// A `CharPalette` displays a grid of characters to paint with, one of which is selected. Characters are spaced one cell apart, and the selected character is drawn with the [`selected_modifier`](CharPalette::selected_modifier) so that it stands out
+#[derive(Debug, Clone)]
+pub struct CharPalette {
+    /// The position of the top left corner of the `CharPalette`
+    pub pos: Vec2D,
+    /// The characters to pick from
+    pub chars: Vec<char>,
+    /// The index of the selected character
+    pub selected: usize,
+    /// How many characters are displayed in each row
+    pub columns: usize,
+    /// The [`Modifier`] used to draw every character but the selected one
+    pub modifier: Modifier,
+    /// The [`Modifier`] used to draw the selected character. Defaults to reversed colours
+    pub selected_modifier: Modifier,
+}
+
+impl CharPalette {
+    /// A selection of characters commonly used in ASCII art
+    pub const DEFAULT_CHARS: &'static str = "█▓▒░#@%&*+=-:.|/\\_()[]<>^~oO";
+
+    /// Create a new `CharPalette` with the given characters, displayed in rows of `columns` characters
+    #[must_use]
+    pub fn new(pos: Vec2D, chars: &str, columns: usize) -> Self {
+        Self {
+            pos,
+            chars: chars.chars().filter(|c| !c.is_control()).collect(),
+            selected: 0,
+            columns,
+            modifier: Modifier::None,
+            selected_modifier: Modifier::Coded(7),
+        }
+    }
+
+    /// Return the selected character, or `None` if there are no characters
+    #[must_use]
+    pub fn selected_char(&self) -> Option<char> {
+        self.chars.get(self.selected).copied()
+    }
+
+    /// Move the selection through the grid in the given direction, for example `Vec2D::new(0, 1)` to select the character below. The selection stops at the edges of the grid
+    pub fn move_selection(&mut self, direction: Vec2D) {
+        self.selected =
+            move_grid_selection(self.selected, self.chars.len(), self.columns, direction);
+    }
+}
+
+impl Default for CharPalette {
+    fn default() -> Self {
+        Self::new(Vec2D::ZERO, Self::DEFAULT_CHARS, 8)
+    }
+}
+
+impl ViewElement for CharPalette {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        self.chars
+            .iter()
+            .enumerate()
+            .map(|(i, text_char)| {
+                let modifier = if i == self.selected {
+                    self.selected_modifier
+                } else {
+                    self.modifier
+                };
+
+                Pixel::new(
+                    self.pos + grid_position(i, self.columns, 2),
+                    ColChar::new(*text_char, modifier),
+                )
+            })
+            .collect()
+    }
+}
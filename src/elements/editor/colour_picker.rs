@@ -0,0 +1,84 @@
+use super::{grid_position, move_grid_selection};
+use crate::elements::{
+    view::{ColChar, Colour, Modifier, ViewElement},
+    Pixel, Vec2D,
+};
+
+/// A `ColourPicker` displays a grid of colour swatches, one of which is selected. Each swatch is two cells wide, and the selected swatch is drawn with the [`selected_char`](ColourPicker::selected_char) so that it stands out
+#[derive(Debug, Clone)]
+pub struct ColourPicker {
+    /// The position of the top left corner of the `ColourPicker`
+    pub pos: Vec2D,
+    /// The colours to pick from
+    pub colours: Vec<Colour>,
+    /// The index of the selected colour
+    pub selected: usize,
+    /// How many swatches are displayed in each row
+    pub columns: usize,
+    /// The character used to draw every swatch but the selected one
+    pub swatch_char: char,
+    /// The character used to draw the selected swatch
+    pub selected_char: char,
+}
+
+impl ColourPicker {
+    /// Create a new `ColourPicker` with the given colours, displayed in rows of `columns` swatches
+    #[must_use]
+    pub const fn new(pos: Vec2D, colours: Vec<Colour>, columns: usize) -> Self {
+        Self {
+            pos,
+            colours,
+            selected: 0,
+            columns,
+            swatch_char: '█',
+            selected_char: '▒',
+        }
+    }
+
+    /// Create a `ColourPicker` with `count` evenly spread, fully saturated hues, followed by a row of greyscale shades from black to white
+    #[must_use]
+    pub fn rainbow(pos: Vec2D, count: usize) -> Self {
+        let count = count.max(2);
+        let mut colours: Vec<Colour> = (0..count)
+            .map(|i| Colour::hsv((i * 256 / count) as u8, 255, 255))
+            .collect();
+        colours.extend((0..count).map(|i| Colour::greyscale((i * 255 / (count - 1)) as u8)));
+
+        Self::new(pos, colours, count)
+    }
+
+    /// Return the selected colour, or `None` if there are no colours
+    #[must_use]
+    pub fn selected_colour(&self) -> Option<Colour> {
+        self.colours.get(self.selected).copied()
+    }
+
+    /// Move the selection through the grid in the given direction, for example `Vec2D::new(1, 0)` to select the swatch on the right. The selection stops at the edges of the grid
+    pub fn move_selection(&mut self, direction: Vec2D) {
+        self.selected =
+            move_grid_selection(self.selected, self.colours.len(), self.columns, direction);
+    }
+}
+
+impl ViewElement for ColourPicker {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        self.colours
+            .iter()
+            .enumerate()
+            .flat_map(|(i, colour)| {
+                let text_char = if i == self.selected {
+                    self.selected_char
+                } else {
+                    self.swatch_char
+                };
+                let fill_char = ColChar::new(text_char, Modifier::Colour(*colour));
+                let pos = self.pos + grid_position(i, self.columns, 2);
+
+                [
+                    Pixel::new(pos, fill_char),
+                    Pixel::new(pos + Vec2D::new(1, 0), fill_char),
+                ]
+            })
+            .collect()
+    }
+}
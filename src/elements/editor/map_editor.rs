@@ -0,0 +1,264 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::elements::{tilemap::TileMap, Line, Rect, Vec2D};
+
+/// The shape of the tiles a [`MapEditor`] paints around each position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Brush {
+    /// A single tile
+    #[default]
+    Point,
+    /// A square reaching `radius` tiles out from the centre in every direction
+    Square(usize),
+    /// A filled circle with the given radius
+    Circle(usize),
+}
+
+impl Brush {
+    /// Return the positions the brush covers when centred on the position
+    #[must_use]
+    pub fn points(self, centre: Vec2D) -> Vec<Vec2D> {
+        let (radius, is_round) = match self {
+            Self::Point => return vec![centre],
+            Self::Square(radius) => (radius as isize, false),
+            Self::Circle(radius) => (radius as isize, true),
+        };
+
+        (-radius..=radius)
+            .flat_map(|y| (-radius..=radius).map(move |x| Vec2D::new(x, y)))
+            .filter(|offset| {
+                !is_round || offset.x * offset.x + offset.y * offset.y <= radius * radius
+            })
+            .map(|offset| centre + offset)
+            .collect()
+    }
+}
+
+/// A single undoable change to the [`MapEditor`]: the layer, position and value before and after of every changed tile
+type MapEdit<T> = Vec<(usize, Vec2D, T, T)>;
+
+/// A `MapEditor` holds the layers of a tile map being edited, with brush, line, rectangle and flood fill tools that paint the active layer. Every tool use is a single change that can be undone and redone, like with the [`Canvas`](super::Canvas). Each layer is a [`TileMap`], so layers can be blit to the `View` from bottom to top to show the map
+/// ```
+/// use gemini_engine::elements::{editor::{Brush, MapEditor}, tilemap::TileMap, Vec2D};
+///
+/// let mut editor = MapEditor::new(TileMap::new(Vec2D::ZERO, 10, 6, '.'));
+/// editor.rect(Vec2D::new(1, 1), Vec2D::new(8, 4), &'#', false);
+/// editor.flood_fill(Vec2D::new(4, 2), &'~');
+/// editor.paint(Vec2D::new(4, 2), Brush::Point, &'o');
+///
+/// assert_eq!(editor.get(Vec2D::new(5, 3)), Some(&'~'));
+///
+/// editor.undo();
+/// editor.undo();
+/// assert_eq!(editor.get(Vec2D::new(5, 3)), Some(&'.'));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MapEditor<T> {
+    layers: Vec<TileMap<T>>,
+    active_layer: usize,
+    undo_stack: Vec<MapEdit<T>>,
+    redo_stack: Vec<MapEdit<T>>,
+}
+
+impl<T: Clone + PartialEq> MapEditor<T> {
+    /// Create a new `MapEditor` with the given map as its only layer
+    #[must_use]
+    pub fn new(base: TileMap<T>) -> Self {
+        Self {
+            layers: vec![base],
+            active_layer: 0,
+            undo_stack: vec![],
+            redo_stack: vec![],
+        }
+    }
+
+    /// Add a layer above the others with every tile set to `fill`, such as a transparent tile, and return its index. Adding a layer can't be undone
+    pub fn add_layer(&mut self, fill: T) -> usize {
+        let base = &self.layers[0];
+        let size = base.size();
+        self.layers.push(TileMap::new(
+            base.pos,
+            size.x as usize,
+            size.y as usize,
+            fill,
+        ));
+
+        self.layers.len() - 1
+    }
+
+    /// Return every layer, from bottom to top
+    #[must_use]
+    pub fn layers(&self) -> &[TileMap<T>] {
+        &self.layers
+    }
+
+    /// Return the index of the layer the tools paint on
+    #[must_use]
+    pub const fn active_layer(&self) -> usize {
+        self.active_layer
+    }
+
+    /// Change the layer the tools paint on. Returns false if there is no layer with that index
+    pub const fn set_active_layer(&mut self, layer: usize) -> bool {
+        let exists = layer < self.layers.len();
+        if exists {
+            self.active_layer = layer;
+        }
+
+        exists
+    }
+
+    /// Return the tile on the active layer at the position, or `None` if the position is outside the map
+    #[must_use]
+    pub fn get(&self, pos: Vec2D) -> Option<&T> {
+        self.layers[self.active_layer].get(pos)
+    }
+
+    /// Set every given position on the active layer to the tile as a single undoable change. Positions outside the map and tiles that already match are skipped. Returns true if anything changed
+    fn apply(&mut self, positions: impl IntoIterator<Item = Vec2D>, tile: &T) -> bool {
+        let layer = self.active_layer;
+        let map = &mut self.layers[layer];
+        let mut edit: MapEdit<T> = vec![];
+        for pos in positions {
+            if let Some(old) = map.get_mut(pos) {
+                if old != tile {
+                    edit.push((layer, pos, old.clone(), tile.clone()));
+                    *old = tile.clone();
+                }
+            }
+        }
+
+        if edit.is_empty() {
+            false
+        } else {
+            self.undo_stack.push(edit);
+            self.redo_stack.clear();
+            true
+        }
+    }
+
+    /// Paint the brush at the position. Returns true if any tile changed
+    pub fn paint(&mut self, pos: Vec2D, brush: Brush, tile: &T) -> bool {
+        self.apply(brush.points(pos), tile)
+    }
+
+    /// Paint the brush along a line between the two positions, as a single change. Returns true if any tile changed
+    pub fn line(&mut self, from: Vec2D, to: Vec2D, brush: Brush, tile: &T) -> bool {
+        let points: Vec<Vec2D> = Line::draw(from, to)
+            .into_iter()
+            .flat_map(|pos| brush.points(pos))
+            .collect();
+
+        self.apply(points, tile)
+    }
+
+    /// Paint a rectangle between two opposite corners, either filled or just its outline. Returns true if any tile changed
+    pub fn rect(&mut self, corner0: Vec2D, corner1: Vec2D, tile: &T, filled: bool) -> bool {
+        let min = Vec2D::new(corner0.x.min(corner1.x), corner0.y.min(corner1.y));
+        let max = Vec2D::new(corner0.x.max(corner1.x), corner0.y.max(corner1.y));
+        let points = Rect::draw(min, max - min + Vec2D::new(1, 1))
+            .into_iter()
+            .filter(|pos| {
+                filled || pos.x == min.x || pos.x == max.x || pos.y == min.y || pos.y == max.y
+            });
+
+        self.apply(points, tile)
+    }
+
+    /// Replace the area of matching, orthogonally connected tiles around the position with the tile. Returns true if any tile changed
+    pub fn flood_fill(&mut self, pos: Vec2D, tile: &T) -> bool {
+        let map = &self.layers[self.active_layer];
+        let Some(target) = map.get(pos) else {
+            return false;
+        };
+
+        let mut visited = HashSet::from([pos]);
+        let mut queue = VecDeque::from([pos]);
+        while let Some(current) = queue.pop_front() {
+            for neighbour in map.neighbours(current) {
+                if map.get(neighbour) == Some(target) && visited.insert(neighbour) {
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        self.apply(visited, tile)
+    }
+
+    /// Returns true if there is a change that can be undone
+    #[must_use]
+    pub const fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    /// Returns true if there is an undone change that can be redone
+    #[must_use]
+    pub const fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Undo the last change. Returns false if there was nothing to undo
+    pub fn undo(&mut self) -> bool {
+        let Some(edit) = self.undo_stack.pop() else {
+            return false;
+        };
+
+        for (layer, pos, before, _) in &edit {
+            self.layers[*layer].set(*pos, before.clone());
+        }
+        self.redo_stack.push(edit);
+
+        true
+    }
+
+    /// Redo the last undone change. Returns false if there was nothing to redo
+    pub fn redo(&mut self) -> bool {
+        let Some(edit) = self.redo_stack.pop() else {
+            return false;
+        };
+
+        for (layer, pos, _, after) in &edit {
+            self.layers[*layer].set(*pos, after.clone());
+        }
+        self.undo_stack.push(edit);
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brushes_and_lines() {
+        assert_eq!(Brush::Square(1).points(Vec2D::ZERO).len(), 9);
+        assert_eq!(Brush::Circle(1).points(Vec2D::ZERO).len(), 5);
+
+        let mut editor = MapEditor::new(TileMap::new(Vec2D::ZERO, 5, 5, 0));
+        assert!(editor.line(Vec2D::new(0, 0), Vec2D::new(4, 4), Brush::Point, &1));
+        assert!(!editor.line(Vec2D::new(0, 0), Vec2D::new(4, 4), Brush::Point, &1));
+        assert_eq!(editor.get(Vec2D::new(2, 2)), Some(&1));
+        assert_eq!(editor.get(Vec2D::new(2, 3)), Some(&0));
+    }
+
+    #[test]
+    fn layers_and_undo() {
+        let mut editor = MapEditor::new(TileMap::new(Vec2D::ZERO, 4, 4, 'g'));
+        let top = editor.add_layer(' ');
+        assert!(editor.set_active_layer(top));
+        assert!(!editor.set_active_layer(5));
+
+        editor.rect(Vec2D::new(3, 3), Vec2D::new(0, 0), &'#', false);
+        editor.flood_fill(Vec2D::new(1, 1), &'~');
+        assert_eq!(editor.layers()[0].get(Vec2D::new(1, 1)), Some(&'g'));
+        assert_eq!(editor.get(Vec2D::new(2, 2)), Some(&'~'));
+        assert_eq!(editor.get(Vec2D::new(3, 0)), Some(&'#'));
+
+        editor.set_active_layer(0);
+        assert!(editor.undo());
+        assert_eq!(editor.layers()[top].get(Vec2D::new(2, 2)), Some(&' '));
+        assert!(editor.redo());
+        assert!(!editor.can_redo());
+    }
+}
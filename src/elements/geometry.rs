@@ -1,5 +1,8 @@
 //! This module contains basic geometry structs that implement [`ViewElement`](super::view::ViewElement), such as [`Line`] or [`Triangle`]
 
+mod circle;
+pub use circle::Circle;
+
 mod line;
 pub use line::Line;
 
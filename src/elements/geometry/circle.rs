@@ -0,0 +1,80 @@
+use crate::{
+    elements::view::{utils, ColChar, Pixel, Vec2D, ViewElement},
+    utils::cell_aspect_ratio,
+};
+
+/// The `Circle` takes a centre and radius, and returns a filled circle when blit to a [`View`](super::super::View). The circle is stretched horizontally by the engine-wide [`cell_aspect_ratio()`] so that it looks round despite terminal cells being taller than they are wide
+pub struct Circle {
+    /// The centre of the `Circle`
+    pub pos: Vec2D,
+    /// The radius of the `Circle`, measured in rows
+    pub radius: f64,
+    /// The [`ColChar`] used to fill the circle
+    pub fill_char: ColChar,
+}
+
+impl Circle {
+    /// Create a new circle using a given centre, radius and [`ColChar`]
+    #[must_use]
+    pub const fn new(pos: Vec2D, radius: f64, fill_char: ColChar) -> Self {
+        Self {
+            pos,
+            radius,
+            fill_char,
+        }
+    }
+
+    /// Draw a filled circle with a given centre and radius in rows, on a grid of cells `aspect_ratio` times taller than they are wide
+    #[must_use]
+    pub fn draw(pos: Vec2D, radius: f64, aspect_ratio: f64) -> Vec<Vec2D> {
+        if radius < 0.0 {
+            return vec![];
+        }
+        let half_height = radius.floor() as isize;
+        let half_width = (radius * aspect_ratio).floor() as isize;
+        // Padding the radius slightly gives rounder edges on small circles
+        let radius_squared = (radius + 0.5).powi(2);
+
+        (-half_height..=half_height)
+            .flat_map(|y| (-half_width..=half_width).map(move |x| Vec2D::new(x, y)))
+            .filter(|offset| {
+                let x = offset.x as f64 / aspect_ratio;
+                let y = offset.y as f64;
+                x.mul_add(x, y * y) <= radius_squared
+            })
+            .map(|offset| pos + offset)
+            .collect()
+    }
+}
+
+impl ViewElement for Circle {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        utils::points_to_pixels(&self.active_points(), self.fill_char)
+    }
+
+    fn active_points(&self) -> Vec<Vec2D> {
+        Self::draw(self.pos, self.radius, cell_aspect_ratio())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::Polygon;
+
+    #[test]
+    fn corrects_for_aspect_ratio() {
+        let points = Circle::draw(Vec2D::ZERO, 3.0, 2.0);
+        let width = points.iter().map(|p| p.x).max().unwrap_or(0) * 2 + 1;
+        let height = points.iter().map(|p| p.y).max().unwrap_or(0) * 2 + 1;
+        assert_eq!((width, height), (13, 7));
+
+        let rotated = Polygon::rotate_points(
+            &[Vec2D::new(6, 0)],
+            Vec2D::ZERO,
+            std::f64::consts::FRAC_PI_2,
+            2.0,
+        );
+        assert_eq!(rotated, vec![Vec2D::new(0, 3)]);
+    }
+}
@@ -1,5 +1,8 @@
 use super::Triangle;
-use crate::elements::view::{utils, ColChar, Pixel, Vec2D, ViewElement};
+use crate::{
+    elements::view::{utils, ColChar, Pixel, Vec2D, ViewElement},
+    utils::cell_aspect_ratio,
+};
 
 fn is_left_turn(p0: Vec2D, p1: Vec2D, p2: Vec2D) -> bool {
     let v1 = p1 - p0;
@@ -45,6 +48,34 @@ impl Polygon {
         Self { points, fill_char }
     }
 
+    /// Rotate the `Polygon`'s points clockwise by `radians` around `centre`, correcting for the engine-wide [`cell_aspect_ratio()`] so that the shape doesn't shear as it turns
+    pub fn rotate(&mut self, centre: Vec2D, radians: f64) {
+        self.points = Self::rotate_points(&self.points, centre, radians, cell_aspect_ratio());
+    }
+
+    /// Rotate points clockwise by `radians` around `centre` on a grid of cells `aspect_ratio` times taller than they are wide. Rotating on the raw grid would squash shapes vertically as they turn
+    #[must_use]
+    pub fn rotate_points(
+        points: &[Vec2D],
+        centre: Vec2D,
+        radians: f64,
+        aspect_ratio: f64,
+    ) -> Vec<Vec2D> {
+        let (sin, cos) = radians.sin_cos();
+
+        points
+            .iter()
+            .map(|point| {
+                let offset = *point - centre;
+                let (x, y) = (offset.x as f64 / aspect_ratio, offset.y as f64);
+                let rotated_x = x.mul_add(cos, -y * sin) * aspect_ratio;
+                let rotated_y = x.mul_add(sin, y * cos);
+
+                centre + Vec2D::new(rotated_x.round() as isize, rotated_y.round() as isize)
+            })
+            .collect()
+    }
+
     /// Split a polygon up into triangles using the ear cutting algorithm. Returns a vec of coordinate sets for each triangle
     #[must_use]
     pub fn triangulate(vertices: &[Vec2D]) -> Vec<[Vec2D; 3]> {
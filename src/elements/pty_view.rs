@@ -0,0 +1,384 @@
+//! This module is home to the [`PtyView`] element, which runs an external program on a pseudo-terminal and displays its output. Only available with the `pty` feature
+
+use std::{
+    error::Error,
+    io::{self, Read, Write},
+    sync::{Arc, Mutex, PoisonError},
+    thread,
+};
+
+pub use portable_pty::CommandBuilder;
+use portable_pty::{native_pty_system, Child, MasterPty, PtySize};
+
+use super::{
+    ascii::AnsiTokeniser,
+    view::{ColChar, Modifier, ViewElement},
+    Pixel, Vec2D,
+};
+use crate::EngineError;
+
+/// The tab stop distance used by most terminals
+const TAB_WIDTH: isize = 8;
+
+/// A minimal terminal emulator, holding the grid of characters written by the program. Handles printable text, SGR colours, cursor movement and clearing, which is enough for most line-based programs
+#[derive(Debug, Clone)]
+struct Screen {
+    size: Vec2D,
+    cells: Vec<ColChar>,
+    cursor: Vec2D,
+    modifier: Modifier,
+    pending: Vec<u8>,
+}
+
+impl Screen {
+    /// Create an empty `Screen`. Negative dimensions are treated as zero
+    fn new(size: Vec2D) -> Self {
+        let size = Vec2D::new(size.x.max(0), size.y.max(0));
+        Self {
+            size,
+            cells: vec![ColChar::EMPTY; (size.x * size.y) as usize],
+            cursor: Vec2D::ZERO,
+            modifier: Modifier::None,
+            pending: vec![],
+        }
+    }
+
+    fn resize(&mut self, size: Vec2D) {
+        let mut resized = Self::new(size);
+        let size = resized.size;
+        for y in 0..size.y.min(self.size.y) {
+            for x in 0..size.x.min(self.size.x) {
+                resized.cells[(y * size.x + x) as usize] =
+                    self.cells[(y * self.size.x + x) as usize];
+            }
+        }
+        resized.cursor = self.cursor;
+        resized.modifier = self.modifier;
+        *self = resized;
+        self.clamp_cursor();
+    }
+
+    /// Keep the cursor inside the grid, or at the origin if the grid is empty
+    fn clamp_cursor(&mut self) {
+        self.cursor = Vec2D::new(
+            self.cursor.x.min(self.size.x - 1).max(0),
+            self.cursor.y.min(self.size.y - 1).max(0),
+        );
+    }
+
+    /// Clear the cells from `start` (inclusive) to `end` (exclusive), as indices into the grid
+    fn clear_range(&mut self, start: isize, end: isize) {
+        let len = self.cells.len() as isize;
+        for cell in &mut self.cells[start.clamp(0, len) as usize..end.clamp(0, len) as usize] {
+            *cell = ColChar::EMPTY;
+        }
+    }
+
+    fn newline(&mut self) {
+        if self.cursor.y + 1 < self.size.y {
+            self.cursor.y += 1;
+        } else if !self.cells.is_empty() {
+            // Scroll everything up by one row
+            self.cells.drain(..self.size.x as usize);
+            self.cells
+                .extend(std::iter::repeat_n(ColChar::EMPTY, self.size.x as usize));
+        }
+    }
+
+    fn put(&mut self, text_char: char) {
+        if self.cursor.x >= self.size.x {
+            self.cursor.x = 0;
+            self.newline();
+        }
+        let i = self.cursor.y * self.size.x + self.cursor.x;
+        if let Some(cell) = self.cells.get_mut(i as usize) {
+            *cell = ColChar::new(text_char, self.modifier);
+        }
+        self.cursor.x += 1;
+    }
+
+    /// Apply a complete CSI sequence, such as `\x1b[2J`
+    fn apply_csi(&mut self, sequence: &str) {
+        let Some(last) = sequence.chars().last() else {
+            return;
+        };
+        let params: Vec<isize> = sequence[2..sequence.len() - 1]
+            .split(';')
+            .map(|param| param.trim_start_matches('?').parse().unwrap_or(0))
+            .collect();
+        let first = params.first().copied().unwrap_or(0);
+        let count = first.max(1);
+
+        match last {
+            'm' => {
+                if let Ok(modifier) = Modifier::parse_ansi(sequence) {
+                    self.modifier = modifier;
+                }
+            }
+            'A' => self.cursor.y -= count,
+            'B' => self.cursor.y += count,
+            'C' => self.cursor.x += count,
+            'D' => self.cursor.x -= count,
+            'G' => self.cursor.x = count - 1,
+            'H' | 'f' => {
+                let column = params.get(1).copied().unwrap_or(0).max(1);
+                self.cursor = Vec2D::new(column - 1, count - 1);
+            }
+            'J' => {
+                let cursor = self.cursor.y * self.size.x + self.cursor.x;
+                match first {
+                    0 => self.clear_range(cursor, self.cells.len() as isize),
+                    1 => self.clear_range(0, cursor + 1),
+                    _ => self.clear_range(0, self.cells.len() as isize),
+                }
+            }
+            'K' => {
+                let row = self.cursor.y * self.size.x;
+                match first {
+                    0 => self.clear_range(row + self.cursor.x, row + self.size.x),
+                    1 => self.clear_range(row, row + self.cursor.x + 1),
+                    _ => self.clear_range(row, row + self.size.x),
+                }
+            }
+            _ => (),
+        }
+
+        self.clamp_cursor();
+    }
+
+    /// Process output from the program. Incomplete escape sequences and UTF-8 characters are kept until the rest arrives
+    fn process(&mut self, bytes: &[u8]) {
+        self.pending.extend_from_slice(bytes);
+        let pending = std::mem::take(&mut self.pending);
+        let (text, mut leftover) = match std::str::from_utf8(&pending) {
+            Ok(text) => (String::from(text), vec![]),
+            Err(e) if e.error_len().is_none() => (
+                String::from_utf8_lossy(&pending[..e.valid_up_to()]).into_owned(),
+                pending[e.valid_up_to()..].to_vec(),
+            ),
+            Err(_) => (String::from_utf8_lossy(&pending).into_owned(), vec![]),
+        };
+
+        let mut rest = text.as_str();
+        while let Some(c) = rest.chars().next() {
+            let mut length = c.len_utf8();
+            match c {
+                '\x1b' => {
+                    let Some(escape_length) = AnsiTokeniser::sequence_length(rest) else {
+                        let mut unfinished = rest.as_bytes().to_vec();
+                        unfinished.append(&mut leftover);
+                        leftover = unfinished;
+                        break;
+                    };
+                    if rest.as_bytes()[1] == b'[' {
+                        self.apply_csi(&rest[..escape_length]);
+                    }
+                    length = escape_length;
+                }
+                '\n' => self.newline(),
+                '\r' => self.cursor.x = 0,
+                '\t' => {
+                    self.cursor.x = (self.cursor.x / TAB_WIDTH + 1) * TAB_WIDTH;
+                    self.clamp_cursor();
+                }
+                '\x08' => self.cursor.x = (self.cursor.x - 1).max(0),
+                c if c.is_control() => (),
+                c => self.put(c),
+            }
+            rest = &rest[length..];
+        }
+
+        self.pending = leftover;
+    }
+}
+
+/// Wrap an error from the pseudo-terminal in an [`EngineError::Io`]
+fn pty_error(err: impl Into<Box<dyn Error + Send + Sync>>) -> EngineError {
+    EngineError::Io(io::Error::other(err))
+}
+
+/// Convert a [`Vec2D`] size to a [`PtySize`]
+fn pty_size(size: Vec2D) -> PtySize {
+    PtySize {
+        rows: u16::try_from(size.y).unwrap_or(u16::MAX),
+        cols: u16::try_from(size.x).unwrap_or(u16::MAX),
+        pixel_width: 0,
+        pixel_height: 0,
+    }
+}
+
+/// A `PtyView` runs an external program on a pseudo-terminal and displays its output inside a rectangle of the [`View`](super::View). Input is forwarded to the program with [`send_input()`](PtyView::send_input()) while the `PtyView` is [`focused`](PtyView::focused). Useful for in-game consoles or terminal multiplexer-style tools
+///
+/// Output is read on a background thread, so the `PtyView` always shows the latest output when blit. Only a small subset of terminal escape codes is understood (colours, cursor movement and clearing), so full-screen programs may not display correctly
+pub struct PtyView {
+    /// The position of the top left corner of the `PtyView`
+    pub pos: Vec2D,
+    /// Whether input passed to [`send_input()`](PtyView::send_input()) is forwarded to the program
+    pub focused: bool,
+    screen: Arc<Mutex<Screen>>,
+    master: Box<dyn MasterPty + Send>,
+    writer: Box<dyn Write + Send>,
+    child: Box<dyn Child + Send + Sync>,
+}
+
+impl PtyView {
+    /// Spawn the command on a new pseudo-terminal of the given size. The `PtyView` starts out focused
+    ///
+    /// # Errors
+    /// Returns an error if the pseudo-terminal couldn't be opened or the command couldn't be spawned
+    pub fn spawn(pos: Vec2D, size: Vec2D, command: CommandBuilder) -> Result<Self, EngineError> {
+        let pair = native_pty_system()
+            .openpty(pty_size(size))
+            .map_err(pty_error)?;
+        let child = pair.slave.spawn_command(command).map_err(pty_error)?;
+        drop(pair.slave);
+
+        let mut reader = pair.master.try_clone_reader().map_err(pty_error)?;
+        let writer = pair.master.take_writer().map_err(pty_error)?;
+
+        let screen = Arc::new(Mutex::new(Screen::new(size)));
+        let thread_screen = Arc::clone(&screen);
+        thread::spawn(move || {
+            let mut buffer = [0; 4096];
+            while let Ok(read @ 1..) = reader.read(&mut buffer) {
+                thread_screen
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .process(&buffer[..read]);
+            }
+        });
+
+        Ok(Self {
+            pos,
+            focused: true,
+            screen,
+            master: pair.master,
+            writer,
+            child,
+        })
+    }
+
+    /// Return the size of the `PtyView`
+    #[must_use]
+    pub fn size(&self) -> Vec2D {
+        self.screen
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .size
+    }
+
+    /// Resize the `PtyView` and the program's terminal
+    ///
+    /// # Errors
+    /// Returns an error if the pseudo-terminal couldn't be resized
+    pub fn resize(&mut self, size: Vec2D) -> Result<(), EngineError> {
+        self.master.resize(pty_size(size)).map_err(pty_error)?;
+        self.screen
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .resize(size);
+
+        Ok(())
+    }
+
+    /// Forward input (for example a key press, or `b"\r"` for enter) to the program if the `PtyView` is focused. Returns whether the input was forwarded
+    ///
+    /// # Errors
+    /// Returns an error if writing to the pseudo-terminal fails
+    pub fn send_input(&mut self, input: &[u8]) -> io::Result<bool> {
+        if !self.focused {
+            return Ok(false);
+        }
+
+        self.writer.write_all(input)?;
+        self.writer.flush()?;
+        Ok(true)
+    }
+
+    /// Returns true if the program is still running
+    pub fn is_running(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+
+    /// Kill the program
+    ///
+    /// # Errors
+    /// Returns an error if the program couldn't be killed
+    pub fn kill(&mut self) -> io::Result<()> {
+        self.child.kill()
+    }
+}
+
+impl Drop for PtyView {
+    fn drop(&mut self) {
+        if self.is_running() {
+            let _ = self.kill();
+        }
+    }
+}
+
+impl ViewElement for PtyView {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let screen = self.screen.lock().unwrap_or_else(PoisonError::into_inner);
+
+        (0..)
+            .zip(&screen.cells)
+            .map(|(i, cell)| {
+                let pos = Vec2D::new(i % screen.size.x, i / screen.size.x);
+                Pixel::new(self.pos + pos, *cell)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(screen: &Screen, y: isize) -> String {
+        screen.cells[(y * screen.size.x) as usize..((y + 1) * screen.size.x) as usize]
+            .iter()
+            .map(|c| c.text_char)
+            .collect()
+    }
+
+    #[test]
+    fn screen_handles_text_and_escapes() {
+        let mut screen = Screen::new(Vec2D::new(6, 2));
+        screen.process(b"ab\r\n\x1b[31mcd\x1b[0m");
+        screen.process(b"\x1b[1;5Hx");
+
+        assert_eq!(row(&screen, 0), "ab  x ");
+        assert_eq!(row(&screen, 1), "cd    ");
+        assert_eq!(screen.cells[6].modifier, Modifier::RED);
+    }
+
+    #[test]
+    fn screen_scrolls_and_waits_for_split_sequences() {
+        let mut screen = Screen::new(Vec2D::new(3, 2));
+        screen.process(b"1\r\n2\r\n3\x1b[");
+        screen.process(b"2K\xe2\x96");
+        screen.process(b"\x88");
+
+        assert_eq!(row(&screen, 0), "2  ");
+        assert_eq!(row(&screen, 1), " █ ");
+    }
+
+    #[test]
+    fn empty_screen_ignores_output() {
+        let mut screen = Screen::new(Vec2D::new(4, 2));
+        screen.resize(Vec2D::ZERO);
+        screen.process(b"ab\t\x1b[2;3Hc\r\n\x1b]0;title\x07d");
+        assert!(screen.cells.is_empty());
+
+        screen.resize(Vec2D::new(2, 1));
+        screen.process(b"\x1b]0;title\x1b\\\re");
+        assert_eq!(row(&screen, 0), "e ");
+
+        for size in [Vec2D::new(4, 0), Vec2D::new(-3, -2), Vec2D::new(0, 3)] {
+            let mut screen = Screen::new(size);
+            screen.process(b"ab\ncd\r\n");
+            assert!(screen.cells.is_empty());
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//! This module is home to [`TileMap`], a fixed size grid of tiles for levels, dungeons and other grid-based worlds. The tiles can be any type, such as an enum of terrain, and are drawn by converting them to [`ColChar`]s
+//! ```
+//! use gemini_engine::elements::{tilemap::TileMap, view::ColChar, Vec2D};
+//!
+//! let mut map = TileMap::new(Vec2D::ZERO, 5, 3, ColChar::BACKGROUND);
+//! map.set(Vec2D::new(2, 1), ColChar::SOLID);
+//!
+//! assert_eq!(map.get(Vec2D::new(2, 1)), Some(&ColChar::SOLID));
+//! assert_eq!(map.get(Vec2D::new(5, 1)), None);
+//! ```
+
+use super::{
+    ascii::{split_rows, DEFAULT_TAB_WIDTH},
+    view::{ColChar, ViewElement},
+    Pixel, Sprite, Vec2D,
+};
+
+/// The four orthogonal directions, used to find a tile's neighbours
+pub const ORTHOGONAL: [Vec2D; 4] = [
+    Vec2D::new(0, -1),
+    Vec2D::new(1, 0),
+    Vec2D::new(0, 1),
+    Vec2D::new(-1, 0),
+];
+
+/// A fixed size grid of tiles. Positions passed to its methods are relative to the top left tile, while [`pos`](TileMap::pos) is only used when drawing it. Any `TileMap` of tiles that convert to [`ColChar`] can be blit to a [`View`](super::View)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileMap<T> {
+    /// The position of the top left tile when drawn
+    pub pos: Vec2D,
+    width: usize,
+    height: usize,
+    tiles: Vec<T>,
+}
+
+impl<T> TileMap<T> {
+    /// Create a new `TileMap` with every tile set to `fill`
+    #[must_use]
+    pub fn new(pos: Vec2D, width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            pos,
+            width,
+            height,
+            tiles: vec![fill; width * height],
+        }
+    }
+
+    /// Create a new `TileMap` with every tile set by calling the function with its position
+    #[must_use]
+    pub fn from_fn(
+        pos: Vec2D,
+        width: usize,
+        height: usize,
+        mut tile: impl FnMut(Vec2D) -> T,
+    ) -> Self {
+        let tiles = (0..width * height)
+            .map(|i| tile(Vec2D::new((i % width) as isize, (i / width) as isize)))
+            .collect();
+
+        Self {
+            pos,
+            width,
+            height,
+            tiles,
+        }
+    }
+
+    /// Return the width and height of the `TileMap` as a [`Vec2D`]
+    #[must_use]
+    pub const fn size(&self) -> Vec2D {
+        Vec2D::new(self.width as isize, self.height as isize)
+    }
+
+    /// Return the index of the tile at the position, or `None` if the position is outside the `TileMap`
+    fn index_of(&self, pos: Vec2D) -> Option<usize> {
+        let x = usize::try_from(pos.x).ok()?;
+        let y = usize::try_from(pos.y).ok()?;
+
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    /// Returns true if the position is inside the `TileMap`
+    #[must_use]
+    pub fn contains(&self, pos: Vec2D) -> bool {
+        self.index_of(pos).is_some()
+    }
+
+    /// Return the tile at the position, or `None` if the position is outside the `TileMap`
+    #[must_use]
+    pub fn get(&self, pos: Vec2D) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.tiles[i])
+    }
+
+    /// Return the tile at the position mutably, or `None` if the position is outside the `TileMap`
+    pub fn get_mut(&mut self, pos: Vec2D) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.tiles[i])
+    }
+
+    /// Set the tile at the position. Returns false if the position is outside the `TileMap`
+    pub fn set(&mut self, pos: Vec2D, tile: T) -> bool {
+        self.get_mut(pos).map(|old| *old = tile).is_some()
+    }
+
+    /// Return every tile, row by row
+    #[must_use]
+    pub fn tiles(&self) -> &[T] {
+        &self.tiles
+    }
+
+    /// Return an iterator over every position in the `TileMap`, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Vec2D> {
+        let width = self.width;
+        (0..self.tiles.len()).map(move |i| Vec2D::new((i % width) as isize, (i / width) as isize))
+    }
+
+    /// Return an iterator over every position in the `TileMap` with its tile, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2D, &T)> {
+        self.positions().zip(&self.tiles)
+    }
+
+    /// Return an iterator over the orthogonal neighbours of the position that are inside the `TileMap`
+    pub fn neighbours(&self, pos: Vec2D) -> impl Iterator<Item = Vec2D> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .map(move |direction| pos + direction)
+            .filter(|neighbour| self.contains(*neighbour))
+    }
+
+    /// Return a new `TileMap` of the same size and position, with every tile converted by the function
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> TileMap<U> {
+        TileMap {
+            pos: self.pos,
+            width: self.width,
+            height: self.height,
+            tiles: self.tiles.iter().map(f).collect(),
+        }
+    }
+}
+
+impl From<&Sprite> for TileMap<ColChar> {
+    /// Convert the `Sprite`'s texture to a `TileMap` with a tile for every character, using the `Sprite`'s modifier. Rows shorter than the longest are padded with spaces
+    fn from(value: &Sprite) -> Self {
+        let rows: Vec<Vec<char>> = split_rows(&value.texture, DEFAULT_TAB_WIDTH)
+            .iter()
+            .map(|row| row.chars().collect())
+            .collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+        Self::from_fn(value.pos, width, rows.len(), |pos| {
+            let text_char = rows[pos.y as usize]
+                .get(pos.x as usize)
+                .copied()
+                .unwrap_or(' ');
+            ColChar::new(text_char, value.modifier)
+        })
+    }
+}
+
+impl<T: Clone + Into<ColChar>> ViewElement for TileMap<T> {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        self.iter()
+            .map(|(pos, tile)| Pixel::new(self.pos + pos, tile.clone().into()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let map = TileMap::from_fn(Vec2D::ZERO, 3, 2, |pos| pos.x + pos.y);
+
+        assert_eq!(map.get(Vec2D::new(2, 1)), Some(&3));
+        assert_eq!(
+            map.neighbours(Vec2D::ZERO).collect::<Vec<_>>(),
+            vec![Vec2D::new(1, 0), Vec2D::new(0, 1)]
+        );
+        assert_eq!(map.map(|n| n * 2).tiles(), &[0, 2, 4, 2, 4, 6]);
+    }
+
+    #[test]
+    fn draws_tiles_at_position() {
+        let map = TileMap::new(Vec2D::new(4, 4), 2, 1, ColChar::SOLID);
+
+        assert_eq!(
+            map.active_points(),
+            vec![Vec2D::new(4, 4), Vec2D::new(5, 4)]
+        );
+    }
+}
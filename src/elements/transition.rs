@@ -0,0 +1,260 @@
+//! This module is home to [`Transition`], which blends between two [`View`] snapshots over time for smooth changes between scenes. Take a snapshot of each scene by cloning its rendered `View`, then blit the `Transition` every frame instead of either scene until it [`is_finished()`](Transition::is_finished())
+//! ```
+//! use gemini_engine::elements::{
+//!     transition::{Transition, TransitionEffect},
+//!     view::{ColChar, Wrapping},
+//!     View,
+//! };
+//! use std::time::Duration;
+//!
+//! let title_screen = View::new(20, 10, ColChar::BACKGROUND);
+//! let level = View::new(20, 10, ColChar::SOLID);
+//!
+//! let mut transition = Transition::new(
+//!     title_screen,
+//!     level,
+//!     TransitionEffect::Iris,
+//!     Duration::from_millis(500),
+//! );
+//!
+//! let mut view = View::new(20, 10, ColChar::EMPTY);
+//! while !transition.is_finished() {
+//!     view.blit(&transition, Wrapping::Ignore);
+//!     // render the view and sleep here
+//!     transition.update(Duration::from_millis(100));
+//! }
+//! ```
+
+use super::{
+    view::{ColChar, Colour, Modifier, ViewElement},
+    Pixel, Vec2D, View,
+};
+use crate::{gameloop::CanAnimate, procgen::random_unit, utils::cell_aspect_ratio};
+use std::time::Duration;
+
+/// The direction a [`TransitionEffect::Wipe`] moves in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WipeDirection {
+    /// The new scene is revealed from the left edge to the right
+    Right,
+    /// The new scene is revealed from the right edge to the left
+    Left,
+    /// The new scene is revealed from the top edge down
+    Down,
+    /// The new scene is revealed from the bottom edge up
+    Up,
+}
+
+/// The effect used by a [`Transition`] to move from one scene to the other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionEffect {
+    /// Fade the old scene out to a solid colour, then fade the new scene in from it
+    Fade(Colour),
+    /// Swap each cell to the new scene at a random moment, with the given seed
+    Dissolve(u64),
+    /// Sweep the new scene across the old one in a direction
+    Wipe(WipeDirection),
+    /// Reveal the new scene through a circle growing from the centre
+    Iris,
+    /// Reveal the new scene in a checkerboard of square tiles of the given size, first on one set of tiles and then the other
+    Checkerboard(usize),
+}
+
+/// A `Transition` blends from one [`View`] snapshot to another over a set duration using a [`TransitionEffect`]. It implements [`ViewElement`], drawing every cell of the transition's current frame. Call [`update()`](Transition::update()) every frame to move it forward
+#[derive(Debug, Clone)]
+pub struct Transition {
+    /// The scene being transitioned from
+    pub from: View,
+    /// The scene being transitioned to. Its size decides the size of the `Transition`
+    pub to: View,
+    /// The effect used to move between the scenes
+    pub effect: TransitionEffect,
+    /// How long the whole transition takes
+    pub duration: Duration,
+    /// How long the transition has been running for
+    pub elapsed: Duration,
+}
+
+impl Transition {
+    /// Create a new `Transition` between two `View` snapshots
+    #[must_use]
+    pub const fn new(from: View, to: View, effect: TransitionEffect, duration: Duration) -> Self {
+        Self {
+            from,
+            to,
+            effect,
+            duration,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Move the transition forward by `delta`, usually the time since the last frame
+    pub fn update(&mut self, delta: Duration) {
+        self.elapsed = (self.elapsed + delta).min(self.duration);
+    }
+
+    /// Return how far through the transition is, from 0.0 at the start to 1.0 when finished
+    #[must_use]
+    pub fn progress(&self) -> f64 {
+        if self.duration.is_zero() {
+            1.0
+        } else {
+            (self.elapsed.as_secs_f64() / self.duration.as_secs_f64()).min(1.0)
+        }
+    }
+
+    /// Returns true once the transition has run for its full duration and only the new scene is shown
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Return true if the cell at the position should show the new scene at the given progress
+    fn shows_new_scene(&self, pos: Vec2D, progress: f64) -> bool {
+        let size = self.to.size();
+        let (width, height) = (size.x.max(1) as f64, size.y.max(1) as f64);
+
+        match self.effect {
+            TransitionEffect::Fade(_) => progress >= 0.5,
+            TransitionEffect::Dissolve(seed) => {
+                random_unit(seed, pos.x as i64, pos.y as i64) < progress
+            }
+            TransitionEffect::Wipe(direction) => match direction {
+                WipeDirection::Right => (pos.x as f64) < progress * width,
+                WipeDirection::Left => (width - 1.0 - pos.x as f64) < progress * width,
+                WipeDirection::Down => (pos.y as f64) < progress * height,
+                WipeDirection::Up => (height - 1.0 - pos.y as f64) < progress * height,
+            },
+            TransitionEffect::Iris => {
+                // Cells are taller than they are wide, so rows are scaled by the cell aspect ratio to keep the iris round
+                let aspect_ratio = cell_aspect_ratio();
+                let centre = (width / 2.0, height / 2.0);
+                let (dx, dy) = (
+                    pos.x as f64 + 0.5 - centre.0,
+                    (pos.y as f64 + 0.5 - centre.1) * aspect_ratio,
+                );
+                let max_radius = centre.0.hypot(centre.1 * aspect_ratio);
+                dx.hypot(dy) < progress * max_radius
+            }
+            TransitionEffect::Checkerboard(tile_size) => {
+                let tile_size = tile_size.max(1) as isize;
+                let (tile_x, tile_y) = (pos.x.div_euclid(tile_size), pos.y.div_euclid(tile_size));
+                let column = pos.x.rem_euclid(tile_size) as f64;
+                let phase_progress = if (tile_x + tile_y) % 2 == 0 {
+                    progress * 2.0
+                } else {
+                    progress.mul_add(2.0, -1.0)
+                };
+                column < phase_progress * tile_size as f64
+            }
+        }
+    }
+
+    /// Return the `ColChar` of the fade at a cell, blending the scene's colour towards the fade colour by `amount`
+    fn fade_cell(cell: ColChar, colour: Colour, amount: f64) -> ColChar {
+        if amount >= 1.0 {
+            return ColChar::SOLID.with_colour(colour);
+        }
+        let cell_colour = match cell.modifier {
+            Modifier::Colour(cell_colour) => cell_colour,
+            _ => Colour::WHITE,
+        };
+
+        cell.with_colour(cell_colour.lerp(colour, amount))
+    }
+}
+
+impl ViewElement for Transition {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let progress = self.progress();
+        let size = self.to.size();
+        let mut pixels = Vec::with_capacity((size.x * size.y).max(0) as usize);
+
+        for y in 0..size.y {
+            for x in 0..size.x {
+                let pos = Vec2D::new(x, y);
+                let new_scene = self.shows_new_scene(pos, progress);
+                let cell = if new_scene {
+                    self.to.get(pos)
+                } else {
+                    self.from.get(pos)
+                }
+                .unwrap_or(self.to.background_char);
+
+                let fill_char = match self.effect {
+                    TransitionEffect::Fade(colour) if new_scene => {
+                        Self::fade_cell(cell, colour, (1.0 - progress) * 2.0)
+                    }
+                    TransitionEffect::Fade(colour) => Self::fade_cell(cell, colour, progress * 2.0),
+                    _ => cell,
+                };
+                pixels.push(Pixel::new(pos, fill_char));
+            }
+        }
+
+        pixels
+    }
+}
+
+impl CanAnimate for Transition {
+    fn is_animating(&self) -> bool {
+        !self.is_finished()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_new_cells(transition: &Transition) -> usize {
+        transition
+            .active_pixels()
+            .iter()
+            .filter(|p| p.fill_char == ColChar::SOLID)
+            .count()
+    }
+
+    #[test]
+    fn effects_start_old_and_end_new() {
+        let effects = [
+            TransitionEffect::Dissolve(5),
+            TransitionEffect::Wipe(WipeDirection::Up),
+            TransitionEffect::Iris,
+            TransitionEffect::Checkerboard(3),
+        ];
+
+        for effect in effects {
+            let mut transition = Transition::new(
+                View::new(12, 6, ColChar::BACKGROUND),
+                View::new(12, 6, ColChar::SOLID),
+                effect,
+                Duration::from_secs(1),
+            );
+            assert_eq!(count_new_cells(&transition), 0, "{effect:?}");
+
+            transition.update(Duration::from_millis(500));
+            let halfway = count_new_cells(&transition);
+            assert!(halfway > 0 && halfway < 72, "{effect:?}");
+
+            transition.update(Duration::from_secs(1));
+            assert!(transition.is_finished());
+            assert_eq!(count_new_cells(&transition), 72, "{effect:?}");
+        }
+    }
+
+    #[test]
+    fn fade_passes_through_colour() {
+        let mut transition = Transition::new(
+            View::new(2, 2, ColChar::BACKGROUND),
+            View::new(2, 2, ColChar::EMPTY),
+            TransitionEffect::Fade(Colour::BLACK),
+            Duration::from_secs(2),
+        );
+        transition.update(Duration::from_secs(1));
+
+        let pixels = transition.active_pixels();
+        assert!(pixels
+            .iter()
+            .all(|p| p.fill_char == ColChar::SOLID.with_colour(Colour::BLACK)));
+    }
+}
@@ -0,0 +1,495 @@
+//! An immediate-mode UI layer drawn over the [`View`](super::View). Rather than building widgets once and keeping them around, widgets are declared every frame with calls such as [`ui.button("Play")`](Ui::button()), which draw the widget and return whether it was clicked in the same call. This makes debug tools and quick menus very fast to write
+//! ```
+//! use gemini_engine::elements::{ui::{Ui, UiInput}, view::{ColChar, Wrapping}, Vec2D, View};
+//!
+//! let mut view = View::new(40, 12, ColChar::BACKGROUND);
+//! let mut ui = Ui::new(Vec2D::new(1, 1));
+//! let mut volume = 0.5;
+//! let mut muted = false;
+//!
+//! // Every frame, with the current state of the mouse
+//! ui.begin_frame(UiInput::default());
+//! if ui.button("Play") {
+//!     // Start the game
+//! }
+//! ui.slider("Volume", &mut volume, 0.0..=1.0);
+//! ui.window("Debug", |ui| {
+//!     ui.label("Everything is fine");
+//!     ui.checkbox("Mute", &mut muted);
+//! });
+//!
+//! view.clear();
+//! view.blit(&ui, Wrapping::Ignore);
+//! ```
+//!
+//! Alongside the [`Ui`] this module holds [`Toasts`], for short-lived notifications stacked in a corner of the screen, [`Tooltips`], for hover text on widgets and elements, [`DragDrop`], for dragging items between areas of the screen, an [`InventoryGrid`] built on it, a [`Clipboard`] for copying and pasting text, [`TextSelection`] for selecting text with the mouse, and HUD components such as the [`HealthBar`] that stay bound to your game's values
+
+mod clipboard;
+pub use clipboard::Clipboard;
+
+mod drag_drop;
+pub use drag_drop::{DragDrop, DragEvent};
+
+mod hud;
+pub use hud::{HealthBar, StatusEffect, StatusEffects};
+
+mod inventory_grid;
+pub use inventory_grid::{InventoryGrid, InventoryItem};
+
+mod text_selection;
+pub use text_selection::TextSelection;
+
+mod toasts;
+pub use toasts::{Severity, ToastCorner, Toasts};
+
+mod tooltips;
+pub use tooltips::Tooltips;
+
+use super::{
+    view::{ColChar, Modifier, ViewElement},
+    Pixel, Vec2D,
+};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    ops::RangeInclusive,
+};
+
+/// The state of the mouse for a single frame of a [`Ui`], taken from whichever input handling your program uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct UiInput {
+    /// The cell the mouse is over, or `None` if the mouse isn't over the `View`
+    pub mouse: Option<Vec2D>,
+    /// Whether the mouse button is held down
+    pub mouse_down: bool,
+}
+
+impl UiInput {
+    /// Create a new `UiInput` with the mouse at a position
+    #[must_use]
+    pub const fn new(mouse: Vec2D, mouse_down: bool) -> Self {
+        Self {
+            mouse: Some(mouse),
+            mouse_down,
+        }
+    }
+}
+
+/// The colours a [`Ui`] draws its widgets with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiStyle {
+    /// Labels and widgets that aren't being interacted with
+    pub text: Modifier,
+    /// Widgets under the mouse
+    pub hovered: Modifier,
+    /// Widgets being held down
+    pub active: Modifier,
+    /// Window frames and separators
+    pub frame: Modifier,
+    /// The width of a slider's track in cells
+    pub slider_width: usize,
+}
+
+impl Default for UiStyle {
+    fn default() -> Self {
+        Self {
+            text: Modifier::None,
+            hovered: Modifier::CYAN,
+            active: Modifier::YELLOW,
+            frame: Modifier::Coded(90),
+            slider_width: 12,
+        }
+    }
+}
+
+/// An area that widgets are laid out in, one per row from top to bottom
+#[derive(Debug, Clone, Copy)]
+struct Layout {
+    /// Where the next widget goes
+    cursor: Vec2D,
+    /// The left edge of the area
+    left: isize,
+    /// The rightmost column used by any widget so far
+    right: isize,
+}
+
+impl Layout {
+    const fn new(pos: Vec2D) -> Self {
+        Self {
+            cursor: pos,
+            left: pos.x,
+            right: pos.x,
+        }
+    }
+}
+
+/// An immediate-mode UI. Call [`begin_frame()`](Ui::begin_frame()) at the start of every frame, declare the widgets, then blit the `Ui` to the [`View`](super::View) to draw them. Widgets are laid out one per row from the `Ui`'s position, and are told apart between frames by their labels, so widgets in the same window should have different labels
+#[derive(Debug, Clone)]
+pub struct Ui {
+    /// The position of the first widget
+    pub pos: Vec2D,
+    /// The colours widgets are drawn with
+    pub style: UiStyle,
+    input: UiInput,
+    previous_mouse_down: bool,
+    /// The widget being held down, if any
+    active: Option<u64>,
+    /// The IDs of the windows the widgets being declared are in, used to tell apart widgets with the same label
+    id_stack: Vec<u64>,
+    layout: Layout,
+    /// The position and size of the most recently declared widget
+    last_widget: Option<(Vec2D, Vec2D)>,
+    window_positions: HashMap<u64, Vec2D>,
+    /// Where the mouse grabbed the window being dragged, relative to the window
+    drag_offset: Vec2D,
+    pixels: Vec<Pixel>,
+}
+
+impl Ui {
+    /// Create a new `Ui` with its first widget at the position
+    #[must_use]
+    pub fn new(pos: Vec2D) -> Self {
+        Self {
+            pos,
+            style: UiStyle::default(),
+            input: UiInput::default(),
+            previous_mouse_down: false,
+            active: None,
+            id_stack: vec![],
+            layout: Layout::new(pos),
+            last_widget: None,
+            window_positions: HashMap::new(),
+            drag_offset: Vec2D::ZERO,
+            pixels: vec![],
+        }
+    }
+
+    /// Return the `Ui` with the modified style
+    #[must_use]
+    pub const fn with_style(mut self, style: UiStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Start a new frame with the current state of the mouse, clearing the widgets from the previous frame
+    pub fn begin_frame(&mut self, input: UiInput) {
+        self.previous_mouse_down = self.input.mouse_down;
+        self.input = input;
+        if !input.mouse_down {
+            self.active = None;
+        }
+
+        self.id_stack.clear();
+        self.layout = Layout::new(self.pos);
+        self.last_widget = None;
+        self.pixels.clear();
+    }
+
+    /// Return true if the mouse is over any widget the `Ui` drew this frame, in which case the click shouldn't be used for anything behind the `Ui`
+    #[must_use]
+    pub fn wants_mouse(&self) -> bool {
+        self.active.is_some()
+            || self
+                .input
+                .mouse
+                .is_some_and(|mouse| self.pixels.iter().any(|pixel| pixel.pos == mouse))
+    }
+
+    /// Return an ID for a widget with the label, unique to the window it is in
+    fn id(&self, label: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.id_stack.last().hash(&mut hasher);
+        label.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Reserve a row of the given width for the next widget, returning its position
+    fn allocate(&mut self, width: usize) -> Vec2D {
+        let pos = self.layout.cursor;
+        self.layout.cursor.y += 1;
+        self.layout.right = self.layout.right.max(pos.x + width as isize);
+        self.last_widget = Some((pos, Vec2D::new(width as isize, 1)));
+
+        pos
+    }
+
+    /// Return the position and size of the most recently declared widget, for example to give it a tooltip with [`Tooltips::register()`]
+    #[must_use]
+    pub const fn last_widget(&self) -> Option<(Vec2D, Vec2D)> {
+        self.last_widget
+    }
+
+    /// Return true if the mouse is over the row of cells
+    fn is_hovered(&self, pos: Vec2D, width: usize) -> bool {
+        self.input.mouse.is_some_and(|mouse| {
+            mouse.y == pos.y && mouse.x >= pos.x && mouse.x < pos.x + width as isize
+        })
+    }
+
+    /// Return true if the mouse button was pressed down this frame
+    const fn just_pressed(&self) -> bool {
+        self.input.mouse_down && !self.previous_mouse_down
+    }
+
+    /// Handle the mouse for a widget covering a row of cells, returning whether it is hovered and whether it was pressed this frame
+    fn interact(&mut self, id: u64, pos: Vec2D, width: usize) -> (bool, bool) {
+        let hovered = self.is_hovered(pos, width);
+        let pressed = hovered && self.just_pressed() && self.active.is_none();
+        if pressed {
+            self.active = Some(id);
+        }
+
+        (hovered, pressed)
+    }
+
+    /// Return the modifier for a widget given how it is being interacted with
+    fn modifier_for(&self, id: u64, hovered: bool) -> Modifier {
+        if self.active == Some(id) {
+            self.style.active
+        } else if hovered {
+            self.style.hovered
+        } else {
+            self.style.text
+        }
+    }
+
+    /// Draw a row of text, including its spaces
+    fn draw_text(&mut self, pos: Vec2D, text: &str, modifier: Modifier) {
+        for (x, text_char) in (0..).zip(text.chars()) {
+            self.pixels.push(Pixel::new(
+                pos + Vec2D::new(x, 0),
+                ColChar::new(text_char, modifier),
+            ));
+        }
+    }
+
+    /// Draw a line of text
+    pub fn label(&mut self, text: &str) {
+        let pos = self.allocate(text.chars().count());
+        self.draw_text(pos, text, self.style.text);
+    }
+
+    /// Draw a horizontal line as wide as the widgets above it
+    pub fn separator(&mut self) {
+        let width = (self.layout.right - self.layout.left).max(1) as usize;
+        let pos = self.allocate(width);
+        self.draw_text(pos, &"─".repeat(width), self.style.frame);
+    }
+
+    /// Draw a button, returning true if it was clicked this frame
+    pub fn button(&mut self, text: &str) -> bool {
+        let content = format!("[ {text} ]");
+        let width = content.chars().count();
+        let id = self.id(text);
+        let pos = self.allocate(width);
+
+        let (hovered, pressed) = self.interact(id, pos, width);
+        self.draw_text(pos, &content, self.modifier_for(id, hovered));
+
+        pressed
+    }
+
+    /// Draw a checkbox, toggling the value when clicked. Returns true if the value changed this frame
+    pub fn checkbox(&mut self, text: &str, value: &mut bool) -> bool {
+        let id = self.id(text);
+        let width = text.chars().count() + 4;
+        let pos = self.allocate(width);
+
+        let (hovered, pressed) = self.interact(id, pos, width);
+        if pressed {
+            *value = !*value;
+        }
+        let mark = if *value { 'x' } else { ' ' };
+        self.draw_text(
+            pos,
+            &format!("[{mark}] {text}"),
+            self.modifier_for(id, hovered),
+        );
+
+        pressed
+    }
+
+    /// Draw a slider, setting the value within the range while the track is held down. Returns true if the value changed this frame
+    pub fn slider(&mut self, text: &str, value: &mut f64, range: RangeInclusive<f64>) -> bool {
+        let id = self.id(text);
+        let (min, max) = (*range.start(), *range.end());
+        let track_width = self.style.slider_width.max(2);
+        let label = format!("{text} ");
+        let label_width = label.chars().count();
+        let pos = self.allocate(label_width + track_width + 8);
+        let track_pos = pos + Vec2D::new(label_width as isize, 0);
+
+        let (hovered, _) = self.interact(id, track_pos, track_width);
+        let old_value = *value;
+        if let (true, Some(mouse)) = (self.active == Some(id), self.input.mouse) {
+            let amount = (mouse.x - track_pos.x) as f64 / (track_width - 1) as f64;
+            *value = (max - min).mul_add(amount.clamp(0.0, 1.0), min);
+        }
+
+        let amount = if max > min {
+            ((*value - min) / (max - min)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let handle = (amount * (track_width - 1) as f64).round() as usize;
+        let track: String = (0..track_width)
+            .map(|x| match x.cmp(&handle) {
+                std::cmp::Ordering::Less => '━',
+                std::cmp::Ordering::Equal => '●',
+                std::cmp::Ordering::Greater => '─',
+            })
+            .collect();
+
+        self.draw_text(pos, &label, self.style.text);
+        self.draw_text(track_pos, &track, self.modifier_for(id, hovered));
+        self.draw_text(
+            track_pos + Vec2D::new(track_width as isize + 1, 0),
+            &format!("{value:.2}"),
+            self.style.text,
+        );
+
+        (*value - old_value).abs() > f64::EPSILON
+    }
+
+    /// Draw a framed window with a title bar, with the widgets declared in `contents` inside it. The window appears at the current layout position the first time it is declared, and can then be moved by dragging its title bar. Windows float above the layout, so they don't move the widgets declared after them
+    pub fn window(&mut self, title: &str, contents: impl FnOnce(&mut Self)) {
+        let id = self.id(title);
+        let pos = *self
+            .window_positions
+            .entry(id)
+            .or_insert(self.layout.cursor);
+        let title_width = title.chars().count() + 4;
+
+        // Dragging the title bar. The window's size is taken from the last frame's contents, so use the title to grab it
+        let (_, pressed) = self.interact(id, pos, title_width);
+        if pressed {
+            if let Some(mouse) = self.input.mouse {
+                self.drag_offset = mouse - pos;
+            }
+        }
+        let pos = match (self.active == Some(id), self.input.mouse) {
+            (true, Some(mouse)) => {
+                let new_pos = mouse - self.drag_offset;
+                self.window_positions.insert(id, new_pos);
+                new_pos
+            }
+            _ => pos,
+        };
+
+        let outer_layout = self.layout;
+        let frame_start = self.pixels.len();
+        self.id_stack.push(id);
+        self.layout = Layout::new(pos + Vec2D::new(2, 1));
+        contents(self);
+        self.id_stack.pop();
+
+        let inner = self.layout;
+        self.layout = outer_layout;
+
+        // Draw the frame and background behind the contents
+        let width = ((inner.right - inner.left) as usize).max(title_width) + 4;
+        let height = (inner.cursor.y - pos.y) as usize + 1;
+        let mut frame = vec![];
+        for y in 0..height {
+            let row: String = match y {
+                0 => format!("┌─ {title} {}┐", "─".repeat(width - title_width - 1)),
+                y if y == height - 1 => format!("└{}┘", "─".repeat(width - 2)),
+                _ => format!("│{}│", " ".repeat(width - 2)),
+            };
+            for (x, text_char) in (0..).zip(row.chars()) {
+                frame.push(Pixel::new(
+                    pos + Vec2D::new(x, y as isize),
+                    ColChar::new(text_char, self.style.frame),
+                ));
+            }
+        }
+        self.pixels.splice(frame_start..frame_start, frame);
+        self.last_widget = Some((pos, Vec2D::new(width as isize, height as isize)));
+    }
+}
+
+impl ViewElement for Ui {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        self.pixels.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn button_clicks_once_per_press() {
+        let mut ui = Ui::new(Vec2D::ZERO);
+        let mut clicks = 0;
+
+        for mouse_down in [false, true, true, false] {
+            ui.begin_frame(UiInput::new(Vec2D::new(2, 1), mouse_down));
+            ui.label("Menu");
+            if ui.button("Play") {
+                clicks += 1;
+            }
+        }
+
+        assert_eq!(clicks, 1);
+        assert!(ui.wants_mouse());
+    }
+
+    #[test]
+    fn slider_follows_held_mouse() {
+        let mut ui = Ui::new(Vec2D::ZERO);
+        ui.style.slider_width = 11;
+        let mut value = 0.0;
+
+        // "Vol " is four cells wide, so the track starts at x = 4
+        for (x, mouse_down) in [(4, false), (4, true), (9, true), (30, true)] {
+            ui.begin_frame(UiInput::new(Vec2D::new(x, 0), mouse_down));
+            ui.slider("Vol", &mut value, 0.0..=10.0);
+            if x == 9 {
+                assert!((value - 5.0).abs() < f64::EPSILON);
+            }
+        }
+
+        assert!((value - 10.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn windows_are_framed_and_draggable() {
+        let mut ui = Ui::new(Vec2D::ZERO);
+        let frame = |ui: &mut Ui, mouse: Vec2D, mouse_down: bool| {
+            ui.begin_frame(UiInput::new(mouse, mouse_down));
+            ui.window("Debug", |ui| ui.label("Hello"));
+        };
+
+        frame(&mut ui, Vec2D::new(3, 0), false);
+        let pixels = ui.active_pixels();
+        assert_eq!(pixels[0].fill_char.text_char, '┌');
+        assert!(pixels
+            .iter()
+            .any(|p| p.pos == Vec2D::new(2, 1) && p.fill_char.text_char == 'H'));
+
+        frame(&mut ui, Vec2D::new(3, 0), true);
+        frame(&mut ui, Vec2D::new(8, 4), true);
+        let pixels = ui.active_pixels();
+        assert_eq!(pixels[0].pos, Vec2D::new(5, 4));
+    }
+
+    #[test]
+    fn window_corners_line_up() {
+        let mut ui = Ui::new(Vec2D::ZERO);
+        ui.begin_frame(UiInput::new(Vec2D::new(-1, -1), false));
+        ui.window("Stats", |ui| ui.label("A much longer label"));
+
+        let column = |c: char| {
+            ui.active_pixels()
+                .iter()
+                .filter(|p| p.fill_char.text_char == c)
+                .map(|p| p.pos.x)
+                .max()
+        };
+        let right = column('┘');
+        assert!(right.is_some());
+        assert_eq!(column('┐'), right);
+        assert_eq!(column('│'), right);
+    }
+}
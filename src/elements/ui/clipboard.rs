@@ -0,0 +1,158 @@
+use crate::utils::{decode_base64, encode_base64};
+use std::{
+    fmt,
+    io::{self, Write},
+};
+
+/// `Clipboard` copies and pastes text for text fields, such as to let players paste in long seeds or names. Copying uses the system clipboard if the `clipboard` feature is enabled and a system clipboard is available, and otherwise sends an OSC 52 escape sequence, which most modern terminals turn into a system copy even over SSH. Pasting reads the system clipboard where possible, and otherwise returns the last text copied or received from the terminal with [`receive_osc52()`](Clipboard::receive_osc52())
+/// ```
+/// use gemini_engine::elements::ui::Clipboard;
+///
+/// assert_eq!(Clipboard::osc52("seed"), "\x1b]52;c;c2VlZA==\x07");
+///
+/// // Terminals reply to `Clipboard::OSC52_QUERY` with the clipboard's contents
+/// let mut clipboard = Clipboard::local();
+/// assert!(clipboard.receive_osc52("\x1b]52;c;bXkgd29ybGQ=\x07"));
+/// assert_eq!(clipboard.paste(), "my world");
+/// ```
+pub struct Clipboard {
+    contents: String,
+    #[cfg(feature = "clipboard")]
+    system: Option<arboard::Clipboard>,
+}
+
+impl Clipboard {
+    /// The escape sequence asking the terminal for the contents of its clipboard. Terminals that allow it reply with an OSC 52 sequence that can be passed to [`receive_osc52()`](Clipboard::receive_osc52()) once it arrives on stdin
+    pub const OSC52_QUERY: &'static str = "\x1b]52;c;?\x07";
+
+    /// Create a new `Clipboard`, connecting to the system clipboard if the `clipboard` feature is enabled
+    #[must_use]
+    #[cfg_attr(not(feature = "clipboard"), allow(clippy::missing_const_for_fn))]
+    pub fn new() -> Self {
+        Self {
+            contents: String::new(),
+            #[cfg(feature = "clipboard")]
+            system: arboard::Clipboard::new().ok(),
+        }
+    }
+
+    /// Create a new `Clipboard` that never uses the system clipboard, so copying always goes through OSC 52
+    #[must_use]
+    pub const fn local() -> Self {
+        Self {
+            contents: String::new(),
+            #[cfg(feature = "clipboard")]
+            system: None,
+        }
+    }
+
+    /// Returns true if the `Clipboard` is connected to the system clipboard
+    #[must_use]
+    pub const fn is_system(&self) -> bool {
+        #[cfg(feature = "clipboard")]
+        return self.system.is_some();
+        #[cfg(not(feature = "clipboard"))]
+        false
+    }
+
+    /// Copy the text, to the system clipboard if connected or by writing an OSC 52 sequence to stdout otherwise
+    ///
+    /// # Errors
+    /// Returns an error if the system clipboard refuses the text or writing to stdout fails
+    pub fn copy(&mut self, text: &str) -> io::Result<()> {
+        text.clone_into(&mut self.contents);
+
+        #[cfg(feature = "clipboard")]
+        if let Some(system) = &mut self.system {
+            return system.set_text(text).map_err(io::Error::other);
+        }
+
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(Self::osc52(text).as_bytes())?;
+        stdout.flush()
+    }
+
+    /// Return the text to paste, from the system clipboard if connected or the last text copied or received otherwise
+    pub fn paste(&mut self) -> String {
+        #[cfg(feature = "clipboard")]
+        if let Some(text) = self
+            .system
+            .as_mut()
+            .and_then(|system| system.get_text().ok())
+        {
+            return text;
+        }
+
+        self.contents.clone()
+    }
+
+    /// Read the terminal's reply to [`OSC52_QUERY`](Clipboard::OSC52_QUERY), ending in either a BEL or ST (`\x1b\\`), and keep its text for the next [`paste()`](Clipboard::paste()). Returns false if the reply isn't a valid OSC 52 sequence
+    pub fn receive_osc52(&mut self, response: &str) -> bool {
+        let Some(payload) = response
+            .strip_prefix("\x1b]52;")
+            .and_then(|s| s.strip_suffix('\x07').or_else(|| s.strip_suffix("\x1b\\")))
+            .and_then(|s| s.split_once(';'))
+            .map(|(_, payload)| payload)
+        else {
+            return false;
+        };
+
+        match decode_base64(payload).and_then(|bytes| String::from_utf8(bytes).ok()) {
+            Some(text) => {
+                self.contents = text;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Return the OSC 52 escape sequence that asks the terminal to copy the text to the system clipboard
+    #[must_use]
+    pub fn osc52(text: &str) -> String {
+        format!("\x1b]52;c;{}\x07", encode_base64(text.as_bytes()))
+    }
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Clipboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Clipboard")
+            .field("contents", &self.contents)
+            .field("is_system", &self.is_system())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_round_trips() {
+        for text in ["", "a", "ab", "abc", "seed: 12345 ✔"] {
+            let encoded = encode_base64(text.as_bytes());
+            assert_eq!(encoded.len() % 4, 0);
+            assert_eq!(decode_base64(&encoded), Some(text.as_bytes().to_vec()));
+        }
+        assert_eq!(encode_base64(b"ab"), "YWI=");
+        assert_eq!(decode_base64("YWI"), Some(b"ab".to_vec()));
+        assert_eq!(decode_base64("Y!I="), None);
+    }
+
+    #[test]
+    fn receives_osc52_replies() {
+        let mut clipboard = Clipboard::local();
+        assert!(!clipboard.is_system());
+        assert!(clipboard.receive_osc52("\x1b]52;c;aGk=\x1b\\"));
+        assert_eq!(clipboard.paste(), "hi");
+
+        assert!(!clipboard.receive_osc52("\x1b]52;c;aGk="));
+        assert!(!clipboard.receive_osc52("\x1b]52;c;/w==\x07"));
+        assert_eq!(clipboard.paste(), "hi");
+    }
+}
@@ -0,0 +1,310 @@
+use super::UiInput;
+use crate::elements::{
+    view::{Modifier, ViewElement},
+    Pixel, Vec2D,
+};
+use std::{collections::VecDeque, fmt};
+
+/// Something that happened to a drag in a [`DragDrop`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DragEvent<T> {
+    /// An item started being dragged from a source
+    Started {
+        /// The ID of the source the item was dragged from
+        source: usize,
+    },
+    /// An item was dropped on a target that accepts it
+    Dropped {
+        /// The item being dragged
+        payload: T,
+        /// The ID of the source the item was dragged from
+        source: usize,
+        /// The ID of the target the item was dropped on
+        target: usize,
+        /// Where the mouse was when the item was dropped
+        pos: Vec2D,
+    },
+    /// An item was let go somewhere that doesn't accept it, so the drag was cancelled
+    Cancelled {
+        /// The item being dragged
+        payload: T,
+        /// The ID of the source the item was dragged from
+        source: usize,
+    },
+}
+
+/// An area an item can be dropped on
+struct DropTarget<T> {
+    id: usize,
+    pos: Vec2D,
+    size: Vec2D,
+    accepts: Box<dyn Fn(&T) -> bool>,
+}
+
+/// An item being dragged
+#[derive(Debug, Clone)]
+struct ActiveDrag<T> {
+    payload: T,
+    source: usize,
+    /// The pixels drawn under the mouse, relative to where the item was grabbed
+    ghost: Vec<Pixel>,
+}
+
+/// Returns true if the point is inside the area
+const fn area_contains(pos: Vec2D, size: Vec2D, point: Vec2D) -> bool {
+    point.x >= pos.x && point.y >= pos.y && point.x < pos.x + size.x && point.y < pos.y + size.y
+}
+
+/// `DragDrop` handles dragging items of type `T` between areas of the screen, such as moving items between inventory slots. Every frame, call [`begin_frame()`](DragDrop::begin_frame()) with the mouse state, declare the [`source()`](DragDrop::source())s items can be dragged from and the [`target()`](DragDrop::target())s they can be dropped on, then call [`end_frame()`](DragDrop::end_frame()) and handle the [`DragEvent`]s from [`events()`](DragDrop::events()). While an item is being dragged, blitting the `DragDrop` draws a ghost of it under the mouse
+/// ```
+/// use gemini_engine::elements::{ui::{DragDrop, DragEvent, UiInput}, view::ColChar, Pixel, Vec2D};
+///
+/// let mut drag_drop: DragDrop<&str> = DragDrop::new();
+/// let ghost = Pixel::new(Vec2D::new(0, 0), ColChar::SOLID);
+///
+/// // Grab the sword in slot 0, then let go of it over slot 1
+/// for (mouse, mouse_down) in [(Vec2D::new(0, 0), true), (Vec2D::new(5, 0), false)] {
+///     drag_drop.begin_frame(UiInput::new(mouse, mouse_down));
+///     drag_drop.source(0, Vec2D::new(0, 0), Vec2D::new(3, 1), || "sword", &ghost);
+///     drag_drop.target(1, Vec2D::new(5, 0), Vec2D::new(3, 1), |_| true);
+///     drag_drop.end_frame();
+/// }
+///
+/// let events: Vec<_> = drag_drop.events().collect();
+/// assert_eq!(events[1], DragEvent::Dropped { payload: "sword", source: 0, target: 1, pos: Vec2D::new(5, 0) });
+/// ```
+pub struct DragDrop<T> {
+    /// The `Modifier` the ghost is drawn with while over a target that doesn't accept it, or over no target at all. Set to `None` to keep the ghost's own colours
+    pub rejected_modifier: Option<Modifier>,
+    input: UiInput,
+    previous_mouse_down: bool,
+    drag: Option<ActiveDrag<T>>,
+    targets: Vec<DropTarget<T>>,
+    events: VecDeque<DragEvent<T>>,
+}
+
+impl<T> DragDrop<T> {
+    /// Create a new `DragDrop` with nothing being dragged
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            rejected_modifier: Some(Modifier::Coded(90)),
+            input: UiInput {
+                mouse: None,
+                mouse_down: false,
+            },
+            previous_mouse_down: false,
+            drag: None,
+            targets: vec![],
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Start a new frame with the current state of the mouse, forgetting last frame's targets
+    pub fn begin_frame(&mut self, input: UiInput) {
+        self.previous_mouse_down = self.input.mouse_down;
+        self.input = input;
+        self.targets.clear();
+    }
+
+    /// Declare an area items can be dragged from. If the mouse is pressed down over the area this frame, `payload` is called to create the item and a drag begins, drawing the `ghost` element under the mouse. The ghost is drawn relative to where the area was grabbed, so passing the element drawn in the area makes it look as though it's being picked up. Returns true if a drag started
+    pub fn source(
+        &mut self,
+        id: usize,
+        pos: Vec2D,
+        size: Vec2D,
+        payload: impl FnOnce() -> T,
+        ghost: &impl ViewElement,
+    ) -> bool {
+        let Some(mouse) = self.input.mouse else {
+            return false;
+        };
+        let just_pressed = self.input.mouse_down && !self.previous_mouse_down;
+        if self.drag.is_some() || !just_pressed || !area_contains(pos, size, mouse) {
+            return false;
+        }
+
+        let ghost = ghost
+            .active_pixels()
+            .into_iter()
+            .map(|pixel| Pixel::new(pixel.pos - mouse, pixel.fill_char))
+            .collect();
+        self.drag = Some(ActiveDrag {
+            payload: payload(),
+            source: id,
+            ghost,
+        });
+        self.events.push_back(DragEvent::Started { source: id });
+
+        true
+    }
+
+    /// Declare an area items can be dropped on, accepting only the items for which `accepts` returns true. Targets declared later are treated as being on top of earlier ones
+    pub fn target(
+        &mut self,
+        id: usize,
+        pos: Vec2D,
+        size: Vec2D,
+        accepts: impl Fn(&T) -> bool + 'static,
+    ) {
+        self.targets.push(DropTarget {
+            id,
+            pos,
+            size,
+            accepts: Box::new(accepts),
+        });
+    }
+
+    /// Return the item being dragged, if any
+    #[must_use]
+    pub fn dragging(&self) -> Option<&T> {
+        self.drag.as_ref().map(|drag| &drag.payload)
+    }
+
+    /// Return the ID of the topmost target under the mouse, and whether it accepts the item being dragged. Returns `None` if nothing is being dragged or the mouse isn't over a target. Useful for highlighting targets while dragging
+    #[must_use]
+    pub fn hovered_target(&self) -> Option<(usize, bool)> {
+        let drag = self.drag.as_ref()?;
+        let mouse = self.input.mouse?;
+
+        self.targets
+            .iter()
+            .rev()
+            .find(|target| area_contains(target.pos, target.size, mouse))
+            .map(|target| (target.id, (target.accepts)(&drag.payload)))
+    }
+
+    /// Finish the frame, dropping the item being dragged if the mouse has been released. Call this after declaring every source and target
+    pub fn end_frame(&mut self) {
+        if self.input.mouse_down {
+            return;
+        }
+        let hovered = self.hovered_target();
+        let Some(drag) = self.drag.take() else {
+            return;
+        };
+
+        self.events.push_back(match (hovered, self.input.mouse) {
+            (Some((target, true)), Some(pos)) => DragEvent::Dropped {
+                payload: drag.payload,
+                source: drag.source,
+                target,
+                pos,
+            },
+            _ => DragEvent::Cancelled {
+                payload: drag.payload,
+                source: drag.source,
+            },
+        });
+    }
+
+    /// Drain and return the events that have happened since this was last called
+    pub fn events(&mut self) -> impl Iterator<Item = DragEvent<T>> + '_ {
+        self.events.drain(..)
+    }
+}
+
+impl<T> Default for DragDrop<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for DragDrop<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DragDrop")
+            .field("rejected_modifier", &self.rejected_modifier)
+            .field("input", &self.input)
+            .field("drag", &self.drag)
+            .field("targets", &self.targets.len())
+            .field("events", &self.events)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> ViewElement for DragDrop<T> {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let (Some(drag), Some(mouse)) = (&self.drag, self.input.mouse) else {
+            return vec![];
+        };
+        let rejected = !matches!(self.hovered_target(), Some((_, true)));
+
+        drag.ghost
+            .iter()
+            .map(|pixel| {
+                let fill_char = match (rejected, self.rejected_modifier) {
+                    (true, Some(modifier)) => pixel.fill_char.with_mod(modifier),
+                    _ => pixel.fill_char,
+                };
+                Pixel::new(mouse + pixel.pos, fill_char)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{view::ColChar, Text};
+
+    /// Run a frame with a source at x = 0 and targets at x = 10 (accepting even numbers) and x = 20 (accepting anything)
+    fn frame(drag_drop: &mut DragDrop<u32>, mouse: Vec2D, mouse_down: bool) {
+        let ghost = Text::new(Vec2D::new(0, 0), "42", Modifier::None);
+        drag_drop.begin_frame(UiInput::new(mouse, mouse_down));
+        drag_drop.source(0, Vec2D::ZERO, Vec2D::new(2, 1), || 41, &ghost);
+        drag_drop.target(1, Vec2D::new(10, 0), Vec2D::new(2, 1), |item| item % 2 == 0);
+        drag_drop.target(2, Vec2D::new(20, 0), Vec2D::new(2, 1), |_| true);
+        drag_drop.end_frame();
+    }
+
+    #[test]
+    fn drops_only_on_accepting_targets() {
+        let mut drag_drop = DragDrop::new();
+
+        frame(&mut drag_drop, Vec2D::new(1, 0), true);
+        assert_eq!(drag_drop.dragging(), Some(&41));
+        frame(&mut drag_drop, Vec2D::new(10, 0), true);
+        assert_eq!(drag_drop.hovered_target(), Some((1, false)));
+        frame(&mut drag_drop, Vec2D::new(10, 0), false);
+
+        frame(&mut drag_drop, Vec2D::new(0, 0), true);
+        frame(&mut drag_drop, Vec2D::new(21, 0), false);
+
+        let events: Vec<_> = drag_drop.events().collect();
+        assert_eq!(
+            events,
+            vec![
+                DragEvent::Started { source: 0 },
+                DragEvent::Cancelled {
+                    payload: 41,
+                    source: 0
+                },
+                DragEvent::Started { source: 0 },
+                DragEvent::Dropped {
+                    payload: 41,
+                    source: 0,
+                    target: 2,
+                    pos: Vec2D::new(21, 0)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ghost_follows_mouse() {
+        let mut drag_drop = DragDrop::new();
+        frame(&mut drag_drop, Vec2D::new(1, 0), true);
+        frame(&mut drag_drop, Vec2D::new(21, 3), true);
+
+        let pixels = drag_drop.active_pixels();
+        assert_eq!(pixels[0].pos, Vec2D::new(20, 3));
+        // Greyed out, as the mouse isn't over a target
+        assert_eq!(pixels[0].fill_char, ColChar::new('4', Modifier::Coded(90)));
+
+        frame(&mut drag_drop, Vec2D::new(21, 0), true);
+        assert_eq!(
+            drag_drop.active_pixels()[1].fill_char.modifier,
+            Modifier::None
+        );
+    }
+}
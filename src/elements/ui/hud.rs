@@ -0,0 +1,327 @@
+use crate::{
+    elements::{
+        charts::ColourThresholds,
+        view::{ColChar, Modifier, ViewElement},
+        Pixel, Vec2D,
+    },
+    gameloop::CanAnimate,
+};
+use std::{fmt, time::Duration};
+
+/// Block characters filling the left of a cell in eighths, from empty to full
+const LEFT_EIGHTHS: [char; 9] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+/// A `HealthBar` is a horizontal bar bound to a value in your game, such as the player's health. Every [`update()`](HealthBar::update()) it reads the value and eases the bar towards it, so there's no need to copy values into the HUD by hand. When the value drops, the lost part of the bar lingers in the [`change_modifier`](HealthBar::change_modifier) as it drains away
+/// ```
+/// use gemini_engine::elements::{ui::HealthBar, Vec2D};
+/// use std::{cell::Cell, rc::Rc, time::Duration};
+///
+/// let health = Rc::new(Cell::new(100.0));
+/// let mut bar = HealthBar::bind(Vec2D::ZERO, 20, 100.0, {
+///     let health = Rc::clone(&health);
+///     move || health.get()
+/// });
+///
+/// health.set(40.0);
+/// bar.update(Duration::from_secs(5));
+/// assert_eq!(bar.displayed(), 40.0);
+/// ```
+pub struct HealthBar {
+    /// The position of the left end of the bar
+    pub pos: Vec2D,
+    /// The width of the bar in cells
+    pub width: usize,
+    /// The value at which the bar is full
+    pub max: f64,
+    /// The colours of the bar, picked by the current value
+    pub colours: ColourThresholds,
+    /// The `Modifier` of the part of the bar that is draining away or filling up
+    pub change_modifier: Modifier,
+    /// How fast the bar moves towards the bound value, as a fraction of the full bar per second
+    pub speed: f64,
+    /// Whether to draw the value and maximum after the bar
+    pub show_value: bool,
+    source: Box<dyn Fn() -> f64>,
+    target: f64,
+    displayed: f64,
+}
+
+impl HealthBar {
+    /// Create a new `HealthBar` bound to the value returned by `source`, which is read on every [`update()`](HealthBar::update()). The bar starts at the current value, drawn in green, turning yellow below half and red below a quarter of `max`
+    #[must_use]
+    pub fn bind(pos: Vec2D, width: usize, max: f64, source: impl Fn() -> f64 + 'static) -> Self {
+        let value = source();
+
+        Self {
+            pos,
+            width,
+            max,
+            colours: ColourThresholds::new(Modifier::RED)
+                .with_threshold(max * 0.25, Modifier::YELLOW)
+                .with_threshold(max * 0.5, Modifier::GREEN),
+            change_modifier: Modifier::Coded(90),
+            speed: 0.5,
+            show_value: false,
+            source: Box::new(source),
+            target: value,
+            displayed: value,
+        }
+    }
+
+    /// Return the `HealthBar` with the modified colours
+    #[must_use]
+    pub fn with_colours(mut self, colours: impl Into<ColourThresholds>) -> Self {
+        self.colours = colours.into();
+        self
+    }
+
+    /// Return the `HealthBar` with the modified `show_value` property
+    #[must_use]
+    pub const fn with_value(mut self, show_value: bool) -> Self {
+        self.show_value = show_value;
+        self
+    }
+
+    /// Return the value the bar is currently showing, which eases towards the bound value
+    #[must_use]
+    pub const fn displayed(&self) -> f64 {
+        self.displayed
+    }
+
+    /// Read the bound value and move the bar towards it by `delta`, usually the time since the last frame
+    pub fn update(&mut self, delta: Duration) {
+        self.target = (self.source)();
+        let step = self.speed * self.max.abs() * delta.as_secs_f64();
+
+        self.displayed = if self.displayed < self.target {
+            (self.displayed + step).min(self.target)
+        } else {
+            (self.displayed - step).max(self.target)
+        };
+    }
+
+    /// Return how many eighths of a cell the value fills
+    fn eighths(&self, value: f64) -> usize {
+        if self.max <= 0.0 {
+            return 0;
+        }
+        ((value / self.max).clamp(0.0, 1.0) * (self.width * 8) as f64).round() as usize
+    }
+}
+
+impl fmt::Debug for HealthBar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HealthBar")
+            .field("pos", &self.pos)
+            .field("width", &self.width)
+            .field("max", &self.max)
+            .field("target", &self.target)
+            .field("displayed", &self.displayed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ViewElement for HealthBar {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let current = self.eighths(self.target.min(self.displayed));
+        let changing = self.eighths(self.target.max(self.displayed));
+        let modifier = self.colours.modifier_for(self.target);
+        let mut pixels = vec![];
+
+        for x in 0..self.width {
+            let cell_start = x * 8;
+            let (level, modifier) = if current > cell_start {
+                // A partly filled cell can't show two colours, so the value's colour takes priority
+                ((current - cell_start).min(8), modifier)
+            } else if changing > cell_start {
+                ((changing - cell_start).min(8), self.change_modifier)
+            } else {
+                (0, modifier)
+            };
+            if level > 0 {
+                pixels.push(Pixel::new(
+                    self.pos + Vec2D::new(x as isize, 0),
+                    ColChar::new(LEFT_EIGHTHS[level], modifier),
+                ));
+            }
+        }
+
+        if self.show_value {
+            let label = format!(" {:.0}/{:.0}", self.target, self.max);
+            for (x, text_char) in (self.width as isize..).zip(label.chars()) {
+                pixels.push(Pixel::new(
+                    self.pos + Vec2D::new(x, 0),
+                    ColChar::new(text_char, Modifier::None),
+                ));
+            }
+        }
+
+        pixels
+    }
+}
+
+impl CanAnimate for HealthBar {
+    fn is_animating(&self) -> bool {
+        (self.displayed - self.target).abs() > f64::EPSILON
+    }
+}
+
+/// A status effect shown by [`StatusEffects`], such as poison or a speed boost
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusEffect {
+    /// The character the effect is shown with
+    pub icon: char,
+    /// The `Modifier` the icon is drawn with
+    pub modifier: Modifier,
+    /// How long is left until the effect wears off, or `None` if it lasts until removed
+    pub remaining: Option<Duration>,
+}
+
+impl StatusEffect {
+    /// Create a new `StatusEffect` with no time limit
+    #[must_use]
+    pub const fn new(icon: char, modifier: Modifier) -> Self {
+        Self {
+            icon,
+            modifier,
+            remaining: None,
+        }
+    }
+
+    /// Return the `StatusEffect` with the modified time remaining
+    #[must_use]
+    pub const fn with_remaining(mut self, remaining: Duration) -> Self {
+        self.remaining = Some(remaining);
+        self
+    }
+}
+
+/// `StatusEffects` shows a row of status effect icons bound to your game's state, read again on every [`update()`](StatusEffects::update()). Icons with little time left blink so that the player notices before they wear off
+/// ```
+/// use gemini_engine::elements::{ui::{StatusEffect, StatusEffects}, view::{Modifier, ViewElement}, Vec2D};
+/// use std::{cell::RefCell, rc::Rc, time::Duration};
+///
+/// let effects = Rc::new(RefCell::new(vec![StatusEffect::new('☠', Modifier::GREEN)]));
+/// let mut hud = StatusEffects::bind(Vec2D::ZERO, {
+///     let effects = Rc::clone(&effects);
+///     move || effects.borrow().clone()
+/// });
+///
+/// effects.borrow_mut().push(StatusEffect::new('»', Modifier::CYAN));
+/// hud.update(Duration::from_millis(16));
+/// assert_eq!(hud.active_pixels().len(), 2);
+/// ```
+pub struct StatusEffects {
+    /// The position of the first icon
+    pub pos: Vec2D,
+    /// The number of cells between icons
+    pub gap: usize,
+    /// Icons blink once their remaining time drops below this
+    pub blink_below: Duration,
+    source: Box<dyn Fn() -> Vec<StatusEffect>>,
+    effects: Vec<StatusEffect>,
+    elapsed: Duration,
+}
+
+impl StatusEffects {
+    /// Create a new `StatusEffects` bound to the effects returned by `source`, which is read on every [`update()`](StatusEffects::update())
+    #[must_use]
+    pub fn bind(pos: Vec2D, source: impl Fn() -> Vec<StatusEffect> + 'static) -> Self {
+        let effects = source();
+
+        Self {
+            pos,
+            gap: 1,
+            blink_below: Duration::from_secs(3),
+            source: Box::new(source),
+            effects,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Read the bound effects again, `delta` being the time since the last frame
+    pub fn update(&mut self, delta: Duration) {
+        self.effects = (self.source)();
+        self.elapsed += delta;
+    }
+}
+
+impl fmt::Debug for StatusEffects {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StatusEffects")
+            .field("pos", &self.pos)
+            .field("effects", &self.effects)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ViewElement for StatusEffects {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        // Blink four times a second
+        let blink_off = (self.elapsed.as_millis() / 125) % 2 == 1;
+
+        (0..)
+            .zip(&self.effects)
+            .filter(|(_, effect)| {
+                !(blink_off && effect.remaining.is_some_and(|r| r < self.blink_below))
+            })
+            .map(|(i, effect)| {
+                Pixel::new(
+                    self.pos + Vec2D::new(i * (self.gap as isize + 1), 0),
+                    ColChar::new(effect.icon, effect.modifier),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::Cell, rc::Rc};
+
+    #[test]
+    fn health_bar_drains_towards_value() {
+        let health = Rc::new(Cell::new(10.0));
+        let mut bar = HealthBar::bind(Vec2D::ZERO, 4, 10.0, {
+            let health = Rc::clone(&health);
+            move || health.get()
+        });
+        assert_eq!(bar.active_pixels().len(), 4);
+
+        health.set(2.5);
+        // The bar drains at half its length per second
+        bar.update(Duration::from_secs(1));
+        assert!((bar.displayed() - 5.0).abs() < 1e-9);
+        assert!(bar.is_animating());
+
+        let chars: Vec<ColChar> = bar.active_pixels().iter().map(|p| p.fill_char).collect();
+        assert_eq!(
+            chars,
+            vec![
+                ColChar::new('█', Modifier::YELLOW),
+                ColChar::new('█', Modifier::Coded(90)),
+            ]
+        );
+
+        bar.update(Duration::from_secs(1));
+        assert!(!bar.is_animating());
+        assert_eq!(bar.active_pixels().len(), 1);
+    }
+
+    #[test]
+    fn expiring_effects_blink() {
+        let mut effects = StatusEffects::bind(Vec2D::ZERO, || {
+            vec![
+                StatusEffect::new('A', Modifier::None),
+                StatusEffect::new('B', Modifier::None).with_remaining(Duration::from_secs(1)),
+            ]
+        });
+        assert_eq!(effects.active_pixels()[1].pos, Vec2D::new(2, 0));
+
+        effects.update(Duration::from_millis(125));
+        assert_eq!(effects.active_pixels().len(), 1);
+        effects.update(Duration::from_millis(125));
+        assert_eq!(effects.active_pixels().len(), 2);
+    }
+}
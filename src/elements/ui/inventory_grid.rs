@@ -0,0 +1,360 @@
+use super::{DragDrop, DragEvent};
+use crate::elements::{
+    editor::move_grid_selection,
+    view::{ColChar, Modifier, ViewElement},
+    Pixel, Sprite, Vec2D,
+};
+
+/// A stack of items held in an [`InventoryGrid`] slot
+#[derive(Debug, Clone)]
+pub struct InventoryItem {
+    /// The name of the item. Items with the same name stack together
+    pub name: String,
+    /// The icon drawn in the item's slot. The icon's position is ignored, as it is always drawn at the top left of the slot
+    pub icon: Sprite,
+    /// How many of the item are in the stack
+    pub count: u32,
+    /// The most of the item that fit in one slot
+    pub max_stack: u32,
+}
+
+impl InventoryItem {
+    /// Create a new stack of a single item that stacks up to `max_stack`
+    #[must_use]
+    pub fn new(name: &str, icon: Sprite, max_stack: u32) -> Self {
+        Self {
+            name: String::from(name),
+            icon,
+            count: 1,
+            max_stack: max_stack.max(1),
+        }
+    }
+
+    /// Return the `InventoryItem` with the modified count
+    #[must_use]
+    pub const fn with_count(mut self, count: u32) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Returns true if the other item can be added to this item's stack
+    #[must_use]
+    pub fn stacks_with(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+/// An `InventoryGrid` displays a grid of item slots, each holding a stack of [`InventoryItem`]s drawn with its icon and count. One slot can be selected with the keyboard, and items can be moved between slots with the mouse through a [`DragDrop`]
+/// ```
+/// use gemini_engine::elements::{ui::{InventoryGrid, InventoryItem}, view::Modifier, Sprite, Vec2D};
+///
+/// let mut inventory = InventoryGrid::new(Vec2D::ZERO, 4, 2);
+/// let potion = InventoryItem::new("Potion", Sprite::new(Vec2D::ZERO, "!", Modifier::RED), 10);
+///
+/// inventory.add(potion.clone().with_count(8));
+/// inventory.add(potion.with_count(5));
+///
+/// // The first slot fills up and the rest spills into the next
+/// assert_eq!(inventory.slots[0].as_ref().map(|item| item.count), Some(10));
+/// assert_eq!(inventory.slots[1].as_ref().map(|item| item.count), Some(3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct InventoryGrid {
+    /// The position of the top left corner of the `InventoryGrid`
+    pub pos: Vec2D,
+    /// The slots of the grid, left to right then top to bottom
+    pub slots: Vec<Option<InventoryItem>>,
+    /// How many slots are in each row
+    pub columns: usize,
+    /// The size of the area inside each slot's frame, where the icon is drawn
+    pub slot_size: Vec2D,
+    /// The selected slot, if any
+    pub selected: Option<usize>,
+    /// The `Modifier` of the slot frames
+    pub frame_modifier: Modifier,
+    /// The `Modifier` of the selected slot's frame
+    pub selected_modifier: Modifier,
+}
+
+impl InventoryGrid {
+    /// Create a new, empty `InventoryGrid` with the given number of columns and rows. Each slot fits a three cell wide, one cell tall icon
+    #[must_use]
+    pub fn new(pos: Vec2D, columns: usize, rows: usize) -> Self {
+        Self {
+            pos,
+            slots: vec![None; columns * rows],
+            columns: columns.max(1),
+            slot_size: Vec2D::new(3, 1),
+            selected: None,
+            frame_modifier: Modifier::Coded(90),
+            selected_modifier: Modifier::YELLOW,
+        }
+    }
+
+    /// Return the `InventoryGrid` with the modified slot size
+    #[must_use]
+    pub const fn with_slot_size(mut self, slot_size: Vec2D) -> Self {
+        self.slot_size = slot_size;
+        self
+    }
+
+    /// Return the position of the top left corner of the slot's frame
+    #[must_use]
+    pub fn slot_pos(&self, index: usize) -> Vec2D {
+        let step = self.slot_size + Vec2D::new(2, 2);
+        let column = (index % self.columns) as isize;
+        let row = (index / self.columns) as isize;
+
+        self.pos + Vec2D::new(column * step.x, row * step.y)
+    }
+
+    /// Return the index of the slot at the screen position, including its frame, or `None` if there isn't one
+    #[must_use]
+    pub fn slot_at(&self, pos: Vec2D) -> Option<usize> {
+        let step = self.slot_size + Vec2D::new(2, 2);
+        let offset = pos - self.pos;
+        if offset.x < 0 || offset.y < 0 || offset.x >= step.x * self.columns as isize {
+            return None;
+        }
+
+        let index = (offset.y / step.y) as usize * self.columns + (offset.x / step.x) as usize;
+        (index < self.slots.len()).then_some(index)
+    }
+
+    /// Return the selected item, if a slot is selected and holds an item
+    #[must_use]
+    pub fn selected_item(&self) -> Option<&InventoryItem> {
+        self.slots.get(self.selected?)?.as_ref()
+    }
+
+    /// Move the selection through the grid in the given direction, for example `Vec2D::new(1, 0)` to select the slot on the right. Selects the first slot if nothing is selected
+    pub fn move_selection(&mut self, direction: Vec2D) {
+        self.selected = Some(self.selected.map_or(0, |selected| {
+            move_grid_selection(selected, self.slots.len(), self.columns, direction)
+        }));
+    }
+
+    /// Add the item to the inventory, topping up existing stacks of it before filling empty slots. Returns whatever didn't fit, if anything
+    pub fn add(&mut self, mut item: InventoryItem) -> Option<InventoryItem> {
+        for slot in self.slots.iter_mut().flatten() {
+            if slot.stacks_with(&item) {
+                let moved = item.count.min(slot.max_stack.saturating_sub(slot.count));
+                slot.count += moved;
+                item.count -= moved;
+            }
+        }
+
+        for slot in &mut self.slots {
+            if item.count == 0 {
+                break;
+            }
+            if slot.is_none() {
+                let moved = item.count.min(item.max_stack);
+                *slot = Some(item.clone().with_count(moved));
+                item.count -= moved;
+            }
+        }
+
+        (item.count > 0).then_some(item)
+    }
+
+    /// Remove and return the whole stack in the slot
+    pub fn take(&mut self, index: usize) -> Option<InventoryItem> {
+        self.slots.get_mut(index)?.take()
+    }
+
+    /// Move the stack in one slot to another. If the other slot holds the same item, as much as fits is added to its stack, otherwise (or if its stack is already full) the two slots are swapped. Returns false if either index is outside the grid
+    pub fn move_item(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.slots.len() || to >= self.slots.len() {
+            return false;
+        }
+        if from == to {
+            return true;
+        }
+
+        if let (Some(source), Some(destination)) = (&self.slots[from], &self.slots[to]) {
+            let moved = source
+                .count
+                .min(destination.max_stack.saturating_sub(destination.count));
+            if destination.stacks_with(source) && moved > 0 {
+                if let Some(destination) = &mut self.slots[to] {
+                    destination.count += moved;
+                }
+                if let Some(source) = &mut self.slots[from] {
+                    source.count -= moved;
+                    if source.count == 0 {
+                        self.slots[from] = None;
+                    }
+                }
+                return true;
+            }
+        }
+
+        self.slots.swap(from, to);
+        true
+    }
+
+    /// Declare every slot to the [`DragDrop`] as both a source and a target, with slot indices as the payload and IDs. Call this every frame between [`DragDrop::begin_frame()`] and [`DragDrop::end_frame()`], then pass the events to [`handle_drag_event()`](InventoryGrid::handle_drag_event())
+    pub fn declare_drag_drop(&self, drag_drop: &mut DragDrop<usize>) {
+        let size = self.slot_size + Vec2D::new(2, 2);
+
+        for (index, slot) in self.slots.iter().enumerate() {
+            let pos = self.slot_pos(index);
+            if let Some(item) = slot {
+                let icon = Sprite::new(
+                    pos + Vec2D::new(1, 1),
+                    &item.icon.texture,
+                    item.icon.modifier,
+                );
+                drag_drop.source(index, pos, size, || index, &icon);
+            }
+            drag_drop.target(index, pos, size, |_| true);
+        }
+    }
+
+    /// Move items for a drop from [`declare_drag_drop()`](InventoryGrid::declare_drag_drop()), selecting the slot the item was dropped in. Returns true if an item was moved
+    pub fn handle_drag_event(&mut self, event: &DragEvent<usize>) -> bool {
+        match *event {
+            DragEvent::Dropped {
+                payload, target, ..
+            } => {
+                let moved = self.move_item(payload, target);
+                if moved {
+                    self.selected = Some(target);
+                }
+                moved
+            }
+            DragEvent::Started { .. } | DragEvent::Cancelled { .. } => false,
+        }
+    }
+}
+
+impl ViewElement for InventoryGrid {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let mut pixels = vec![];
+        let inner = self.slot_size;
+
+        for (index, slot) in self.slots.iter().enumerate() {
+            let pos = self.slot_pos(index);
+            let modifier = if self.selected == Some(index) {
+                self.selected_modifier
+            } else {
+                self.frame_modifier
+            };
+
+            // The count is drawn in the bottom right of the frame
+            let count = slot
+                .as_ref()
+                .filter(|item| item.count > 1)
+                .map(|item| item.count.to_string())
+                .unwrap_or_default();
+            let count_start = inner.x + 1 - count.chars().count() as isize;
+
+            for y in 0..inner.y + 2 {
+                for x in 0..inner.x + 2 {
+                    let is_bottom = y == inner.y + 1;
+                    let text_char = match (x, y) {
+                        (0, 0) => '┌',
+                        (x, 0) if x == inner.x + 1 => '┐',
+                        (0, _) if is_bottom => '└',
+                        (x, _) if is_bottom && x == inner.x + 1 => '┘',
+                        (x, _) if is_bottom && x >= count_start.max(1) => {
+                            count.chars().nth((x - count_start) as usize).unwrap_or('─')
+                        }
+                        (_, 0) => '─',
+                        (_, _) if is_bottom => '─',
+                        (0, _) => '│',
+                        (x, _) if x == inner.x + 1 => '│',
+                        _ => continue,
+                    };
+                    pixels.push(Pixel::new(
+                        pos + Vec2D::new(x, y),
+                        ColChar::new(text_char, modifier),
+                    ));
+                }
+            }
+
+            if let Some(item) = slot {
+                pixels.extend(
+                    Sprite::draw(
+                        pos + Vec2D::new(1, 1),
+                        &item.icon.texture,
+                        item.icon.modifier,
+                    )
+                    .into_iter()
+                    .filter(|pixel| {
+                        let offset = pixel.pos - pos - Vec2D::new(1, 1);
+                        offset.x < inner.x && offset.y < inner.y
+                    }),
+                );
+            }
+        }
+
+        pixels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::ui::UiInput;
+
+    fn item(name: &str, count: u32) -> InventoryItem {
+        InventoryItem::new(name, Sprite::new(Vec2D::ZERO, "/", Modifier::None), 5).with_count(count)
+    }
+
+    #[test]
+    fn stacks_merge_and_swap() {
+        let mut inventory = InventoryGrid::new(Vec2D::ZERO, 3, 1);
+        assert!(inventory.add(item("Arrow", 7)).is_none());
+        assert!(inventory.add(item("Bow", 1)).is_none());
+        let leftover = inventory.add(item("Rock", 12));
+        assert_eq!(leftover.map(|item| item.count), Some(12));
+
+        let counts = |inventory: &InventoryGrid| -> Vec<Option<u32>> {
+            inventory
+                .slots
+                .iter()
+                .map(|slot| slot.as_ref().map(|item| item.count))
+                .collect()
+        };
+
+        // The full stack can't take any more arrows, so the slots swap
+        assert!(inventory.move_item(1, 0));
+        assert_eq!(counts(&inventory), vec![Some(2), Some(5), Some(1)]);
+
+        // Partial stacks merge, leaving the rest behind
+        inventory.slots[2] = Some(item("Arrow", 4));
+        assert!(inventory.move_item(2, 0));
+        assert_eq!(counts(&inventory), vec![Some(5), Some(5), Some(1)]);
+        assert!(!inventory.move_item(0, 3));
+    }
+
+    #[test]
+    fn drag_between_slots_and_draw() {
+        let mut inventory = InventoryGrid::new(Vec2D::new(1, 1), 2, 2);
+        inventory.add(item("Arrow", 3));
+        assert_eq!(inventory.slot_at(Vec2D::new(7, 5)), Some(3));
+        assert_eq!(inventory.slot_at(Vec2D::new(0, 1)), None);
+
+        let mut drag_drop = DragDrop::new();
+        for (mouse, mouse_down) in [(Vec2D::new(2, 2), true), (Vec2D::new(7, 5), false)] {
+            drag_drop.begin_frame(UiInput::new(mouse, mouse_down));
+            inventory.declare_drag_drop(&mut drag_drop);
+            drag_drop.end_frame();
+        }
+        for event in drag_drop.events() {
+            inventory.handle_drag_event(&event);
+        }
+        assert!(inventory.slots[0].is_none());
+        assert_eq!(inventory.selected_item().map(|i| i.count), Some(3));
+
+        let pixels = inventory.active_pixels();
+        let at = |pos: Vec2D| pixels.iter().find(|p| p.pos == pos).map(|p| p.fill_char);
+        assert_eq!(at(Vec2D::new(7, 5)).map(|c| c.text_char), Some('/'));
+        assert_eq!(
+            at(Vec2D::new(9, 6)),
+            Some(ColChar::new('3', Modifier::YELLOW))
+        );
+    }
+}
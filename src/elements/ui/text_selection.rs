@@ -0,0 +1,241 @@
+use std::io;
+
+use super::{Clipboard, UiInput};
+use crate::elements::{
+    ascii::split_rows,
+    view::{ColChar, Modifier, ViewElement},
+    Pixel, Text, Vec2D,
+};
+
+/// `TextSelection` lets the player select part of a [`Text`] by dragging the mouse over it, for log viewers and chat windows. Call [`update()`](TextSelection::update()) every frame with the `Text` and the state of the mouse, then blit the `TextSelection` after the `Text` to highlight the selected characters. A click without dragging clears the selection
+/// ```
+/// use gemini_engine::elements::{ui::{TextSelection, UiInput}, view::Modifier, Text, Vec2D};
+///
+/// let log = Text::new(Vec2D::new(2, 1), "Joined the game\nHello there", Modifier::None);
+/// let mut selection = TextSelection::new();
+///
+/// for (mouse, mouse_down) in [((9, 1), true), ((6, 2), true), ((6, 2), false)] {
+///     selection.update(&log, UiInput::new(Vec2D::new(mouse.0, mouse.1), mouse_down));
+/// }
+///
+/// assert_eq!(selection.selected_text(&log), "the game\nHello");
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextSelection {
+    /// The `Modifier` selected characters are highlighted with. Defaults to reversed colours
+    pub highlight: Modifier,
+    /// The row and column the selection was started from
+    anchor: Option<(usize, usize)>,
+    /// The row and column the selection was dragged to
+    head: Option<(usize, usize)>,
+    dragging: bool,
+    previous_mouse_down: bool,
+    pixels: Vec<Pixel>,
+}
+
+impl TextSelection {
+    /// Create a new `TextSelection` with nothing selected
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            highlight: Modifier::Coded(7),
+            anchor: None,
+            head: None,
+            dragging: false,
+            previous_mouse_down: false,
+            pixels: vec![],
+        }
+    }
+
+    /// Return the `TextSelection` with a different highlight
+    #[must_use]
+    pub const fn with_highlight(mut self, highlight: Modifier) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Return the start of each row of the `Text` on screen and the characters in it
+    fn rows(text: &Text) -> Vec<(Vec2D, Vec<char>)> {
+        (0..)
+            .zip(split_rows(&text.content, text.tab_width))
+            .map(|(y, row)| {
+                let chars: Vec<char> = row.chars().collect();
+                let x = text.align.apply_to(text.pos.x, chars.len() as isize);
+                (Vec2D::new(x, text.pos.y + y), chars)
+            })
+            .collect()
+    }
+
+    /// Return the row and column of the character under the position, clamped to the nearest character of the text
+    fn cell_at(rows: &[(Vec2D, Vec<char>)], pos: Vec2D) -> Option<(usize, usize)> {
+        let first = rows.first()?.0.y;
+        let row = (pos.y - first).clamp(0, rows.len() as isize - 1) as usize;
+        let (start, chars) = &rows[row];
+        let column = (pos.x - start.x).clamp(0, chars.len().max(1) as isize - 1) as usize;
+
+        Some((row, column))
+    }
+
+    /// Returns true if the position is over one of the `Text`'s characters
+    fn is_over(rows: &[(Vec2D, Vec<char>)], pos: Vec2D) -> bool {
+        rows.iter().any(|(start, chars)| {
+            pos.y == start.y && pos.x >= start.x && pos.x < start.x + chars.len() as isize
+        })
+    }
+
+    /// Handle the mouse for this frame, starting a selection when the mouse is pressed over the `Text` and extending it while the mouse is dragged, even outside the `Text`
+    pub fn update(&mut self, text: &Text, input: UiInput) {
+        let rows = Self::rows(text);
+        let just_pressed = input.mouse_down && !self.previous_mouse_down;
+        self.previous_mouse_down = input.mouse_down;
+
+        if let Some(mouse) = input.mouse {
+            if just_pressed {
+                self.dragging = Self::is_over(&rows, mouse);
+                self.anchor = self.dragging.then(|| Self::cell_at(&rows, mouse)).flatten();
+                self.head = self.anchor;
+            } else if self.dragging && input.mouse_down {
+                self.head = Self::cell_at(&rows, mouse);
+            }
+        }
+        if !input.mouse_down {
+            self.dragging = false;
+            if self.anchor == self.head {
+                self.clear();
+            }
+        }
+
+        self.pixels.clear();
+        if let Some((start, end)) = self.range() {
+            for (row, (row_start, chars)) in rows.iter().enumerate() {
+                for (column, text_char) in chars.iter().enumerate() {
+                    if (start..=end).contains(&(row, column)) {
+                        self.pixels.push(Pixel::new(
+                            *row_start + Vec2D::new(column as isize, 0),
+                            ColChar::new(*text_char, self.highlight),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Return the first and last selected row and column, in reading order
+    fn range(&self) -> Option<((usize, usize), (usize, usize))> {
+        let (anchor, head) = (self.anchor?, self.head?);
+
+        Some((anchor.min(head), anchor.max(head)))
+    }
+
+    /// Returns true if any text is selected
+    #[must_use]
+    pub const fn has_selection(&self) -> bool {
+        self.anchor.is_some()
+    }
+
+    /// Returns true if the player is dragging out a selection
+    #[must_use]
+    pub const fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    /// Clear the selection
+    pub fn clear(&mut self) {
+        self.anchor = None;
+        self.head = None;
+        self.pixels.clear();
+    }
+
+    /// Return the selected characters of the `Text`, with a line break between rows
+    #[must_use]
+    pub fn selected_text(&self, text: &Text) -> String {
+        let Some((start, end)) = self.range() else {
+            return String::new();
+        };
+
+        split_rows(&text.content, text.tab_width)
+            .iter()
+            .enumerate()
+            .skip(start.0)
+            .take(end.0 - start.0 + 1)
+            .map(|(row, chars)| {
+                let first = if row == start.0 { start.1 } else { 0 };
+                let last = if row == end.0 { end.1 + 1 } else { usize::MAX };
+                chars
+                    .chars()
+                    .skip(first)
+                    .take(last.saturating_sub(first))
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Copy the selected characters of the `Text` to the clipboard. Returns false without copying if nothing is selected
+    ///
+    /// # Errors
+    /// Returns an error if the clipboard fails to copy the text
+    pub fn copy(&self, text: &Text, clipboard: &mut Clipboard) -> io::Result<bool> {
+        if !self.has_selection() {
+            return Ok(false);
+        }
+        clipboard.copy(&self.selected_text(text))?;
+
+        Ok(true)
+    }
+}
+
+impl Default for TextSelection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewElement for TextSelection {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        self.pixels.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::ascii::TextAlign;
+
+    fn drag(selection: &mut TextSelection, text: &Text, from: Vec2D, to: Vec2D) {
+        selection.update(text, UiInput::new(from, true));
+        selection.update(text, UiInput::new(to, true));
+        selection.update(text, UiInput::new(to, false));
+    }
+
+    #[test]
+    fn selects_backwards_and_highlights() {
+        let text = Text::new(Vec2D::new(10, 0), "abcd\nef", Modifier::None)
+            .with_align(TextAlign::Centered);
+        let mut selection = TextSelection::new();
+
+        // The rows start at x = 8 and x = 9
+        drag(&mut selection, &text, Vec2D::new(9, 1), Vec2D::new(9, 0));
+        assert_eq!(selection.selected_text(&text), "bcd\ne");
+        assert_eq!(selection.active_pixels().len(), 4);
+        assert_eq!(selection.active_pixels()[0].pos, Vec2D::new(9, 0));
+        assert!(!selection.is_dragging());
+    }
+
+    #[test]
+    fn clicks_clear_and_miss() {
+        let text = Text::new(Vec2D::ZERO, "hello", Modifier::None);
+        let mut selection = TextSelection::new();
+        drag(&mut selection, &text, Vec2D::new(0, 0), Vec2D::new(30, 5));
+        assert_eq!(selection.selected_text(&text), "hello");
+
+        drag(&mut selection, &text, Vec2D::new(2, 0), Vec2D::new(2, 0));
+        assert!(!selection.has_selection());
+        drag(&mut selection, &text, Vec2D::new(6, 0), Vec2D::new(1, 0));
+        assert!(!selection.has_selection());
+        assert_eq!(
+            selection.copy(&text, &mut Clipboard::local()).ok(),
+            Some(false)
+        );
+    }
+}
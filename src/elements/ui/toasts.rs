@@ -0,0 +1,313 @@
+use crate::{
+    elements::{
+        view::{ColChar, Modifier, ViewElement},
+        Pixel, Vec2D,
+    },
+    gameloop::CanAnimate,
+};
+use std::{collections::VecDeque, time::Duration};
+
+/// How important a [`Toasts`] message is, which determines its colour and icon
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Severity {
+    /// General information, drawn in cyan
+    #[default]
+    Info,
+    /// Something went well, such as an achievement or a saved game, drawn in green
+    Success,
+    /// Something the player should know about, drawn in yellow
+    Warning,
+    /// Something went wrong, drawn in red
+    Error,
+}
+
+impl Severity {
+    /// Return the `Modifier` messages with the `Severity` are drawn with
+    #[must_use]
+    pub const fn modifier(self) -> Modifier {
+        match self {
+            Self::Info => Modifier::CYAN,
+            Self::Success => Modifier::GREEN,
+            Self::Warning => Modifier::YELLOW,
+            Self::Error => Modifier::RED,
+        }
+    }
+
+    /// Return the icon drawn before messages with the `Severity`
+    #[must_use]
+    pub const fn icon(self) -> char {
+        match self {
+            Self::Info => 'i',
+            Self::Success => '✔',
+            Self::Warning => '!',
+            Self::Error => '✖',
+        }
+    }
+}
+
+/// The corner of the screen that [`Toasts`] appear in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToastCorner {
+    /// The top left corner, with new toasts stacking downwards
+    TopLeft,
+    /// The top right corner, with new toasts stacking downwards
+    #[default]
+    TopRight,
+    /// The bottom left corner, with new toasts stacking upwards
+    BottomLeft,
+    /// The bottom right corner, with new toasts stacking upwards
+    BottomRight,
+}
+
+/// A single message shown by [`Toasts`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Toast {
+    message: String,
+    severity: Severity,
+    age: Duration,
+}
+
+/// `Toasts` shows short-lived messages such as item pickups or achievements, stacked in a corner of the screen. Each toast slides in from the edge, stays for a while, then slides back out. Messages beyond [`max_visible`](Toasts::max_visible) wait in a queue until there is room. Call [`update()`](Toasts::update()) every frame to move them along
+/// ```
+/// use gemini_engine::elements::{ui::{Severity, Toasts}, Vec2D};
+/// use std::time::Duration;
+///
+/// let mut toasts = Toasts::new(Vec2D::new(60, 20));
+/// toasts.push("Picked up a sword", Severity::Info);
+/// toasts.push("Achievement unlocked!", Severity::Success);
+///
+/// toasts.update(Duration::from_secs(10));
+/// assert!(toasts.is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toasts {
+    /// The size of the screen the toasts are drawn on
+    pub screen_size: Vec2D,
+    /// The corner the toasts appear in
+    pub corner: ToastCorner,
+    /// How long each toast is shown for, including sliding in and out
+    pub duration: Duration,
+    /// How long a toast takes to slide in or out
+    pub slide_duration: Duration,
+    /// The most toasts shown at once
+    pub max_visible: usize,
+    visible: Vec<Toast>,
+    queue: VecDeque<Toast>,
+}
+
+impl Toasts {
+    /// Create a new, empty `Toasts` for a screen of the given size, showing up to 5 toasts in the top right corner for 3 seconds each
+    #[must_use]
+    pub const fn new(screen_size: Vec2D) -> Self {
+        Self {
+            screen_size,
+            corner: ToastCorner::TopRight,
+            duration: Duration::from_secs(3),
+            slide_duration: Duration::from_millis(250),
+            max_visible: 5,
+            visible: vec![],
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Return the `Toasts` with the modified corner
+    #[must_use]
+    pub const fn with_corner(mut self, corner: ToastCorner) -> Self {
+        self.corner = corner;
+        self
+    }
+
+    /// Return the `Toasts` with the modified duration
+    #[must_use]
+    pub const fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Queue a message to be shown
+    pub fn push(&mut self, message: impl Into<String>, severity: Severity) {
+        self.queue.push_back(Toast {
+            message: message.into(),
+            severity,
+            age: Duration::ZERO,
+        });
+        self.fill_from_queue();
+    }
+
+    /// Return the number of toasts being shown or waiting to be shown
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.visible.len() + self.queue.len()
+    }
+
+    /// Return true if there are no toasts being shown or waiting to be shown
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.visible.is_empty() && self.queue.is_empty()
+    }
+
+    /// Remove every toast, including those waiting to be shown
+    pub fn clear(&mut self) {
+        self.visible.clear();
+        self.queue.clear();
+    }
+
+    /// Move queued toasts into view while there is room
+    fn fill_from_queue(&mut self) {
+        while self.visible.len() < self.max_visible {
+            match self.queue.pop_front() {
+                Some(toast) => self.visible.push(toast),
+                None => break,
+            }
+        }
+    }
+
+    /// Age the toasts by `delta`, usually the time since the last frame, removing expired toasts and showing queued ones in their place
+    pub fn update(&mut self, delta: Duration) {
+        let mut remaining = delta;
+        // Step through expiries one at a time so that queued toasts only start ageing once they're shown
+        while !remaining.is_zero() && !self.visible.is_empty() {
+            let oldest = self
+                .visible
+                .iter()
+                .map(|toast| toast.age)
+                .max()
+                .unwrap_or_default();
+            let step = remaining.min(
+                self.duration
+                    .saturating_sub(oldest)
+                    .max(Duration::from_nanos(1)),
+            );
+
+            for toast in &mut self.visible {
+                toast.age += step;
+            }
+            self.visible.retain(|toast| toast.age < self.duration);
+            self.fill_from_queue();
+            remaining = remaining.saturating_sub(step);
+        }
+    }
+
+    /// Return how far the toast has slid into view, from 0.0 (hidden) to 1.0 (fully shown)
+    fn slide_amount(&self, toast: &Toast) -> f64 {
+        if self.slide_duration.is_zero() {
+            return 1.0;
+        }
+        let slide = self.slide_duration.as_secs_f64();
+        let sliding_in = toast.age.as_secs_f64() / slide;
+        let sliding_out = self.duration.saturating_sub(toast.age).as_secs_f64() / slide;
+
+        sliding_in.min(sliding_out).clamp(0.0, 1.0)
+    }
+}
+
+impl ViewElement for Toasts {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let mut pixels = vec![];
+        let from_right = matches!(
+            self.corner,
+            ToastCorner::TopRight | ToastCorner::BottomRight
+        );
+        let from_bottom = matches!(
+            self.corner,
+            ToastCorner::BottomLeft | ToastCorner::BottomRight
+        );
+
+        for (i, toast) in (0..).zip(&self.visible) {
+            let modifier = toast.severity.modifier();
+            let inner = format!(" {} {} ", toast.severity.icon(), toast.message);
+            let inner_width = inner.chars().count();
+            let width = inner_width as isize + 2;
+
+            let rows = [
+                format!("╭{}╮", "─".repeat(inner_width)),
+                format!("│{inner}│"),
+                format!("╰{}╯", "─".repeat(inner_width)),
+            ];
+
+            // Slide in from the nearest edge of the screen
+            let hidden = ((1.0 - self.slide_amount(toast)) * width as f64).round() as isize;
+            let x = if from_right {
+                self.screen_size.x - width + hidden
+            } else {
+                -hidden
+            };
+            let y = if from_bottom {
+                self.screen_size.y - 3 * (i + 1)
+            } else {
+                3 * i
+            };
+
+            for (row_y, row) in (0..).zip(&rows) {
+                for (column, text_char) in (0..).zip(row.chars()) {
+                    let is_message = row_y == 1 && column > 2 && column < width - 1;
+                    pixels.push(Pixel::new(
+                        Vec2D::new(x + column, y + row_y),
+                        ColChar::new(
+                            text_char,
+                            if is_message { Modifier::None } else { modifier },
+                        ),
+                    ));
+                }
+            }
+        }
+
+        pixels
+    }
+}
+
+impl CanAnimate for Toasts {
+    fn is_animating(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toasts_queue_and_expire() {
+        let mut toasts = Toasts::new(Vec2D::new(40, 10));
+        toasts.max_visible = 2;
+        for message in ["one", "two", "three"] {
+            toasts.push(message, Severity::Info);
+        }
+        assert_eq!(toasts.len(), 3);
+        assert_eq!(toasts.visible.len(), 2);
+
+        // The first two expire after 3 seconds, then the third starts ageing
+        toasts.update(Duration::from_secs(4));
+        assert_eq!(toasts.len(), 1);
+        assert_eq!(toasts.visible[0].message, "three");
+        toasts.update(Duration::from_millis(1500));
+        assert!(toasts.is_animating());
+        toasts.update(Duration::from_millis(500));
+        assert!(toasts.is_empty());
+    }
+
+    #[test]
+    fn toasts_slide_in_from_corner() {
+        let mut toasts = Toasts::new(Vec2D::new(40, 10)).with_corner(ToastCorner::BottomRight);
+        toasts.push("Hi", Severity::Error);
+
+        // " ✖ Hi " plus the frame is 8 cells wide
+        let left_edge = |toasts: &Toasts| {
+            toasts
+                .active_pixels()
+                .iter()
+                .map(|p| p.pos.x)
+                .min()
+                .unwrap_or_default()
+        };
+        assert_eq!(left_edge(&toasts), 40);
+        toasts.update(Duration::from_millis(125));
+        assert_eq!(left_edge(&toasts), 36);
+        toasts.update(Duration::from_millis(500));
+        assert_eq!(left_edge(&toasts), 32);
+
+        let pixels = toasts.active_pixels();
+        assert_eq!(pixels[0].pos.y, 7);
+        assert_eq!(pixels[0].fill_char.modifier, Modifier::RED);
+    }
+}
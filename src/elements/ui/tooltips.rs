@@ -0,0 +1,210 @@
+use crate::elements::{
+    view::{ColChar, Modifier, ViewElement},
+    Pixel, Vec2D,
+};
+use std::time::Duration;
+
+/// An area of the screen with hover text
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HoverArea {
+    pos: Vec2D,
+    size: Vec2D,
+    text: String,
+}
+
+impl HoverArea {
+    const fn contains(&self, point: Vec2D) -> bool {
+        point.x >= self.pos.x
+            && point.y >= self.pos.y
+            && point.x < self.pos.x + self.size.x
+            && point.y < self.pos.y + self.size.y
+    }
+}
+
+/// `Tooltips` shows hover text for areas of the screen in a framed panel next to the mouse, once the mouse has rested over an area for the [`delay`](Tooltips::delay). The panel is placed below and to the right of the mouse, flipping to the other side when it would go off the edge of the screen. Every frame, call [`begin_frame()`](Tooltips::begin_frame()), [`register()`](Tooltips::register()) the areas with hover text, then [`update()`](Tooltips::update()) with the mouse position
+/// ```
+/// use gemini_engine::elements::{ui::{Tooltips, Ui, UiInput}, Vec2D};
+/// use std::time::Duration;
+///
+/// let mut ui = Ui::new(Vec2D::ZERO);
+/// let mut tooltips = Tooltips::new(Vec2D::new(40, 10));
+/// let mouse = Vec2D::new(2, 0);
+///
+/// ui.begin_frame(UiInput::new(mouse, false));
+/// tooltips.begin_frame();
+/// ui.button("Save");
+/// if let Some((pos, size)) = ui.last_widget() {
+///     tooltips.register(pos, size, "Save the game to disk");
+/// }
+/// // The mouse arrives, then rests for a second
+/// tooltips.update(Some(mouse), Duration::ZERO);
+/// tooltips.update(Some(mouse), Duration::from_secs(1));
+///
+/// assert_eq!(tooltips.current(), Some("Save the game to disk"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tooltips {
+    /// The size of the screen, used to keep tooltips on it
+    pub screen_size: Vec2D,
+    /// How long the mouse has to rest over an area before its tooltip appears
+    pub delay: Duration,
+    /// The `Modifier` of the tooltip's frame
+    pub frame_modifier: Modifier,
+    /// The `Modifier` of the tooltip's text
+    pub text_modifier: Modifier,
+    areas: Vec<HoverArea>,
+    mouse: Option<Vec2D>,
+    rest_time: Duration,
+}
+
+impl Tooltips {
+    /// Create a new `Tooltips` for a screen of the given size, with a delay of half a second
+    #[must_use]
+    pub const fn new(screen_size: Vec2D) -> Self {
+        Self {
+            screen_size,
+            delay: Duration::from_millis(500),
+            frame_modifier: Modifier::Coded(90),
+            text_modifier: Modifier::None,
+            areas: vec![],
+            mouse: None,
+            rest_time: Duration::ZERO,
+        }
+    }
+
+    /// Return the `Tooltips` with the modified delay
+    #[must_use]
+    pub const fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Forget the areas registered last frame. Call this before registering this frame's areas
+    pub fn begin_frame(&mut self) {
+        self.areas.clear();
+    }
+
+    /// Give an area of the screen hover text. Areas registered later are treated as being on top of earlier ones. The text can span several lines
+    pub fn register(&mut self, pos: Vec2D, size: Vec2D, text: impl Into<String>) {
+        self.areas.push(HoverArea {
+            pos,
+            size,
+            text: text.into(),
+        });
+    }
+
+    /// Update the mouse position, `delta` being the time since the last frame. The tooltip is hidden whenever the mouse moves, and reappears once it has rested for the [`delay`](Tooltips::delay)
+    pub fn update(&mut self, mouse: Option<Vec2D>, delta: Duration) {
+        if mouse == self.mouse {
+            self.rest_time += delta;
+        } else {
+            self.mouse = mouse;
+            self.rest_time = Duration::ZERO;
+        }
+    }
+
+    /// Return the text of the tooltip currently being shown, if any
+    #[must_use]
+    pub fn current(&self) -> Option<&str> {
+        if self.rest_time < self.delay {
+            return None;
+        }
+        let mouse = self.mouse?;
+
+        self.areas
+            .iter()
+            .rev()
+            .find(|area| area.contains(mouse))
+            .map(|area| area.text.as_str())
+    }
+}
+
+impl ViewElement for Tooltips {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let (Some(text), Some(mouse)) = (self.current(), self.mouse) else {
+            return vec![];
+        };
+
+        let lines: Vec<&str> = text.lines().collect();
+        let inner_width = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0)
+            + 2;
+        let size = Vec2D::new(inner_width as isize + 2, lines.len() as isize + 2);
+
+        // Below and to the right of the mouse, flipping to stay on the screen
+        let mut pos = mouse + Vec2D::new(1, 1);
+        if pos.x + size.x > self.screen_size.x {
+            pos.x = mouse.x - size.x;
+        }
+        if pos.y + size.y > self.screen_size.y {
+            pos.y = mouse.y - size.y;
+        }
+        pos.x = pos.x.max(0);
+        pos.y = pos.y.max(0);
+
+        let mut rows = vec![(format!("┌{}┐", "─".repeat(inner_width)), false)];
+        rows.extend(
+            lines
+                .iter()
+                .map(|line| (format!("│ {line:<0$}│", inner_width - 1), true)),
+        );
+        rows.push((format!("└{}┘", "─".repeat(inner_width)), false));
+
+        let mut pixels = vec![];
+        for (y, (row, has_text)) in (0..).zip(rows) {
+            for (x, text_char) in (0..).zip(row.chars()) {
+                let is_text = has_text && x > 0 && x < size.x - 1;
+                let modifier = if is_text {
+                    self.text_modifier
+                } else {
+                    self.frame_modifier
+                };
+                pixels.push(Pixel::new(
+                    pos + Vec2D::new(x, y),
+                    ColChar::new(text_char, modifier),
+                ));
+            }
+        }
+
+        pixels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tooltip_waits_for_mouse_to_rest() {
+        let mut tooltips = Tooltips::new(Vec2D::new(40, 10));
+        tooltips.register(Vec2D::new(0, 0), Vec2D::new(10, 1), "Below");
+        tooltips.register(Vec2D::new(5, 0), Vec2D::new(10, 1), "On top");
+
+        tooltips.update(Some(Vec2D::new(6, 0)), Duration::ZERO);
+        tooltips.update(Some(Vec2D::new(6, 0)), Duration::from_millis(300));
+        assert_eq!(tooltips.current(), None);
+        tooltips.update(Some(Vec2D::new(6, 0)), Duration::from_millis(300));
+        assert_eq!(tooltips.current(), Some("On top"));
+
+        tooltips.update(Some(Vec2D::new(2, 0)), Duration::from_secs(1));
+        assert_eq!(tooltips.current(), None);
+    }
+
+    #[test]
+    fn tooltip_flips_at_screen_edges() {
+        let mut tooltips = Tooltips::new(Vec2D::new(20, 10)).with_delay(Duration::ZERO);
+        tooltips.register(Vec2D::ZERO, Vec2D::new(20, 10), "Hi\nthere");
+
+        tooltips.update(Some(Vec2D::new(2, 2)), Duration::ZERO);
+        let pixels = tooltips.active_pixels();
+        assert_eq!(pixels[0].pos, Vec2D::new(3, 3));
+        // "there" plus padding and frame is 9 cells wide and 4 tall
+        assert_eq!(pixels.len(), 9 * 4);
+
+        tooltips.update(Some(Vec2D::new(18, 8)), Duration::ZERO);
+        assert_eq!(tooltips.active_pixels()[0].pos, Vec2D::new(9, 4));
+    }
+}
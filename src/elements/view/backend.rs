@@ -0,0 +1,164 @@
+use super::Vec2D;
+use crate::utils;
+use std::io::{self, Write};
+
+/// A `RenderBackend` is where rendered frames are sent, so that the terminal library used to show them can be swapped out and tests can capture frames without a real terminal. Draw a [`View`](super::View) through one with [`View::display_to()`](super::View::display_to())
+///
+/// [`StdoutBackend`] writes straight to stdout, [`WriterBackend`] writes to anything implementing [`io::Write`], such as a crossterm or termion raw mode stdout, and [`MemoryBackend`] keeps every frame in memory
+pub trait RenderBackend {
+    /// Write a rendered frame, made of text and ANSI escape sequences
+    ///
+    /// # Errors
+    /// Returns an error if the frame couldn't be written
+    fn write_frame(&mut self, frame: &str) -> io::Result<()>;
+
+    /// Return the size of the terminal in cells, or `None` if it isn't known
+    fn size(&self) -> Option<Vec2D> {
+        None
+    }
+}
+
+/// A [`RenderBackend`] that writes frames to stdout and flushes it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StdoutBackend;
+
+impl RenderBackend for StdoutBackend {
+    fn write_frame(&mut self, frame: &str) -> io::Result<()> {
+        utils::write_synchronized(&mut io::stdout().lock(), |stdout| {
+            stdout.write_all(frame.as_bytes())
+        })
+    }
+
+    fn size(&self) -> Option<Vec2D> {
+        utils::get_terminal_size_as_vec2d()
+    }
+}
+
+/// A [`RenderBackend`] that writes frames to any [`io::Write`], such as a file, a socket or another terminal library's output
+#[derive(Debug, Clone, Default)]
+pub struct WriterBackend<W: Write> {
+    /// The writer frames are written to
+    pub writer: W,
+    /// The size reported by [`size()`](RenderBackend::size())
+    pub size: Option<Vec2D>,
+}
+
+impl<W: Write> WriterBackend<W> {
+    /// Create a new `WriterBackend` writing to the writer, with an unknown size
+    pub const fn new(writer: W) -> Self {
+        Self { writer, size: None }
+    }
+}
+
+impl<W: Write> RenderBackend for WriterBackend<W> {
+    fn write_frame(&mut self, frame: &str) -> io::Result<()> {
+        self.writer.write_all(frame.as_bytes())?;
+        self.writer.flush()
+    }
+
+    fn size(&self) -> Option<Vec2D> {
+        self.size
+    }
+}
+
+/// A [`RenderBackend`] that keeps every frame in memory, for tests and tools that inspect the output
+/// ```
+/// use gemini_engine::elements::{view::{ColChar, MemoryBackend}, View};
+///
+/// let mut backend = MemoryBackend::new(None);
+/// View::new(3, 1, ColChar::SOLID).display_to(&mut backend).unwrap();
+///
+/// assert_eq!(backend.frames.len(), 1);
+/// assert!(backend.last_frame().is_some_and(|frame| frame.contains("███")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MemoryBackend {
+    /// Every frame written, from oldest to newest
+    pub frames: Vec<String>,
+    /// The size reported by [`size()`](RenderBackend::size())
+    pub size: Option<Vec2D>,
+}
+
+impl MemoryBackend {
+    /// Create a new `MemoryBackend` with no frames, reporting the given size
+    #[must_use]
+    pub const fn new(size: Option<Vec2D>) -> Self {
+        Self {
+            frames: vec![],
+            size,
+        }
+    }
+
+    /// Return the most recently written frame, if any
+    #[must_use]
+    pub fn last_frame(&self) -> Option<&str> {
+        self.frames.last().map(String::as_str)
+    }
+}
+
+impl RenderBackend for MemoryBackend {
+    fn write_frame(&mut self, frame: &str) -> io::Result<()> {
+        self.frames.push(frame.to_string());
+        Ok(())
+    }
+
+    fn size(&self) -> Option<Vec2D> {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::view::{ColChar, Modifier, View, Wrapping};
+
+    #[test]
+    fn captures_frames_without_a_terminal() {
+        let mut view = View::new(2, 1, ColChar::EMPTY);
+        view.plot(
+            Vec2D::new(1, 0),
+            ColChar::new('x', Modifier::RED),
+            Wrapping::Panic,
+        );
+
+        let mut writer = WriterBackend::new(vec![]);
+        view.display_to(&mut writer)
+            .expect("writing to a Vec can't fail");
+        let mut memory = MemoryBackend::new(Some(view.size()));
+        view.display_to(&mut memory)
+            .expect("MemoryBackend can't fail");
+
+        let frame = memory.last_frame().expect("a frame was written");
+        assert_eq!(frame, "\x1b[H\x1b[J \x1b[0m\x1b[31mx\x1b[0m\r\n\x1b[J");
+        assert_eq!(writer.writer, frame.as_bytes());
+        assert_eq!(memory.size(), Some(Vec2D::new(2, 1)));
+    }
+
+    #[test]
+    fn synchronized_frames_are_wrapped() {
+        utils::set_synchronized_output(Some(true));
+        let mut output = vec![];
+        utils::write_synchronized(&mut output, |out| out.write_all(b"frame"))
+            .expect("writing to a Vec can't fail");
+        let mut interrupted = vec![];
+        let result = utils::write_synchronized(&mut interrupted, |out| {
+            out.write_all(b"fra")?;
+            Err(io::Error::other("interrupted"))
+        });
+        utils::set_synchronized_output(None);
+        assert_eq!(output, b"\x1b[?2026hframe\x1b[?2026l");
+        assert!(result.is_err());
+        assert_eq!(interrupted, b"\x1b[?2026hfra\x1b[?2026l");
+
+        assert!(utils::detect_synchronized_output_from(
+            Some("foot-extra"),
+            None,
+            false
+        ));
+        assert!(!utils::detect_synchronized_output_from(
+            Some("xterm-256color"),
+            None,
+            false
+        ));
+    }
+}
@@ -0,0 +1,244 @@
+use super::{export::DEFAULT_TEXT, Bitmap, ColChar, Colour, ModifierSet, TextStyle, View};
+
+/// The width of a cell drawn by [`Bitmap::from_view_with_font()`], in pixels at a scale of 1
+pub const FONT_CELL_WIDTH: usize = 6;
+/// The height of a cell drawn by [`Bitmap::from_view_with_font()`], in pixels at a scale of 1
+pub const FONT_CELL_HEIGHT: usize = 12;
+
+/// How far down the cell the top of each glyph is drawn
+const GLYPH_TOP: usize = 2;
+
+/// A 5x7 font covering printable ASCII from `' '` to `'~'`. Each glyph is five columns from left to right, with the lowest bit of each column at the top
+const GLYPHS: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x00, 0x00, 0x5F, 0x00, 0x00],
+    [0x00, 0x07, 0x00, 0x07, 0x00],
+    [0x14, 0x7F, 0x14, 0x7F, 0x14],
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12],
+    [0x23, 0x13, 0x08, 0x64, 0x62],
+    [0x36, 0x49, 0x55, 0x22, 0x50],
+    [0x00, 0x05, 0x03, 0x00, 0x00],
+    [0x00, 0x1C, 0x22, 0x41, 0x00],
+    [0x00, 0x41, 0x22, 0x1C, 0x00],
+    [0x08, 0x2A, 0x1C, 0x2A, 0x08],
+    [0x08, 0x08, 0x3E, 0x08, 0x08],
+    [0x00, 0x50, 0x30, 0x00, 0x00],
+    [0x08, 0x08, 0x08, 0x08, 0x08],
+    [0x00, 0x60, 0x60, 0x00, 0x00],
+    [0x20, 0x10, 0x08, 0x04, 0x02],
+    [0x3E, 0x51, 0x49, 0x45, 0x3E],
+    [0x00, 0x42, 0x7F, 0x40, 0x00],
+    [0x42, 0x61, 0x51, 0x49, 0x46],
+    [0x21, 0x41, 0x45, 0x4B, 0x31],
+    [0x18, 0x14, 0x12, 0x7F, 0x10],
+    [0x27, 0x45, 0x45, 0x45, 0x39],
+    [0x3C, 0x4A, 0x49, 0x49, 0x30],
+    [0x01, 0x71, 0x09, 0x05, 0x03],
+    [0x36, 0x49, 0x49, 0x49, 0x36],
+    [0x06, 0x49, 0x49, 0x29, 0x1E],
+    [0x00, 0x36, 0x36, 0x00, 0x00],
+    [0x00, 0x56, 0x36, 0x00, 0x00],
+    [0x08, 0x14, 0x22, 0x41, 0x00],
+    [0x14, 0x14, 0x14, 0x14, 0x14],
+    [0x00, 0x41, 0x22, 0x14, 0x08],
+    [0x02, 0x01, 0x51, 0x09, 0x06],
+    [0x32, 0x49, 0x79, 0x41, 0x3E],
+    [0x7E, 0x11, 0x11, 0x11, 0x7E],
+    [0x7F, 0x49, 0x49, 0x49, 0x36],
+    [0x3E, 0x41, 0x41, 0x41, 0x22],
+    [0x7F, 0x41, 0x41, 0x22, 0x1C],
+    [0x7F, 0x49, 0x49, 0x49, 0x41],
+    [0x7F, 0x09, 0x09, 0x09, 0x01],
+    [0x3E, 0x41, 0x49, 0x49, 0x7A],
+    [0x7F, 0x08, 0x08, 0x08, 0x7F],
+    [0x00, 0x41, 0x7F, 0x41, 0x00],
+    [0x20, 0x40, 0x41, 0x3F, 0x01],
+    [0x7F, 0x08, 0x14, 0x22, 0x41],
+    [0x7F, 0x40, 0x40, 0x40, 0x40],
+    [0x7F, 0x02, 0x0C, 0x02, 0x7F],
+    [0x7F, 0x04, 0x08, 0x10, 0x7F],
+    [0x3E, 0x41, 0x41, 0x41, 0x3E],
+    [0x7F, 0x09, 0x09, 0x09, 0x06],
+    [0x3E, 0x41, 0x51, 0x21, 0x5E],
+    [0x7F, 0x09, 0x19, 0x29, 0x46],
+    [0x46, 0x49, 0x49, 0x49, 0x31],
+    [0x01, 0x01, 0x7F, 0x01, 0x01],
+    [0x3F, 0x40, 0x40, 0x40, 0x3F],
+    [0x1F, 0x20, 0x40, 0x20, 0x1F],
+    [0x3F, 0x40, 0x38, 0x40, 0x3F],
+    [0x63, 0x14, 0x08, 0x14, 0x63],
+    [0x07, 0x08, 0x70, 0x08, 0x07],
+    [0x61, 0x51, 0x49, 0x45, 0x43],
+    [0x00, 0x7F, 0x41, 0x41, 0x00],
+    [0x02, 0x04, 0x08, 0x10, 0x20],
+    [0x00, 0x41, 0x41, 0x7F, 0x00],
+    [0x04, 0x02, 0x01, 0x02, 0x04],
+    [0x40, 0x40, 0x40, 0x40, 0x40],
+    [0x00, 0x01, 0x02, 0x04, 0x00],
+    [0x20, 0x54, 0x54, 0x54, 0x78],
+    [0x7F, 0x48, 0x44, 0x44, 0x38],
+    [0x38, 0x44, 0x44, 0x44, 0x20],
+    [0x38, 0x44, 0x44, 0x48, 0x7F],
+    [0x38, 0x54, 0x54, 0x54, 0x18],
+    [0x08, 0x7E, 0x09, 0x01, 0x02],
+    [0x0C, 0x52, 0x52, 0x52, 0x3E],
+    [0x7F, 0x08, 0x04, 0x04, 0x78],
+    [0x00, 0x44, 0x7D, 0x40, 0x00],
+    [0x20, 0x40, 0x44, 0x3D, 0x00],
+    [0x7F, 0x10, 0x28, 0x44, 0x00],
+    [0x00, 0x41, 0x7F, 0x40, 0x00],
+    [0x7C, 0x04, 0x18, 0x04, 0x78],
+    [0x7C, 0x08, 0x04, 0x04, 0x78],
+    [0x38, 0x44, 0x44, 0x44, 0x38],
+    [0x7C, 0x14, 0x14, 0x14, 0x08],
+    [0x08, 0x14, 0x14, 0x18, 0x7C],
+    [0x7C, 0x08, 0x04, 0x04, 0x08],
+    [0x48, 0x54, 0x54, 0x54, 0x20],
+    [0x04, 0x3F, 0x44, 0x40, 0x20],
+    [0x3C, 0x40, 0x40, 0x20, 0x7C],
+    [0x1C, 0x20, 0x40, 0x20, 0x1C],
+    [0x3C, 0x40, 0x30, 0x40, 0x3C],
+    [0x44, 0x28, 0x10, 0x28, 0x44],
+    [0x0C, 0x50, 0x50, 0x50, 0x3C],
+    [0x44, 0x64, 0x54, 0x4C, 0x44],
+    [0x00, 0x08, 0x36, 0x41, 0x00],
+    [0x00, 0x00, 0x7F, 0x00, 0x00],
+    [0x00, 0x41, 0x36, 0x08, 0x00],
+    [0x08, 0x04, 0x08, 0x10, 0x08],
+];
+
+/// The glyph drawn for characters the font doesn't cover: a hollow box
+const MISSING_GLYPH: [u8; 5] = [0x7F, 0x41, 0x41, 0x41, 0x7F];
+
+/// How a cell is filled when rasterised with the bundled font
+enum CellShape {
+    /// A glyph from the font, drawn in the foreground colour
+    Glyph([u8; 5]),
+    /// The top and bottom halves filled with the foreground colour
+    Halves(bool, bool),
+    /// The whole cell filled with the foreground colour mixed into the background by the amount
+    Shade(f64),
+}
+
+impl CellShape {
+    const fn of(text_char: char) -> Self {
+        match text_char {
+            '█' => Self::Shade(1.0),
+            '▓' => Self::Shade(0.75),
+            '▒' => Self::Shade(0.5),
+            '░' => Self::Shade(0.25),
+            '▀' => Self::Halves(true, false),
+            '▄' => Self::Halves(false, true),
+            ' '..='~' => Self::Glyph(GLYPHS[text_char as usize - ' ' as usize]),
+            _ => Self::Glyph(MISSING_GLYPH),
+        }
+    }
+
+    /// Returns true if the pixel of the cell, in pixels at a scale of 1, is drawn in the foreground colour
+    fn is_lit(&self, x: usize, y: usize) -> bool {
+        match *self {
+            Self::Glyph(columns) => {
+                let Some(row) = y.checked_sub(GLYPH_TOP).filter(|row| *row < 7) else {
+                    return false;
+                };
+                columns.get(x).is_some_and(|column| column >> row & 1 == 1)
+            }
+            Self::Halves(top, bottom) => {
+                if y < FONT_CELL_HEIGHT / 2 {
+                    top
+                } else {
+                    bottom
+                }
+            }
+            Self::Shade(_) => true,
+        }
+    }
+}
+
+impl Bitmap {
+    /// Rasterise the `View` into a `Bitmap` using the engine's bundled 5x7 monospace font, drawing every cell as a block of [`FONT_CELL_WIDTH`] by [`FONT_CELL_HEIGHT`] pixels, multiplied by `scale`. Colours come from each cell's [`Modifier`](super::Modifier), with light grey text on black where it has none. Block and shade characters fill the cell, and characters outside printable ASCII are drawn as a hollow box
+    #[must_use]
+    pub fn from_view_with_font(view: &View, scale: usize) -> Self {
+        let scale = scale.max(1);
+        let (cell_width, cell_height) = (FONT_CELL_WIDTH * scale, FONT_CELL_HEIGHT * scale);
+        let width = view.width * cell_width;
+        let height = view.height * cell_height;
+        let mut pixels = vec![Colour::BLACK; width * height];
+
+        for y in 0..view.height {
+            for (x, cell) in view.render_row(y).into_iter().enumerate().take(view.width) {
+                let (foreground, background) = Self::font_colours(cell);
+                let shape = CellShape::of(cell.text_char);
+                let lit = match shape {
+                    CellShape::Shade(amount) => background.lerp(foreground, amount),
+                    _ => foreground,
+                };
+
+                for cy in 0..cell_height {
+                    let start = (y * cell_height + cy) * width + x * cell_width;
+                    for (cx, pixel) in pixels[start..start + cell_width].iter_mut().enumerate() {
+                        *pixel = if shape.is_lit(cx / scale, cy / scale) {
+                            lit
+                        } else {
+                            background
+                        };
+                    }
+                }
+            }
+        }
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Return the foreground and background colours of the cell, swapping them if it's reversed
+    fn font_colours(cell: ColChar) -> (Colour, Colour) {
+        let set = ModifierSet::new().merge(cell.modifier);
+        let foreground = set.foreground.unwrap_or(DEFAULT_TEXT);
+        let background = set.background.unwrap_or(Colour::BLACK);
+
+        if set.style.contains(TextStyle::REVERSE) {
+            (background, foreground)
+        } else {
+            (foreground, background)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{
+        view::{Modifier, Wrapping},
+        Vec2D,
+    };
+
+    #[test]
+    fn draws_glyphs_and_blocks() {
+        let mut view = View::new(2, 1, ColChar::EMPTY);
+        view.plot(
+            Vec2D::new(0, 0),
+            ColChar::new('I', Modifier::from_rgb(255, 0, 0)),
+            Wrapping::Panic,
+        );
+        view.plot(
+            Vec2D::new(1, 0),
+            ColChar::new('▄', Modifier::from_rgb(0, 0, 255)),
+            Wrapping::Panic,
+        );
+
+        let bitmap = Bitmap::from_view_with_font(&view, 2);
+        assert_eq!((bitmap.width, bitmap.height), (24, 24));
+        let at = |x: usize, y: usize| bitmap.pixels[y * bitmap.width + x];
+
+        // The stem of the I runs down the middle column
+        assert_eq!(at(4, 2), Colour::BLACK);
+        assert_eq!(at(4, 6), Colour::rgb(255, 0, 0));
+        assert_eq!(at(0, 6), Colour::BLACK);
+        assert_eq!(at(14, 4), Colour::BLACK);
+        assert_eq!(at(14, 20), Colour::rgb(0, 0, 255));
+    }
+}
@@ -0,0 +1,159 @@
+use super::{Bitmap, ColChar, Colour, Modifier, ModifierSet, Vec2D};
+use crate::elements::Pixel;
+
+/// The quarter block characters, indexed by which quarters are filled: 1 for the top left, 2 for the top right, 4 for the bottom left and 8 for the bottom right
+const QUARTER_BLOCKS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+/// How finely [`Bitmap::to_block_art()`] divides each terminal cell when converting an image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageQuality {
+    /// One image pixel per cell, drawn as `'█'`
+    Full,
+    /// Two image pixels per cell, stacked vertically as the halves of a `'▀'`
+    #[default]
+    HalfBlock,
+    /// Four image pixels per cell in a 2x2 grid, drawn with the quarter block characters such as `'▚'` and `'▟'`. Each cell can only show two colours, so the pair that best matches the four pixels is chosen
+    QuarterBlock,
+}
+
+impl ImageQuality {
+    /// Return how many image pixels wide and tall each cell covers
+    #[must_use]
+    pub const fn cell_size(self) -> Vec2D {
+        match self {
+            Self::Full => Vec2D::new(1, 1),
+            Self::HalfBlock => Vec2D::new(1, 2),
+            Self::QuarterBlock => Vec2D::new(2, 2),
+        }
+    }
+
+    /// Return the quarter patterns a cell can be drawn with. Patterns that are the inverse of another are left out, as swapping the colours draws the same thing
+    const fn patterns(self) -> &'static [usize] {
+        match self {
+            Self::Full => &[15],
+            Self::HalfBlock => &[15, 3],
+            Self::QuarterBlock => &[15, 1, 2, 3, 4, 5, 6, 7],
+        }
+    }
+}
+
+/// Return the average of the colours
+fn mean(colours: &[Colour]) -> Colour {
+    let count = colours.len().max(1) as u32;
+    let sum = colours.iter().fold([0u32; 3], |[r, g, b], c| {
+        [r + u32::from(c.r), g + u32::from(c.g), b + u32::from(c.b)]
+    });
+    let [r, g, b] = sum.map(|channel| (channel / count) as u8);
+
+    Colour::rgb(r, g, b)
+}
+
+/// Return the squared distance between two colours
+fn distance(a: Colour, b: Colour) -> u32 {
+    [(a.r, b.r), (a.g, b.g), (a.b, b.b)]
+        .into_iter()
+        .map(|(a, b)| u32::from(a.abs_diff(b)).pow(2))
+        .sum()
+}
+
+impl Bitmap {
+    /// Return the colour of the pixel, clamping the position to the edges of the `Bitmap`
+    fn clamped_pixel(&self, x: usize, y: usize) -> Colour {
+        let x = x.min(self.width.saturating_sub(1));
+        let y = y.min(self.height.saturating_sub(1));
+
+        self.pixels
+            .get(y * self.width + x)
+            .copied()
+            .unwrap_or(Colour::BLACK)
+    }
+
+    /// Convert the image into coloured block characters, with the top left cell at `pos`. Each cell covers the [`cell_size()`](ImageQuality::cell_size()) of the quality in image pixels, and is drawn with the block character and foreground and background colours that match those pixels most closely. Images whose size isn't a multiple of the cell size repeat their last row and column
+    #[must_use]
+    pub fn to_block_art(&self, pos: Vec2D, quality: ImageQuality) -> Vec<Pixel> {
+        let cell_size = quality.cell_size();
+        let (cell_width, cell_height) = (cell_size.x as usize, cell_size.y as usize);
+        let mut pixels = vec![];
+
+        for cy in 0..self.height.div_ceil(cell_height) {
+            for cx in 0..self.width.div_ceil(cell_width) {
+                // The quarters of the cell, in the order of the pattern bits
+                let quarters = [(0, 0), (1, 0), (0, 1), (1, 1)].map(|(qx, qy)| {
+                    self.clamped_pixel(
+                        cx * cell_width + qx * (cell_width - 1),
+                        cy * cell_height + qy * (cell_height - 1),
+                    )
+                });
+
+                let cell = Self::best_block(quarters, quality.patterns());
+                pixels.push(Pixel::new(pos + Vec2D::new(cx as isize, cy as isize), cell));
+            }
+        }
+
+        pixels
+    }
+
+    /// Return the block character and colours that best match the four quarters of a cell
+    fn best_block(quarters: [Colour; 4], patterns: &[usize]) -> ColChar {
+        let mut best = (u32::MAX, 15, Colour::BLACK, Colour::BLACK);
+        for &pattern in patterns {
+            let (mut lit, mut unlit) = (vec![], vec![]);
+            for (i, colour) in quarters.into_iter().enumerate() {
+                if pattern >> i & 1 == 1 {
+                    lit.push(colour);
+                } else {
+                    unlit.push(colour);
+                }
+            }
+            let (foreground, background) = (mean(&lit), mean(&unlit));
+
+            let error: u32 = lit.iter().map(|c| distance(*c, foreground)).sum::<u32>()
+                + unlit.iter().map(|c| distance(*c, background)).sum::<u32>();
+            if error < best.0 {
+                best = (error, pattern, foreground, background);
+            }
+        }
+
+        let (_, pattern, foreground, background) = best;
+        if pattern == 15 {
+            ColChar::new('█', Modifier::Colour(foreground))
+        } else {
+            ColChar::new(
+                QUARTER_BLOCKS[pattern],
+                ModifierSet::new()
+                    .with_foreground(foreground)
+                    .with_background(background)
+                    .into(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_the_closest_quarter_pattern() {
+        let (red, blue) = (Colour::rgb(255, 0, 0), Colour::rgb(0, 0, 255));
+        // A diagonal of red on blue, with a plain red column to the right
+        let image = Bitmap {
+            width: 3,
+            height: 2,
+            pixels: vec![red, blue, red, blue, red, red],
+        };
+
+        let cells = image.to_block_art(Vec2D::new(1, 1), ImageQuality::QuarterBlock);
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells[0].pos, Vec2D::new(1, 1));
+        assert_eq!(cells[0].fill_char.text_char, '▞');
+        assert_eq!(cells[0].fill_char.modifier.colour(), Some(blue));
+        assert_eq!(cells[1].fill_char, ColChar::new('█', Modifier::Colour(red)));
+
+        let halves = image.to_block_art(Vec2D::ZERO, ImageQuality::HalfBlock);
+        assert_eq!(halves.len(), 3);
+        assert_eq!(halves[0].fill_char.text_char, '▀');
+    }
+}
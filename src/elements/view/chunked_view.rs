@@ -0,0 +1,163 @@
+use std::{collections::HashMap, io};
+
+use super::{ColChar, Vec2D, View, ViewElement, Wrapping};
+
+/// A `ChunkedView` is a canvas for worlds far larger than the terminal, such as a 10,000x10,000 cell map. The canvas is split into square chunks which are only allocated once something is plotted to them, and only the window seen by the [`camera`](ChunkedView::camera) is rendered
+#[derive(Debug, Clone)]
+pub struct ChunkedView {
+    /// The width of the whole canvas
+    pub width: usize,
+    /// The height of the whole canvas
+    pub height: usize,
+    /// The character that empty cells are filled with
+    pub background_char: ColChar,
+    /// The position of the top left corner of the rendered window on the canvas
+    pub camera: Vec2D,
+    /// The size of the rendered window
+    pub window_size: Vec2D,
+    chunks: HashMap<Vec2D, Vec<ColChar>>,
+}
+
+impl ChunkedView {
+    /// The width and height of each chunk
+    pub const CHUNK_SIZE: isize = 64;
+
+    /// Create a new `ChunkedView` with the size of the whole canvas, the size of the rendered window and a background character. The camera starts at the top left corner of the canvas
+    #[must_use]
+    pub fn new(width: usize, height: usize, window_size: Vec2D, background_char: ColChar) -> Self {
+        Self {
+            width,
+            height,
+            background_char,
+            camera: Vec2D::ZERO,
+            window_size,
+            chunks: HashMap::new(),
+        }
+    }
+
+    /// Return the width and height of the whole canvas as a [`Vec2D`]
+    #[must_use]
+    pub const fn size(&self) -> Vec2D {
+        Vec2D::new(self.width as isize, self.height as isize)
+    }
+
+    /// Return the number of chunks that have been allocated
+    #[must_use]
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Return the chunk coordinates and the index within the chunk for an in-bounds position
+    fn locate(pos: Vec2D) -> (Vec2D, usize) {
+        let chunk = Vec2D::new(
+            pos.x.div_euclid(Self::CHUNK_SIZE),
+            pos.y.div_euclid(Self::CHUNK_SIZE),
+        );
+        let local = pos - chunk * Self::CHUNK_SIZE;
+
+        (chunk, (local.y * Self::CHUNK_SIZE + local.x) as usize)
+    }
+
+    /// Clear the `ChunkedView`, freeing every chunk
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+    }
+
+    /// Plot a pixel to the `ChunkedView`, allocating its chunk if necessary. Accepts a [`Vec2D`] (the position of the pixel on the whole canvas), [`ColChar`] and a [`Wrapping`] enum variant
+    pub fn plot(&mut self, pos: Vec2D, c: ColChar, wrapping: Wrapping) {
+        if let Some(wrapped_pos) = wrapping.handle_bounds(pos, self.size()) {
+            let (chunk, i) = Self::locate(wrapped_pos);
+            let background_char = self.background_char;
+            self.chunks.entry(chunk).or_insert_with(|| {
+                vec![background_char; (Self::CHUNK_SIZE * Self::CHUNK_SIZE) as usize]
+            })[i] = c;
+        }
+    }
+
+    /// Return the [`ColChar`] at the position on the whole canvas, or `None` if the position is out of bounds
+    #[must_use]
+    pub fn get(&self, pos: Vec2D) -> Option<ColChar> {
+        let size = self.size();
+        if pos.x < 0 || pos.y < 0 || pos.x >= size.x || pos.y >= size.y {
+            return None;
+        }
+
+        let (chunk, i) = Self::locate(pos);
+        Some(
+            self.chunks
+                .get(&chunk)
+                .map_or(self.background_char, |cells| cells[i]),
+        )
+    }
+
+    /// Blit a struct implementing [`ViewElement`] to the `ChunkedView`
+    pub fn blit(&mut self, element: &impl ViewElement, wrapping: Wrapping) {
+        for pixel in element.active_pixels() {
+            self.plot(pixel.pos, pixel.fill_char, wrapping);
+        }
+    }
+
+    /// Move the camera so that the target is at the centre of the window, without showing anything beyond the edges of the canvas
+    pub fn follow(&mut self, target: Vec2D) {
+        let max = self.size() - self.window_size;
+        let camera = target - self.window_size / 2;
+
+        self.camera = Vec2D::new(camera.x.min(max.x).max(0), camera.y.min(max.y).max(0));
+    }
+
+    /// Return a [`View`] of the window seen by the camera. Only the chunks inside the window are read
+    #[must_use]
+    pub fn window(&self) -> View {
+        let mut view = View::new(
+            self.window_size.x.max(0) as usize,
+            self.window_size.y.max(0) as usize,
+            self.background_char,
+        );
+
+        for y in 0..self.window_size.y {
+            for x in 0..self.window_size.x {
+                let local = Vec2D::new(x, y);
+                if let Some(c) = self.get(self.camera + local) {
+                    view.plot(local, c, Wrapping::Ignore);
+                }
+            }
+        }
+
+        view
+    }
+
+    /// Display the window seen by the camera. See [`View::display_render()`]
+    ///
+    /// # Errors
+    /// Returns the `Result` from writing to `io::stdout().lock()`
+    pub fn display_render(&self) -> io::Result<()> {
+        self.window().display_render()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_touched_chunks_are_allocated() {
+        let mut view = ChunkedView::new(10_000, 10_000, Vec2D::new(20, 10), ColChar::EMPTY);
+        view.plot(Vec2D::new(9_000, 9_000), ColChar::SOLID, Wrapping::Panic);
+        view.plot(Vec2D::new(9_001, 9_000), ColChar::SOLID, Wrapping::Panic);
+
+        assert_eq!(view.chunk_count(), 1);
+        assert_eq!(view.get(Vec2D::new(9_000, 9_000)), Some(ColChar::SOLID));
+        assert_eq!(view.get(Vec2D::new(0, 0)), Some(ColChar::EMPTY));
+        assert_eq!(view.get(Vec2D::new(10_000, 0)), None);
+    }
+
+    #[test]
+    fn follow_clamps_camera_to_canvas() {
+        let mut view = ChunkedView::new(100, 50, Vec2D::new(20, 10), ColChar::EMPTY);
+        view.follow(Vec2D::new(50, 25));
+        assert_eq!(view.camera, Vec2D::new(40, 20));
+
+        view.follow(Vec2D::new(99, 0));
+        assert_eq!(view.camera, Vec2D::new(80, 0));
+    }
+}
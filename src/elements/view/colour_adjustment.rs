@@ -0,0 +1,136 @@
+use super::{ColChar, Colour, Modifier};
+
+/// Global colour settings applied to every RGB [`Colour`] as the [`View`](super::View) is rendered, leaving the pixels themselves unchanged. Different terminals and lighting make games look darker or more washed out than intended, so these are best exposed in an options menu. Coded ANSI colours are left to the terminal's own palette
+/// ```
+/// use gemini_engine::elements::view::{ColourAdjustment, Colour};
+///
+/// let adjustment = ColourAdjustment::default().with_brightness(0.2).with_saturation(0.0);
+/// let adjusted = adjustment.apply_to_colour(Colour::rgb(200, 50, 50));
+///
+/// assert_eq!(adjusted.r, adjusted.g);
+/// assert!(ColourAdjustment::default().is_neutral());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColourAdjustment {
+    /// Values above 1.0 brighten mid-tones and values below 1.0 darken them, without changing black or white
+    pub gamma: f64,
+    /// Added to every channel, where 1.0 is the full range from black to white
+    pub brightness: f64,
+    /// Values above 1.0 push channels away from mid-grey and values below 1.0 pull them towards it
+    pub contrast: f64,
+    /// 0.0 turns every colour grey, 1.0 leaves it unchanged and higher values make colours more vivid
+    pub saturation: f64,
+}
+
+impl ColourAdjustment {
+    /// A `ColourAdjustment` that leaves every colour unchanged
+    pub const NEUTRAL: Self = Self {
+        gamma: 1.0,
+        brightness: 0.0,
+        contrast: 1.0,
+        saturation: 1.0,
+    };
+
+    /// Return the `ColourAdjustment` with its [`gamma`](ColourAdjustment::gamma) field set to the chosen value
+    #[must_use]
+    pub const fn with_gamma(mut self, gamma: f64) -> Self {
+        self.gamma = gamma;
+        self
+    }
+
+    /// Return the `ColourAdjustment` with its [`brightness`](ColourAdjustment::brightness) field set to the chosen value
+    #[must_use]
+    pub const fn with_brightness(mut self, brightness: f64) -> Self {
+        self.brightness = brightness;
+        self
+    }
+
+    /// Return the `ColourAdjustment` with its [`contrast`](ColourAdjustment::contrast) field set to the chosen value
+    #[must_use]
+    pub const fn with_contrast(mut self, contrast: f64) -> Self {
+        self.contrast = contrast;
+        self
+    }
+
+    /// Return the `ColourAdjustment` with its [`saturation`](ColourAdjustment::saturation) field set to the chosen value
+    #[must_use]
+    pub const fn with_saturation(mut self, saturation: f64) -> Self {
+        self.saturation = saturation;
+        self
+    }
+
+    /// Returns true if the `ColourAdjustment` leaves every colour unchanged, so the render can skip it
+    #[must_use]
+    pub fn is_neutral(&self) -> bool {
+        *self == Self::NEUTRAL
+    }
+
+    /// Return the `Colour` with the gamma, brightness, contrast and saturation adjustments applied in that order
+    #[must_use]
+    pub fn apply_to_colour(&self, colour: Colour) -> Colour {
+        let gamma = if self.gamma > 0.0 { self.gamma } else { 1.0 };
+        let [r, g, b] = [colour.r, colour.g, colour.b].map(|channel| {
+            let value = (f64::from(channel) / 255.0).powf(1.0 / gamma);
+            (value - 0.5).mul_add(self.contrast, 0.5) + self.brightness
+        });
+
+        let grey = 0.2126f64.mul_add(r, 0.7152f64.mul_add(g, 0.0722 * b));
+        let [r, g, b] = [r, g, b].map(|value| {
+            let value = (value - grey).mul_add(self.saturation, grey);
+            (value.clamp(0.0, 1.0) * 255.0).round() as u8
+        });
+
+        Colour::rgb(r, g, b)
+    }
+
+    /// Return the `ColChar` with its colour adjusted, if its [`Modifier`] is a [`Modifier::Colour`], [`Modifier::Background`] or a [`Modifier::Set`] with colours
+    #[must_use]
+    pub fn apply(&self, colchar: ColChar) -> ColChar {
+        match colchar.modifier {
+            Modifier::Colour(colour) => colchar.with_colour(self.apply_to_colour(colour)),
+            Modifier::Background(colour) => colchar.with_bg_colour(self.apply_to_colour(colour)),
+            Modifier::Set(mut set) => {
+                set.foreground = set.foreground.map(|c| self.apply_to_colour(c));
+                set.background = set.background.map(|c| self.apply_to_colour(c));
+                colchar.with_mod(Modifier::Set(set))
+            }
+            _ => colchar,
+        }
+    }
+}
+
+impl Default for ColourAdjustment {
+    fn default() -> Self {
+        Self::NEUTRAL
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neutral_leaves_colours_unchanged() {
+        let colour = Colour::rgb(12, 130, 250);
+        assert_eq!(ColourAdjustment::NEUTRAL.apply_to_colour(colour), colour);
+
+        let coded = ColChar::SOLID.with_mod(Modifier::RED);
+        let adjustment = ColourAdjustment::default().with_brightness(0.5);
+        assert_eq!(adjustment.apply(coded), coded);
+    }
+
+    #[test]
+    fn adjusts_colours() {
+        let grey = Colour::greyscale(128);
+        let brighter = ColourAdjustment::default().with_gamma(2.0);
+        assert!(brighter.apply_to_colour(grey).r > 128);
+        assert_eq!(brighter.apply_to_colour(Colour::WHITE), Colour::WHITE);
+
+        let flat = ColourAdjustment::default().with_contrast(0.0);
+        assert_eq!(flat.apply_to_colour(Colour::BLACK), Colour::greyscale(128));
+
+        let vivid = ColourAdjustment::default().with_saturation(2.0);
+        let colour = vivid.apply_to_colour(Colour::rgb(150, 100, 100));
+        assert!(colour.r > 150 && colour.g < 100);
+    }
+}
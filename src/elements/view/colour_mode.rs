@@ -0,0 +1,229 @@
+use super::{ColChar, Colour, Dithering, Modifier, Palette, Pixel};
+use crate::EngineError;
+use std::{
+    env,
+    fmt::{self, Write},
+    str::FromStr,
+};
+
+/// How many colours the terminal can show, detected with [`ColourMode::detect()`] or chosen with `--color-mode` in [`EngineArgs`](crate::gameloop::EngineArgs)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColourMode {
+    /// Full 24-bit colour, passed straight through. Accepted as `truecolor` or `24bit`
+    #[default]
+    TrueColour,
+    /// The 256 xterm colours. Accepted as `256`
+    Xterm256,
+    /// The 16 standard ANSI colours. Accepted as `16`
+    Ansi16,
+    /// No colour at all, for terminals or logs that can't show it. Accepted as `none`
+    None,
+}
+
+impl ColourMode {
+    /// Detect how many colours the terminal supports from the `NO_COLOR`, `COLORTERM` and `TERM` environment variables. Read [`ColourMode::detect_from()`] for how they are interpreted
+    #[must_use]
+    pub fn detect() -> Self {
+        if env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+            return Self::None;
+        }
+
+        Self::detect_from(
+            env::var("COLORTERM").ok().as_deref(),
+            env::var("TERM").ok().as_deref(),
+        )
+    }
+
+    /// Work out the colour mode from the values of the `COLORTERM` and `TERM` environment variables. `COLORTERM=truecolor` (or `24bit`) and `TERM`s ending in `-direct` mean true colour, `TERM`s containing `256color` mean 256 colours and `TERM=dumb` means no colour. Other `TERM`s fall back to the 16 ANSI colours, while a missing `TERM`, as on Windows, is assumed to support true colour
+    #[must_use]
+    pub fn detect_from(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            return Self::TrueColour;
+        }
+
+        match term {
+            None => Self::TrueColour,
+            Some("dumb") => Self::None,
+            Some(term) if term.ends_with("-direct") => Self::TrueColour,
+            Some(term) if term.contains("256color") => Self::Xterm256,
+            Some(_) => Self::Ansi16,
+        }
+    }
+
+    /// Return the [`Palette`] colours should be quantised to in this mode, or `None` if colours are left as they are or removed entirely. The palettes are built once and shared
+    #[must_use]
+    pub fn palette(self) -> Option<&'static Palette> {
+        match self {
+            Self::Xterm256 => Some(Palette::shared_xterm256()),
+            Self::Ansi16 => Some(Palette::shared_ansi16()),
+            Self::TrueColour | Self::None => None,
+        }
+    }
+
+    /// Return the pixels as they should be drawn in this mode: quantised to the mode's [`palette()`](ColourMode::palette()), or stripped of their modifiers in [`ColourMode::None`]
+    #[must_use]
+    pub fn apply_to(self, pixels: &[Pixel]) -> Vec<Pixel> {
+        match (self, self.palette()) {
+            (Self::None, _) => pixels
+                .iter()
+                .map(|pixel| {
+                    Pixel::new(
+                        pixel.pos,
+                        ColChar::new(pixel.fill_char.text_char, Modifier::None),
+                    )
+                })
+                .collect(),
+            (_, Some(palette)) => palette.quantise_pixels(pixels, Dithering::None),
+            (_, None) => pixels.to_vec(),
+        }
+    }
+
+    /// Return the escape sequence that displays the [`Modifier`] in this mode, as written by [`write_escape_sequence()`](ColourMode::write_escape_sequence())
+    #[must_use]
+    pub fn escape_sequence(self, modifier: Modifier) -> String {
+        let mut sequence = String::new();
+        let _ = self.write_escape_sequence(&mut sequence, modifier);
+        sequence
+    }
+
+    /// Write the escape sequence that displays the [`Modifier`] in this mode. Colours are converted to the nearest 256-colour or 16-colour code where needed, and nothing is written in [`ColourMode::None`]
+    ///
+    /// # Errors
+    /// Returns an error if writing to `f` fails
+    pub fn write_escape_sequence(self, f: &mut impl Write, modifier: Modifier) -> fmt::Result {
+        match (self, modifier) {
+            (Self::None, _) | (_, Modifier::None) => Ok(()),
+            (Self::TrueColour, _) | (_, Modifier::Coded(_)) => write!(f, "{modifier}"),
+            (_, Modifier::Colour(colour)) => {
+                f.write_str("\x1b[")?;
+                self.write_colour_params(f, colour, false)?;
+                f.write_char('m')
+            }
+            (_, Modifier::Background(colour)) => {
+                f.write_str("\x1b[")?;
+                self.write_colour_params(f, colour, true)?;
+                f.write_char('m')
+            }
+            (_, Modifier::Set(set)) if set.is_empty() => Ok(()),
+            (_, Modifier::Set(set)) => {
+                f.write_str("\x1b[")?;
+                let mut separator = "";
+                for code in set.style.codes() {
+                    write!(f, "{separator}{code}")?;
+                    separator = ";";
+                }
+                for (colour, background) in [(set.foreground, false), (set.background, true)] {
+                    if let Some(colour) = colour {
+                        f.write_str(separator)?;
+                        self.write_colour_params(f, colour, background)?;
+                        separator = ";";
+                    }
+                }
+                f.write_char('m')
+            }
+        }
+    }
+
+    /// Write the SGR parameters for the colour in this mode, as either a text or background colour
+    fn write_colour_params(
+        self,
+        f: &mut impl Write,
+        colour: Colour,
+        background: bool,
+    ) -> fmt::Result {
+        let index = self
+            .palette()
+            .and_then(|palette| palette.nearest_index(colour));
+
+        match (self, index) {
+            (Self::Ansi16, Some(index)) => {
+                let base = if background { 40 } else { 30 };
+                let code = if index < 8 {
+                    base + index
+                } else {
+                    base + 60 + index - 8
+                };
+                write!(f, "{code}")
+            }
+            (Self::Xterm256, Some(index)) => {
+                write!(f, "{};5;{index}", if background { 48 } else { 38 })
+            }
+            _ => write!(
+                f,
+                "{};2;{};{};{}",
+                if background { 48 } else { 38 },
+                colour.r,
+                colour.g,
+                colour.b
+            ),
+        }
+    }
+}
+
+impl FromStr for ColourMode {
+    type Err = EngineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "truecolor" | "truecolour" | "24bit" => Ok(Self::TrueColour),
+            "256" => Ok(Self::Xterm256),
+            "16" => Ok(Self::Ansi16),
+            "none" => Ok(Self::None),
+            _ => Err(EngineError::InvalidData(format!(
+                "Unknown colour mode {s}, expected truecolor, 256, 16 or none"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::Vec2D;
+
+    #[test]
+    fn colour_modes_change_pixels() {
+        let pixels = [Pixel::new(
+            Vec2D::ZERO,
+            ColChar::SOLID.with_colour(Colour::rgb(250, 10, 10)),
+        )];
+
+        assert_eq!(ColourMode::TrueColour.apply_to(&pixels), pixels);
+        assert_eq!(
+            ColourMode::None.apply_to(&pixels)[0].fill_char,
+            ColChar::SOLID
+        );
+        assert_eq!(
+            ColourMode::Ansi16.apply_to(&pixels)[0].fill_char.modifier,
+            Modifier::Colour(Palette::ansi16().nearest(Colour::rgb(250, 10, 10)))
+        );
+    }
+
+    #[test]
+    fn detects_colour_mode_from_environment() {
+        let detect = ColourMode::detect_from;
+        assert_eq!(
+            detect(Some("truecolor"), Some("xterm")),
+            ColourMode::TrueColour
+        );
+        assert_eq!(detect(None, Some("xterm-256color")), ColourMode::Xterm256);
+        assert_eq!(detect(None, Some("xterm-direct")), ColourMode::TrueColour);
+        assert_eq!(detect(None, Some("linux")), ColourMode::Ansi16);
+        assert_eq!(detect(None, Some("dumb")), ColourMode::None);
+        assert_eq!(detect(None, None), ColourMode::TrueColour);
+    }
+
+    #[test]
+    fn escape_sequences_fall_back_to_fewer_colours() {
+        let red = Modifier::from_rgb(250, 10, 10);
+        assert_eq!(ColourMode::TrueColour.escape_sequence(red), red.to_string());
+        assert_eq!(ColourMode::Xterm256.escape_sequence(red), "\x1b[38;5;9m");
+        assert_eq!(ColourMode::Ansi16.escape_sequence(red), "\x1b[91m");
+        assert_eq!(
+            ColourMode::Ansi16.escape_sequence(Modifier::from_bg_rgb(0, 0, 230)),
+            "\x1b[44m"
+        );
+        assert_eq!(ColourMode::None.escape_sequence(red), "");
+        assert_eq!(ColourMode::Ansi16.escape_sequence(Modifier::END), "\x1b[0m");
+    }
+}
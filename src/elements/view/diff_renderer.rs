@@ -0,0 +1,196 @@
+use std::{
+    fmt::Write as _,
+    io::{self, Write},
+};
+
+use super::{ColChar, ColourMode, Modifier, View};
+use crate::utils::{self as crate_utils, is_wide};
+
+/// `DiffRenderer` displays a [`View`] by only redrawing the cells that changed since the last frame it displayed, instead of the whole canvas. This stops flickering and saves a lot of bandwidth over slow connections like SSH. Keep one `DiffRenderer` for the whole game and call [`display()`](DiffRenderer::display()) in place of [`View::display_render()`]
+/// ```
+/// use gemini_engine::elements::{view::{ColChar, DiffRenderer, Wrapping}, Vec2D, View};
+///
+/// let mut view = View::new(20, 5, ColChar::EMPTY);
+/// let mut renderer = DiffRenderer::new();
+///
+/// // The first frame is drawn in full
+/// let first = renderer.render(&view);
+///
+/// view.plot(Vec2D::new(3, 2), ColChar::SOLID, Wrapping::Panic);
+/// let second = renderer.render(&view);
+///
+/// // Only the changed cell is redrawn, after moving the cursor to it
+/// assert_eq!(second, "\x1b[3;4H█");
+/// assert!(second.len() < first.len());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DiffRenderer {
+    /// The rows of the last frame as they were printed, and whether coordinate numbers were shown
+    previous: Option<(Vec<Vec<ColChar>>, bool)>,
+}
+
+impl DiffRenderer {
+    /// Create a new `DiffRenderer`. The first frame it renders is drawn in full
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { previous: None }
+    }
+
+    /// Forget the last frame so that the next one is drawn in full, such as after the terminal is resized or something else has been printed over the game
+    pub fn invalidate(&mut self) {
+        self.previous = None;
+    }
+
+    /// Return the escape sequences and characters that update the terminal from the last rendered frame to the `View`, and remember the `View` as the last frame. If there is no last frame or the `View` has changed size, the whole screen is cleared and redrawn
+    pub fn render(&mut self, view: &View) -> String {
+        engine_span!("diff_render");
+        let rows: Vec<Vec<ColChar>> = (0..view.height).map(|y| view.render_row(y)).collect();
+        let numbers = view.coord_numbers_in_render;
+        let offset = usize::from(numbers);
+        let mut output = String::new();
+
+        match &self.previous {
+            Some((previous, had_numbers))
+                if *had_numbers == numbers
+                    && previous.len() == rows.len()
+                    && previous.first().map(Vec::len) == rows.first().map(Vec::len) =>
+            {
+                for (y, (row, old)) in rows.iter().zip(previous).enumerate() {
+                    if row == old {
+                        continue;
+                    }
+
+                    if row.len() != old.len() || row.iter().chain(old).any(|c| is_wide(c.text_char))
+                    {
+                        // Cells after a wide glyph don't line up with columns, so redraw the whole row
+                        write_run(&mut output, view.colour_mode, y + offset, offset, row);
+                        continue;
+                    }
+
+                    let mut x = 0;
+                    while x < row.len() {
+                        if row[x] == old[x] {
+                            x += 1;
+                            continue;
+                        }
+                        let start = x;
+                        while x < row.len() && row[x] != old[x] {
+                            x += 1;
+                        }
+                        write_run(
+                            &mut output,
+                            view.colour_mode,
+                            y + offset,
+                            start + offset,
+                            &row[start..x],
+                        );
+                    }
+                }
+            }
+            _ => {
+                output.push_str("\x1b[H\x1b[J");
+                if numbers {
+                    let nums: String = (0..view.width)
+                        .map(|i| i.to_string().chars().last().unwrap_or(' '))
+                        .collect();
+                    let _ = write!(output, " {nums}");
+                }
+                for (y, row) in rows.iter().enumerate() {
+                    if numbers {
+                        let num = y.to_string().chars().last().unwrap_or(' ');
+                        let _ = write!(output, "\x1b[{};1H{num}", y + offset + 1);
+                    }
+                    write_run(&mut output, view.colour_mode, y + offset, offset, row);
+                }
+            }
+        }
+
+        self.previous = Some((rows, numbers));
+        output
+    }
+
+    /// Display the `View`, redrawing only the cells that changed since the last frame. If the `View`'s [`block_until_resized`](View::block_until_resized) is set, this blocks until the terminal is big enough
+    ///
+    /// # Errors
+    /// Returns the `Result` from writing to `io::stdout().lock()`
+    pub fn display(&mut self, view: &View) -> io::Result<()> {
+        if view.block_until_resized {
+            crate_utils::block_until_resized(view.size());
+        }
+
+        let first_frame = self.previous.is_none();
+        let frame = self.render(view);
+        crate_utils::write_synchronized(&mut io::stdout().lock(), |stdout| {
+            if first_frame {
+                // The first frame goes through the View's own render, which makes room for it in the terminal
+                write!(stdout, "{view}")
+            } else {
+                stdout.write_all(frame.as_bytes())
+            }
+        })
+    }
+}
+
+/// Write a run of cells starting at the zero-based row and column, moving the cursor there first and resetting the modifier at the end. Modifiers are converted to the [`ColourMode`]
+fn write_run(
+    output: &mut String,
+    colour_mode: ColourMode,
+    row: usize,
+    column: usize,
+    cells: &[ColChar],
+) {
+    let _ = write!(output, "\x1b[{};{}H", row + 1, column + 1);
+
+    let mut current = Modifier::None;
+    for cell in cells {
+        if cell.modifier != current {
+            if current != Modifier::None {
+                let _ = colour_mode.write_escape_sequence(output, Modifier::END);
+            }
+            let _ = colour_mode.write_escape_sequence(output, cell.modifier);
+            current = cell.modifier;
+        }
+        output.push(cell.printable_char());
+    }
+
+    if current != Modifier::None {
+        let _ = colour_mode.write_escape_sequence(output, Modifier::END);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{view::Wrapping, Vec2D};
+
+    #[test]
+    fn redraws_only_changed_runs() {
+        let mut view = View::new(6, 2, ColChar::EMPTY);
+        let mut renderer = DiffRenderer::new();
+        assert!(renderer.render(&view).starts_with("\x1b[H\x1b[J"));
+        assert_eq!(renderer.render(&view), "");
+
+        let red = ColChar::SOLID.with_mod(Modifier::RED);
+        view.plot(Vec2D::new(1, 1), red, Wrapping::Panic);
+        view.plot(Vec2D::new(2, 1), red, Wrapping::Panic);
+        view.plot(Vec2D::new(5, 0), ColChar::SOLID, Wrapping::Panic);
+        assert_eq!(
+            renderer.render(&view),
+            "\x1b[1;6H█\x1b[2;2H\x1b[31m██\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn full_redraw_when_size_changes() {
+        let mut renderer = DiffRenderer::new();
+        renderer.render(&View::new(4, 2, ColChar::EMPTY));
+        assert!(renderer
+            .render(&View::new(5, 2, ColChar::EMPTY))
+            .starts_with("\x1b[H\x1b[J"));
+
+        renderer.invalidate();
+        assert!(renderer
+            .render(&View::new(5, 2, ColChar::EMPTY))
+            .starts_with("\x1b[H\x1b[J"));
+    }
+}
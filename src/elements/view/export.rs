@@ -0,0 +1,162 @@
+use super::{Colour, Modifier, ModifierSet, TextStyle, View};
+use std::{fmt::Write, fs, io, path::Path};
+
+/// The colour of text without a colour `Modifier` when a `View` is exported as HTML or drawn into a [`Bitmap`](super::Bitmap)
+pub const DEFAULT_TEXT: Colour = Colour::rgb(204, 204, 204);
+
+/// Return the CSS for a run of characters with the [`Modifier`]. Colours and styles are merged into a [`ModifierSet`] first, so every kind of `Modifier` is drawn the same way
+fn span_style(modifier: Modifier) -> String {
+    let set = ModifierSet::new().merge(modifier);
+    let mut foreground = set.foreground.unwrap_or(DEFAULT_TEXT);
+    let mut background = set.background;
+    if set.style.contains(TextStyle::REVERSE) {
+        background = Some(foreground);
+        foreground = set.background.unwrap_or(Colour::BLACK);
+    }
+
+    let mut style = format!("color:{foreground}");
+    if let Some(background) = background {
+        let _ = write!(style, ";background:{background}");
+    }
+    if set.style.contains(TextStyle::BOLD) {
+        style.push_str(";font-weight:bold");
+    }
+    if set.style.contains(TextStyle::DIM) {
+        style.push_str(";opacity:0.6");
+    }
+    if set.style.contains(TextStyle::ITALIC) {
+        style.push_str(";font-style:italic");
+    }
+    let lines: Vec<&str> = [
+        (TextStyle::UNDERLINE, "underline"),
+        (TextStyle::STRIKETHROUGH, "line-through"),
+    ]
+    .into_iter()
+    .filter(|(line, _)| set.style.contains(*line))
+    .map(|(_, name)| name)
+    .collect();
+    if !lines.is_empty() {
+        let _ = write!(style, ";text-decoration:{}", lines.join(" "));
+    }
+
+    style
+}
+
+impl View {
+    /// Return the `View` as a standalone HTML page, holding the [`to_html_block()`](View::to_html_block()) on a black background
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"></head>\n<body style=\"background:#000\">\n{}\n</body>\n</html>\n",
+            self.to_html_block()
+        )
+    }
+
+    /// Return the `View` as a `<pre>` block to embed in a web page, such as a blog post or documentation, with each run of same-styled characters wrapped in a `<span>`. Foreground and background colours, bold, dim, italic, underline, strikethrough and reversed colours are kept, while modifiers without a colour are drawn in a light grey. Cells are rendered like the terminal output, with the [`glyph_policy`](View::glyph_policy) and [`colour_adjustment`](View::colour_adjustment) applied and control characters replaced by whitespace
+    /// ```
+    /// use gemini_engine::elements::{view::{ColChar, Modifier, Wrapping}, Vec2D, View};
+    ///
+    /// let mut view = View::new(2, 1, ColChar::EMPTY);
+    /// view.plot(Vec2D::new(1, 0), ColChar::new('!', Modifier::from_rgb(255, 128, 0)), Wrapping::Panic);
+    ///
+    /// assert_eq!(
+    ///     view.to_html_block(),
+    ///     "<pre style=\"font-family:monospace;line-height:1.2\"><span style=\"color:#cccccc\"> </span><span style=\"color:#ff8000\">!</span>\n</pre>"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_html_block(&self) -> String {
+        let mut html = String::from("<pre style=\"font-family:monospace;line-height:1.2\">");
+
+        for y in 0..self.height {
+            let mut current: Option<String> = None;
+            for pixel in self.render_row(y) {
+                let style = span_style(pixel.modifier);
+                if current.as_ref() != Some(&style) {
+                    if current.is_some() {
+                        html.push_str("</span>");
+                    }
+                    let _ = write!(html, "<span style=\"{style}\">");
+                    current = Some(style);
+                }
+                match pixel.printable_char() {
+                    '&' => html.push_str("&amp;"),
+                    '<' => html.push_str("&lt;"),
+                    '>' => html.push_str("&gt;"),
+                    text_char => html.push(text_char),
+                }
+            }
+            if current.is_some() {
+                html.push_str("</span>");
+            }
+            html.push('\n');
+        }
+
+        html.push_str("</pre>");
+        html
+    }
+
+    /// Write the `View` to an HTML file as returned by [`to_html()`](View::to_html()), replacing the file if it already exists
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be written to
+    pub fn export_html(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_html())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{
+        view::{ColChar, ColourAdjustment, Wrapping},
+        Vec2D,
+    };
+
+    #[test]
+    fn exports_coloured_spans() {
+        let mut view = View::new(3, 1, ColChar::EMPTY);
+        view.plot(
+            Vec2D::new(1, 0),
+            ColChar::new('<', Modifier::RED),
+            Wrapping::Panic,
+        );
+
+        let html = view.to_html();
+        assert!(html.contains(
+            "<span style=\"color:#cccccc\"> </span><span style=\"color:#cd0000\">&lt;</span><span style=\"color:#cccccc\"> </span>\n"
+        ));
+    }
+
+    #[test]
+    fn exports_backgrounds_and_styles() {
+        let set = ModifierSet::new()
+            .with_background(Colour::rgb(0, 0, 255))
+            .with_style(TextStyle::BOLD | TextStyle::UNDERLINE | TextStyle::STRIKETHROUGH);
+        assert_eq!(
+            span_style(set.into()),
+            "color:#cccccc;background:#0000ff;font-weight:bold;text-decoration:underline line-through"
+        );
+        assert_eq!(
+            span_style(Modifier::Coded(7)),
+            "color:#000000;background:#cccccc"
+        );
+    }
+
+    #[test]
+    fn exports_rendered_cells() {
+        let mut view = View::new(2, 1, ColChar::EMPTY).with_colour_adjustment(ColourAdjustment {
+            brightness: -1.0,
+            ..ColourAdjustment::NEUTRAL
+        });
+        view.plot(
+            Vec2D::new(0, 0),
+            ColChar::new('\x07', Modifier::from_rgb(255, 0, 0)),
+            Wrapping::Panic,
+        );
+
+        let html = view.to_html();
+        assert!(html.contains("<span style=\"color:#000000\"> </span>"));
+        assert!(!html.contains('\x07'));
+    }
+}
@@ -0,0 +1,124 @@
+use super::{ColChar, Pixel, Vec2D, View, ViewElement, Wrapping};
+
+/// A `FixedView` is a canvas with a size fixed at compile time, stored in an array rather than a `Vec` so it never allocates after creation. It suits small overlays such as HUDs and minimaps, which are drawn to on their own and then blit to the main [`View`] at [`pos`](FixedView::pos) like any other [`ViewElement`]
+///
+/// Every cell is drawn when blit, apart from cells holding [`ColChar::VOID`], so a `FixedView` with a `VOID` background is a transparent overlay
+/// ```
+/// use gemini_engine::elements::{view::{ColChar, FixedView, Modifier, Wrapping}, Text, Vec2D, View};
+///
+/// let mut hud: FixedView<8, 1> = FixedView::new(Vec2D::new(1, 0), ColChar::VOID);
+/// hud.blit(&Text::new(Vec2D::ZERO, "HP 10", Modifier::None), Wrapping::Ignore);
+///
+/// let mut view = View::new(12, 4, ColChar::EMPTY);
+/// view.blit(&hud, Wrapping::Ignore);
+/// assert_eq!(view.get(Vec2D::new(1, 0)).map(|c| c.text_char), Some('H'));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedView<const W: usize, const H: usize> {
+    /// The position of the `FixedView`'s top left corner when blit to another `View`
+    pub pos: Vec2D,
+    /// The character that the `FixedView` is filled with on clear
+    pub background_char: ColChar,
+    pixels: [[ColChar; W]; H],
+}
+
+impl<const W: usize, const H: usize> FixedView<W, H> {
+    /// Create a new `FixedView` at the position, filled with the background character
+    #[must_use]
+    pub const fn new(pos: Vec2D, background_char: ColChar) -> Self {
+        Self {
+            pos,
+            background_char,
+            pixels: [[background_char; W]; H],
+        }
+    }
+
+    /// Return the width and height of the `FixedView` as a [`Vec2D`]
+    #[must_use]
+    pub const fn size(&self) -> Vec2D {
+        Vec2D::new(W as isize, H as isize)
+    }
+
+    /// Clear the `FixedView` of all pixels, filling it with the background character
+    pub const fn clear(&mut self) {
+        self.pixels = [[self.background_char; W]; H];
+    }
+
+    /// Plot a pixel to the `FixedView`, relative to its top left corner. Works the same way as [`View::plot()`]
+    pub fn plot(&mut self, pos: Vec2D, c: ColChar, wrapping: Wrapping) {
+        if let Some(wrapped_pos) = wrapping.handle_bounds(pos, self.size()) {
+            self.pixels[wrapped_pos.y.unsigned_abs()][wrapped_pos.x.unsigned_abs()] = c;
+        }
+    }
+
+    /// Blit a struct implementing [`ViewElement`] to the `FixedView`, relative to its top left corner
+    pub fn blit(&mut self, element: &impl ViewElement, wrapping: Wrapping) {
+        for pixel in element.active_pixels() {
+            self.plot(pixel.pos, pixel.fill_char, wrapping);
+        }
+    }
+
+    /// Return the [`ColChar`] at the position, or `None` if it's out of bounds
+    #[must_use]
+    pub fn get(&self, pos: Vec2D) -> Option<ColChar> {
+        let x = usize::try_from(pos.x).ok()?;
+        let y = usize::try_from(pos.y).ok()?;
+
+        self.pixels.get(y)?.get(x).copied()
+    }
+
+    /// Return a [`View`] with the same size and contents, for displaying the `FixedView` on its own
+    #[must_use]
+    pub fn to_view(&self) -> View {
+        let mut view = View::new(W, H, self.background_char);
+        view.blit(
+            &Pixels(self.pixels.as_flattened(), W, Vec2D::ZERO),
+            Wrapping::Ignore,
+        );
+        view
+    }
+}
+
+impl<const W: usize, const H: usize> ViewElement for FixedView<W, H> {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        Pixels(self.pixels.as_flattened(), W, self.pos).active_pixels()
+    }
+}
+
+/// The non-[`VOID`](ColChar::VOID) cells of a row-major canvas of the given width, offset by the position
+struct Pixels<'a>(&'a [ColChar], usize, Vec2D);
+
+impl ViewElement for Pixels<'_> {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let Self(pixels, width, offset) = *self;
+        (0..)
+            .zip(pixels.chunks(width.max(1)))
+            .flat_map(|(y, row)| {
+                (0..)
+                    .zip(row)
+                    .filter(|(_, c)| **c != ColChar::VOID)
+                    .map(move |(x, c)| Pixel::new(offset + Vec2D::new(x, y), *c))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plots_wraps_and_clears() {
+        let mut hud: FixedView<3, 2> = FixedView::new(Vec2D::new(5, 5), ColChar::EMPTY);
+        hud.plot(Vec2D::new(4, -1), ColChar::SOLID, Wrapping::Wrap);
+        assert_eq!(hud.get(Vec2D::new(1, 1)), Some(ColChar::SOLID));
+        assert_eq!(hud.get(Vec2D::new(3, 0)), None);
+        assert_eq!(hud.active_pixels().len(), 6);
+        assert_eq!(hud.active_pixels()[4].pos, Vec2D::new(6, 6));
+
+        hud.background_char = ColChar::VOID;
+        hud.clear();
+        assert!(hud.active_pixels().is_empty());
+        assert_eq!(hud.to_view().size(), Vec2D::new(3, 2));
+    }
+}
@@ -0,0 +1,125 @@
+use std::env;
+
+use super::ColChar;
+use crate::utils::is_wide;
+
+/// `GlyphPolicy` determines how the [`View`](super::View) renders glyphs that the terminal may not be able to display in a single cell, such as emoji and CJK characters. Use [`GlyphPolicy::detect()`] to pick a policy suited to the current terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GlyphPolicy {
+    /// Print every glyph as-is. Wide glyphs will push the rest of their row out of alignment
+    #[default]
+    Allow,
+    /// Print wide glyphs and leave out the cell after each one, so that the rest of the row stays aligned. A wide glyph in the last column is replaced with a whitespace, as it wouldn't fit
+    AccountForWidth,
+    /// Replace wide glyphs with the given fallback character
+    ReplaceWide(char),
+    /// Replace every non-ASCII glyph with the given fallback character, for terminals without unicode support
+    AsciiOnly(char),
+}
+
+impl GlyphPolicy {
+    /// The fallback character used by [`GlyphPolicy::detect()`]
+    pub const DEFAULT_FALLBACK: char = '?';
+
+    /// Pick a policy for the current terminal based on the environment: [`GlyphPolicy::AsciiOnly`] if the locale isn't UTF-8, [`GlyphPolicy::ReplaceWide`] on the Linux console (which can't display wide glyphs) and [`GlyphPolicy::AccountForWidth`] otherwise
+    #[must_use]
+    pub fn detect() -> Self {
+        if cfg!(windows) {
+            return Self::AccountForWidth;
+        }
+
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|key| env::var(key).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default()
+            .to_lowercase();
+
+        if !(locale.contains("utf-8") || locale.contains("utf8")) {
+            Self::AsciiOnly(Self::DEFAULT_FALLBACK)
+        } else if env::var("TERM").is_ok_and(|term| term == "linux") {
+            Self::ReplaceWide(Self::DEFAULT_FALLBACK)
+        } else {
+            Self::AccountForWidth
+        }
+    }
+
+    /// Apply the policy to a row of the `View`, returning the `ColChar`s that should be printed
+    #[must_use]
+    pub fn apply_to_row(self, row: &[ColChar]) -> Vec<ColChar> {
+        match self {
+            Self::Allow => row.to_vec(),
+            Self::AccountForWidth => {
+                let mut output = Vec::with_capacity(row.len());
+                let mut x = 0;
+                while x < row.len() {
+                    let c = row[x];
+                    if is_wide(c.text_char) {
+                        if x + 1 < row.len() {
+                            output.push(c);
+                            x += 1;
+                        } else {
+                            output.push(c.with_char(' '));
+                        }
+                    } else {
+                        output.push(c);
+                    }
+                    x += 1;
+                }
+                output
+            }
+            Self::ReplaceWide(fallback) => row
+                .iter()
+                .map(|c| {
+                    if is_wide(c.text_char) {
+                        c.with_char(fallback)
+                    } else {
+                        *c
+                    }
+                })
+                .collect(),
+            Self::AsciiOnly(fallback) => row
+                .iter()
+                .map(|c| {
+                    if c.text_char.is_ascii() {
+                        *c
+                    } else {
+                        c.with_char(fallback)
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(text: &str) -> Vec<ColChar> {
+        text.chars().map(|c| ColChar::EMPTY.with_char(c)).collect()
+    }
+
+    fn text(row: &[ColChar]) -> String {
+        row.iter().map(|c| c.text_char).collect()
+    }
+
+    #[test]
+    fn wide_glyphs_take_two_cells() {
+        let policy = GlyphPolicy::AccountForWidth;
+        assert_eq!(text(&policy.apply_to_row(&row("a😀bcd"))), "a😀cd");
+        assert_eq!(text(&policy.apply_to_row(&row("ab😀"))), "ab ");
+    }
+
+    #[test]
+    fn fallbacks_replace_glyphs() {
+        assert_eq!(
+            text(&GlyphPolicy::ReplaceWide('?').apply_to_row(&row("█😀a"))),
+            "█?a"
+        );
+        assert_eq!(
+            text(&GlyphPolicy::AsciiOnly('#').apply_to_row(&row("█😀a"))),
+            "##a"
+        );
+    }
+}
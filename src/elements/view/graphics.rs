@@ -0,0 +1,354 @@
+use super::{ColChar, Colour, Modifier, Palette, Vec2D, View};
+use crate::utils::{encode_base64, is_terminal, write_synchronized};
+use std::{
+    env,
+    fmt::Write as _,
+    io::{self, Write},
+};
+
+/// The image and placement id used for every Kitty graphics protocol image, so each one replaces the last
+const KITTY_IMAGE_ID: u32 = 1;
+
+/// A terminal graphics protocol that can draw real bitmaps, used by [`GraphicsRenderer`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    /// DEC Sixel graphics, supported by terminals such as foot, mlterm, Contour and xterm with `-ti vt340`. Colours are limited to the 256 colour xterm palette
+    Sixel,
+    /// The Kitty graphics protocol, supported by kitty, `WezTerm`, Ghostty and Konsole, with full RGB colour
+    Kitty,
+}
+
+impl GraphicsProtocol {
+    /// Work out which graphics protocol the terminal supports from its environment variables, or `None` if it doesn't seem to support either. See [`detect_from()`](GraphicsProtocol::detect_from()) for the rules
+    #[must_use]
+    pub fn detect() -> Option<Self> {
+        Self::detect_from(
+            env::var("TERM").ok().as_deref(),
+            env::var("TERM_PROGRAM").ok().as_deref(),
+            env::var_os("KITTY_WINDOW_ID").is_some(),
+        )
+    }
+
+    /// Work out the graphics protocol from the values of the `TERM` and `TERM_PROGRAM` environment variables and whether `KITTY_WINDOW_ID` is set. Set the protocol with [`GraphicsRenderer::with_protocol()`] for terminals that aren't detected
+    #[must_use]
+    pub fn detect_from(
+        term: Option<&str>,
+        term_program: Option<&str>,
+        kitty_window: bool,
+    ) -> Option<Self> {
+        if kitty_window
+            || is_terminal(
+                term,
+                term_program,
+                &["xterm-kitty", "xterm-ghostty"],
+                &["WezTerm", "ghostty"],
+            )
+        {
+            Some(Self::Kitty)
+        } else if term.is_some_and(|term| term.contains("sixel"))
+            || is_terminal(term, None, &["foot", "mlterm", "contour"], &[])
+        {
+            Some(Self::Sixel)
+        } else {
+            None
+        }
+    }
+}
+
+/// An RGB image, rasterised from a [`View`] by [`Bitmap::from_view()`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bitmap {
+    /// The width of the image in pixels
+    pub width: usize,
+    /// The height of the image in pixels
+    pub height: usize,
+    /// The colour of every pixel, row by row
+    pub pixels: Vec<Colour>,
+}
+
+impl Bitmap {
+    /// Rasterise the `View` into a `Bitmap`, drawing every cell as a block of `cell_size` pixels. Without a font to draw glyphs with, each cell is filled with its background colour if it has one, otherwise the colour of its character, or black for spaces. Half blocks (`'▀'` and `'▄'`) are drawn as two halves, so a [`HalfBlockView`](super::HalfBlockView) keeps its full resolution
+    #[must_use]
+    pub fn from_view(view: &View, cell_size: Vec2D) -> Self {
+        let (cell_width, cell_height) = (cell_size.x.max(1) as usize, cell_size.y.max(1) as usize);
+        let width = view.width * cell_width;
+        let height = view.height * cell_height;
+        let mut pixels = vec![Colour::BLACK; width * height];
+
+        for y in 0..view.height {
+            for (x, cell) in view.render_row(y).into_iter().enumerate().take(view.width) {
+                let (top, bottom) = Self::cell_colours(cell);
+                for cy in 0..cell_height {
+                    let colour = if cy < cell_height / 2 { top } else { bottom };
+                    let start = (y * cell_height + cy) * width + x * cell_width;
+                    pixels[start..start + cell_width].fill(colour);
+                }
+            }
+        }
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Return the colours of the top and bottom halves of the cell
+    fn cell_colours(cell: ColChar) -> (Colour, Colour) {
+        let (foreground, background) = match cell.modifier {
+            Modifier::Background(colour) => (None, Some(colour)),
+            Modifier::Set(set) => (set.foreground, set.background),
+            modifier => (modifier.colour(), None),
+        };
+        match cell.text_char {
+            '▀' => (
+                foreground.unwrap_or(Colour::WHITE),
+                background.unwrap_or(Colour::BLACK),
+            ),
+            '▄' => (
+                background.unwrap_or(Colour::BLACK),
+                foreground.unwrap_or(Colour::WHITE),
+            ),
+            ' ' => (
+                background.unwrap_or(Colour::BLACK),
+                background.unwrap_or(Colour::BLACK),
+            ),
+            _ => {
+                let colour = foreground.or(background).unwrap_or(Colour::WHITE);
+                (colour, colour)
+            }
+        }
+    }
+
+    /// Encode the `Bitmap` as a Kitty graphics protocol escape sequence, sending the raw RGB data in chunks. Every sequence uses the same image and placement id, so displaying a new frame replaces the last one instead of piling up images in the terminal's memory
+    #[must_use]
+    pub fn to_kitty(&self) -> String {
+        let data: Vec<u8> = self
+            .pixels
+            .iter()
+            .flat_map(|colour| [colour.r, colour.g, colour.b])
+            .collect();
+        let encoded = encode_base64(&data);
+        let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(4096).collect();
+
+        let mut output = String::new();
+        for (i, chunk) in chunks.iter().enumerate() {
+            let more = u8::from(i + 1 < chunks.len());
+            let chunk = String::from_utf8_lossy(chunk);
+            if i == 0 {
+                let _ = write!(
+                    output,
+                    "\x1b_Ga=T,i={KITTY_IMAGE_ID},p={KITTY_IMAGE_ID},q=2,f=24,s={},v={},m={more};{chunk}\x1b\\",
+                    self.width, self.height
+                );
+            } else {
+                let _ = write!(output, "\x1b_Gm={more};{chunk}\x1b\\");
+            }
+        }
+
+        output
+    }
+
+    /// Encode the `Bitmap` as a Sixel escape sequence, with its colours converted to the nearest in the 256 colour xterm palette
+    #[must_use]
+    pub fn to_sixel(&self) -> String {
+        let palette = Palette::xterm256();
+        let indices: Vec<usize> = self
+            .pixels
+            .iter()
+            .map(|colour| palette.nearest_index(*colour).unwrap_or(0))
+            .collect();
+        let mut used: Vec<usize> = indices.clone();
+        used.sort_unstable();
+        used.dedup();
+
+        let mut output = format!("\x1bPq\"1;1;{};{}", self.width, self.height);
+        for &i in &used {
+            let c = palette.colours[i];
+            let percent = |value: u8| u32::from(value) * 100 / 255;
+            let _ = write!(
+                output,
+                "#{i};2;{};{};{}",
+                percent(c.r),
+                percent(c.g),
+                percent(c.b)
+            );
+        }
+
+        for band in (0..self.height).step_by(6) {
+            let rows = 6.min(self.height - band);
+            let mut first = true;
+            for &colour in &used {
+                let sixels: Vec<u8> = (0..self.width)
+                    .map(|x| {
+                        (0..rows)
+                            .filter(|row| indices[(band + row) * self.width + x] == colour)
+                            .fold(0, |bits, row| bits | 1 << row)
+                    })
+                    .collect();
+                if sixels.iter().all(|bits| *bits == 0) {
+                    continue;
+                }
+                if !first {
+                    output.push('$');
+                }
+                first = false;
+                let _ = write!(output, "#{colour}");
+                write_sixel_run(&mut output, &sixels);
+            }
+            output.push('-');
+        }
+        output.push_str("\x1b\\");
+
+        output
+    }
+}
+
+/// Write a row of sixels, compressing repeats with run-length encoding
+fn write_sixel_run(output: &mut String, sixels: &[u8]) {
+    let mut i = 0;
+    while i < sixels.len() {
+        let run = sixels[i..]
+            .iter()
+            .take_while(|bits| **bits == sixels[i])
+            .count();
+        let c = char::from(63 + sixels[i]);
+        if run > 3 {
+            let _ = write!(output, "!{run}{c}");
+        } else {
+            output.extend(std::iter::repeat_n(c, run));
+        }
+        i += run;
+    }
+}
+
+/// A `GraphicsRenderer` draws a [`View`] as a real bitmap using the Sixel or Kitty graphics protocol if the terminal supports one, so that every cell becomes a crisp block of colour, and falls back to rendering characters as normal otherwise
+/// ```
+/// use gemini_engine::elements::{view::{ColChar, GraphicsProtocol, GraphicsRenderer}, Vec2D, View};
+///
+/// let view = View::new(4, 2, ColChar::SOLID.with_rgb(255, 0, 0));
+/// let renderer = GraphicsRenderer::new()
+///     .with_protocol(Some(GraphicsProtocol::Kitty))
+///     .with_cell_size(Vec2D::new(8, 16));
+///
+/// assert!(renderer.render(&view).starts_with("\x1b[H\x1b_Ga=T,i=1,p=1,q=2,f=24,s=32,v=32,"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GraphicsRenderer {
+    /// The graphics protocol to draw with, or `None` to render characters as normal
+    pub protocol: Option<GraphicsProtocol>,
+    /// The size in pixels each cell of the `View` is drawn as. Defaults to 8x16, the size of a cell in many terminals
+    pub cell_size: Vec2D,
+}
+
+impl GraphicsRenderer {
+    /// Create a new `GraphicsRenderer` using the graphics protocol detected with [`GraphicsProtocol::detect()`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            protocol: GraphicsProtocol::detect(),
+            cell_size: Vec2D::new(8, 16),
+        }
+    }
+
+    /// Return the `GraphicsRenderer` with a different graphics protocol
+    #[must_use]
+    pub const fn with_protocol(mut self, protocol: Option<GraphicsProtocol>) -> Self {
+        self.protocol = protocol;
+        self
+    }
+
+    /// Return the `GraphicsRenderer` with a different cell size
+    #[must_use]
+    pub const fn with_cell_size(mut self, cell_size: Vec2D) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    /// Return the escape sequences that draw the `View` at the top left of the terminal, or the `View`'s normal character rendering if there is no graphics protocol
+    #[must_use]
+    pub fn render(&self, view: &View) -> String {
+        let Some(protocol) = self.protocol else {
+            return format!("{view}");
+        };
+
+        let bitmap = Bitmap::from_view(view, self.cell_size);
+        let image = match protocol {
+            GraphicsProtocol::Sixel => bitmap.to_sixel(),
+            GraphicsProtocol::Kitty => bitmap.to_kitty(),
+        };
+        format!("\x1b[H{image}")
+    }
+
+    /// Draw the `View` to stdout with [`render()`](GraphicsRenderer::render())
+    ///
+    /// # Errors
+    /// Returns an error if writing to stdout fails
+    pub fn display_render(&self, view: &View) -> io::Result<()> {
+        let frame = self.render(view);
+        write_synchronized(&mut io::stdout().lock(), |stdout| {
+            stdout.write_all(frame.as_bytes())
+        })
+    }
+}
+
+impl Default for GraphicsRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::view::Wrapping;
+
+    #[test]
+    fn detects_protocols() {
+        assert_eq!(
+            GraphicsProtocol::detect_from(Some("xterm-256color"), Some("WezTerm"), false),
+            Some(GraphicsProtocol::Kitty)
+        );
+        assert_eq!(
+            GraphicsProtocol::detect_from(Some("foot-extra"), None, false),
+            Some(GraphicsProtocol::Sixel)
+        );
+        assert_eq!(
+            GraphicsProtocol::detect_from(Some("xterm"), None, false),
+            None
+        );
+    }
+
+    #[test]
+    fn rasterises_and_encodes_sixels() {
+        let mut view = View::new(2, 1, ColChar::EMPTY);
+        view.plot(
+            Vec2D::new(1, 0),
+            ColChar::new('▀', Modifier::from_rgb(255, 0, 0)),
+            Wrapping::Panic,
+        );
+
+        let bitmap = Bitmap::from_view(&view, Vec2D::new(1, 2));
+        assert_eq!(
+            bitmap.pixels,
+            vec![
+                Colour::BLACK,
+                Colour::rgb(255, 0, 0),
+                Colour::BLACK,
+                Colour::BLACK
+            ]
+        );
+        // Black and red are colours 0 and 9 in the xterm palette
+        assert_eq!(
+            bitmap.to_sixel(),
+            "\x1bPq\"1;1;2;2#0;2;0;0;0#9;2;100;0;0#0BA$#9?@-\x1b\\"
+        );
+        let mut run = String::new();
+        write_sixel_run(&mut run, &[1, 1, 1, 1, 1, 2]);
+        assert_eq!(run, "!5@A");
+        assert_eq!(
+            bitmap.to_kitty(),
+            "\x1b_Ga=T,i=1,p=1,q=2,f=24,s=2,v=2,m=0;AAAA/wAAAAAAAAAA\x1b\\"
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use super::{ColChar, Colour, Modifier, ModifierSet, View};
+use std::io;
+
+/// A `HalfBlockView` doubles the vertical resolution of a [`View`] by drawing two pixels in every terminal cell, as the top and bottom halves of a `'▀'` character with separate foreground and background colours. Since terminal cells are about twice as tall as they are wide, this also makes every pixel roughly square, which suits sprite-heavy games
+///
+/// Blit everything to the inner [`view`](HalfBlockView::view) as normal, which is twice as tall as the rendered output, then call [`display_render()`](HalfBlockView::display_render()). Only the colour of each pixel is drawn: pixels left as the `View`'s [`background_char`](View::background_char) are transparent, showing the terminal's background, and pixels without an RGB or standard ANSI colour are drawn white
+/// ```
+/// use gemini_engine::elements::{view::{ColChar, Colour, HalfBlockView, Modifier, ModifierSet, Wrapping}, Vec2D};
+///
+/// let mut screen = HalfBlockView::new(4, 4);
+/// screen.view.plot(Vec2D::new(0, 0), ColChar::SOLID.with_rgb(255, 0, 0), Wrapping::Panic);
+/// screen.view.plot(Vec2D::new(0, 1), ColChar::SOLID.with_rgb(0, 0, 255), Wrapping::Panic);
+///
+/// let cells = screen.compose();
+/// assert_eq!(cells.size(), Vec2D::new(4, 2));
+/// assert_eq!(
+///     cells.get(Vec2D::new(0, 0)),
+///     Some(ColChar::new('▀', Modifier::Set(
+///         ModifierSet::new().with_foreground(Colour::rgb(255, 0, 0)).with_background(Colour::rgb(0, 0, 255))
+///     )))
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct HalfBlockView {
+    /// The `View` everything is blit to, with two rows of pixels for every row of terminal cells
+    pub view: View,
+}
+
+impl HalfBlockView {
+    /// Create a new `HalfBlockView` with the width and height in pixels. The rendered output is `width` cells wide and half of `height` cells tall, rounded up
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            view: View::new(width, height, ColChar::EMPTY),
+        }
+    }
+
+    /// Return the colour of the pixel in the displayed cells, or `None` if nothing has been drawn there
+    fn colour_at(&self, cells: &[ColChar], x: usize, y: usize) -> Option<Colour> {
+        if y >= self.view.height {
+            return None;
+        }
+        let pixel = cells[self.view.width * y + x];
+        if pixel == self.view.background_char {
+            None
+        } else {
+            Some(pixel.modifier.colour().unwrap_or(Colour::WHITE))
+        }
+    }
+
+    /// Return a `View` of terminal cells with every pair of pixel rows packed into one row of half blocks. Pixels are read from the inner `View`'s [`front_buffer()`](View::front_buffer()), and its colour mode, colour adjustment, glyph policy and static layer are carried over
+    #[must_use]
+    pub fn compose(&self) -> View {
+        let mut cells = View::new(
+            self.view.width,
+            self.view.height.div_ceil(2),
+            ColChar::EMPTY,
+        )
+        .with_colour_mode(self.view.colour_mode)
+        .with_colour_adjustment(self.view.colour_adjustment)
+        .with_glyph_policy(self.view.glyph_policy)
+        .with_static_layer(self.view.static_layer.clone());
+        let pixels = self.view.front_buffer();
+
+        for row in 0..cells.height {
+            for x in 0..cells.width {
+                let cell = match (
+                    self.colour_at(pixels, x, row * 2),
+                    self.colour_at(pixels, x, row * 2 + 1),
+                ) {
+                    (None, None) => continue,
+                    (Some(top), None) => ColChar::new('▀', Modifier::Colour(top)),
+                    (None, Some(bottom)) => ColChar::new('▄', Modifier::Colour(bottom)),
+                    (Some(top), Some(bottom)) => ColChar::new(
+                        '▀',
+                        ModifierSet::new()
+                            .with_foreground(top)
+                            .with_background(bottom)
+                            .into(),
+                    ),
+                };
+                cells.pixels[cells.width * row + x] = cell;
+            }
+        }
+
+        cells
+    }
+
+    /// Display the `HalfBlockView` to the terminal. Uses [`View::display_render()`] under the hood
+    ///
+    /// # Errors
+    /// Returns an error if writing to stdout fails
+    pub fn display_render(&self) -> io::Result<()> {
+        self.compose().display_render()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{
+        view::{ColourAdjustment, ColourMode, Wrapping},
+        Vec2D,
+    };
+
+    #[test]
+    fn packs_pixel_pairs_into_cells() {
+        let mut screen = HalfBlockView::new(3, 3);
+        screen
+            .view
+            .plot(Vec2D::new(1, 1), ColChar::SOLID, Wrapping::Panic);
+        screen.view.plot(
+            Vec2D::new(2, 2),
+            ColChar::new('x', Modifier::GREEN),
+            Wrapping::Panic,
+        );
+
+        let cells = screen.compose();
+        assert_eq!(cells.size(), Vec2D::new(3, 2));
+        assert_eq!(cells.get(Vec2D::new(0, 0)), Some(ColChar::EMPTY));
+        assert_eq!(
+            cells.get(Vec2D::new(1, 0)),
+            Some(ColChar::new('▄', Modifier::Colour(Colour::WHITE)))
+        );
+        assert_eq!(
+            cells.get(Vec2D::new(2, 1)),
+            Some(ColChar::new(
+                '▀',
+                Modifier::Colour(Modifier::GREEN.colour().expect("a standard colour"))
+            ))
+        );
+    }
+
+    #[test]
+    fn composes_the_front_buffer_with_the_views_settings() {
+        let mut screen = HalfBlockView::new(1, 2);
+        screen.view.colour_mode = ColourMode::Ansi16;
+        screen.view.colour_adjustment = ColourAdjustment::NEUTRAL.with_brightness(0.5);
+        screen
+            .view
+            .plot(Vec2D::ZERO, ColChar::SOLID, Wrapping::Panic);
+        screen.view.swap_buffers();
+        screen.view.clear();
+
+        let cells = screen.compose();
+        assert_eq!(
+            cells.get(Vec2D::ZERO),
+            Some(ColChar::new('▀', Modifier::Colour(Colour::WHITE)))
+        );
+        assert_eq!(cells.colour_mode, ColourMode::Ansi16);
+        assert_eq!(cells.colour_adjustment, screen.view.colour_adjustment);
+    }
+}
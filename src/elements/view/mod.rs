@@ -1,25 +1,61 @@
 //! This module is home to the [`View`] struct, which handles the printing of pixels to an ANSI standard text output
+use crate::{utils as crate_utils, EngineError};
 use std::fmt::Write as Write2; // Import the Write trait from std::fmt
-use crate::utils as crate_utils;
 use std::{
     fmt::{self, Display, Formatter},
     io::{self, Write},
 };
 
+mod backend;
+mod bitmap_font;
+mod block_art;
+mod chunked_view;
+mod colour_adjustment;
+mod colour_mode;
+mod diff_renderer;
+mod export;
+mod fixed_view;
+mod glyph_policy;
+mod graphics;
+mod half_block_view;
 mod pixel;
+#[cfg(feature = "png")]
+mod png_export;
+pub mod post_process;
 mod scale_to_fit;
+mod static_layer;
+mod terminal_session;
 pub mod utils;
 mod view_element;
+mod virtual_view;
+mod web;
 mod wrapping;
 
+pub use backend::{MemoryBackend, RenderBackend, StdoutBackend, WriterBackend};
+pub use bitmap_font::{FONT_CELL_HEIGHT, FONT_CELL_WIDTH};
+pub use block_art::ImageQuality;
+pub use chunked_view::ChunkedView;
+pub use colour_adjustment::ColourAdjustment;
+pub use colour_mode::ColourMode;
+pub use diff_renderer::DiffRenderer;
+pub use fixed_view::FixedView;
+pub use glyph_policy::GlyphPolicy;
+pub use graphics::{Bitmap, GraphicsProtocol, GraphicsRenderer};
+pub use half_block_view::HalfBlockView;
 #[allow(deprecated)]
 pub use pixel::{
-    colchar::{ColChar, Colour, Modifier},
+    colchar::{ColChar, Colour, Dithering, Gradient, Modifier, ModifierSet, Palette, TextStyle},
     vec2d::Vec2D,
+    vec2f::{Rounding, Vec2F},
     Pixel, Point,
 };
+pub use post_process::PostProcess;
 pub use scale_to_fit::ScaleFitView;
+pub use static_layer::StaticLayer;
+pub use terminal_session::TerminalSession;
 pub use view_element::ViewElement;
+pub use virtual_view::{VirtualScaling, VirtualView};
+pub use web::WebBackend;
 pub use wrapping::Wrapping;
 
 /// The View struct is the canvas on which you will print all of your `ViewElement`s. In normal use, you would clear the View, `blit` all your `ViewElement`s to it and then render. The following example demonstrates a piece of code that will render a View of width 9 and height 3, with a single Pixel in the middle
@@ -45,7 +81,27 @@ pub struct View {
     pub coord_numbers_in_render: bool,
     /// If true, [`View.display_render`] will block until the console window is resized to fit the `View`
     pub block_until_resized: bool,
+    /// How glyphs that may not fit in a single cell, such as emoji, are rendered. Defaults to [`GlyphPolicy::Allow`]
+    pub glyph_policy: GlyphPolicy,
+    /// A pre-rasterised background which the `View` is filled with on clear, instead of the [`background_char`](View::background_char). Ignored if its size doesn't match the `View`'s
+    pub static_layer: Option<StaticLayer>,
+    /// Gamma, brightness, contrast and saturation adjustments applied to RGB colours as the `View` is rendered. Defaults to [`ColourAdjustment::NEUTRAL`]
+    pub colour_adjustment: ColourAdjustment,
+    /// How many colours the terminal can show. Colours are converted to the nearest ones the terminal supports as the `View` is displayed. Defaults to [`ColourMode::TrueColour`], use [`ColourMode::detect()`] to match the terminal
+    pub colour_mode: ColourMode,
+    /// If set, out-of-bounds pixels are handled with this [`Wrapping`] instead of the one passed to each plot and blit. Set it to [`Wrapping::Panic`] while debugging to catch layout bugs anywhere in the game. Defaults to `None`
+    pub wrapping_override: Option<Wrapping>,
+    /// The position in world space of the `View`'s top left cell, used by [`plot_world()`](View::plot_world()) and [`blit_world()`](View::blit_world()). World positions can be negative, so worlds can be centred on the origin. Defaults to [`Vec2D::ZERO`], where world and screen positions are the same
+    pub camera: Vec2D,
+    /// If true, [`clear()`](View::clear()) checks the size of the terminal every frame and resizes the `View` to fill it, leaving the bottom row free so the line after the frame doesn't scroll it away. Check [`was_resized()`](View::was_resized()) after clearing to adapt layouts to the new [`size()`](View::size()). Defaults to false
+    pub auto_resize: bool,
+    /// The size of the `View` before the last [`clear()`](View::clear()) resized it to fit the terminal, or `None` if it wasn't resized
+    resized_from: Option<Vec2D>,
     pixels: Vec<ColChar>,
+    /// The last frame passed to [`swap_buffers()`](View::swap_buffers()), which is displayed instead of `pixels` once double buffering is in use
+    front: Option<Vec<ColChar>>,
+    /// The distance of each cell's pixel, or empty if nothing has been plotted with depth since the `View` was created
+    depth: Vec<f64>,
 }
 
 impl View {
@@ -58,13 +114,42 @@ impl View {
             background_char,
             coord_numbers_in_render: false,
             block_until_resized: false,
+            glyph_policy: GlyphPolicy::Allow,
+            static_layer: None,
+            colour_adjustment: ColourAdjustment::NEUTRAL,
+            colour_mode: ColourMode::TrueColour,
+            wrapping_override: None,
+            camera: Vec2D::ZERO,
+            auto_resize: false,
+            resized_from: None,
             pixels: Vec::with_capacity(width * height),
+            front: None,
+            depth: vec![],
         };
         view.clear();
 
         view
     }
 
+    /// Create a new `View` like [`new()`](View::new()), but return an error instead of a `View` that panics when drawn to if the size has no area
+    ///
+    /// # Errors
+    /// Returns [`EngineError::InvalidSize`] if the width or height is zero
+    pub fn try_new(
+        width: usize,
+        height: usize,
+        background_char: ColChar,
+    ) -> Result<Self, EngineError> {
+        if width == 0 || height == 0 {
+            return Err(EngineError::InvalidSize(Vec2D::new(
+                width as isize,
+                height as isize,
+            )));
+        }
+
+        Ok(Self::new(width, height, background_char))
+    }
+
     /// Return the `View` with its [`coord_numbers_in_render`](View::coord_numbers_in_render) field set to the chosen value. Consumes the original `View`
     #[must_use]
     pub const fn with_coord_numbers(mut self, coord_numbers_in_render: bool) -> Self {
@@ -79,6 +164,68 @@ impl View {
         self
     }
 
+    /// Return the `View` with its [`glyph_policy`](View::glyph_policy) field set to the chosen value. Consumes the original `View`
+    #[must_use]
+    pub const fn with_glyph_policy(mut self, glyph_policy: GlyphPolicy) -> Self {
+        self.glyph_policy = glyph_policy;
+        self
+    }
+
+    /// Return the `View` with its [`static_layer`](View::static_layer) field set to the chosen value. Consumes the original `View`
+    #[must_use]
+    pub fn with_static_layer(mut self, static_layer: Option<StaticLayer>) -> Self {
+        self.static_layer = static_layer;
+        self.clear();
+        self
+    }
+
+    /// Return the `View` with its [`colour_adjustment`](View::colour_adjustment) field set to the chosen value. Consumes the original `View`
+    #[must_use]
+    pub const fn with_colour_adjustment(mut self, colour_adjustment: ColourAdjustment) -> Self {
+        self.colour_adjustment = colour_adjustment;
+        self
+    }
+
+    /// Return the `View` with its [`colour_mode`](View::colour_mode) field set to the chosen value. Consumes the original `View`
+    #[must_use]
+    pub const fn with_colour_mode(mut self, colour_mode: ColourMode) -> Self {
+        self.colour_mode = colour_mode;
+        self
+    }
+
+    /// Return the `View` with its [`auto_resize`](View::auto_resize) field set to the chosen value. Consumes the original `View`
+    #[must_use]
+    pub const fn with_auto_resize(mut self, auto_resize: bool) -> Self {
+        self.auto_resize = auto_resize;
+        self
+    }
+
+    /// Change the size of the `View` and clear it
+    pub fn resize(&mut self, width: usize, height: usize) {
+        self.width = width;
+        self.height = height;
+        self.clear();
+    }
+
+    /// Returns true if the last [`clear()`](View::clear()) resized the `View` to fit the terminal, with [`auto_resize`](View::auto_resize) on
+    #[must_use]
+    pub const fn was_resized(&self) -> bool {
+        self.resized_from.is_some()
+    }
+
+    /// Set the size of the `View` to fill a terminal of the given size, leaving the bottom row free. Returns true if the size changed
+    fn fit_to_terminal(&mut self, terminal_size: Vec2D) -> bool {
+        let width = terminal_size.x.max(1) as usize;
+        let height = (terminal_size.y - 1).max(1) as usize;
+        if (width, height) == (self.width, self.height) {
+            return false;
+        }
+
+        self.width = width;
+        self.height = height;
+        true
+    }
+
     /// Return the width and height of the `View` as a [`Vec2D`]
     #[must_use]
     pub const fn size(&self) -> Vec2D {
@@ -91,26 +238,329 @@ impl View {
         self.size() / 2
     }
 
-    /// Clear the `View` of all pixels
+    /// Return the position on the `View` of a position in world space, given the [`camera`](View::camera)
+    #[must_use]
+    pub fn world_to_screen(&self, pos: Vec2D) -> Vec2D {
+        pos - self.camera
+    }
+
+    /// Return the position in world space of a position on the `View`, such as the mouse, given the [`camera`](View::camera)
+    #[must_use]
+    pub fn screen_to_world(&self, pos: Vec2D) -> Vec2D {
+        pos + self.camera
+    }
+
+    /// Move the [`camera`](View::camera) so that the position in world space is at the centre of the `View`
+    pub fn centre_camera_on(&mut self, pos: Vec2D) {
+        self.camera = pos - self.center();
+    }
+
+    /// Clear the `View` of all pixels and depths. If the `View` has a [`StaticLayer`] of the same size, the `View` is filled with it instead. With [`auto_resize`](View::auto_resize) on, the `View` is resized to fit the terminal first
     pub fn clear(&mut self) {
-        self.pixels = vec![self.background_char; self.width * self.height];
+        let previous_size = self.size();
+        let resized = self.auto_resize
+            && crate_utils::get_terminal_size_as_vec2d()
+                .is_some_and(|terminal_size| self.fit_to_terminal(terminal_size));
+        self.resized_from = resized.then_some(previous_size);
+        match &self.static_layer {
+            Some(layer) if layer.size() == self.size() => {
+                self.pixels.clear();
+                self.pixels.extend_from_slice(layer.pixels());
+            }
+            _ => self.pixels = vec![self.background_char; self.width * self.height],
+        }
+        if !self.depth.is_empty() {
+            self.depth = vec![f64::INFINITY; self.width * self.height];
+        }
     }
 
     /// Plot a pixel to the `View`. Accepts a [`Vec2D`] (the position of the pixel), [`ColChar`] (what the pixel should look like/what colour it should be), and a [`Wrapping`] enum variant (Please see the [Wrapping] documentation for more info)
     pub fn plot(&mut self, pos: Vec2D, c: ColChar, wrapping: Wrapping) {
-        if let Some(wrapped_pos) = wrapping.handle_bounds(pos, self.size()) {
+        if let Some(wrapped_pos) = self.wrapping_for(wrapping).handle_bounds(pos, self.size()) {
             let i = self.width * wrapped_pos.y.unsigned_abs() + wrapped_pos.x.unsigned_abs();
+            self.set_without_depth(i, c);
+        }
+    }
+
+    /// Return the [`Wrapping`] to handle out-of-bounds pixels with: the [`wrapping_override`](View::wrapping_override) if set, otherwise the one passed
+    #[must_use]
+    pub fn wrapping_for(&self, wrapping: Wrapping) -> Wrapping {
+        self.wrapping_override.unwrap_or(wrapping)
+    }
+
+    /// Plot a pixel to the `View` like [`plot()`](View::plot()), but return an error instead of panicking if the pixel is out of bounds with [`Wrapping::Panic`]
+    ///
+    /// # Errors
+    /// Returns [`EngineError::OutOfBounds`] if the pixel is out of bounds with `Wrapping::Panic`, or [`EngineError::InvalidSize`] if the `View` has no area
+    pub fn try_plot(
+        &mut self,
+        pos: Vec2D,
+        c: ColChar,
+        wrapping: Wrapping,
+    ) -> Result<(), EngineError> {
+        if let Some(wrapped_pos) = self
+            .wrapping_for(wrapping)
+            .try_handle_bounds(pos, self.size())?
+        {
+            let i = self.width * wrapped_pos.y.unsigned_abs() + wrapped_pos.x.unsigned_abs();
+            self.set_without_depth(i, c);
+        }
+
+        Ok(())
+    }
+
+    /// Plot a pixel to the `View` at a distance from the viewer, only if nothing nearer has been plotted with depth to the same cell since the last [`clear()`](View::clear()). Lower depths are nearer, and uses the same units as the 3D `Viewport::render_with_depth()` so that 2D elements can be placed in front of or behind parts of a 3D scene. Pixels plotted with [`plot()`](View::plot()) don't have a depth, so they are drawn over anything already there and can be covered by any later pixel with a depth
+    pub fn plot_with_depth(&mut self, pos: Vec2D, c: ColChar, depth: f64, wrapping: Wrapping) {
+        let Some(wrapped_pos) = self.wrapping_for(wrapping).handle_bounds(pos, self.size()) else {
+            return;
+        };
+        if self.depth.is_empty() {
+            self.depth = vec![f64::INFINITY; self.width * self.height];
+        }
+
+        let i = self.width * wrapped_pos.y.unsigned_abs() + wrapped_pos.x.unsigned_abs();
+        if depth < self.depth[i] {
+            self.depth[i] = depth;
             self.pixels[i] = c;
         }
     }
 
+    /// Plot a pixel to the `View` at `alpha` opacity, blending it with the pixel already there using [`ColChar::blend_over()`] instead of overwriting it. An `alpha` of 1.0 is the same as [`plot()`](View::plot()), and 0.0 leaves the `View` unchanged
+    pub fn plot_with_alpha(&mut self, pos: Vec2D, c: ColChar, alpha: f64, wrapping: Wrapping) {
+        let Some(wrapped_pos) = self.wrapping_for(wrapping).handle_bounds(pos, self.size()) else {
+            return;
+        };
+        if alpha <= 0.0 {
+            return;
+        }
+
+        let i = self.width * wrapped_pos.y.unsigned_abs() + wrapped_pos.x.unsigned_abs();
+        let c = if alpha >= 1.0 {
+            c
+        } else {
+            c.blend_over(self.pixels[i], alpha)
+        };
+        self.set_without_depth(i, c);
+    }
+
+    /// Overwrite the pixel at the index and forget its depth, so that any later pixel plotted with depth is drawn over it
+    fn set_without_depth(&mut self, i: usize, c: ColChar) {
+        self.pixels[i] = c;
+        if let Some(depth) = self.depth.get_mut(i) {
+            *depth = f64::INFINITY;
+        }
+    }
+
+    /// Return the depth of the nearest pixel plotted with [`plot_with_depth()`](View::plot_with_depth()) at the position since the last [`clear()`](View::clear()), or `None` if there isn't one, it has since been drawn over without a depth or the position is outside the `View`
+    #[must_use]
+    pub fn depth_at(&self, pos: Vec2D) -> Option<f64> {
+        let x = usize::try_from(pos.x).ok().filter(|x| *x < self.width)?;
+        let y = usize::try_from(pos.y).ok().filter(|y| *y < self.height)?;
+
+        self.depth
+            .get(self.width * y + x)
+            .copied()
+            .filter(|depth| depth.is_finite())
+    }
+
+    /// Return the [`ColChar`] currently plotted at the position, or `None` if the position is outside the `View`
+    #[must_use]
+    pub fn get(&self, pos: Vec2D) -> Option<ColChar> {
+        let x = usize::try_from(pos.x).ok().filter(|x| *x < self.width)?;
+        let y = usize::try_from(pos.y).ok().filter(|y| *y < self.height)?;
+
+        self.pixels.get(self.width * y + x).copied()
+    }
+
+    /// Return every cell of the `View` in row-major order, so the cell at `(x, y)` is at index `y * width + x`. Useful for reading the whole canvas at once, such as to feed it to a bot as an observation
+    #[must_use]
+    pub fn cells(&self) -> &[ColChar] {
+        &self.pixels
+    }
+
+    /// Return a hash of the `View`'s size and [`cells()`](View::cells()), for checking that a frame is drawn exactly the same as before, such as in regression tests. The hash is the same on every platform and release, as it uses FNV-1a rather than std's hashers
+    #[must_use]
+    pub fn canvas_hash(&self) -> u64 {
+        let mut hash = crate_utils::FNV1A_OFFSET_BASIS;
+        let mut feed = |bytes: &[u8]| hash = crate_utils::fnv1a(hash, bytes);
+
+        feed(&(self.width as u64).to_le_bytes());
+        feed(&(self.height as u64).to_le_bytes());
+        for cell in &self.pixels {
+            feed(&u32::from(cell.text_char).to_le_bytes());
+            feed(cell.modifier.to_string().as_bytes());
+            // 0xff never appears in UTF-8, so it keeps one cell's modifier from running into the next cell
+            feed(&[0xff]);
+        }
+
+        hash
+    }
+
+    /// Move the frame drawn so far to the front buffer, where it's displayed from, so that drawing the next frame doesn't show until it's swapped in too. Every render of the `View` shows the front buffer once this has been called, while [`plot()`](View::plot()), [`get()`](View::get()) and [`cells()`](View::cells()) keep working on the back buffer. The back buffer is left holding an older frame, so [`clear()`](View::clear()) it before drawing the next one
+    /// ```
+    /// use gemini_engine::elements::{view::{ColChar, Wrapping}, Vec2D, View};
+    ///
+    /// let mut view = View::new(3, 1, ColChar::EMPTY);
+    /// view.plot(Vec2D::new(0, 0), ColChar::SOLID, Wrapping::Panic);
+    /// view.swap_buffers();
+    ///
+    /// // Drawing the next frame doesn't change what's displayed until the next swap
+    /// view.clear();
+    /// view.plot(Vec2D::new(2, 0), ColChar::SOLID, Wrapping::Panic);
+    /// assert_eq!(view.front_buffer(), [ColChar::SOLID, ColChar::EMPTY, ColChar::EMPTY]);
+    ///
+    /// view.swap_buffers();
+    /// assert_eq!(view.front_buffer(), [ColChar::EMPTY, ColChar::EMPTY, ColChar::SOLID]);
+    /// ```
+    pub fn swap_buffers(&mut self) {
+        match &mut self.front {
+            Some(front) if front.len() == self.pixels.len() => {
+                std::mem::swap(front, &mut self.pixels);
+            }
+            _ => self.front = Some(self.pixels.clone()),
+        }
+    }
+
+    /// Returns true if [`swap_buffers()`](View::swap_buffers()) has been called, so that renders show the front buffer
+    #[must_use]
+    pub const fn is_double_buffered(&self) -> bool {
+        self.front.is_some()
+    }
+
+    /// Return the cells that are displayed when the `View` is rendered: the front buffer if [`swap_buffers()`](View::swap_buffers()) has been called, or the cells being drawn to otherwise
+    #[must_use]
+    pub fn front_buffer(&self) -> &[ColChar] {
+        self.displayed()
+    }
+
+    /// Return the cells to render, falling back to the back buffer if the front buffer doesn't match the `View`'s size
+    fn displayed(&self) -> &[ColChar] {
+        match &self.front {
+            Some(front) if front.len() == self.width * self.height => front,
+            _ => &self.pixels,
+        }
+    }
+
     /// Blit a struct implementing [`ViewElement`] to the `View`
     pub fn blit(&mut self, element: &impl ViewElement, wrapping: Wrapping) {
+        engine_span!("blit");
         for pixel in element.active_pixels() {
             self.plot(pixel.pos, pixel.fill_char, wrapping);
         }
     }
 
+    /// Plot a pixel at a position in world space, which is moved onto the `View` by the [`camera`](View::camera). Use [`Wrapping::Ignore`] to skip pixels the camera can't see
+    pub fn plot_world(&mut self, pos: Vec2D, c: ColChar, wrapping: Wrapping) {
+        self.plot(self.world_to_screen(pos), c, wrapping);
+    }
+
+    /// Blit a struct implementing [`ViewElement`] positioned in world space to the `View`, moving every pixel by the [`camera`](View::camera). Elements such as HUDs that should stay put on screen are blit with [`blit()`](View::blit()) as usual
+    /// ```
+    /// use gemini_engine::elements::{view::{ColChar, Wrapping}, Pixel, Vec2D, View};
+    ///
+    /// let mut view = View::new(20, 10, ColChar::EMPTY);
+    /// view.centre_camera_on(Vec2D::new(-100, -50));
+    ///
+    /// view.blit_world(&Pixel::new(Vec2D::new(-100, -50), ColChar::SOLID), Wrapping::Ignore);
+    /// assert_eq!(view.get(Vec2D::new(10, 5)), Some(ColChar::SOLID));
+    /// assert_eq!(view.screen_to_world(Vec2D::new(10, 5)), Vec2D::new(-100, -50));
+    /// ```
+    pub fn blit_world(&mut self, element: &impl ViewElement, wrapping: Wrapping) {
+        engine_span!("blit_world");
+        for pixel in element.active_pixels() {
+            self.plot_world(pixel.pos, pixel.fill_char, wrapping);
+        }
+    }
+
+    /// Blit many elements to the `View` in one pass, giving the same result as blitting each of them in order with [`blit()`](View::blit()). The pixels of every element are collected and bounds checked together, then sorted by their cell so that the canvas is written from top to bottom
+    ///
+    /// Like `blit()`, every pixel is written as it is: the depth buffer isn't checked or updated and nothing is blended. Use [`blit_with_depth()`](View::blit_with_depth()) or [`blit_with_alpha()`](View::blit_with_alpha()) for each element that needs them
+    /// ```
+    /// use gemini_engine::elements::{view::{ColChar, ViewElement, Wrapping}, Pixel, Rect, Vec2D, View};
+    ///
+    /// let floor = Rect::new(Vec2D::new(0, 3), Vec2D::new(10, 1), ColChar::SOLID);
+    /// let player = Pixel::new(Vec2D::new(4, 3), ColChar::new('@', ColChar::SOLID.modifier));
+    ///
+    /// let mut view = View::new(10, 4, ColChar::EMPTY);
+    /// view.blit_all(&[&floor, &player], Wrapping::Ignore);
+    ///
+    /// assert_eq!(view.get(Vec2D::new(4, 3)).map(|c| c.text_char), Some('@'));
+    /// ```
+    pub fn blit_all(&mut self, elements: &[&dyn ViewElement], wrapping: Wrapping) {
+        engine_span!("blit_all", elements = elements.len());
+        let (size, wrapping) = (self.size(), self.wrapping_for(wrapping));
+
+        let mut writes: Vec<(usize, ColChar)> = elements
+            .iter()
+            .flat_map(|element| element.active_pixels())
+            .filter_map(|pixel| {
+                let pos = wrapping.handle_bounds(pixel.pos, size)?;
+                Some((
+                    self.width * pos.y.unsigned_abs() + pos.x.unsigned_abs(),
+                    pixel.fill_char,
+                ))
+            })
+            .collect();
+        // A stable sort keeps later elements after earlier ones in the same cell, so they still overwrite them
+        writes.sort_by_key(|(i, _)| *i);
+
+        for (i, c) in writes {
+            self.set_without_depth(i, c);
+        }
+    }
+
+    /// Blit a struct implementing [`ViewElement`] to the `View` like [`blit()`](View::blit()), but return an error instead of panicking if any pixel is out of bounds with [`Wrapping::Panic`]. Every pixel is checked before any are plotted, so the `View` is left unchanged if an error is returned
+    ///
+    /// # Errors
+    /// Returns [`EngineError::OutOfBounds`] for the first pixel that is out of bounds with `Wrapping::Panic`, or [`EngineError::InvalidSize`] if the `View` has no area
+    pub fn try_blit(
+        &mut self,
+        element: &impl ViewElement,
+        wrapping: Wrapping,
+    ) -> Result<(), EngineError> {
+        engine_span!("try_blit");
+        let (size, checked) = (self.size(), self.wrapping_for(wrapping));
+        let pixels = element.active_pixels();
+        for pixel in &pixels {
+            checked.try_handle_bounds(pixel.pos, size)?;
+        }
+        for pixel in pixels {
+            self.plot(pixel.pos, pixel.fill_char, wrapping);
+        }
+
+        Ok(())
+    }
+
+    /// Blit a struct implementing [`ViewElement`] to the `View` at a distance from the viewer, using [`plot_with_depth()`](View::plot_with_depth()) for every pixel
+    pub fn blit_with_depth(&mut self, element: &impl ViewElement, depth: f64, wrapping: Wrapping) {
+        engine_span!("blit_with_depth", depth);
+        for pixel in element.active_pixels() {
+            self.plot_with_depth(pixel.pos, pixel.fill_char, depth, wrapping);
+        }
+    }
+
+    /// Blit a struct implementing [`ViewElement`] to the `View` at `alpha` opacity, using [`plot_with_alpha()`](View::plot_with_alpha()) for every pixel, for translucent overlays, fog and ghost sprites
+    pub fn blit_with_alpha(&mut self, element: &impl ViewElement, alpha: f64, wrapping: Wrapping) {
+        engine_span!("blit_with_alpha", alpha);
+        for pixel in element.active_pixels() {
+            self.plot_with_alpha(pixel.pos, pixel.fill_char, alpha, wrapping);
+        }
+    }
+
+    /// Blit a struct implementing [`ViewElement`] to the `View`, only plotting the pixels the [`Mask`](crate::elements::containers::Mask) allows
+    pub fn blit_masked(
+        &mut self,
+        element: &impl ViewElement,
+        mask: &crate::elements::containers::Mask,
+        wrapping: Wrapping,
+    ) {
+        for pixel in element.active_pixels() {
+            if mask.allows(pixel.pos) {
+                self.plot(pixel.pos, pixel.fill_char, wrapping);
+            }
+        }
+    }
+
     /// Blit a struct implementing [`ViewElement`] to the `View` with a doubled width. Blitting a `Pixel` at `Vec2D(5,3)`, for example, will result in a blit at `Vec2D(10,3)` and `Vec2D(11,3)` being plotted to. Useful when you want to work with more square pixels, as single text characters are much taller than they are wide
     pub fn blit_double_width(&mut self, element: &impl ViewElement, wrapping: Wrapping) {
         for pixel in element.active_pixels() {
@@ -120,18 +570,66 @@ impl View {
         }
     }
 
-    /// Display the `View`. `View` implements the `Display` trait and so can be rendered in many ways (such as `println!("{view}");`), but this is intended to be the fastest way possible.
+    /// Return the escape sequences and characters that draw the `View` as a single frame, like its `Display` implementation but without first scrolling the terminal to make room
+    #[must_use]
+    pub fn render_frame(&self) -> String {
+        Frame(self).to_string()
+    }
+
+    /// Draw the `View` as a single frame through a [`RenderBackend`], such as a [`MemoryBackend`] to capture the output in tests
+    ///
+    /// # Errors
+    /// Returns any error from the backend
+    pub fn display_to(&self, backend: &mut impl RenderBackend) -> io::Result<()> {
+        engine_span!("display_to", width = self.width, height = self.height);
+        backend.write_frame(&self.render_frame())
+    }
+
+    /// Write the escape sequences and characters that draw the `View` from the top left of the terminal
+    fn write_frame(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("\x1b[H\x1b[J")?;
+        if self.coord_numbers_in_render {
+            let nums: String = (0..self.width)
+                .map(|i| i.to_string().chars().last().unwrap_or(' '))
+                .collect();
+            writeln!(f, " {nums}")?;
+        }
+        for y in 0..self.height {
+            if self.coord_numbers_in_render {
+                let num = y.to_string().chars().last().unwrap_or(' ');
+                write!(f, "{num}")?;
+            }
+
+            let row = self.render_row(y);
+
+            for x in 0..row.len() {
+                row[x].display_with_prev_and_next(
+                    f,
+                    self.colour_mode,
+                    x.checked_sub(1).map(|i| row[i].modifier),
+                    row.get(x + 1).map(|c| c.modifier),
+                )?;
+            }
+            f.write_str("\r\n")?;
+        }
+        f.write_str("\x1b[J")?;
+
+        Ok(())
+    }
+
+    /// Display the `View`. `View` implements the `Display` trait and so can be rendered in many ways (such as `println!("{view}");`), but this is intended to be the fastest way possible. To only redraw the cells that changed since the last frame, use a [`DiffRenderer`] instead. The frame is wrapped in synchronized update escape sequences when [`synchronized_output()`](utils::synchronized_output()) is on, so the terminal never shows it half drawn
     ///
     /// # Errors
     /// Returns the `Result` from writing to `io::stdout().lock()`. You can ignore it with `let _ = ...` most of the time
     pub fn display_render(&self) -> io::Result<()> {
+        engine_span!("display_render", width = self.width, height = self.height);
         let mut stdout = io::stdout().lock();
         if self.block_until_resized {
             let view_size = self.size();
             crate_utils::block_until_resized(view_size);
         }
 
-        write!(stdout, "{self}")
+        crate_utils::write_synchronized(&mut stdout, |stdout| write!(stdout, "{self}"))
     }
 
     /// Writes the View to a `std::string::String` similar to the implementation of the Display
@@ -140,10 +638,9 @@ impl View {
     pub fn to_string(&self) -> Result<String, fmt::Error> {
         let mut output = String::new(); // Create a String buffer
 
-
         // Write the escape sequences to clear the terminal
         // output.push_str("\x1b[H\x1b[J");
-        
+
         if self.coord_numbers_in_render {
             let nums: String = (0..self.width)
                 .map(|i| i.to_string().chars().last().unwrap_or(' '))
@@ -157,21 +654,15 @@ impl View {
                 write!(output, "{num}")?;
             }
 
-            let row = &self.pixels[self.width * y..self.width * (y + 1)];
+            let row = self.render_row(y);
 
-            row[0].write_with_prev_and_next(&mut output, None, Some(row[1].modifier))?;
-            for x in 1..(row.len() - 1) {
+            for x in 0..row.len() {
                 row[x].write_with_prev_and_next(
                     &mut output,
-                    Some(row[x - 1].modifier),
-                    Some(row[x + 1].modifier),
+                    x.checked_sub(1).map(|i| row[i].modifier),
+                    row.get(x + 1).map(|c| c.modifier),
                 )?;
             }
-            row[row.len() - 1].write_with_prev_and_next(
-                &mut output,
-                Some(row[row.len() - 2].modifier),
-                None,
-            )?;
             output.push_str("\r\n"); // Use push_str for new line
         }
 
@@ -180,46 +671,223 @@ impl View {
         Ok(output) // Return the constructed string
     }
 
+    /// Return the row of `ColChar`s to print, with the [`glyph_policy`](View::glyph_policy) and [`colour_adjustment`](View::colour_adjustment) applied
+    fn render_row(&self, y: usize) -> Vec<ColChar> {
+        let mut row = self
+            .glyph_policy
+            .apply_to_row(&self.displayed()[self.width * y..self.width * (y + 1)]);
+        if !self.colour_adjustment.is_neutral() {
+            for c in &mut row {
+                *c = self.colour_adjustment.apply(*c);
+            }
+        }
+
+        row
+    }
 }
 
 impl Display for View {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         crate::utils::prepare_terminal(f).map_err(|_| fmt::Error)?;
 
-        f.write_str("\x1b[H\x1b[J")?;
-        if self.coord_numbers_in_render {
-            let nums: String = (0..self.width)
-                .map(|i| i.to_string().chars().last().unwrap_or(' '))
-                .collect();
-            writeln!(f, " {nums}")?;
-        }
-        for y in 0..self.height {
-            if self.coord_numbers_in_render {
-                let num = y.to_string().chars().last().unwrap_or(' ');
-                write!(f, "{num}")?;
-            }
+        Frame(self).fmt(f)
+    }
+}
 
-            let row = &self.pixels[self.width * y..self.width * (y + 1)];
+/// Displays a [`View`] as a single frame, without preparing the terminal first
+struct Frame<'a>(&'a View);
 
-            row[0].display_with_prev_and_next(f, None, Some(row[1].modifier))?;
-            for x in 1..(row.len() - 1) {
-                row[x].display_with_prev_and_next(
-                    f,
-                    Some(row[x - 1].modifier),
-                    Some(row[x + 1].modifier),
-                )?;
-            }
-            row[row.len() - 1].display_with_prev_and_next(
-                f,
-                Some(row[row.len() - 2].modifier),
-                None,
-            )?;
-            f.write_str("\r\n")?;
+impl Display for Frame<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Self(view) = self;
+        view.write_frame(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{Line, Rect};
+
+    #[test]
+    fn depth_decides_overlap_regardless_of_order() {
+        let mut view = View::new(3, 1, ColChar::EMPTY);
+        let (near, far) = (ColChar::SOLID, ColChar::BACKGROUND);
+
+        view.plot_with_depth(Vec2D::new(0, 0), near, 1.0, Wrapping::Panic);
+        view.plot_with_depth(Vec2D::new(0, 0), far, 5.0, Wrapping::Panic);
+        view.plot_with_depth(Vec2D::new(1, 0), far, 5.0, Wrapping::Panic);
+        view.plot_with_depth(Vec2D::new(1, 0), near, 1.0, Wrapping::Panic);
+        assert_eq!(view.get(Vec2D::new(0, 0)), Some(near));
+        assert_eq!(view.get(Vec2D::new(1, 0)), Some(near));
+        assert_eq!(view.depth_at(Vec2D::new(1, 0)), Some(1.0));
+        assert_eq!(view.depth_at(Vec2D::new(2, 0)), None);
+
+        view.clear();
+        assert_eq!(view.depth_at(Vec2D::new(0, 0)), None);
+        view.plot_with_depth(Vec2D::new(0, 0), far, 5.0, Wrapping::Panic);
+        assert_eq!(view.get(Vec2D::new(0, 0)), Some(far));
+    }
+
+    #[test]
+    fn plotting_without_depth_forgets_the_depth() {
+        let mut view = View::new(3, 1, ColChar::EMPTY);
+        let (near, far) = (ColChar::SOLID, ColChar::BACKGROUND);
+
+        for x in 0..3 {
+            view.plot_with_depth(Vec2D::new(x, 0), near, 1.0, Wrapping::Panic);
         }
-        f.write_str("\x1b[J")?;
+        view.plot(Vec2D::new(0, 0), ColChar::EMPTY, Wrapping::Panic);
+        assert!(view
+            .try_plot(Vec2D::new(1, 0), ColChar::EMPTY, Wrapping::Panic)
+            .is_ok());
+        view.plot_with_alpha(Vec2D::new(2, 0), ColChar::EMPTY, 0.5, Wrapping::Panic);
 
-        Ok(())
+        for x in 0..3 {
+            assert_eq!(view.depth_at(Vec2D::new(x, 0)), None);
+            view.plot_with_depth(Vec2D::new(x, 0), far, 5.0, Wrapping::Panic);
+            assert_eq!(view.get(Vec2D::new(x, 0)), Some(far));
+        }
+    }
+
+    #[test]
+    fn alpha_blends_with_the_canvas() {
+        let mut view = View::new(3, 1, ColChar::EMPTY);
+        view.plot(
+            Vec2D::new(1, 0),
+            ColChar::new('#', Modifier::from_rgb(0, 0, 200)),
+            Wrapping::Panic,
+        );
+        let ghost = Rect::new(
+            Vec2D::ZERO,
+            Vec2D::new(3, 1),
+            ColChar::SOLID.with_rgb(200, 0, 0),
+        );
+
+        view.blit_with_alpha(&ghost, 0.25, Wrapping::Panic);
+        assert_eq!(
+            view.get(Vec2D::new(0, 0)),
+            Some(ColChar::SOLID.with_rgb(241, 191, 191))
+        );
+        assert_eq!(
+            view.get(Vec2D::new(1, 0)),
+            Some(ColChar::new('#', Modifier::from_rgb(50, 0, 150)))
+        );
+
+        view.plot_with_alpha(
+            Vec2D::new(2, 0),
+            ColChar::SOLID.with_bg_rgb(0, 100, 0),
+            0.5,
+            Wrapping::Panic,
+        );
+        let set = ModifierSet::new()
+            .with_foreground(Colour::rgb(248, 223, 223))
+            .with_background(Colour::rgb(0, 50, 0));
+        assert_eq!(
+            view.get(Vec2D::new(2, 0)),
+            Some(ColChar::new('█', set.into()))
+        );
+
+        view.plot_with_alpha(Vec2D::new(0, 0), ColChar::SOLID, 0.0, Wrapping::Panic);
+        view.plot_with_alpha(Vec2D::new(1, 0), ColChar::SOLID, 1.0, Wrapping::Panic);
+        assert_eq!(view.get(Vec2D::new(1, 0)), Some(ColChar::SOLID));
+    }
+
+    #[test]
+    fn fallible_plotting_reports_errors() {
+        let mut view = View::new(4, 2, ColChar::EMPTY);
+        let rect = Rect::new(Vec2D::new(2, 0), Vec2D::new(3, 2), ColChar::SOLID);
+
+        assert_eq!(
+            view.try_blit(&rect, Wrapping::Panic),
+            Err(EngineError::OutOfBounds {
+                pos: Vec2D::new(4, 0),
+                size: Vec2D::new(4, 2)
+            })
+        );
+        assert_eq!(view.get(Vec2D::new(2, 0)), Some(ColChar::EMPTY));
+        assert_eq!(view.try_blit(&rect, Wrapping::Wrap), Ok(()));
+        assert_eq!(view.get(Vec2D::new(0, 1)), Some(ColChar::SOLID));
+        assert_eq!(
+            view.try_plot(Vec2D::new(-1, 0), ColChar::SOLID, Wrapping::Ignore),
+            Ok(())
+        );
+
+        assert_eq!(
+            View::try_new(3, 0, ColChar::EMPTY).err(),
+            Some(EngineError::InvalidSize(Vec2D::new(3, 0)))
+        );
+        let mut empty = View::new(0, 0, ColChar::EMPTY);
+        assert!(empty
+            .try_plot(Vec2D::ZERO, ColChar::SOLID, Wrapping::Wrap)
+            .is_err());
     }
-}
 
+    #[test]
+    fn clamping_and_wrapping_override() {
+        let mut view = View::new(3, 2, ColChar::EMPTY);
+        view.plot(Vec2D::new(7, -4), ColChar::SOLID, Wrapping::Clamp);
+        assert_eq!(view.get(Vec2D::new(2, 0)), Some(ColChar::SOLID));
 
+        view.wrapping_override = Some(Wrapping::Panic);
+        assert!(view
+            .try_plot(Vec2D::new(3, 0), ColChar::SOLID, Wrapping::Ignore)
+            .is_err());
+        view.wrapping_override = Some(Wrapping::Ignore);
+        view.plot(Vec2D::new(4, 0), ColChar::SOLID, Wrapping::Wrap);
+        assert_eq!(view.get(Vec2D::new(1, 0)), Some(ColChar::EMPTY));
+    }
+
+    #[test]
+    fn world_space_follows_the_camera() {
+        let mut view = View::new(4, 4, ColChar::EMPTY);
+        let line = Line::new(Vec2D::new(-3, -1), Vec2D::new(3, -1), ColChar::SOLID);
+
+        view.camera = Vec2D::new(-2, -2);
+        view.blit_world(&line, Wrapping::Ignore);
+        let drawn = (0..4)
+            .filter(|x| view.get(Vec2D::new(*x, 1)) == Some(ColChar::SOLID))
+            .count();
+        assert_eq!(drawn, 4);
+        assert_eq!(view.world_to_screen(Vec2D::new(-3, -1)), Vec2D::new(-1, 1));
+    }
+
+    #[test]
+    fn blit_all_matches_blitting_in_order() {
+        let back = Rect::new(Vec2D::new(-1, 0), Vec2D::new(4, 3), ColChar::SOLID);
+        let front = Line::new(Vec2D::new(0, 2), Vec2D::new(3, -1), ColChar::BACKGROUND);
+        let elements: [&dyn ViewElement; 2] = [&back, &front];
+
+        let mut batched = View::new(3, 3, ColChar::EMPTY);
+        batched.blit_all(&elements, Wrapping::Wrap);
+        let mut sequential = View::new(3, 3, ColChar::EMPTY);
+        sequential.blit(&back, Wrapping::Wrap);
+        sequential.blit(&front, Wrapping::Wrap);
+        assert_eq!(batched.pixels, sequential.pixels);
+    }
+
+    #[test]
+    fn fits_the_terminal_size() {
+        let mut view = View::new(10, 5, ColChar::EMPTY);
+        assert!(view.fit_to_terminal(Vec2D::new(80, 24)));
+        assert_eq!(view.size(), Vec2D::new(80, 23));
+        assert!(!view.fit_to_terminal(Vec2D::new(80, 24)));
+
+        view.resize(3, 2);
+        assert_eq!(view.cells().len(), 6);
+        assert!(!view.was_resized());
+    }
+
+    #[test]
+    fn to_string_keeps_modifiers() {
+        let mut view = View::new(3, 1, ColChar::EMPTY);
+        let red = ColChar::new('x', Modifier::RED);
+        view.plot(Vec2D::new(1, 0), red, Wrapping::Panic);
+        view.plot(Vec2D::new(2, 0), red, Wrapping::Panic);
+
+        assert_eq!(
+            view.to_string().expect("writing to a String can't fail"),
+            " \x1b[0m\x1b[31mxx\x1b[0m\r\n"
+        );
+    }
+}
@@ -2,6 +2,7 @@ use super::ViewElement;
 
 pub mod colchar;
 pub mod vec2d;
+pub mod vec2f;
 
 use colchar::ColChar;
 use vec2d::Vec2D;
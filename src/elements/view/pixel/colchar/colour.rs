@@ -1,4 +1,5 @@
 use std::{
+    fmt::{self, Display},
     ops::{Add, AddAssign, Mul, MulAssign},
     str::FromStr,
 };
@@ -23,15 +24,19 @@ pub struct Colour {
 impl FromStr for Colour {
     type Err = String;
 
-    /// Colours should be passed in the format `<r>,<g>,<b>`, for example `255,0,0` for red
+    /// Colours should be passed either in the format `<r>,<g>,<b>`, for example `255,0,0` for red, or as a hex code accepted by [`Colour::from_hex()`], for example `#ff0000`
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().starts_with('#') {
+            return Self::from_hex(s.trim())
+                .ok_or_else(|| format!("Invalid hex colour {s}, expected #rrggbb or #rgb"));
+        }
+
         let s = s.replace(' ', "");
         let parts: Vec<&str> = s.split(',').collect();
 
         if parts.len() != 3 {
             return Err(String::from("Incorrect number of arguments, string must be in format r,g,b to be parsed correctly"));
         }
-        println!("{parts:?}");
 
         let mut nums = Vec::new();
 
@@ -62,6 +67,45 @@ impl Colour {
         Self { r, g, b }
     }
 
+    /// Create a `Colour` from a hex code in the format `#rrggbb` or the shorthand `#rgb`, with or without the `#`. Returns `None` if the code isn't valid. Being `const`, it can define palettes as constants:
+    /// ```
+    /// use gemini_engine::elements::view::Colour;
+    ///
+    /// const ORANGE: Colour = Colour::from_hex("#ff8800").expect("valid hex code");
+    ///
+    /// assert_eq!(ORANGE, Colour::rgb(255, 136, 0));
+    /// assert_eq!(Colour::from_hex("f80"), Some(ORANGE));
+    /// assert_eq!(ORANGE.to_string(), "#ff8800");
+    /// ```
+    #[must_use]
+    pub const fn from_hex(hex: &str) -> Option<Self> {
+        let digits = match hex.as_bytes() {
+            [b'#', digits @ ..] | digits => digits,
+        };
+
+        let mut values = [0u8; 6];
+        let mut i = 0;
+        while i < digits.len() && i < 6 {
+            values[i] = match digits[i] {
+                digit @ b'0'..=b'9' => digit - b'0',
+                digit @ b'a'..=b'f' => digit - b'a' + 10,
+                digit @ b'A'..=b'F' => digit - b'A' + 10,
+                _ => return None,
+            };
+            i += 1;
+        }
+
+        match digits.len() {
+            3 => Some(Self::rgb(values[0] * 17, values[1] * 17, values[2] * 17)),
+            6 => Some(Self::rgb(
+                values[0] * 16 + values[1],
+                values[2] * 16 + values[3],
+                values[4] * 16 + values[5],
+            )),
+            _ => None,
+        }
+    }
+
     /// Create a `Colour` from a single brightness value, resulting in a shade of grey
     #[must_use]
     pub const fn greyscale(v: u8) -> Self {
@@ -71,13 +115,22 @@ impl Colour {
     /// Create a `Colour` from an HSV value
     #[must_use]
     pub fn hsv(hue: u8, sat: u8, val: u8) -> Self {
-        let hue = f32::from(hue) / 255.0;
-        let sat = f32::from(sat) / 255.0;
-        let val = f32::from(val) / 255.0;
+        Self::from_hsv_f64(
+            f64::from(hue) / 255.0,
+            f64::from(sat) / 255.0,
+            f64::from(val) / 255.0,
+        )
+    }
+
+    /// Create a `Colour` from HSV values between 0.0 and 1.0
+    fn from_hsv_f64(hue: f64, sat: f64, val: f64) -> Self {
+        let hue = hue.rem_euclid(1.0);
+        let sat = sat.clamp(0.0, 1.0);
+        let val = val.clamp(0.0, 1.0);
 
         let index = (hue * 6.0).floor();
         let f = hue.mul_add(6.0, -index);
-        let p = val * f.mul_add(-sat, 1.0);
+        let p = val * (1.0 - sat);
         let q = val * f.mul_add(-sat, 1.0);
         let t = val * (1.0 - f).mul_add(-sat, 1.0);
 
@@ -88,7 +141,7 @@ impl Colour {
             (p, q, val),
             (t, p, val),
             (val, p, q),
-        ][(index % 6.0).floor() as usize];
+        ][(index % 6.0) as usize];
 
         Self::rgb(
             mul_by_f64_to_u8(red, 255.0),
@@ -96,6 +149,124 @@ impl Colour {
             mul_by_f64_to_u8(blue, 255.0),
         )
     }
+
+    /// Return the `Colour`'s hue, saturation and value, each between 0.0 and 1.0
+    fn to_hsv_f64(self) -> (f64, f64, f64) {
+        let red = f64::from(self.r) / 255.0;
+        let green = f64::from(self.g) / 255.0;
+        let blue = f64::from(self.b) / 255.0;
+
+        let max = red.max(green).max(blue);
+        let min = red.min(green).min(blue);
+        let delta = max - min;
+
+        let hue = if delta == 0.0 {
+            0.0
+        } else if (max - red).abs() < f64::EPSILON {
+            ((green - blue) / delta).rem_euclid(6.0)
+        } else if (max - green).abs() < f64::EPSILON {
+            (blue - red) / delta + 2.0
+        } else {
+            (red - green) / delta + 4.0
+        } / 6.0;
+        let sat = if max == 0.0 { 0.0 } else { delta / max };
+
+        (hue, sat, max)
+    }
+
+    /// Return the `Colour` as an HSV value, in the same format accepted by [`Colour::hsv()`]
+    #[must_use]
+    pub fn to_hsv(self) -> (u8, u8, u8) {
+        let (hue, sat, val) = self.to_hsv_f64();
+
+        (
+            mul_by_f64_to_u8(hue, 255.0),
+            mul_by_f64_to_u8(sat, 255.0),
+            mul_by_f64_to_u8(val, 255.0),
+        )
+    }
+
+    /// Return the `Colour` with its hue rotated by the given number of degrees. Negative values rotate the other way
+    #[must_use]
+    pub fn shift_hue(self, degrees: f64) -> Self {
+        let (hue, sat, val) = self.to_hsv_f64();
+        Self::from_hsv_f64(hue + degrees / 360.0, sat, val)
+    }
+
+    /// Return the `Colour` with its saturation increased by `amount`, where 1.0 is the full range of saturation. The saturation is capped at fully saturated
+    #[must_use]
+    pub fn saturate(self, amount: f64) -> Self {
+        let (hue, sat, val) = self.to_hsv_f64();
+        Self::from_hsv_f64(hue, sat + amount, val)
+    }
+
+    /// Return the `Colour` with its saturation decreased by `amount`, where 1.0 is the full range of saturation. A `Colour` desaturated by 1.0 is a shade of grey
+    #[must_use]
+    pub fn desaturate(self, amount: f64) -> Self {
+        self.saturate(-amount)
+    }
+
+    /// Return the `Colour` blended towards `other` by `amount`, where 0.0 returns this `Colour` and 1.0 returns `other`. The amount is clamped to between 0.0 and 1.0
+    #[must_use]
+    pub fn lerp(self, other: Self, amount: f64) -> Self {
+        let amount = amount.clamp(0.0, 1.0);
+        let mix = |from: u8, to: u8| {
+            mul_by_f64_to_u8(
+                (f64::from(to) - f64::from(from)).mul_add(amount, f64::from(from)),
+                1.0,
+            )
+        };
+
+        Self::rgb(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+        )
+    }
+
+    /// Return the relative luminance of the `Colour` as defined by WCAG 2, from 0.0 for black to 1.0 for white
+    #[must_use]
+    pub fn relative_luminance(self) -> f64 {
+        let linear = |channel: u8| {
+            let c = f64::from(channel) / 255.0;
+            if c <= 0.040_45 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+
+        0.2126f64.mul_add(
+            linear(self.r),
+            0.7152f64.mul_add(linear(self.g), 0.0722 * linear(self.b)),
+        )
+    }
+
+    /// Return the WCAG 2 contrast ratio between the two colours, from 1.0 for identical colours to 21.0 for black on white. Text should have a contrast ratio of at least 4.5 with its background to be readable
+    #[must_use]
+    pub fn contrast_ratio(self, other: Self) -> f64 {
+        let a = self.relative_luminance();
+        let b = other.relative_luminance();
+
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// Return either black or white, whichever is more readable as text on the given background
+    #[must_use]
+    pub fn readable_on(background: Self) -> Self {
+        if Self::BLACK.contrast_ratio(background) >= Self::WHITE.contrast_ratio(background) {
+            Self::BLACK
+        } else {
+            Self::WHITE
+        }
+    }
+}
+
+impl Display for Colour {
+    /// Colours are displayed as a hex code such as `#ff8800`, which [`Colour::from_str()`] can read back
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
 }
 
 impl Add for Colour {
@@ -131,3 +302,63 @@ impl MulAssign<f64> for Colour {
         self.r = mul_by_f64_to_u8(self.b, rhs);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hsv_round_trip() {
+        let colour = Colour::rgb(200, 100, 50);
+        let (h, s, v) = colour.to_hsv_f64();
+        let round_trip = Colour::from_hsv_f64(h, s, v);
+
+        assert!(colour.r.abs_diff(round_trip.r) <= 3);
+        assert!(colour.g.abs_diff(round_trip.g) <= 3);
+        assert!(colour.b.abs_diff(round_trip.b) <= 3);
+    }
+
+    #[test]
+    fn hsv_primaries() {
+        assert_eq!(Colour::hsv(0, 255, 255), Colour::rgb(255, 0, 0));
+        assert_eq!(Colour::hsv(85, 255, 255), Colour::rgb(0, 255, 0));
+        assert_eq!(Colour::hsv(0, 0, 255), Colour::WHITE);
+    }
+
+    #[test]
+    fn shift_hue_and_desaturate() {
+        assert_eq!(
+            Colour::rgb(255, 0, 0).shift_hue(120.0),
+            Colour::rgb(0, 255, 0)
+        );
+        assert_eq!(
+            Colour::rgb(255, 0, 0).shift_hue(-120.0),
+            Colour::rgb(0, 0, 255)
+        );
+        assert_eq!(Colour::rgb(255, 0, 0).desaturate(1.0), Colour::WHITE);
+    }
+
+    #[test]
+    fn contrast() {
+        assert!((Colour::BLACK.contrast_ratio(Colour::WHITE) - 21.0).abs() < 0.01);
+        assert!(
+            (Colour::rgb(10, 20, 30).contrast_ratio(Colour::rgb(10, 20, 30)) - 1.0).abs() < 0.01
+        );
+        assert_eq!(Colour::readable_on(Colour::rgb(255, 255, 0)), Colour::BLACK);
+        assert_eq!(Colour::readable_on(Colour::rgb(0, 0, 128)), Colour::WHITE);
+    }
+
+    #[test]
+    fn hex_parsing_and_formatting() {
+        assert_eq!(Colour::from_hex("#1A2b3C"), Some(Colour::rgb(26, 43, 60)));
+        assert_eq!(Colour::from_hex("#fff"), Some(Colour::WHITE));
+        assert_eq!(Colour::from_hex("#ff88"), None);
+        assert_eq!(Colour::from_hex("#gg8800"), None);
+        assert_eq!(Colour::from_hex(""), None);
+
+        let colour = Colour::rgb(3, 200, 255);
+        assert_eq!(colour.to_string().parse(), Ok(colour));
+        assert_eq!(" 3, 200,255".parse(), Ok(colour));
+        assert!("#12345".parse::<Colour>().is_err());
+    }
+}
@@ -0,0 +1,112 @@
+use super::Colour;
+
+/// A `Gradient` blends smoothly between any number of colour stops, each placed at a position from 0.0 to 1.0. Sample it with [`sample()`](Gradient::sample()) for the colour of a health bar at a certain health, or fill whole elements with it using [`GradientFill`](crate::elements::containers::GradientFill) for sky and fire effects
+/// ```
+/// use gemini_engine::elements::view::{Colour, Gradient};
+///
+/// let health = Gradient::new(vec![
+///     (0.0, Colour::rgb(255, 0, 0)),
+///     (0.5, Colour::rgb(255, 255, 0)),
+///     (1.0, Colour::rgb(0, 255, 0)),
+/// ]);
+///
+/// assert_eq!(health.sample(0.5), Colour::rgb(255, 255, 0));
+/// assert_eq!(health.sample(0.75), Colour::rgb(128, 255, 0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    stops: Vec<(f64, Colour)>,
+}
+
+impl Gradient {
+    /// Create a new `Gradient` from a list of positions and the colours at them. The stops don't have to be in order
+    #[must_use]
+    pub fn new(stops: Vec<(f64, Colour)>) -> Self {
+        let mut gradient = Self { stops };
+        gradient.sort();
+        gradient
+    }
+
+    /// Create a new `Gradient` with the colours spread evenly from 0.0 to 1.0
+    #[must_use]
+    pub fn even(colours: &[Colour]) -> Self {
+        let last = colours.len().saturating_sub(1).max(1) as f64;
+        Self {
+            stops: (0..)
+                .zip(colours)
+                .map(|(i, colour)| (f64::from(i) / last, *colour))
+                .collect(),
+        }
+    }
+
+    /// Return the `Gradient` with another colour stop
+    #[must_use]
+    pub fn with_stop(mut self, pos: f64, colour: Colour) -> Self {
+        self.stops.push((pos, colour));
+        self.sort();
+        self
+    }
+
+    /// Return the colour stops of the `Gradient`, in order of position
+    #[must_use]
+    pub fn stops(&self) -> &[(f64, Colour)] {
+        &self.stops
+    }
+
+    fn sort(&mut self) {
+        self.stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+    }
+
+    /// Return the colour at the position, blended with [`Colour::lerp()`] between the stops either side of it. Positions before the first stop or after the last take that stop's colour, and an empty `Gradient` is always black
+    #[must_use]
+    pub fn sample(&self, t: f64) -> Colour {
+        let Some(&(first_pos, first)) = self.stops.first() else {
+            return Colour::BLACK;
+        };
+        if t <= first_pos {
+            return first;
+        }
+
+        for pair in self.stops.windows(2) {
+            let ((from_pos, from), (to_pos, to)) = (pair[0], pair[1]);
+            if t <= to_pos {
+                let span = to_pos - from_pos;
+                let amount = if span > 0.0 {
+                    (t - from_pos) / span
+                } else {
+                    1.0
+                };
+                return from.lerp(to, amount);
+            }
+        }
+
+        self.stops.last().map_or(first, |stop| stop.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_between_unsorted_stops() {
+        let gradient = Gradient::new(vec![
+            (1.0, Colour::rgb(0, 0, 200)),
+            (0.0, Colour::rgb(200, 0, 0)),
+        ])
+        .with_stop(0.5, Colour::rgb(0, 200, 0));
+        assert_eq!(gradient.stops()[1].1, Colour::rgb(0, 200, 0));
+        assert_eq!(gradient.sample(-1.0), Colour::rgb(200, 0, 0));
+        assert_eq!(gradient.sample(0.25), Colour::rgb(100, 100, 0));
+        assert_eq!(gradient.sample(2.0), Colour::rgb(0, 0, 200));
+    }
+
+    #[test]
+    fn even_stops() {
+        let gradient = Gradient::even(&[Colour::BLACK, Colour::WHITE, Colour::BLACK]);
+        assert_eq!(gradient.stops()[1].0.to_bits(), 0.5f64.to_bits());
+        assert_eq!(gradient.sample(0.5), Colour::WHITE);
+        assert_eq!(Gradient::even(&[]).sample(0.5), Colour::BLACK);
+        assert_eq!(Gradient::even(&[Colour::WHITE]).sample(0.2), Colour::WHITE);
+    }
+}
@@ -1,10 +1,18 @@
 use std::fmt::{self, Debug, Display};
 mod colour;
+mod gradient;
 mod modifier;
+mod modifier_set;
+mod palette;
 pub use colour::Colour;
+pub use gradient::Gradient;
 pub use modifier::Modifier;
+pub use modifier_set::{ModifierSet, TextStyle};
+pub use palette::{Dithering, Palette};
 use std::fmt::Write; // Import the Write trait from std::fmt
 
+use crate::{elements::view::ColourMode, utils::is_zero_width};
+
 /// We use `ColChar` to say exactly what each pixel should look like and what colour it should be. That is, the [`View`](super::super::View)'s canvas is just a vector of `ColChar`s under the hood. `ColChar` has the [`text_char`](ColChar::text_char) and [`modifier`](ColChar::modifier) properties. [`text_char`](ColChar::text_char) is the single ascii character used as the "pixel" when the [`View`](super::super::View) is rendered, whereas [`modifier`](ColChar::modifier) can give that pixel a colour or make it bold/italic
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ColChar {
@@ -95,10 +103,61 @@ impl ColChar {
         }
     }
 
-    /// Return the displayed `ColChar`, omitting the `Modifier`s where necessary
+    /// Return a `ColChar` with the same `text_char` and new `modifier` of the `Modifier::Background` enum variant from RGB values
+    #[must_use]
+    pub const fn with_bg_rgb(self, r: u8, g: u8, b: u8) -> Self {
+        Self {
+            text_char: self.text_char,
+            modifier: Modifier::from_bg_rgb(r, g, b),
+        }
+    }
+
+    /// Return a `ColChar` with the same `text_char` and new `modifier` of the `Modifier::Background` enum variant from a [`Colour`]. Use this with [`ColChar::EMPTY`] to paint solid blocks of colour
+    #[must_use]
+    pub const fn with_bg_colour(self, colour: Colour) -> Self {
+        Self {
+            text_char: self.text_char,
+            modifier: Modifier::Background(colour),
+        }
+    }
+
+    /// Return this `ColChar` drawn at `alpha` opacity over the `under` one, blending their colours with [`Colour::lerp()`]. This `ColChar`'s character is used if `alpha` is at least 0.5 or `under` is an [`EMPTY`](ColChar::EMPTY) space, otherwise the character underneath shows through with its colour tinted. Cells without a text colour are treated as white and cells without a background colour as black, matching a default terminal. Background colours are only kept if either `ColChar` has one
+    #[must_use]
+    pub fn blend_over(self, under: Self, alpha: f64) -> Self {
+        let alpha = alpha.clamp(0.0, 1.0);
+        let background = |modifier: Modifier| match modifier {
+            Modifier::Background(colour) => Some(colour),
+            Modifier::Set(set) => set.background,
+            _ => None,
+        };
+        let foreground = |c: Self| c.modifier.colour().unwrap_or(Colour::WHITE);
+
+        let text_char = if alpha >= 0.5 || under.text_char == ' ' {
+            self.text_char
+        } else {
+            under.text_char
+        };
+        let colour = foreground(under).lerp(foreground(self), alpha);
+        let modifier = match (background(under.modifier), background(self.modifier)) {
+            (None, None) => Modifier::Colour(colour),
+            (under_bg, over_bg) => ModifierSet::new()
+                .with_foreground(colour)
+                .with_background(
+                    under_bg
+                        .unwrap_or(Colour::BLACK)
+                        .lerp(over_bg.unwrap_or(Colour::BLACK), alpha),
+                )
+                .into(),
+        };
+
+        Self::new(text_char, modifier)
+    }
+
+    /// Return the displayed `ColChar`, omitting the `Modifier`s where necessary and converting them to the [`ColourMode`]. Control and zero-width characters are written as a whitespace
     pub(crate) fn display_with_prev_and_next(
         self,
         f: &mut fmt::Formatter,
+        colour_mode: ColourMode,
         prev_mod: Option<Modifier>,
         next_mod: Option<Modifier>,
     ) -> fmt::Result {
@@ -113,10 +172,12 @@ impl ColChar {
             Modifier::END
         };
 
-        write!(f, "{}{}{}", modifier, self.text_char, end)
+        colour_mode.write_escape_sequence(f, modifier)?;
+        f.write_char(self.printable_char())?;
+        colour_mode.write_escape_sequence(f, end)
     }
-    
-    /// Writes the displayed `ColChar`, omitting the `Modifier`s where necessary
+
+    /// Writes the displayed `ColChar`, omitting the `Modifier`s where necessary. Control and zero-width characters are written as a whitespace
     pub(crate) fn write_with_prev_and_next(
         self,
         o: &mut std::string::String,
@@ -126,8 +187,7 @@ impl ColChar {
         let modifier = if prev_mod == Some(self.modifier) {
             Modifier::None
         } else {
-            Modifier::None
-            // self.modifier
+            self.modifier
         };
         let end = if next_mod == Some(self.modifier) {
             Modifier::None
@@ -135,7 +195,16 @@ impl ColChar {
             Modifier::END
         };
 
-        write!(o, "{}{}{}", modifier, self.text_char, end)
+        write!(o, "{}{}{}", modifier, self.printable_char(), end)
+    }
+
+    /// Return the `text_char`, or a whitespace if it is a control or zero-width character that would corrupt the layout of the rendered output
+    pub(crate) fn printable_char(self) -> char {
+        if self.text_char.is_control() || is_zero_width(self.text_char) {
+            ' '
+        } else {
+            self.text_char
+        }
     }
 }
 
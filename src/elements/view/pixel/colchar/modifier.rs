@@ -1,8 +1,10 @@
-use super::Colour;
+use super::{Colour, ModifierSet, Palette};
+use crate::EngineError;
 use std::fmt::Display;
 
-/// The `Modifier` enum is used for adding modifications to text such as colour, bold/italic/underline and others. It's essentially a wrapper for `\x1b[{x}m`, where {x} is a code or rgb value of some sort. `Modifier` is primarily used by [`ColChar`](super::ColChar) as one of its properties
+/// The `Modifier` enum is used for adding modifications to text such as colour, bold/italic/underline and others. Use [`Modifier::Set`] to combine several of them. It's essentially a wrapper for `\x1b[{x}m`, where {x} is a code or rgb value of some sort. `Modifier` is primarily used by [`ColChar`](super::ColChar) as one of its properties
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub enum Modifier {
     /// Represents a [`Modifier`] by an escape code. A `Modifier::Coded(31)` would return a `\x1b[31m`.
     ///
@@ -10,6 +12,10 @@ pub enum Modifier {
     Coded(u8),
     /// Represents a `Modifier` by a [`Colour`], which itself is an RGB value
     Colour(Colour),
+    /// Represents a `Modifier` that fills the background of the cell with a [`Colour`], for painting solid blocks of colour without relying on `█` characters
+    Background(Colour),
+    /// Represents a `Modifier` by a [`ModifierSet`], for combining a foreground colour, background colour and text styles in one cell
+    Set(ModifierSet),
     /// Represents a lack of `Modifier`, if you don't want the pixel to be coloured or decorated in any way
     #[default]
     None,
@@ -37,11 +43,92 @@ impl Modifier {
         Self::Colour(Colour::rgb(r, g, b))
     }
 
+    /// Create a `Modifier::Background` from an RGB value
+    #[must_use]
+    pub const fn from_bg_rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::Background(Colour::rgb(r, g, b))
+    }
+
     /// Create a `Modifier::Colour` from an HSV value
     #[must_use]
     pub fn from_hsv(h: u8, s: u8, v: u8) -> Self {
         Self::Colour(Colour::hsv(h, s, v))
     }
+
+    /// Return the colour the `Modifier` draws text in, if any. Standard and bright ANSI colour codes (`30`-`37` and `90`-`97`) are converted using the xterm defaults from [`Palette::ansi16()`], while other codes return `None`
+    #[must_use]
+    pub fn colour(&self) -> Option<Colour> {
+        match *self {
+            Self::Colour(colour) => Some(colour),
+            Self::Set(set) => set.foreground,
+            Self::Coded(code @ 30..=37) => {
+                Some(Palette::shared_ansi16().colours[usize::from(code - 30)])
+            }
+            Self::Coded(code @ 90..=97) => {
+                Some(Palette::shared_ansi16().colours[usize::from(code - 82)])
+            }
+            Self::Coded(_) | Self::Background(_) | Self::None => None,
+        }
+    }
+
+    /// Parse an ANSI SGR escape sequence such as `"\x1b[31m"` or `"\x1b[38;2;255;0;0m"` into a `Modifier`. Only the last code in a sequence with several (such as `"\x1b[1;31m"`) is used, so build a [`ModifierSet`] to keep them all. Reset codes (`0` or an empty sequence) and default colour codes (`39` and `49`) return [`Modifier::None`]. Extended background colours (`48;2;<r>;<g>;<b>` and `48;5;<n>`) become a [`Modifier::Background`], and 256-colour codes (`38;5;<n>`) are converted to a [`Modifier::Colour`]
+    ///
+    /// # Errors
+    /// Returns an error if the string isn't a single SGR sequence or contains codes that can't be parsed
+    pub fn parse_ansi(sequence: &str) -> Result<Self, EngineError> {
+        let params = sequence
+            .strip_prefix("\x1b[")
+            .and_then(|s| s.strip_suffix('m'))
+            .ok_or_else(|| {
+                EngineError::InvalidData(String::from(
+                    "SGR sequences must be in the format \\x1b[<codes>m",
+                ))
+            })?;
+
+        let codes = params
+            .split(';')
+            .map(|code| {
+                if code.is_empty() {
+                    Ok(0)
+                } else {
+                    code.parse::<u8>().map_err(|_| {
+                        EngineError::InvalidData(format!(
+                            "Could not parse code {code:?} in SGR sequence"
+                        ))
+                    })
+                }
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+
+        let mut modifier = Self::None;
+        let mut codes = codes.into_iter();
+        while let Some(code) = codes.next() {
+            modifier = match code {
+                0 | 39 | 49 => Self::None,
+                38 => Self::Colour(Self::parse_extended_colour(&mut codes)?),
+                48 => Self::Background(Self::parse_extended_colour(&mut codes)?),
+                code => Self::Coded(code),
+            };
+        }
+
+        Ok(modifier)
+    }
+
+    /// Parse the parameters of an extended colour code (the part after `38;` or `48;`), either `2;<r>;<g>;<b>` or `5;<n>`
+    fn parse_extended_colour(codes: &mut impl Iterator<Item = u8>) -> Result<Colour, EngineError> {
+        let invalid = |message: &str| EngineError::InvalidData(String::from(message));
+        match codes.next() {
+            Some(2) => match (codes.next(), codes.next(), codes.next()) {
+                (Some(r), Some(g), Some(b)) => Ok(Colour::rgb(r, g, b)),
+                _ => Err(invalid("Incomplete RGB colour in SGR sequence")),
+            },
+            Some(5) => codes
+                .next()
+                .map(|index| Palette::shared_xterm256().colours[usize::from(index)])
+                .ok_or_else(|| invalid("Incomplete 256-colour code in SGR sequence")),
+            _ => Err(invalid("Unknown extended colour in SGR sequence")),
+        }
+    }
 }
 
 impl Display for Modifier {
@@ -49,7 +136,47 @@ impl Display for Modifier {
         match self {
             Self::Coded(code) => write!(f, "\x1b[{code}m"),
             Self::Colour(c) => write!(f, "\x1b[38;2;{};{};{}m", c.r, c.g, c.b),
+            Self::Background(c) => write!(f, "\x1b[48;2;{};{};{}m", c.r, c.g, c.b),
+            Self::Set(set) => write!(f, "{set}"),
             Self::None => Ok(()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::view::ColChar;
+
+    #[test]
+    fn parse_sgr_sequences() {
+        assert_eq!(Modifier::parse_ansi("\x1b[31m"), Ok(Modifier::RED));
+        assert_eq!(
+            Modifier::parse_ansi("\x1b[1;38;2;1;2;3m"),
+            Ok(Modifier::from_rgb(1, 2, 3))
+        );
+        assert_eq!(Modifier::parse_ansi("\x1b[m"), Ok(Modifier::None));
+        assert!(Modifier::parse_ansi("31").is_err());
+    }
+
+    #[test]
+    fn background_round_trips_through_ansi() {
+        let background = Modifier::from_bg_rgb(10, 20, 30);
+        assert_eq!(background.to_string(), "\x1b[48;2;10;20;30m");
+        assert_eq!(
+            Modifier::parse_ansi(&background.to_string()),
+            Ok(background)
+        );
+        assert_eq!(background.colour(), None);
+        assert_eq!(
+            Modifier::parse_ansi("\x1b[48;5;196m"),
+            Ok(Modifier::from_bg_rgb(255, 0, 0))
+        );
+        assert_eq!(
+            ColChar::EMPTY
+                .with_bg_colour(Colour::rgb(0, 0, 255))
+                .to_string(),
+            "\x1b[48;2;0;0;255m \x1b[0m"
+        );
+    }
+}
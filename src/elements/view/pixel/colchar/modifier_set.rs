@@ -0,0 +1,216 @@
+use super::{Colour, Modifier};
+use std::{
+    fmt::{self, Display},
+    ops::{BitOr, BitOrAssign},
+};
+
+/// A combination of text styles such as bold and underline, used by [`ModifierSet`]. Styles can be combined with `|`, like `TextStyle::BOLD | TextStyle::ITALIC`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TextStyle(u16);
+
+impl TextStyle {
+    /// No styles
+    pub const NONE: Self = Self(0);
+    /// Bold or increased intensity
+    pub const BOLD: Self = Self(1 << 1);
+    /// Faint or decreased intensity
+    pub const DIM: Self = Self(1 << 2);
+    /// Italic
+    pub const ITALIC: Self = Self(1 << 3);
+    /// Underlined
+    pub const UNDERLINE: Self = Self(1 << 4);
+    /// Slowly blinking
+    pub const BLINK: Self = Self(1 << 5);
+    /// Foreground and background colours swapped
+    pub const REVERSE: Self = Self(1 << 7);
+    /// Crossed out
+    pub const STRIKETHROUGH: Self = Self(1 << 9);
+
+    /// Returns true if every style in `other` is also in this one
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns true if there are no styles
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Return the style for an SGR code from `1` to `9`, or `None` if the code isn't one of the supported styles
+    #[must_use]
+    pub const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            1 | 2 | 3 | 4 | 5 | 7 | 9 => Some(Self(1 << code)),
+            _ => None,
+        }
+    }
+
+    /// Return the SGR codes of every style, from lowest to highest
+    #[must_use]
+    pub fn codes(self) -> Vec<u8> {
+        (1..=9).filter(|code| self.0 & 1 << code != 0).collect()
+    }
+}
+
+impl BitOr for TextStyle {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for TextStyle {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// A `ModifierSet` holds a foreground colour, a background colour and any combination of [`TextStyle`]s together, which a single [`Modifier`] can't. It's displayed as one merged ANSI sequence, and is put in a [`ColChar`](super::ColChar) as a [`Modifier::Set`]
+/// ```
+/// use gemini_engine::elements::view::{ColChar, Colour, Modifier, ModifierSet, TextStyle};
+///
+/// let warning = ModifierSet::new()
+///     .with_foreground(Colour::rgb(255, 255, 0))
+///     .with_background(Colour::rgb(64, 0, 0))
+///     .with_style(TextStyle::BOLD | TextStyle::UNDERLINE);
+///
+/// assert_eq!(
+///     warning.to_string(),
+///     "\x1b[1;4;38;2;255;255;0;48;2;64;0;0m"
+/// );
+///
+/// let cell = ColChar::new('!', warning.into());
+/// assert_eq!(cell.modifier, Modifier::Set(warning));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ModifierSet {
+    /// The colour of the text, or `None` for the terminal's default
+    pub foreground: Option<Colour>,
+    /// The colour behind the text, or `None` for the terminal's default
+    pub background: Option<Colour>,
+    /// The styles applied to the text
+    pub style: TextStyle,
+}
+
+impl ModifierSet {
+    /// Create an empty `ModifierSet`, with no colours or styles
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            foreground: None,
+            background: None,
+            style: TextStyle::NONE,
+        }
+    }
+
+    /// Return the `ModifierSet` with a foreground colour
+    #[must_use]
+    pub const fn with_foreground(mut self, colour: Colour) -> Self {
+        self.foreground = Some(colour);
+        self
+    }
+
+    /// Return the `ModifierSet` with a background colour
+    #[must_use]
+    pub const fn with_background(mut self, colour: Colour) -> Self {
+        self.background = Some(colour);
+        self
+    }
+
+    /// Return the `ModifierSet` with the styles added to its existing ones
+    #[must_use]
+    pub const fn with_style(mut self, style: TextStyle) -> Self {
+        self.style = TextStyle(self.style.0 | style.0);
+        self
+    }
+
+    /// Returns true if the `ModifierSet` has no colours or styles
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.foreground.is_none() && self.background.is_none() && self.style.is_empty()
+    }
+
+    /// Return the `ModifierSet` with the [`Modifier`] layered on top of it. Colours replace the matching colour of the set, style codes are added to its styles, and [`Modifier::END`] clears it. Standard ANSI colour codes are converted with [`Modifier::colour()`], while other codes are ignored
+    #[must_use]
+    pub fn merge(mut self, modifier: Modifier) -> Self {
+        match modifier {
+            Modifier::Coded(0) => return Self::new(),
+            Modifier::Coded(code) => {
+                if let Some(style) = TextStyle::from_code(code) {
+                    self.style |= style;
+                } else if let Some(colour) = modifier.colour() {
+                    self.foreground = Some(colour);
+                }
+            }
+            Modifier::Colour(colour) => self.foreground = Some(colour),
+            Modifier::Background(colour) => self.background = Some(colour),
+            Modifier::Set(set) => {
+                self.foreground = set.foreground.or(self.foreground);
+                self.background = set.background.or(self.background);
+                self.style |= set.style;
+            }
+            Modifier::None => (),
+        }
+
+        self
+    }
+}
+
+impl From<ModifierSet> for Modifier {
+    fn from(set: ModifierSet) -> Self {
+        if set.is_empty() {
+            Self::None
+        } else {
+            Self::Set(set)
+        }
+    }
+}
+
+impl Display for ModifierSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        let mut params: Vec<String> = self.style.codes().iter().map(u8::to_string).collect();
+        if let Some(c) = self.foreground {
+            params.push(format!("38;2;{};{};{}", c.r, c.g, c.b));
+        }
+        if let Some(c) = self.background {
+            params.push(format!("48;2;{};{};{}", c.r, c.g, c.b));
+        }
+
+        write!(f, "\x1b[{}m", params.join(";"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn styles_combine_and_list_codes() {
+        let style = TextStyle::BOLD | TextStyle::STRIKETHROUGH | TextStyle::ITALIC;
+        assert_eq!(style.codes(), vec![1, 3, 9]);
+        assert!(style.contains(TextStyle::BOLD | TextStyle::ITALIC));
+        assert!(!style.contains(TextStyle::DIM));
+        assert_eq!(TextStyle::from_code(6), None);
+        assert_eq!(ModifierSet::new().to_string(), "");
+    }
+
+    #[test]
+    fn merges_modifiers() {
+        let set = ModifierSet::new()
+            .merge(Modifier::RED)
+            .merge(Modifier::Coded(1))
+            .merge(Modifier::from_bg_rgb(0, 0, 255));
+        assert_eq!(set.foreground, Modifier::RED.colour());
+        assert_eq!(set.background, Some(Colour::rgb(0, 0, 255)));
+        assert_eq!(set.style, TextStyle::BOLD);
+        assert!(set.merge(Modifier::END).is_empty());
+        assert_eq!(Modifier::from(ModifierSet::new()), Modifier::None);
+    }
+}
@@ -0,0 +1,303 @@
+use super::{Colour, Modifier};
+use crate::elements::{Pixel, Vec2D};
+use std::sync::OnceLock;
+
+static ANSI16: OnceLock<Palette> = OnceLock::new();
+static XTERM256: OnceLock<Palette> = OnceLock::new();
+
+/// The 4x4 Bayer matrix used for [`Dithering::Ordered`], with values from 0 to 15
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// How a [`Palette`] spreads out the error when quantising colours, which reduces colour banding on terminals with limited colours
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dithering {
+    /// Replace every colour with the nearest colour in the palette
+    #[default]
+    None,
+    /// Offset every colour by a repeating 4x4 Bayer pattern before picking the nearest colour. Stable between frames, making it suited to animated sprites
+    Ordered,
+    /// Spread each pixel's quantisation error to its unprocessed neighbours. Gives the smoothest result for still images
+    FloydSteinberg,
+}
+
+impl Dithering {
+    /// Return the threshold of the repeating 4x4 Bayer pattern at the position, from 0.0 up to but not including 1.0. Comparing a value against the threshold spreads it evenly over neighbouring cells, as used for [`Dithering::Ordered`] and [`Opacity`](crate::elements::containers::Opacity)
+    #[must_use]
+    pub fn bayer_threshold(pos: Vec2D) -> f64 {
+        f64::from(BAYER_4X4[pos.y.rem_euclid(4) as usize][pos.x.rem_euclid(4) as usize]) / 16.0
+    }
+}
+
+/// A limited set of colours that images and gradients can be quantised to, for terminals without truecolor support
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    /// The colours in the palette
+    pub colours: Vec<Colour>,
+}
+
+impl Palette {
+    /// Create a new `Palette` from a list of colours
+    #[must_use]
+    pub const fn new(colours: Vec<Colour>) -> Self {
+        Self { colours }
+    }
+
+    /// The 16 standard ANSI colours, using the xterm defaults
+    #[must_use]
+    pub fn ansi16() -> Self {
+        Self::new(vec![
+            Colour::rgb(0, 0, 0),
+            Colour::rgb(205, 0, 0),
+            Colour::rgb(0, 205, 0),
+            Colour::rgb(205, 205, 0),
+            Colour::rgb(0, 0, 238),
+            Colour::rgb(205, 0, 205),
+            Colour::rgb(0, 205, 205),
+            Colour::rgb(229, 229, 229),
+            Colour::rgb(127, 127, 127),
+            Colour::rgb(255, 0, 0),
+            Colour::rgb(0, 255, 0),
+            Colour::rgb(255, 255, 0),
+            Colour::rgb(92, 92, 255),
+            Colour::rgb(255, 0, 255),
+            Colour::rgb(0, 255, 255),
+            Colour::rgb(255, 255, 255),
+        ])
+    }
+
+    /// The 256 xterm colours: the 16 ANSI colours, a 6x6x6 colour cube and 24 shades of grey
+    #[must_use]
+    pub fn xterm256() -> Self {
+        const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+        let mut palette = Self::ansi16();
+        for r in LEVELS {
+            for g in LEVELS {
+                for b in LEVELS {
+                    palette.colours.push(Colour::rgb(r, g, b));
+                }
+            }
+        }
+        palette
+            .colours
+            .extend((0..24).map(|i| Colour::greyscale(8 + i * 10)));
+
+        palette
+    }
+
+    /// Return a shared copy of [`ansi16()`](Palette::ansi16()), built the first time it's needed
+    pub(crate) fn shared_ansi16() -> &'static Self {
+        ANSI16.get_or_init(Self::ansi16)
+    }
+
+    /// Return a shared copy of [`xterm256()`](Palette::xterm256()), built the first time it's needed
+    pub(crate) fn shared_xterm256() -> &'static Self {
+        XTERM256.get_or_init(Self::xterm256)
+    }
+
+    /// Return the colour in the palette closest to the given colour, or the given colour if the palette is empty
+    #[must_use]
+    pub fn nearest(&self, colour: Colour) -> Colour {
+        self.nearest_index(colour)
+            .map_or(colour, |index| self.colours[index])
+    }
+
+    /// Return the index of the colour in the palette closest to the given colour, or `None` if the palette is empty. For [`Palette::ansi16()`] and [`Palette::xterm256()`] this is the terminal's own number for the colour
+    #[must_use]
+    pub fn nearest_index(&self, colour: Colour) -> Option<usize> {
+        let distance = |c: &Colour| {
+            let dr = i32::from(c.r) - i32::from(colour.r);
+            let dg = i32::from(c.g) - i32::from(colour.g);
+            let db = i32::from(c.b) - i32::from(colour.b);
+            dr * dr + dg * dg + db * db
+        };
+
+        self.colours
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, c)| distance(c))
+            .map(|(index, _)| index)
+    }
+
+    /// Return the colour in the palette with the highest contrast ratio against the background, for picking readable text colours. Returns black or white (whichever is more readable) if the palette is empty
+    #[must_use]
+    pub fn most_readable_on(&self, background: Colour) -> Colour {
+        self.colours
+            .iter()
+            .copied()
+            .max_by(|a, b| {
+                a.contrast_ratio(background)
+                    .total_cmp(&b.contrast_ratio(background))
+            })
+            .unwrap_or_else(|| Colour::readable_on(background))
+    }
+
+    /// Quantise an image, given as rows of `width` colours, to the palette using the chosen dithering
+    #[must_use]
+    pub fn quantise_image(
+        &self,
+        image: &[Colour],
+        width: usize,
+        dithering: Dithering,
+    ) -> Vec<Colour> {
+        let width = width.max(1);
+
+        match dithering {
+            Dithering::None => image.iter().map(|c| self.nearest(*c)).collect(),
+            Dithering::Ordered => {
+                image
+                    .iter()
+                    .enumerate()
+                    .map(|(i, c)| {
+                        self.nearest(self.ordered_offset(
+                            *c,
+                            Vec2D::new((i % width) as isize, (i / width) as isize),
+                        ))
+                    })
+                    .collect()
+            }
+            Dithering::FloydSteinberg => self.floyd_steinberg(image, width),
+        }
+    }
+
+    /// Quantise the colours of the pixels to the palette using the chosen dithering. Only pixels with a [`Modifier::Colour`] are changed. Pixels are dithered based on their positions, so the result is the same regardless of their order
+    #[must_use]
+    pub fn quantise_pixels(&self, pixels: &[Pixel], dithering: Dithering) -> Vec<Pixel> {
+        let coloured: Vec<(usize, Vec2D, Colour)> = pixels
+            .iter()
+            .enumerate()
+            .filter_map(|(i, pixel)| match pixel.fill_char.modifier {
+                Modifier::Colour(colour) => Some((i, pixel.pos, colour)),
+                _ => None,
+            })
+            .collect();
+        let (Some(min_x), Some(min_y), Some(max_x), Some(max_y)) = (
+            coloured.iter().map(|(_, pos, _)| pos.x).min(),
+            coloured.iter().map(|(_, pos, _)| pos.y).min(),
+            coloured.iter().map(|(_, pos, _)| pos.x).max(),
+            coloured.iter().map(|(_, pos, _)| pos.y).max(),
+        ) else {
+            return pixels.to_vec();
+        };
+
+        // Rasterise the coloured pixels onto a grid covering their bounding box
+        let width = (max_x - min_x + 1) as usize;
+        let height = (max_y - min_y + 1) as usize;
+        let index_of = |pos: Vec2D| (pos.y - min_y) as usize * width + (pos.x - min_x) as usize;
+        let mut image = vec![Colour::BLACK; width * height];
+        for (_, pos, colour) in &coloured {
+            image[index_of(*pos)] = *colour;
+        }
+
+        let quantised = if dithering == Dithering::Ordered {
+            // Use the pixels' real positions so the pattern doesn't shift as the sprite moves
+            coloured
+                .iter()
+                .map(|(_, pos, colour)| {
+                    (
+                        index_of(*pos),
+                        self.nearest(self.ordered_offset(*colour, *pos)),
+                    )
+                })
+                .fold(image, |mut image, (i, colour)| {
+                    image[i] = colour;
+                    image
+                })
+        } else {
+            self.quantise_image(&image, width, dithering)
+        };
+
+        let mut output = pixels.to_vec();
+        for (i, pos, _) in coloured {
+            output[i].fill_char.modifier = Modifier::Colour(quantised[index_of(pos)]);
+        }
+        output
+    }
+
+    /// Offset the colour by the Bayer matrix value at the position, scaled to the average distance between colours in the palette
+    fn ordered_offset(&self, colour: Colour, pos: Vec2D) -> Colour {
+        let spread = 256.0 / (self.colours.len().max(2) as f64).cbrt();
+        let threshold = Dithering::bayer_threshold(pos) - 0.5;
+        let offset = |channel: u8| {
+            (f64::from(channel) + spread * threshold)
+                .clamp(0.0, 255.0)
+                .round() as u8
+        };
+
+        Colour::rgb(offset(colour.r), offset(colour.g), offset(colour.b))
+    }
+
+    /// Quantise the image with Floyd-Steinberg error diffusion
+    fn floyd_steinberg(&self, image: &[Colour], width: usize) -> Vec<Colour> {
+        let mut working: Vec<[f64; 3]> = image
+            .iter()
+            .map(|c| [f64::from(c.r), f64::from(c.g), f64::from(c.b)])
+            .collect();
+        let mut output = Vec::with_capacity(image.len());
+
+        for i in 0..working.len() {
+            let [red, green, blue] =
+                working[i].map(|channel| channel.clamp(0.0, 255.0).round() as u8);
+            let quantised = self.nearest(Colour::rgb(red, green, blue));
+            output.push(quantised);
+
+            let error = [
+                working[i][0] - f64::from(quantised.r),
+                working[i][1] - f64::from(quantised.g),
+                working[i][2] - f64::from(quantised.b),
+            ];
+            let (x, y) = (i % width, i / width);
+            let mut spread = |dx: isize, dy: usize, weight: f64| {
+                let nx = x as isize + dx;
+                if nx < 0 || nx >= width as isize {
+                    return;
+                }
+                if let Some(target) = working.get_mut((y + dy) * width + nx as usize) {
+                    for (channel, e) in target.iter_mut().zip(error) {
+                        *channel = e.mul_add(weight, *channel);
+                    }
+                }
+            };
+            spread(1, 0, 7.0 / 16.0);
+            spread(-1, 1, 3.0 / 16.0);
+            spread(0, 1, 5.0 / 16.0);
+            spread(1, 1, 1.0 / 16.0);
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn black_and_white() -> Palette {
+        Palette::new(vec![Colour::BLACK, Colour::WHITE])
+    }
+
+    #[test]
+    fn nearest_colour() {
+        assert_eq!(
+            Palette::ansi16().nearest(Colour::rgb(250, 10, 10)),
+            Colour::rgb(255, 0, 0)
+        );
+        assert_eq!(Palette::xterm256().colours.len(), 256);
+    }
+
+    #[test]
+    fn dithering_mixes_palette_colours() {
+        let grey = vec![Colour::greyscale(128); 16];
+
+        for dithering in [Dithering::Ordered, Dithering::FloydSteinberg] {
+            let output = black_and_white().quantise_image(&grey, 4, dithering);
+            let whites = output.iter().filter(|c| **c == Colour::WHITE).count();
+
+            assert!(output.iter().all(|c| black_and_white().colours.contains(c)));
+            assert!(
+                (6..=10).contains(&whites),
+                "{dithering:?} gave {whites} whites"
+            );
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use std::{
+    cmp::PartialEq,
+    fmt::{Display, Result},
+};
+
+use super::vec2d::Vec2D;
+use crate::procgen::random_unit;
+
+/// How a [`Vec2F`] position is turned into the [`Vec2D`] cell it's drawn in, used by [`Vec2F::round_with()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rounding {
+    /// Round down, so an object only moves into a cell once it's completely reached it
+    Floor,
+    /// Round to the nearest cell
+    #[default]
+    Round,
+    /// Round up or down at random, with a chance of rounding up equal to how far the position is into the next cell. Pass a different seed every frame, such as the frame number, and a slow-moving object flickers between the two cells in proportion to where it is, so it appears to move smoothly instead of jumping a whole cell at a time
+    Stochastic(u64),
+}
+
+/// A pair of `f64` used for smooth positions, velocities and forces on a 2D plane. Use [`Vec2F::round()`] to find the cell a position is in
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vec2F {
+    /// X-coordinate
+    pub x: f64,
+    /// Y-coordinate
+    pub y: f64,
+}
+
+impl Vec2F {
+    impl_vec_single_value_const!(Vec2F, ZERO, 0.0, (x, y));
+
+    impl_vec_core!(Vec2F, f64, (x, y));
+
+    /// Return the dot product in combination with another `Vec2F`
+    #[must_use]
+    pub fn dot(&self, other: Self) -> f64 {
+        self.x.mul_add(other.x, self.y * other.y)
+    }
+
+    /// The length/magnitude of the `Vec2F`
+    #[must_use]
+    pub fn magnitude(&self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    /// Return a `Vec2F` in the same direction with a magnitude of 1. Returns [`Vec2F::ZERO`] if the magnitude is 0
+    #[must_use]
+    pub fn normal(self) -> Self {
+        let magnitude = self.magnitude();
+        if magnitude == 0.0 {
+            Self::ZERO
+        } else {
+            self / magnitude
+        }
+    }
+
+    /// Return the `Vec2F` shortened to a magnitude of `max` if it's any longer
+    #[must_use]
+    pub fn clamp_magnitude(self, max: f64) -> Self {
+        if self.magnitude() > max {
+            self.normal() * max
+        } else {
+            self
+        }
+    }
+
+    /// Return the `Vec2F` rotated 90 degrees anticlockwise, so that with y pointing down it's the direction to the left
+    #[must_use]
+    pub const fn perpendicular(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    /// Return the nearest [`Vec2D`]
+    #[must_use]
+    pub const fn round(self) -> Vec2D {
+        Vec2D::new(self.x.round() as isize, self.y.round() as isize)
+    }
+
+    /// Return the [`Vec2D`] cell the position is drawn in with the [`Rounding`] strategy
+    #[must_use]
+    pub fn round_with(self, rounding: Rounding) -> Vec2D {
+        match rounding {
+            Rounding::Floor => Vec2D::new(self.x.floor() as isize, self.y.floor() as isize),
+            Rounding::Round => self.round(),
+            Rounding::Stochastic(seed) => {
+                let round = |value: f64, axis: i64| {
+                    let floor = value.floor();
+                    let threshold = random_unit(seed, axis, floor as i64);
+                    (if value - floor > threshold {
+                        floor + 1.0
+                    } else {
+                        floor
+                    }) as isize
+                };
+                Vec2D::new(round(self.x, 0), round(self.y, 1))
+            }
+        }
+    }
+}
+
+impl Display for Vec2F {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result {
+        write!(f, "Vec2F({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: Into<f64>> From<(T, T)> for Vec2F {
+    fn from(value: (T, T)) -> Self {
+        Self {
+            x: value.0.into(),
+            y: value.1.into(),
+        }
+    }
+}
+
+impl From<Vec2D> for Vec2F {
+    fn from(value: Vec2D) -> Self {
+        Self::new(value.x as f64, value.y as f64)
+    }
+}
+
+impl_vec_add!(Vec2F, (x, y));
+impl_vec_sub!(Vec2F, (x, y));
+impl_vec_neg!(Vec2F, 0.0, (x, y));
+impl_vec_mul!(Vec2F, (x, y));
+impl_vec_mul_single!(Vec2F, f64, (x, y));
+impl_vec_div!(Vec2F, (x, y));
+impl_vec_div_single!(Vec2F, f64, (x, y));
+impl_vec_rem!(Vec2F, (x, y));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normal_and_clamp() {
+        assert_eq!(Vec2F::new(3.0, 4.0).normal(), Vec2F::new(0.6, 0.8));
+        assert_eq!(Vec2F::ZERO.normal(), Vec2F::ZERO);
+        assert_eq!(
+            Vec2F::new(6.0, 8.0).clamp_magnitude(5.0),
+            Vec2F::new(3.0, 4.0)
+        );
+    }
+
+    #[test]
+    fn stochastic_rounding_follows_the_fraction() {
+        let pos = Vec2F::new(2.25, -0.5);
+        assert_eq!(pos.round_with(Rounding::Floor), Vec2D::new(2, -1));
+        assert_eq!(pos.round_with(Rounding::Round), pos.round());
+
+        let rounded_up = (0..1000)
+            .filter(|seed| pos.round_with(Rounding::Stochastic(*seed)).x == 3)
+            .count();
+        assert!((200..300).contains(&rounded_up));
+        assert_eq!(
+            Vec2F::new(4.0, 1.0).round_with(Rounding::Stochastic(7)),
+            Vec2D::new(4, 1)
+        );
+    }
+
+    #[test]
+    fn rounds_to_vec2d() {
+        assert_eq!(Vec2F::new(1.6, -2.4).round(), Vec2D::new(2, -2));
+        assert_eq!(Vec2F::from(Vec2D::new(3, 1)), Vec2F::new(3.0, 1.0));
+    }
+}
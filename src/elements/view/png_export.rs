@@ -0,0 +1,127 @@
+use super::{Bitmap, Vec2D, View};
+use crate::EngineError;
+use std::{fs, path::Path, time::Duration};
+
+impl Bitmap {
+    /// Return the width and height of the `Bitmap` as `u32`s, checking that it can be encoded
+    fn png_size(&self) -> Result<(u32, u32), EngineError> {
+        let invalid_size =
+            || EngineError::InvalidSize(Vec2D::new(self.width as isize, self.height as isize));
+        let width = u32::try_from(self.width).map_err(|_| invalid_size())?;
+        let height = u32::try_from(self.height).map_err(|_| invalid_size())?;
+        if width == 0 || height == 0 || self.pixels.len() != self.width * self.height {
+            return Err(invalid_size());
+        }
+
+        Ok((width, height))
+    }
+
+    /// Return the raw RGB bytes of every pixel
+    fn rgb_data(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|colour| [colour.r, colour.g, colour.b])
+            .collect()
+    }
+
+    /// Encode the `Bitmap` as an 8-bit RGB PNG image. Only available with the `png` feature
+    ///
+    /// # Errors
+    /// Returns [`EngineError::InvalidSize`] if the `Bitmap` is empty or its size doesn't fit in a PNG, or [`EngineError::InvalidData`] if encoding fails
+    pub fn to_png(&self) -> Result<Vec<u8>, EngineError> {
+        Self::encode_png(std::slice::from_ref(self), None)
+    }
+
+    /// Encode the `Bitmap`s as the frames of an animated PNG (APNG) that loops forever, showing each frame for `frame_time`. Browsers and most image viewers play APNGs, while others show the first frame. Only available with the `png` feature
+    ///
+    /// # Errors
+    /// Returns [`EngineError::InvalidData`] if there are no frames or encoding fails, or [`EngineError::InvalidSize`] if a frame is empty or a different size to the first
+    pub fn to_apng(frames: &[Self], frame_time: Duration) -> Result<Vec<u8>, EngineError> {
+        Self::encode_png(frames, Some(frame_time))
+    }
+
+    /// Encode the frames as a PNG, which is animated if there's a frame time
+    fn encode_png(frames: &[Self], frame_time: Option<Duration>) -> Result<Vec<u8>, EngineError> {
+        let first = frames
+            .first()
+            .ok_or_else(|| EngineError::InvalidData(String::from("No frames to encode")))?;
+        let (width, height) = first.png_size()?;
+        for frame in frames {
+            if frame.png_size()? != (width, height) {
+                return Err(EngineError::InvalidSize(Vec2D::new(
+                    frame.width as isize,
+                    frame.height as isize,
+                )));
+            }
+        }
+        let encoding_error = |err: png::EncodingError| EngineError::InvalidData(err.to_string());
+
+        let mut png = vec![];
+        let mut encoder = png::Encoder::new(&mut png, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        if let Some(frame_time) = frame_time {
+            let millis = u16::try_from(frame_time.as_millis()).unwrap_or(u16::MAX);
+            let frame_count = u32::try_from(frames.len()).unwrap_or(u32::MAX);
+            encoder
+                .set_animated(frame_count, 0)
+                .and_then(|()| encoder.set_frame_delay(millis, 1000))
+                .map_err(encoding_error)?;
+        }
+
+        let mut writer = encoder.write_header().map_err(encoding_error)?;
+        for frame in frames {
+            writer
+                .write_image_data(&frame.rgb_data())
+                .map_err(encoding_error)?;
+        }
+        writer.finish().map_err(encoding_error)?;
+
+        Ok(png)
+    }
+}
+
+impl View {
+    /// Return the `View` as a PNG image, drawn with the bundled font by [`Bitmap::from_view_with_font()`] at the given scale. Only available with the `png` feature
+    ///
+    /// # Errors
+    /// Returns an error if the `View` has no area or the image couldn't be encoded
+    pub fn to_png(&self, scale: usize) -> Result<Vec<u8>, EngineError> {
+        Bitmap::from_view_with_font(self, scale).to_png()
+    }
+
+    /// Write the `View` to a PNG file as returned by [`to_png()`](View::to_png()), replacing the file if it already exists. Only available with the `png` feature
+    ///
+    /// # Errors
+    /// Returns an error if the image couldn't be encoded, or [`EngineError::Io`] if the file couldn't be written to
+    pub fn export_png(&self, path: impl AsRef<Path>, scale: usize) -> Result<(), EngineError> {
+        Ok(fs::write(path, self.to_png(scale)?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::view::{ColChar, Modifier};
+
+    #[test]
+    fn encodes_a_decodable_png() {
+        let view = View::new(3, 2, ColChar::new('@', Modifier::from_rgb(0, 255, 0)));
+        let png = view.to_png(1).expect("The view should encode");
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        let mut reader = png::Decoder::new(png.as_slice())
+            .read_info()
+            .expect("The PNG should decode");
+        let mut data = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut data).expect("The PNG has a frame");
+        assert_eq!((info.width, info.height), (18, 24));
+
+        let empty = Bitmap {
+            width: 0,
+            height: 0,
+            pixels: vec![],
+        };
+        assert_eq!(empty.to_png(), Err(EngineError::InvalidSize(Vec2D::ZERO)));
+    }
+}
@@ -0,0 +1,369 @@
+//! Effects applied to the whole canvas of a [`View`] after everything has been blit to it. Read the [`PostProcess`] documentation for more info
+
+use super::{ColChar, Colour, Modifier, ModifierSet, View};
+
+/// A `PostProcess` effect reads back every cell of a [`View`] once the frame has been drawn and changes their colours, giving the whole frame a look such as a soft focus or an old CRT monitor. Apply effects with [`View::post_process()`] just before rendering, in the order they should be layered
+/// ```
+/// use gemini_engine::elements::{view::{post_process::{Glow, Scanlines}, ColChar, Colour, Wrapping}, Vec2D, View};
+///
+/// let mut view = View::new(5, 5, ColChar::EMPTY);
+/// view.plot(Vec2D::new(2, 2), ColChar::SOLID.with_rgb(255, 255, 200), Wrapping::Panic);
+///
+/// view.post_process(&Glow::new(0.8, 2, 0.5));
+/// view.post_process(&Scanlines::new(0.3));
+///
+/// // The cells around the bright one are lit up from behind
+/// assert_ne!(view.get(Vec2D::new(1, 2)), Some(ColChar::EMPTY));
+/// ```
+pub trait PostProcess {
+    /// Apply the effect to every cell of the `View`
+    fn apply(&self, view: &mut View);
+}
+
+/// Return the foreground and background colours of the cell
+fn colours(cell: ColChar) -> (Option<Colour>, Option<Colour>) {
+    let set = ModifierSet::new().merge(cell.modifier);
+    (set.foreground, set.background)
+}
+
+/// Return the cell with its colours replaced, keeping its character and styles
+fn with_colours(cell: ColChar, foreground: Option<Colour>, background: Option<Colour>) -> ColChar {
+    let mut set = ModifierSet::new().merge(cell.modifier);
+    set.foreground = foreground;
+    set.background = background;
+
+    let modifier = match (foreground, background) {
+        (Some(colour), None) if set.style.is_empty() => Modifier::Colour(colour),
+        _ => set.into(),
+    };
+    cell.with_mod(modifier)
+}
+
+/// Return every cell within `radius` cells of `(x, y)` in a `width` by `height` grid, along with its distance
+fn neighbours(
+    (x, y): (usize, usize),
+    radius: usize,
+    (width, height): (usize, usize),
+) -> impl Iterator<Item = (usize, usize, f64)> {
+    let xs = x.saturating_sub(radius)..=(x + radius).min(width.saturating_sub(1));
+    let ys = y.saturating_sub(radius)..=(y + radius).min(height.saturating_sub(1));
+
+    ys.flat_map(move |ny| {
+        xs.clone().map(move |nx| {
+            let distance = (nx.abs_diff(x) as f64).hypot(ny.abs_diff(y) as f64);
+            (nx, ny, distance)
+        })
+    })
+}
+
+/// A box blur of the foreground colours. Each coloured cell takes the average colour of the cells within the radius, with uncoloured cells counting as black, so colours bleed into each other and fade at their edges
+///
+/// Use [`Blur::gaussian()`] for a softer blur. Blurring with the same box several times weights nearer cells more than further ones, which looks close to a gaussian blur without its cost
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Blur {
+    /// How many cells away colours are blurred from in each pass
+    pub radius: usize,
+    /// How many times the box blur is applied. Defaults to 1
+    pub passes: usize,
+}
+
+impl Blur {
+    /// Create a new `Blur` with the radius and a single pass
+    #[must_use]
+    pub const fn new(radius: usize) -> Self {
+        Self { radius, passes: 1 }
+    }
+
+    /// Create a new `Blur` approximating a gaussian blur with three box blur passes of the radius
+    #[must_use]
+    pub const fn gaussian(radius: usize) -> Self {
+        Self::new(radius).with_passes(3)
+    }
+
+    /// Return the `Blur` with a different number of passes
+    #[must_use]
+    pub const fn with_passes(mut self, passes: usize) -> Self {
+        self.passes = passes;
+        self
+    }
+
+    /// Apply a single box blur pass to the `View`
+    fn box_pass(&self, view: &mut View) {
+        let size = (view.width, view.height);
+        let foregrounds: Vec<Option<Colour>> = view.pixels.iter().map(|c| colours(*c).0).collect();
+
+        for y in 0..view.height {
+            for x in 0..view.width {
+                let i = y * view.width + x;
+                let (Some(_), background) = colours(view.pixels[i]) else {
+                    continue;
+                };
+
+                let (mut sum, mut count) = ([0.0; 3], 0.0);
+                for (nx, ny, _) in neighbours((x, y), self.radius, size) {
+                    let colour = foregrounds[ny * view.width + nx].unwrap_or(Colour::BLACK);
+                    for (total, channel) in sum.iter_mut().zip([colour.r, colour.g, colour.b]) {
+                        *total += f64::from(channel);
+                    }
+                    count += 1.0;
+                }
+                let [r, g, b] = sum.map(|total| (total / count).round() as u8);
+
+                view.pixels[i] =
+                    with_colours(view.pixels[i], Some(Colour::rgb(r, g, b)), background);
+            }
+        }
+    }
+}
+
+impl PostProcess for Blur {
+    fn apply(&self, view: &mut View) {
+        for _ in 0..self.passes {
+            self.box_pass(view);
+        }
+    }
+}
+
+/// Bloom around bright cells. Every cell whose foreground colour has a [`relative_luminance()`](Colour::relative_luminance()) of at least the threshold lights up the backgrounds of the cells around it, fading out over the radius
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glow {
+    /// How bright a cell must be to glow, from 0.0 to 1.0
+    pub threshold: f64,
+    /// How many cells away the glow reaches
+    pub radius: usize,
+    /// How much of the bright cell's colour is added to the cells right next to it, from 0.0 to 1.0
+    pub strength: f64,
+}
+
+impl Glow {
+    /// Create a new `Glow` with the threshold, radius and strength
+    #[must_use]
+    pub const fn new(threshold: f64, radius: usize, strength: f64) -> Self {
+        Self {
+            threshold,
+            radius,
+            strength,
+        }
+    }
+}
+
+impl PostProcess for Glow {
+    fn apply(&self, view: &mut View) {
+        let size = (view.width, view.height);
+        let mut glow = vec![[0.0f64; 3]; view.pixels.len()];
+
+        for y in 0..view.height {
+            for x in 0..view.width {
+                let Some(colour) = colours(view.pixels[y * view.width + x]).0 else {
+                    continue;
+                };
+                if colour.relative_luminance() < self.threshold {
+                    continue;
+                }
+
+                for (nx, ny, distance) in neighbours((x, y), self.radius, size) {
+                    let falloff = 1.0 - distance / (self.radius as f64 + 1.0);
+                    if falloff <= 0.0 {
+                        continue;
+                    }
+                    let light = &mut glow[ny * view.width + nx];
+                    for (total, channel) in light.iter_mut().zip([colour.r, colour.g, colour.b]) {
+                        *total += f64::from(channel) * self.strength * falloff;
+                    }
+                }
+            }
+        }
+
+        for (cell, light) in view.pixels.iter_mut().zip(glow) {
+            if light.iter().all(|light| *light <= 0.0) {
+                continue;
+            }
+            let (foreground, background) = colours(*cell);
+            let background = background.unwrap_or(Colour::BLACK);
+            let add = |channel: u8, light: f64| (f64::from(channel) + light).min(255.0) as u8;
+            let (r, g, b) = (
+                add(background.r, light[0]),
+                add(background.g, light[1]),
+                add(background.b, light[2]),
+            );
+
+            *cell = with_colours(*cell, foreground, Some(Colour::rgb(r, g, b)));
+        }
+    }
+}
+
+/// Darkens every other row, like the gaps between the scanlines of a CRT monitor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scanlines {
+    /// How much darker the darkened rows are, from 0.0 for unchanged to 1.0 for black
+    pub darkness: f64,
+}
+
+impl Scanlines {
+    /// Create a new `Scanlines` with the darkness
+    #[must_use]
+    pub const fn new(darkness: f64) -> Self {
+        Self { darkness }
+    }
+}
+
+impl PostProcess for Scanlines {
+    fn apply(&self, view: &mut View) {
+        let width = view.width.max(1);
+        for row in view.pixels.chunks_mut(width).skip(1).step_by(2) {
+            for cell in row {
+                let (foreground, background) = colours(*cell);
+                let darken = |colour: Colour| colour.lerp(Colour::BLACK, self.darkness);
+                if foreground.is_some() || background.is_some() {
+                    *cell = with_colours(*cell, foreground.map(darken), background.map(darken));
+                }
+            }
+        }
+    }
+}
+
+/// Bends the frame outwards like the curved glass of a CRT monitor. Cells are pulled towards the centre more the further they are from it, and cells pulled in from outside the `View` are filled with its [`background_char`](View::background_char). Depths from [`View::plot_with_depth()`] move with their cells, so [`View::depth_at()`] still matches what is drawn
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Curvature {
+    /// How strongly the frame is bent. 0.0 leaves it flat, and around 0.1 to 0.3 looks like a CRT
+    pub amount: f64,
+}
+
+impl Curvature {
+    /// Create a new `Curvature` with the amount
+    #[must_use]
+    pub const fn new(amount: f64) -> Self {
+        Self { amount }
+    }
+}
+
+impl PostProcess for Curvature {
+    fn apply(&self, view: &mut View) {
+        let source = view.pixels.clone();
+        let source_depth = view.depth.clone();
+        let (width, height) = (view.width as f64, view.height as f64);
+
+        for y in 0..view.height {
+            for x in 0..view.width {
+                // The position of the cell's centre, from -1.0 to 1.0 in both directions
+                let nx = ((x as f64 + 0.5) / width).mul_add(2.0, -1.0);
+                let ny = ((y as f64 + 0.5) / height).mul_add(2.0, -1.0);
+                let bend = nx.mul_add(nx, ny * ny).mul_add(self.amount, 1.0);
+                let (sx, sy) = (nx * bend, ny * bend);
+
+                let i = y * view.width + x;
+                let from = (sx.abs() <= 1.0 && sy.abs() <= 1.0).then(|| {
+                    let sx = ((sx.midpoint(1.0) * width) as usize).min(view.width - 1);
+                    let sy = ((sy.midpoint(1.0) * height) as usize).min(view.height - 1);
+                    sy * view.width + sx
+                });
+
+                view.pixels[i] = from.map_or(view.background_char, |from| source[from]);
+                if !source_depth.is_empty() {
+                    view.depth[i] = from.map_or(f64::INFINITY, |from| source_depth[from]);
+                }
+            }
+        }
+    }
+}
+
+impl View {
+    /// Apply a [`PostProcess`] effect to every cell drawn so far
+    pub fn post_process(&mut self, effect: &impl PostProcess) {
+        engine_span!("post_process");
+        effect.apply(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{view::Wrapping, Vec2D};
+
+    #[test]
+    fn blur_and_scanlines_change_colours() {
+        let mut view = View::new(3, 2, ColChar::EMPTY);
+        view.plot(
+            Vec2D::new(0, 0),
+            ColChar::SOLID.with_rgb(90, 90, 90),
+            Wrapping::Panic,
+        );
+        view.plot(
+            Vec2D::new(1, 0),
+            ColChar::SOLID.with_rgb(0, 0, 0),
+            Wrapping::Panic,
+        );
+        view.post_process(&Blur::new(1));
+        // Averaged with the black cell and the two uncoloured cells below
+        assert_eq!(
+            view.get(Vec2D::new(0, 0)),
+            Some(ColChar::SOLID.with_rgb(23, 23, 23))
+        );
+
+        view.plot(
+            Vec2D::new(2, 1),
+            ColChar::SOLID.with_rgb(200, 100, 0),
+            Wrapping::Panic,
+        );
+        view.post_process(&Scanlines::new(0.5));
+        assert_eq!(
+            view.get(Vec2D::new(2, 1)),
+            Some(ColChar::SOLID.with_rgb(100, 50, 0))
+        );
+        assert_eq!(view.get(Vec2D::new(0, 1)), Some(ColChar::EMPTY));
+    }
+
+    #[test]
+    fn curvature_pulls_in_the_background() {
+        let mut view = View::new(10, 10, ColChar::EMPTY);
+        view.blit(
+            &crate::elements::Rect::new(Vec2D::ZERO, view.size(), ColChar::SOLID),
+            Wrapping::Panic,
+        );
+        view.post_process(&Curvature::new(0.3));
+
+        assert_eq!(view.get(Vec2D::new(0, 0)), Some(ColChar::EMPTY));
+        assert_eq!(view.get(Vec2D::new(5, 5)), Some(ColChar::SOLID));
+    }
+
+    #[test]
+    fn curvature_moves_depths_with_their_cells() {
+        let mut view = View::new(10, 10, ColChar::EMPTY);
+        for y in 0..10 {
+            for x in 0..10 {
+                view.plot_with_depth(Vec2D::new(x, y), ColChar::SOLID, 2.0, Wrapping::Panic);
+            }
+        }
+        view.plot_with_depth(Vec2D::new(0, 5), ColChar::SOLID, 1.0, Wrapping::Panic);
+        view.post_process(&Curvature::new(0.3));
+
+        assert_eq!(view.depth_at(Vec2D::new(0, 0)), None);
+        assert_eq!(view.depth_at(Vec2D::new(5, 5)), Some(2.0));
+        let edge = (0..10).find(|x| view.depth_at(Vec2D::new(*x, 5)) == Some(1.0));
+        assert!(edge.is_some_and(|x| x > 0));
+    }
+
+    #[test]
+    fn gaussian_blur_weights_nearer_cells_more() {
+        let mut view = View::new(7, 1, ColChar::SOLID.with_rgb(0, 0, 0));
+        view.plot(
+            Vec2D::new(3, 0),
+            ColChar::SOLID.with_rgb(255, 255, 255),
+            Wrapping::Panic,
+        );
+        let mut boxed = view.clone();
+        boxed.post_process(&Blur::new(1));
+        view.post_process(&Blur::gaussian(1));
+
+        let red = |view: &View, x| {
+            colours(view.get(Vec2D::new(x, 0)).expect("in bounds"))
+                .0
+                .map_or(0, |colour| colour.r)
+        };
+        // A single box pass spreads the colour evenly, while several passes fall off with distance
+        assert_eq!(red(&boxed, 2), red(&boxed, 3));
+        assert!(red(&view, 3) > red(&view, 2));
+        assert!(red(&view, 2) > red(&view, 1));
+        assert!(red(&view, 1) > red(&view, 0));
+    }
+}
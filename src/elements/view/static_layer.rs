@@ -0,0 +1,90 @@
+use super::{ColChar, Vec2D, ViewElement, Wrapping};
+
+/// A `StaticLayer` is a pre-rasterised background for a [`View`](super::View). Elements are blit to it once (or again after you [`clear()`](StaticLayer::clear()) it), and every time the `View` is cleared it is filled with the baked layer instead of the background character, so backgrounds, starfields and level geometry cost nothing per frame
+///
+/// ```
+/// use gemini_engine::elements::{view::{ColChar, StaticLayer, Wrapping}, Rect, Vec2D, View};
+///
+/// let mut layer = StaticLayer::new(20, 10, ColChar::BACKGROUND);
+/// layer.blit(&Rect::new(Vec2D::new(2, 2), Vec2D::new(4, 3), ColChar::SOLID), Wrapping::Ignore);
+///
+/// let mut view = View::new(20, 10, ColChar::BACKGROUND).with_static_layer(Some(layer));
+/// view.clear(); // the View now contains the rect, without blitting it again
+/// ```
+#[derive(Debug, Clone)]
+pub struct StaticLayer {
+    /// The width of the `StaticLayer`. Should match the width of the `View` it's attached to
+    width: usize,
+    /// The height of the `StaticLayer`. Should match the height of the `View` it's attached to
+    height: usize,
+    /// The character that the `StaticLayer` is filled with on clear
+    pub background_char: ColChar,
+    pixels: Vec<ColChar>,
+}
+
+impl StaticLayer {
+    /// Create a new, empty `StaticLayer` using the width, height and background character of the `View` it will be attached to
+    #[must_use]
+    pub fn new(width: usize, height: usize, background_char: ColChar) -> Self {
+        Self {
+            width,
+            height,
+            background_char,
+            pixels: vec![background_char; width * height],
+        }
+    }
+
+    /// Return the width and height of the `StaticLayer` as a [`Vec2D`]
+    #[must_use]
+    pub const fn size(&self) -> Vec2D {
+        Vec2D::new(self.width as isize, self.height as isize)
+    }
+
+    /// Clear the `StaticLayer` of all pixels, so that it can be rasterised again
+    pub fn clear(&mut self) {
+        self.pixels.fill(self.background_char);
+    }
+
+    /// Plot a pixel to the `StaticLayer`. Works the same way as [`View::plot()`](super::View::plot())
+    pub fn plot(&mut self, pos: Vec2D, c: ColChar, wrapping: Wrapping) {
+        if let Some(wrapped_pos) = wrapping.handle_bounds(pos, self.size()) {
+            let i = self.width * wrapped_pos.y.unsigned_abs() + wrapped_pos.x.unsigned_abs();
+            self.pixels[i] = c;
+        }
+    }
+
+    /// Blit a struct implementing [`ViewElement`] to the `StaticLayer`
+    pub fn blit(&mut self, element: &impl ViewElement, wrapping: Wrapping) {
+        for pixel in element.active_pixels() {
+            self.plot(pixel.pos, pixel.fill_char, wrapping);
+        }
+    }
+
+    /// Return the baked pixels of the `StaticLayer`, row by row
+    pub(crate) fn pixels(&self) -> &[ColChar] {
+        &self.pixels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::View;
+
+    #[test]
+    fn clearing_the_view_restores_the_layer() {
+        let mut layer = StaticLayer::new(3, 2, ColChar::BACKGROUND);
+        layer.plot(Vec2D::new(1, 1), ColChar::SOLID, Wrapping::Panic);
+
+        let mut view = View::new(3, 2, ColChar::EMPTY).with_static_layer(Some(layer.clone()));
+        view.plot(Vec2D::new(0, 0), ColChar::SOLID, Wrapping::Panic);
+        view.clear();
+        assert_eq!(view.get(Vec2D::new(0, 0)), Some(ColChar::BACKGROUND));
+        assert_eq!(view.get(Vec2D::new(1, 1)), Some(ColChar::SOLID));
+
+        // A layer of the wrong size is ignored in favour of the background character
+        let mut wrong_size = View::new(4, 2, ColChar::EMPTY).with_static_layer(Some(layer));
+        wrong_size.clear();
+        assert_eq!(wrong_size.get(Vec2D::new(1, 1)), Some(ColChar::EMPTY));
+    }
+}
@@ -0,0 +1,176 @@
+use std::{
+    io::{self, Write},
+    panic,
+    process::{Command, Stdio},
+    sync::{Mutex, Once, PoisonError},
+};
+
+/// Switch to the alternate screen and hide the cursor
+const ENTER_SEQUENCE: &str = "\x1b[?1049h\x1b[?25l";
+/// Reset any styles left over from the last frame, show the cursor and leave the alternate screen
+const LEAVE_SEQUENCE: &str = "\x1b[0m\x1b[?25h\x1b[?1049l";
+
+/// The terminal state to restore when the active session ends
+struct SavedState {
+    /// The `stty` settings from before raw mode was enabled, if it was
+    stty_mode: Option<String>,
+}
+
+static ACTIVE_SESSION: Mutex<Option<SavedState>> = Mutex::new(None);
+static PANIC_HOOK: Once = Once::new();
+
+/// Save the terminal's `stty` settings and enable raw mode, returning the saved settings or `None` if stdin isn't a terminal
+#[cfg(unix)]
+fn enable_raw_mode() -> Option<String> {
+    let saved = Command::new("stty")
+        .arg("-g")
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let mode = String::from_utf8(saved.stdout).ok()?.trim().to_owned();
+
+    let status = Command::new("stty")
+        .args(["raw", "-echo"])
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .status()
+        .ok()?;
+    status.success().then_some(mode)
+}
+
+/// Raw mode is only supported on Unix
+#[cfg(not(unix))]
+fn enable_raw_mode() -> Option<String> {
+    None
+}
+
+/// Restore the `stty` settings saved by [`enable_raw_mode()`]
+fn restore_stty(mode: &str) {
+    let _ = Command::new("stty")
+        .arg(mode)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .status();
+}
+
+/// End the active session if there is one, restoring the terminal
+fn end_session() {
+    let saved = ACTIVE_SESSION
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    if let Some(saved) = saved {
+        if let Some(mode) = &saved.stty_mode {
+            restore_stty(mode);
+        }
+        let mut stdout = io::stdout().lock();
+        let _ = stdout.write_all(LEAVE_SEQUENCE.as_bytes());
+        let _ = stdout.flush();
+    }
+}
+
+/// A `TerminalSession` prepares the terminal for a full screen game: it switches to the alternate screen, hides the cursor and enables raw mode, so key presses arrive immediately without being echoed. Everything is put back when the session is dropped, and a panic hook restores the terminal before the panic message is printed, so a crashed game never leaves the player's shell in a broken state
+///
+/// Only one session can be active at a time. Raw mode is enabled with `stty` and is only available on Unix when stdin is a terminal. Check [`is_raw_mode()`](TerminalSession::is_raw_mode()) if the game depends on it. Because raw mode stops `"\n"` from returning the cursor to the start of the line, print `"\r\n"` instead
+/// ```no_run
+/// use gemini_engine::elements::view::{ColChar, TerminalSession, View};
+///
+/// let session = TerminalSession::new().expect("Couldn't set up the terminal");
+/// let view = View::new(40, 10, ColChar::BACKGROUND);
+/// view.display_render().unwrap();
+///
+/// // The terminal is restored when `session` is dropped, even if the game panics first
+/// drop(session);
+/// ```
+#[derive(Debug)]
+pub struct TerminalSession {
+    raw_mode: bool,
+}
+
+impl TerminalSession {
+    /// Start a new `TerminalSession`, switching to the alternate screen, hiding the cursor and enabling raw mode. The first session also installs the panic hook, which runs before any previously installed hook
+    ///
+    /// # Errors
+    /// Returns an error of kind [`AlreadyExists`](io::ErrorKind::AlreadyExists) if another session is active, or any error from writing to stdout
+    pub fn new() -> io::Result<Self> {
+        let mut active = ACTIVE_SESSION
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if active.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "A TerminalSession is already active",
+            ));
+        }
+
+        PANIC_HOOK.call_once(|| {
+            let previous_hook = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                end_session();
+                previous_hook(info);
+            }));
+        });
+
+        let stty_mode = enable_raw_mode();
+        let raw_mode = stty_mode.is_some();
+        *active = Some(SavedState { stty_mode });
+        drop(active);
+
+        let mut stdout = io::stdout().lock();
+        let entered = stdout
+            .write_all(ENTER_SEQUENCE.as_bytes())
+            .and_then(|()| stdout.flush());
+        drop(stdout);
+        if let Err(err) = entered {
+            end_session();
+            return Err(err);
+        }
+
+        Ok(Self { raw_mode })
+    }
+
+    /// Returns true if raw mode was enabled for the session
+    #[must_use]
+    pub const fn is_raw_mode(&self) -> bool {
+        self.raw_mode
+    }
+
+    /// Returns true if a `TerminalSession` is currently active
+    #[must_use]
+    pub fn is_active() -> bool {
+        ACTIVE_SESSION
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_some()
+    }
+}
+
+impl Drop for TerminalSession {
+    fn drop(&mut self) {
+        end_session();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaving_undoes_entering() {
+        // Every mode switched on when entering is switched off again when leaving
+        for mode in ENTER_SEQUENCE
+            .split("\x1b[")
+            .filter(|mode| !mode.is_empty())
+        {
+            let (setting, switch) = mode.split_at(mode.len() - 1);
+            let undo = format!("\x1b[{setting}{}", if switch == "h" { "l" } else { "h" });
+            assert!(
+                LEAVE_SEQUENCE.contains(&undo),
+                "{setting} isn't switched back"
+            );
+        }
+        assert!(!TerminalSession::is_active());
+    }
+}
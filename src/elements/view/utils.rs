@@ -1,6 +1,10 @@
 //! A module containing various helper functions and structs
 use super::{ColChar, Pixel, Vec2D};
-pub use crate::utils::get_terminal_size_as_vec2d;
+pub use crate::utils::{
+    cell_aspect_ratio, detect_synchronized_output_from, get_terminal_size_as_vec2d,
+    set_cell_aspect_ratio, set_synchronized_output, synchronized_output, BEGIN_SYNCHRONIZED_UPDATE,
+    DEFAULT_CELL_ASPECT_RATIO, END_SYNCHRONIZED_UPDATE,
+};
 
 /// Combine a vector of [`Vec2D`]s and a single `fill_char` into a vector of `(Vec2D, char)` tuples, ready to return for `ViewElement::active_pixels`. Useful if your [`ViewElement`](super::ViewElement) only has one fill character across all of it
 #[must_use]
@@ -35,3 +39,31 @@ pub fn is_clockwise(points: &[Vec2D]) -> bool {
 
     m.iter().sum::<isize>() <= 0
 }
+
+/// How a movement speeds up and slows down over its time, such as a [`CameraRail`](crate::gameloop::camera_rail::CameraRail) moving into a keyframe or a [`FloatingText`](crate::elements::ascii::FloatingText) label rising
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Easing {
+    /// Move at a constant speed
+    #[default]
+    Linear,
+    /// Start slowly and speed up
+    EaseIn,
+    /// Start quickly and slow down
+    EaseOut,
+    /// Start and finish slowly, moving fastest in the middle
+    EaseInOut,
+}
+
+impl Easing {
+    /// Return how far along the movement is, from 0.0 to 1.0, after the fraction of its time
+    #[must_use]
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => t * (2.0 - t),
+            Self::EaseInOut => t * t * 2.0f64.mul_add(-t, 3.0),
+        }
+    }
+}
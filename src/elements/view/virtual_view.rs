@@ -0,0 +1,195 @@
+use super::{ColChar, Vec2D, View, Wrapping};
+use crate::utils;
+use std::io;
+
+/// How a [`VirtualView`] fits its fixed-size `View` into the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VirtualScaling {
+    /// Draw the `View` at its normal size in the centre of the terminal, surrounded by letterboxing. If the terminal is too small, the edges of the `View` are cropped evenly
+    #[default]
+    Centre,
+    /// Scale the `View` up by the largest whole number that still fits the terminal, drawing every cell as a square block of copies, and centre it. Falls back to a scale of 1 if the terminal is smaller than the `View`
+    Integer,
+}
+
+/// A `VirtualView` renders the game at a fixed virtual resolution, regardless of the size of the terminal. Blit everything to the inner [`view`](VirtualView::view) as normal, then call [`display_render()`](VirtualView::display_render()) to draw it centred (and optionally scaled up) inside the terminal, with the leftover space filled by the [`letterbox_char`](VirtualView::letterbox_char). Layouts stay the same however big or small the player's terminal is
+#[derive(Debug, Clone)]
+pub struct VirtualView {
+    /// The fixed-size `View` that everything is blit to
+    pub view: View,
+    /// How the `View` is fitted into the terminal
+    pub scaling: VirtualScaling,
+    /// The character the space around the `View` is filled with
+    pub letterbox_char: ColChar,
+    /// How many rows to leave clear below the rendered view, like [`ScaleFitView::empty_row_count`](super::ScaleFitView::empty_row_count)
+    pub empty_row_count: isize,
+}
+
+impl VirtualView {
+    /// Create a new `VirtualView` with a fixed width and height, centred in the terminal with empty letterboxing
+    #[must_use]
+    pub fn new(width: usize, height: usize, background_char: ColChar) -> Self {
+        Self {
+            view: View::new(width, height, background_char),
+            scaling: VirtualScaling::Centre,
+            letterbox_char: ColChar::EMPTY,
+            empty_row_count: 1,
+        }
+    }
+
+    /// Return the `VirtualView` with the modified scaling
+    #[must_use]
+    pub const fn with_scaling(mut self, scaling: VirtualScaling) -> Self {
+        self.scaling = scaling;
+        self
+    }
+
+    /// Return the `VirtualView` with the modified letterbox character
+    #[must_use]
+    pub const fn with_letterbox_char(mut self, letterbox_char: ColChar) -> Self {
+        self.letterbox_char = letterbox_char;
+        self
+    }
+
+    /// Return the `VirtualView` with the modified [`empty_row_count`](VirtualView::empty_row_count)
+    #[must_use]
+    pub const fn with_empty_row_count(mut self, empty_row_count: isize) -> Self {
+        self.empty_row_count = empty_row_count;
+        self
+    }
+
+    /// Return the scale the `View` is drawn at on a screen of the given size
+    #[must_use]
+    pub fn scale_for(&self, screen_size: Vec2D) -> isize {
+        let size = self.view.size();
+        if self.scaling == VirtualScaling::Integer && size.x > 0 && size.y > 0 {
+            (screen_size.x / size.x).min(screen_size.y / size.y).max(1)
+        } else {
+            1
+        }
+    }
+
+    /// Return the position of the `View`'s top left corner on a screen of the given size. This is negative if the `View` is cropped
+    #[must_use]
+    pub fn offset_for(&self, screen_size: Vec2D) -> Vec2D {
+        (screen_size - self.view.size() * self.scale_for(screen_size)) / 2
+    }
+
+    /// Convert a position on a screen of the given size, such as the position of a mouse click, to a position on the `View`. Returns `None` if the position is in the letterboxing
+    #[must_use]
+    pub fn screen_to_virtual(&self, screen_size: Vec2D, pos: Vec2D) -> Option<Vec2D> {
+        let scale = self.scale_for(screen_size);
+        let relative = pos - self.offset_for(screen_size);
+        let virtual_pos = Vec2D::new(relative.x.div_euclid(scale), relative.y.div_euclid(scale));
+
+        self.view.get(virtual_pos).map(|_| virtual_pos)
+    }
+
+    /// Return a `View` of the given screen size with the virtual `View` fitted into it. Cells are read from the virtual `View`'s [`front_buffer()`](View::front_buffer()), and its colour mode, colour adjustment and glyph policy are carried over
+    #[must_use]
+    pub fn compose(&self, screen_size: Vec2D) -> View {
+        let mut screen = View::new(
+            screen_size.x.max(0) as usize,
+            screen_size.y.max(0) as usize,
+            self.letterbox_char,
+        )
+        .with_colour_mode(self.view.colour_mode)
+        .with_colour_adjustment(self.view.colour_adjustment)
+        .with_glyph_policy(self.view.glyph_policy);
+        let cells = self.view.front_buffer();
+
+        for y in 0..screen_size.y {
+            for x in 0..screen_size.x {
+                let pos = Vec2D::new(x, y);
+                if let Some(cell) = self.screen_to_virtual(screen_size, pos).map(|virtual_pos| {
+                    cells[self.view.width * virtual_pos.y as usize + virtual_pos.x as usize]
+                }) {
+                    screen.plot(pos, cell, Wrapping::Ignore);
+                }
+            }
+        }
+
+        screen
+    }
+
+    /// Clear the virtual `View`
+    pub fn clear(&mut self) {
+        self.view.clear();
+    }
+
+    /// Display the virtual `View` fitted into the terminal. If the terminal size can't be found, the `View` is displayed as it is
+    ///
+    /// # Errors
+    /// Returns the `Result` from writing to `io::stdout().lock()`. You can ignore it with `let _ = ...` most of the time
+    pub fn display_render(&self) -> io::Result<()> {
+        utils::get_terminal_size_as_vec2d().map_or_else(
+            || self.view.display_render(),
+            |term_size| {
+                let screen_size = term_size - Vec2D::new(0, self.empty_row_count + 1);
+                self.compose(screen_size).display_render()
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::view::{ColourAdjustment, ColourMode};
+
+    #[test]
+    fn centres_with_letterboxing() {
+        let mut virtual_view =
+            VirtualView::new(2, 1, ColChar::SOLID).with_letterbox_char(ColChar::BACKGROUND);
+        virtual_view
+            .view
+            .plot(Vec2D::new(1, 0), ColChar::EMPTY, Wrapping::Panic);
+
+        let screen = virtual_view.compose(Vec2D::new(4, 3));
+        assert_eq!(screen.get(Vec2D::new(1, 1)), Some(ColChar::SOLID));
+        assert_eq!(screen.get(Vec2D::new(2, 1)), Some(ColChar::EMPTY));
+        assert_eq!(screen.get(Vec2D::new(0, 0)), Some(ColChar::BACKGROUND));
+        assert_eq!(
+            virtual_view.screen_to_virtual(Vec2D::new(4, 3), Vec2D::new(3, 1)),
+            None
+        );
+    }
+
+    #[test]
+    fn integer_scaling_repeats_cells() {
+        let virtual_view =
+            VirtualView::new(2, 2, ColChar::SOLID).with_scaling(VirtualScaling::Integer);
+        let screen_size = Vec2D::new(9, 5);
+
+        assert_eq!(virtual_view.scale_for(screen_size), 2);
+        assert_eq!(virtual_view.offset_for(screen_size), Vec2D::new(2, 0));
+        assert_eq!(
+            virtual_view.screen_to_virtual(screen_size, Vec2D::new(5, 3)),
+            Some(Vec2D::new(1, 1))
+        );
+        assert_eq!(
+            virtual_view.compose(screen_size).get(Vec2D::new(4, 4)),
+            Some(ColChar::EMPTY)
+        );
+    }
+
+    #[test]
+    fn composes_the_front_buffer_with_the_views_settings() {
+        let mut virtual_view = VirtualView::new(1, 1, ColChar::EMPTY);
+        virtual_view.view.colour_mode = ColourMode::Ansi16;
+        virtual_view.view.colour_adjustment = ColourAdjustment::NEUTRAL.with_gamma(2.0);
+        virtual_view
+            .view
+            .plot(Vec2D::ZERO, ColChar::SOLID, Wrapping::Panic);
+        virtual_view.view.swap_buffers();
+        virtual_view.view.clear();
+
+        let screen = virtual_view.compose(Vec2D::new(3, 1));
+        assert_eq!(screen.get(Vec2D::new(1, 0)), Some(ColChar::SOLID));
+        assert_eq!(screen.colour_mode, ColourMode::Ansi16);
+        assert_eq!(
+            screen.colour_adjustment,
+            virtual_view.view.colour_adjustment
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use super::View;
+use std::fmt::{self, Debug};
+
+/// A `WebBackend` draws [`View`]s into an element of a web page, so games compiled to `wasm32` can be embedded in a page for demos. Each frame is turned into a `<pre>` element of coloured `<span>`s, as in [`View::to_html_block()`], and handed to a callback that puts it on the page, which only runs when the frame has changed
+///
+/// The engine doesn't depend on any browser bindings, so the callback is where the game's own `wasm-bindgen` or `web-sys` code sets the element's `innerHTML`
+/// ```
+/// use gemini_engine::elements::view::{ColChar, View, WebBackend};
+/// use std::{cell::RefCell, rc::Rc};
+///
+/// // In the browser, this would be something like `element.set_inner_html(html)`
+/// let page = Rc::new(RefCell::new(String::new()));
+/// let element = Rc::clone(&page);
+/// let mut backend = WebBackend::new(move |html| *element.borrow_mut() = html.to_string());
+///
+/// let view = View::new(4, 2, ColChar::SOLID);
+/// assert!(backend.render(&view));
+/// assert!(page.borrow().starts_with("<pre"));
+///
+/// // Drawing the same frame again leaves the page alone
+/// assert!(!backend.render(&view));
+/// ```
+pub struct WebBackend {
+    set_inner_html: Box<dyn FnMut(&str)>,
+    last_frame: String,
+}
+
+impl WebBackend {
+    /// Create a new `WebBackend` that passes each changed frame's HTML to the callback
+    pub fn new(set_inner_html: impl FnMut(&str) + 'static) -> Self {
+        Self {
+            set_inner_html: Box::new(set_inner_html),
+            last_frame: String::new(),
+        }
+    }
+
+    /// Draw the `View` to the page, returning false without calling the callback if it's unchanged since the last frame
+    pub fn render(&mut self, view: &View) -> bool {
+        let html = view.to_html_block();
+        if html == self.last_frame {
+            return false;
+        }
+
+        (self.set_inner_html)(&html);
+        self.last_frame = html;
+        true
+    }
+
+    /// Return the HTML of the last frame drawn to the page
+    #[must_use]
+    pub fn last_frame(&self) -> &str {
+        &self.last_frame
+    }
+}
+
+impl Debug for WebBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebBackend")
+            .field("last_frame", &self.last_frame)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{
+        view::{ColChar, Modifier, Wrapping},
+        Vec2D,
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    #[test]
+    fn only_changed_frames_reach_the_page() {
+        let updates = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&updates);
+        let mut backend = WebBackend::new(move |_| counter.set(counter.get() + 1));
+
+        let mut view = View::new(3, 1, ColChar::EMPTY);
+        backend.render(&view);
+        backend.render(&view);
+        view.plot(
+            Vec2D::new(1, 0),
+            ColChar::new('&', Modifier::RED),
+            Wrapping::Panic,
+        );
+        backend.render(&view);
+
+        assert_eq!(updates.get(), 2);
+        assert!(backend.last_frame().contains("&amp;"));
+        assert!(backend.last_frame().ends_with("</pre>"));
+    }
+}
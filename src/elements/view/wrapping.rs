@@ -1,39 +1,59 @@
 use super::Vec2D;
+use crate::EngineError;
 
 /// Wrapping is used to determine how you want to handle out-of-bounds pixels during plotting pixels to the screen. Here's how each possible value functions:
 #[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
 pub enum Wrapping {
     /// `Wrapping::Wrap` wraps any out of bounds pixels around to the other side. This is useful if you have an object that travels the entirety of the screen and appears on the other side when it reaches the end.
     Wrap,
     /// `Wrapping::Ignore` simply skips all out-of-bounds pixels. This is useful if you might have an object clipping through the edge of the screen but don't want it to wrap to the other side like [`Wrapping::Wrap`] or panic and end the process like [`Wrapping::Panic`]
     Ignore,
-    /// `Wrapping::Panic` will `panic!` if any pixels are out of bounds. You should use this if you have your own wrapping system implemented
+    /// `Wrapping::Panic` will `panic!` if any pixels are out of bounds. You should use this if you have your own wrapping system implemented, or to catch layout bugs. Use [`View::try_blit()`](super::View::try_blit()) to get an error instead of a panic
     Panic,
+    /// `Wrapping::Clamp` moves out-of-bounds pixels to the nearest edge of the screen. This is useful for things that should always stay visible, such as off-screen indicators pointing towards objectives
+    Clamp,
 }
 
 impl Wrapping {
     /// Handle the position based on the given bounds and the Wrapping variation (See the [Wrapping] documentation for more info)
     ///
     /// # Panics
-    /// `Wrapping::Panic` will panic if the position is out of bounds
+    /// `Wrapping::Panic` will panic if the position is out of bounds, and every variant panics if the bounds have no area
     #[must_use]
     pub fn handle_bounds(&self, pos: Vec2D, bounds: Vec2D) -> Option<Vec2D> {
+        match self.try_handle_bounds(pos, bounds) {
+            Ok(pos) => pos,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Handle the position like [`handle_bounds()`](Wrapping::handle_bounds()), but return an error instead of panicking if the position is out of bounds with `Wrapping::Panic`
+    ///
+    /// # Errors
+    /// Returns [`EngineError::OutOfBounds`] if the position is out of bounds with `Wrapping::Panic`, or [`EngineError::InvalidSize`] if the bounds have no area
+    pub fn try_handle_bounds(
+        &self,
+        pos: Vec2D,
+        bounds: Vec2D,
+    ) -> Result<Option<Vec2D>, EngineError> {
+        if bounds.x <= 0 || bounds.y <= 0 {
+            return Err(EngineError::InvalidSize(bounds));
+        }
         let in_bounds_pos = pos % bounds;
 
         match self {
-            Self::Wrap => Some(in_bounds_pos),
-            Self::Ignore => {
-                if pos == in_bounds_pos {
-                    Some(pos)
-                } else {
-                    None
-                }
-            }
+            Self::Wrap => Ok(Some(in_bounds_pos)),
+            Self::Ignore => Ok((pos == in_bounds_pos).then_some(pos)),
+            Self::Clamp => Ok(Some(Vec2D::new(
+                pos.x.clamp(0, bounds.x - 1),
+                pos.y.clamp(0, bounds.y - 1),
+            ))),
             Self::Panic => {
                 if pos == in_bounds_pos {
-                    Some(pos)
+                    Ok(Some(pos))
                 } else {
-                    panic!("{pos} is out of bounds");
+                    Err(EngineError::OutOfBounds { pos, size: bounds })
                 }
             }
         }
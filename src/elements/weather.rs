@@ -0,0 +1,175 @@
+//! This module holds ambient weather effects: [`Rain`], [`Snow`] and [`Fog`]. Each is an overlay covering an area, with wind, an adjustable intensity and several depth layers, where further layers are dimmer and slower. Call `update()` on them every frame, then draw them with [`composite()`] to choose how they mix with what's already on the [`View`]
+
+mod fog;
+pub use fog::Fog;
+
+mod rain;
+pub use rain::Rain;
+
+mod snow;
+pub use snow::Snow;
+
+use super::{
+    view::{ViewElement, Wrapping},
+    Vec2D, View,
+};
+use crate::procgen::Rng;
+
+/// How an overlay's pixels are combined with the pixels already on the [`View`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    /// Overwrite whatever is on the `View`, the same as [`View::blit()`]
+    #[default]
+    Replace,
+    /// Only draw on cells that still hold the `View`'s [`background_char`](View::background_char), so the effect appears behind everything else
+    Behind,
+    /// Keep the characters already on the `View` and only apply the overlay's [`Modifier`](super::view::Modifier), tinting the scene
+    Tint,
+}
+
+/// Blit a [`ViewElement`] to the [`View`], combining its pixels with those already there using the [`BlendMode`]
+pub fn composite(
+    view: &mut View,
+    element: &impl ViewElement,
+    blend_mode: BlendMode,
+    wrapping: Wrapping,
+) {
+    let size = view.size();
+
+    for pixel in element.active_pixels() {
+        let Some(pos) = view.wrapping_for(wrapping).handle_bounds(pixel.pos, size) else {
+            continue;
+        };
+        let Some(existing) = view.get(pos) else {
+            continue;
+        };
+
+        let fill_char = match blend_mode {
+            BlendMode::Replace => pixel.fill_char,
+            BlendMode::Behind if existing == view.background_char => pixel.fill_char,
+            BlendMode::Behind => continue,
+            BlendMode::Tint => existing.with_mod(pixel.fill_char.modifier),
+        };
+        view.plot(pos, fill_char, Wrapping::Ignore);
+    }
+}
+
+/// A single falling particle, such as a raindrop or snowflake
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Particle {
+    x: f64,
+    y: f64,
+    /// How close the particle is, from 0.0 on the furthest layer to 1.0 on the nearest
+    depth: f64,
+    /// A random offset used to stop particles moving in lockstep
+    phase: f64,
+}
+
+/// The shared simulation behind [`Rain`] and [`Snow`]: particles fall through an area, blown sideways by the wind, and are respawned at the top when they leave it
+#[derive(Debug, Clone)]
+struct ParticleField {
+    size: Vec2D,
+    particles: Vec<Particle>,
+    rng: Rng,
+}
+
+impl ParticleField {
+    const fn new(size: Vec2D, seed: u64) -> Self {
+        Self {
+            size,
+            particles: vec![],
+            rng: Rng::new(seed),
+        }
+    }
+
+    /// Return a new particle at a random position. If `at_top` is true it is placed on the top row instead of anywhere in the area
+    fn spawn(&mut self, layers: u8, at_top: bool) -> Particle {
+        let layer = (self.rng.next_f64() * f64::from(layers.max(1))).floor();
+        Particle {
+            x: self.rng.next_f64() * self.size.x as f64,
+            y: if at_top {
+                0.0
+            } else {
+                self.rng.next_f64() * self.size.y as f64
+            },
+            depth: if layers > 1 {
+                (layer / f64::from(layers - 1)).min(1.0)
+            } else {
+                1.0
+            },
+            phase: self.rng.next_f64() * std::f64::consts::TAU,
+        }
+    }
+
+    /// Grow or shrink the number of particles to match the intensity, which is the fraction of cells holding a particle
+    fn match_intensity(&mut self, intensity: f64, layers: u8) {
+        let area = (self.size.x * self.size.y).max(0) as f64;
+        let target = (area * intensity.clamp(0.0, 1.0)).round() as usize;
+
+        self.particles.truncate(target);
+        while self.particles.len() < target {
+            let particle = self.spawn(layers, false);
+            self.particles.push(particle);
+        }
+    }
+
+    /// Move every particle by its velocity, which is given by `velocity(particle)` in cells per second, respawning any that fall out of the bottom
+    fn step(&mut self, seconds: f64, layers: u8, velocity: impl Fn(&Particle) -> (f64, f64)) {
+        let (width, height) = (self.size.x as f64, self.size.y as f64);
+
+        for i in 0..self.particles.len() {
+            let (dx, dy) = velocity(&self.particles[i]);
+            let particle = &mut self.particles[i];
+            particle.x = dx.mul_add(seconds, particle.x).rem_euclid(width.max(1.0));
+            particle.y = dy.mul_add(seconds, particle.y);
+
+            if particle.y >= height || particle.y < 0.0 {
+                self.particles[i] = self.spawn(layers, true);
+            }
+        }
+    }
+}
+
+/// Return the speed multiplier for a particle at the given depth, so that further particles move more slowly
+const fn depth_speed(depth: f64) -> f64 {
+    depth.mul_add(0.6, 0.4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{
+        view::{ColChar, Modifier},
+        PixelContainer,
+    };
+
+    #[test]
+    fn blend_modes() {
+        let mut view = View::new(3, 1, ColChar::BACKGROUND);
+        view.plot(Vec2D::new(1, 0), ColChar::SOLID, Wrapping::Panic);
+        let red = ColChar::new('x', Modifier::RED);
+        let points: Vec<Vec2D> = (0..3).map(|x| Vec2D::new(x, 0)).collect();
+        let overlay = PixelContainer::from((points.as_slice(), red));
+
+        let mut behind = view.clone();
+        composite(&mut behind, &overlay, BlendMode::Behind, Wrapping::Ignore);
+        assert_eq!(behind.get(Vec2D::new(0, 0)), Some(red));
+        assert_eq!(behind.get(Vec2D::new(1, 0)), Some(ColChar::SOLID));
+
+        composite(&mut view, &overlay, BlendMode::Tint, Wrapping::Ignore);
+        assert_eq!(
+            view.get(Vec2D::new(1, 0)),
+            Some(ColChar::SOLID.with_mod(red.modifier))
+        );
+    }
+
+    #[test]
+    fn particles_match_intensity() {
+        let mut field = ParticleField::new(Vec2D::new(10, 10), 1);
+        field.match_intensity(0.25, 3);
+        assert_eq!(field.particles.len(), 25);
+
+        field.step(100.0, 3, |_| (1.0, 1.0));
+        assert!(field.particles.iter().all(|p| p.y < 10.0 && p.x < 10.0));
+    }
+}
@@ -0,0 +1,142 @@
+use super::depth_speed;
+use crate::{
+    elements::{
+        backgrounds::NoiseBackground,
+        view::{ColChar, Colour, Modifier, ViewElement},
+        Pixel, Vec2D,
+    },
+    gameloop::CanAnimate,
+};
+use std::time::Duration;
+
+/// `Fog` covers an area with drifting banks of mist made from layered noise. Further depth layers drift more slowly, giving the fog a sense of depth. It's designed to be drawn with [`BlendMode::Tint`](super::BlendMode::Tint) or [`BlendMode::Behind`](super::BlendMode::Behind), though it can also be drawn over everything. Call [`update()`](Fog::update()) every frame to move the fog
+#[derive(Debug, Clone)]
+pub struct Fog {
+    /// The position of the top left corner of the `Fog`
+    pub pos: Vec2D,
+    /// The width and height of the `Fog`
+    pub size: Vec2D,
+    /// How thick the fog is, between 0.0 (no fog) and 1.0 (fog everywhere)
+    pub intensity: f64,
+    /// How fast the wind moves the nearest layer of fog, in cells per second. Negative values blow to the left
+    pub wind: f64,
+    /// The number of depth layers of fog
+    pub layers: u8,
+    /// The colour of the fog at its thickest
+    pub colour: Colour,
+    noise: NoiseBackground,
+    elapsed: Duration,
+}
+
+impl Fog {
+    /// Create a new `Fog` covering an area, with a grey colour, two depth layers and a gentle wind
+    #[must_use]
+    pub fn new(pos: Vec2D, size: Vec2D, intensity: f64, seed: u64) -> Self {
+        Self {
+            pos,
+            size,
+            intensity,
+            wind: 2.0,
+            layers: 2,
+            colour: Colour::greyscale(200),
+            noise: NoiseBackground::new(Vec2D::ZERO, size, seed).with_scale(12.0, 3),
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Return the `Fog` with the modified wind
+    #[must_use]
+    pub const fn with_wind(mut self, wind: f64) -> Self {
+        self.wind = wind;
+        self
+    }
+
+    /// Return the `Fog` with the modified number of depth layers
+    #[must_use]
+    pub const fn with_layers(mut self, layers: u8) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Return the `Fog` with the modified colour
+    #[must_use]
+    pub const fn with_colour(mut self, colour: Colour) -> Self {
+        self.colour = colour;
+        self
+    }
+
+    /// Move the fog forward by `delta`, usually the time since the last frame
+    pub fn update(&mut self, delta: Duration) {
+        self.elapsed += delta;
+    }
+
+    /// Return the thickness of the fog at a cell, between 0.0 and 1.0, taking the thickest of all the layers
+    #[must_use]
+    pub fn thickness(&self, pos: Vec2D) -> f64 {
+        let drift = self.wind * self.elapsed.as_secs_f64();
+        let layers = self.layers.max(1);
+
+        (0..layers)
+            .map(|layer| {
+                let depth = if layers > 1 {
+                    f64::from(layer) / f64::from(layers - 1)
+                } else {
+                    1.0
+                };
+                // Offset each layer so they don't line up with each other
+                let layer_offset = f64::from(layer) * 97.0;
+                let value = self.noise.sample(
+                    drift.mul_add(depth_speed(depth), pos.x as f64 - layer_offset),
+                    pos.y as f64 + layer_offset,
+                );
+                ((value + self.intensity - 1.0) / self.intensity.max(f64::EPSILON)).clamp(0.0, 1.0)
+                    * depth_speed(depth)
+            })
+            .fold(0.0, f64::max)
+    }
+}
+
+impl ViewElement for Fog {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let mut pixels = vec![];
+
+        for y in 0..self.size.y {
+            for x in 0..self.size.x {
+                let thickness = self.thickness(Vec2D::new(x, y));
+                let fill = match thickness {
+                    t if t > 0.6 => '▒',
+                    t if t > 0.15 => '░',
+                    _ => continue,
+                };
+
+                pixels.push(Pixel::new(
+                    self.pos + Vec2D::new(x, y),
+                    ColChar::new(fill, Modifier::Colour(self.colour * thickness)),
+                ));
+            }
+        }
+
+        pixels
+    }
+}
+
+impl CanAnimate for Fog {
+    fn is_animating(&self) -> bool {
+        self.intensity > 0.0 && self.wind != 0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intensity_controls_coverage() {
+        let size = Vec2D::new(30, 10);
+        let thin = Fog::new(Vec2D::ZERO, size, 0.0, 2).active_pixels().len();
+        let thick = Fog::new(Vec2D::ZERO, size, 1.0, 2).active_pixels().len();
+
+        assert_eq!(thin, 0);
+        assert!(thick > 150);
+    }
+}
@@ -0,0 +1,140 @@
+use super::{depth_speed, ParticleField};
+use crate::{
+    elements::{
+        view::{ColChar, Colour, Modifier, ViewElement},
+        Pixel, Vec2D,
+    },
+    gameloop::CanAnimate,
+};
+use std::time::Duration;
+
+/// `Rain` fills an area with falling raindrops, slanted by the wind. Drops on further depth layers are dimmer and fall more slowly. Call [`update()`](Rain::update()) every frame to move the rain
+#[derive(Debug, Clone)]
+pub struct Rain {
+    /// The position of the top left corner of the `Rain`
+    pub pos: Vec2D,
+    /// The fraction of cells holding a raindrop, between 0.0 and 1.0
+    pub intensity: f64,
+    /// How fast raindrops on the nearest layer fall, in cells per second
+    pub speed: f64,
+    /// How fast the wind blows raindrops sideways, in cells per second. Negative values blow to the left
+    pub wind: f64,
+    /// The number of depth layers raindrops are spread across
+    pub layers: u8,
+    /// The colour of raindrops on the nearest layer
+    pub colour: Colour,
+    field: ParticleField,
+}
+
+impl Rain {
+    /// Create a new `Rain` covering an area, with a light blue-grey colour, three depth layers and no wind
+    #[must_use]
+    pub fn new(pos: Vec2D, size: Vec2D, intensity: f64, seed: u64) -> Self {
+        let mut rain = Self {
+            pos,
+            intensity,
+            speed: 30.0,
+            wind: 0.0,
+            layers: 3,
+            colour: Colour::rgb(170, 190, 220),
+            field: ParticleField::new(size, seed),
+        };
+        rain.field.match_intensity(intensity, rain.layers);
+        rain
+    }
+
+    /// Return the `Rain` with the modified wind
+    #[must_use]
+    pub const fn with_wind(mut self, wind: f64) -> Self {
+        self.wind = wind;
+        self
+    }
+
+    /// Return the `Rain` with the modified speed
+    #[must_use]
+    pub const fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Return the `Rain` with the modified number of depth layers
+    #[must_use]
+    pub const fn with_layers(mut self, layers: u8) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Return the `Rain` with the modified colour
+    #[must_use]
+    pub const fn with_colour(mut self, colour: Colour) -> Self {
+        self.colour = colour;
+        self
+    }
+
+    /// Return the width and height of the area covered by the `Rain`
+    #[must_use]
+    pub const fn size(&self) -> Vec2D {
+        self.field.size
+    }
+
+    /// Move the rain forward by `delta`, usually the time since the last frame. Changes to the [`intensity`](Rain::intensity) take effect here
+    pub fn update(&mut self, delta: Duration) {
+        self.field.match_intensity(self.intensity, self.layers);
+        let (speed, wind) = (self.speed, self.wind);
+        self.field
+            .step(delta.as_secs_f64(), self.layers, |particle| {
+                let multiplier = depth_speed(particle.depth);
+                (wind * multiplier, speed * multiplier)
+            });
+    }
+
+    /// Return the character raindrops are drawn with, slanted to match the wind
+    fn drop_char(&self) -> char {
+        let slant = self.wind / self.speed.abs().max(f64::EPSILON);
+        if slant > 0.25 {
+            '\\'
+        } else if slant < -0.25 {
+            '/'
+        } else {
+            '|'
+        }
+    }
+}
+
+impl ViewElement for Rain {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        let drop_char = self.drop_char();
+
+        self.field
+            .particles
+            .iter()
+            .map(|particle| {
+                let colour = self.colour * depth_speed(particle.depth);
+                Pixel::new(
+                    self.pos + Vec2D::new(particle.x as isize, particle.y as isize),
+                    ColChar::new(drop_char, Modifier::Colour(colour)),
+                )
+            })
+            .collect()
+    }
+}
+
+impl CanAnimate for Rain {
+    fn is_animating(&self) -> bool {
+        self.intensity > 0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rain_slants_with_wind() {
+        let rain = Rain::new(Vec2D::ZERO, Vec2D::new(20, 10), 0.1, 4).with_wind(15.0);
+        let pixels = rain.active_pixels();
+
+        assert_eq!(pixels.len(), 20);
+        assert!(pixels.iter().all(|p| p.fill_char.text_char == '\\'));
+    }
+}
@@ -0,0 +1,142 @@
+use super::{depth_speed, ParticleField};
+use crate::{
+    elements::{
+        view::{ColChar, Colour, Modifier, ViewElement},
+        Pixel, Vec2D,
+    },
+    gameloop::CanAnimate,
+};
+use std::time::Duration;
+
+/// `Snow` fills an area with slowly falling snowflakes that sway from side to side as they are carried by the wind. Flakes on further depth layers are smaller, dimmer and slower. Call [`update()`](Snow::update()) every frame to move the snow
+#[derive(Debug, Clone)]
+pub struct Snow {
+    /// The position of the top left corner of the `Snow`
+    pub pos: Vec2D,
+    /// The fraction of cells holding a snowflake, between 0.0 and 1.0
+    pub intensity: f64,
+    /// How fast snowflakes on the nearest layer fall, in cells per second
+    pub speed: f64,
+    /// How fast the wind blows snowflakes sideways, in cells per second. Negative values blow to the left
+    pub wind: f64,
+    /// How far snowflakes sway from side to side, in cells per second
+    pub sway: f64,
+    /// The number of depth layers snowflakes are spread across
+    pub layers: u8,
+    /// The colour of snowflakes on the nearest layer
+    pub colour: Colour,
+    elapsed: Duration,
+    field: ParticleField,
+}
+
+impl Snow {
+    /// Create a new `Snow` covering an area, with white flakes, three depth layers and no wind
+    #[must_use]
+    pub fn new(pos: Vec2D, size: Vec2D, intensity: f64, seed: u64) -> Self {
+        let mut snow = Self {
+            pos,
+            intensity,
+            speed: 4.0,
+            wind: 0.0,
+            sway: 1.5,
+            layers: 3,
+            colour: Colour::WHITE,
+            elapsed: Duration::ZERO,
+            field: ParticleField::new(size, seed),
+        };
+        snow.field.match_intensity(intensity, snow.layers);
+        snow
+    }
+
+    /// Return the `Snow` with the modified wind
+    #[must_use]
+    pub const fn with_wind(mut self, wind: f64) -> Self {
+        self.wind = wind;
+        self
+    }
+
+    /// Return the `Snow` with the modified speed and sway
+    #[must_use]
+    pub const fn with_speed(mut self, speed: f64, sway: f64) -> Self {
+        self.speed = speed;
+        self.sway = sway;
+        self
+    }
+
+    /// Return the `Snow` with the modified number of depth layers
+    #[must_use]
+    pub const fn with_layers(mut self, layers: u8) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Return the `Snow` with the modified colour
+    #[must_use]
+    pub const fn with_colour(mut self, colour: Colour) -> Self {
+        self.colour = colour;
+        self
+    }
+
+    /// Return the width and height of the area covered by the `Snow`
+    #[must_use]
+    pub const fn size(&self) -> Vec2D {
+        self.field.size
+    }
+
+    /// Move the snow forward by `delta`, usually the time since the last frame. Changes to the [`intensity`](Snow::intensity) take effect here
+    pub fn update(&mut self, delta: Duration) {
+        self.elapsed += delta;
+        self.field.match_intensity(self.intensity, self.layers);
+
+        let (speed, wind, sway) = (self.speed, self.wind, self.sway);
+        let time = self.elapsed.as_secs_f64();
+        self.field
+            .step(delta.as_secs_f64(), self.layers, |particle| {
+                let multiplier = depth_speed(particle.depth);
+                let drift = sway * time.mul_add(2.0, particle.phase).sin();
+                ((wind + drift) * multiplier, speed * multiplier)
+            });
+    }
+}
+
+impl ViewElement for Snow {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        self.field
+            .particles
+            .iter()
+            .map(|particle| {
+                let flake = if particle.depth > 0.5 { '*' } else { '.' };
+                let colour = self.colour * depth_speed(particle.depth);
+                Pixel::new(
+                    self.pos + Vec2D::new(particle.x as isize, particle.y as isize),
+                    ColChar::new(flake, Modifier::Colour(colour)),
+                )
+            })
+            .collect()
+    }
+}
+
+impl CanAnimate for Snow {
+    fn is_animating(&self) -> bool {
+        self.intensity > 0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snow_stays_in_area() {
+        let mut snow = Snow::new(Vec2D::new(5, 5), Vec2D::new(10, 6), 0.2, 9).with_wind(-3.0);
+        for _ in 0..50 {
+            snow.update(Duration::from_millis(100));
+        }
+
+        let pixels = snow.active_pixels();
+        assert_eq!(pixels.len(), 12);
+        assert!(pixels
+            .iter()
+            .all(|p| (5..15).contains(&p.pos.x) && (5..11).contains(&p.pos.y)));
+    }
+}
@@ -0,0 +1,531 @@
+//! A bounding volume hierarchy ([`Bvh`]) over a mesh's triangles, for finding which triangles a ray or sphere touches without testing every face. Used for picking objects with the mouse through [`Viewport::screen_ray()`](super::Viewport::screen_ray()) and for colliding with static meshes in a [`PhysicsWorld`](super::physics::PhysicsWorld)
+//! ```
+//! use gemini_engine::elements3d::{bvh::{Bvh, Ray}, Mesh3D, Vec3D};
+//!
+//! let cube = Mesh3D::default_cube();
+//! let bvh = Bvh::new(&cube);
+//!
+//! let ray = Ray::new(Vec3D::new(0.0, 0.0, 5.0), Vec3D::new(0.0, 0.0, -1.0));
+//! let hit = bvh.raycast(ray, f64::INFINITY).unwrap();
+//! assert_eq!(hit.distance, 4.0);
+//! ```
+
+use super::{Mesh3D, Vec3D};
+
+/// The most triangles stored in a single leaf of a [`Bvh`]
+const LEAF_SIZE: usize = 4;
+
+/// An axis-aligned bounding box
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    /// The corner of the box with the lowest coordinates
+    pub min: Vec3D,
+    /// The corner of the box with the highest coordinates
+    pub max: Vec3D,
+}
+
+impl Aabb {
+    /// An empty box containing nothing, which grows to fit whatever is added to it
+    pub const EMPTY: Self = Self {
+        min: Vec3D::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+        max: Vec3D::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+    };
+
+    /// Return the smallest box containing every point
+    #[must_use]
+    pub fn from_points(points: &[Vec3D]) -> Self {
+        points
+            .iter()
+            .fold(Self::EMPTY, |bounds, point| bounds.union_point(*point))
+    }
+
+    /// Return the smallest box containing both this box and the point
+    #[must_use]
+    pub const fn union_point(self, point: Vec3D) -> Self {
+        Self {
+            min: Vec3D::new(
+                self.min.x.min(point.x),
+                self.min.y.min(point.y),
+                self.min.z.min(point.z),
+            ),
+            max: Vec3D::new(
+                self.max.x.max(point.x),
+                self.max.y.max(point.y),
+                self.max.z.max(point.z),
+            ),
+        }
+    }
+
+    /// Return the smallest box containing both boxes
+    #[must_use]
+    pub const fn union(self, other: Self) -> Self {
+        self.union_point(other.min).union_point(other.max)
+    }
+
+    /// Return the centre of the box
+    #[must_use]
+    pub fn centre(&self) -> Vec3D {
+        (self.min + self.max) / 2.0
+    }
+
+    /// Return the point in the box closest to the given point
+    #[must_use]
+    pub const fn closest_point(&self, point: Vec3D) -> Vec3D {
+        Vec3D::new(
+            point.x.clamp(self.min.x, self.max.x),
+            point.y.clamp(self.min.y, self.max.y),
+            point.z.clamp(self.min.z, self.max.z),
+        )
+    }
+
+    /// Return the distance along the ray at which it enters the box, or `None` if it misses the box or only reaches it after `max_distance`. Returns 0.0 if the ray starts inside the box
+    #[must_use]
+    pub fn ray_distance(&self, ray: Ray, max_distance: f64) -> Option<f64> {
+        let origin = axes(ray.origin);
+        let direction = axes(ray.direction);
+        let (min, max) = (axes(self.min), axes(self.max));
+        let (mut near, mut far) = (0.0f64, max_distance);
+
+        for axis in 0..3 {
+            let inverse = 1.0 / direction[axis];
+            let mut t0 = (min[axis] - origin[axis]) * inverse;
+            let mut t1 = (max[axis] - origin[axis]) * inverse;
+            if inverse < 0.0 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            near = near.max(t0);
+            far = far.min(t1);
+            if far < near {
+                return None;
+            }
+        }
+
+        Some(near)
+    }
+}
+
+/// Return the components of the vector as an array, so that they can be indexed by axis
+const fn axes(vector: Vec3D) -> [f64; 3] {
+    [vector.x, vector.y, vector.z]
+}
+
+/// A ray in 3D space, starting at an origin and travelling in one direction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    /// Where the ray starts
+    pub origin: Vec3D,
+    /// The direction of the ray, with a length of 1
+    pub direction: Vec3D,
+}
+
+impl Ray {
+    /// Create a new `Ray`. The direction is normalised
+    #[must_use]
+    pub fn new(origin: Vec3D, direction: Vec3D) -> Self {
+        Self {
+            origin,
+            direction: direction.normal(),
+        }
+    }
+
+    /// Return the point on the ray the given distance from its origin
+    #[must_use]
+    pub fn at(&self, distance: f64) -> Vec3D {
+        self.origin + self.direction * distance
+    }
+}
+
+/// Where a [`Ray`] hit a triangle in a [`Bvh`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    /// The distance along the ray to the hit
+    pub distance: f64,
+    /// The point that was hit
+    pub point: Vec3D,
+    /// The normal of the triangle that was hit, facing back towards the ray's origin
+    pub normal: Vec3D,
+    /// The index of the hit face in the original mesh's [`faces`](Mesh3D::faces)
+    pub face_index: usize,
+}
+
+/// A triangle in a [`Bvh`] touched by a sphere
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SphereHit {
+    /// The point on the triangle closest to the sphere's centre
+    pub point: Vec3D,
+    /// The distance from the sphere's centre to the `point`
+    pub distance: f64,
+    /// The index of the touched face in the original mesh's [`faces`](Mesh3D::faces)
+    pub face_index: usize,
+}
+
+/// A triangle stored in a [`Bvh`], along with the face it was taken from
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Triangle {
+    vertices: [Vec3D; 3],
+    face_index: usize,
+}
+
+impl Triangle {
+    fn bounds(&self) -> Aabb {
+        Aabb::from_points(&self.vertices)
+    }
+
+    fn centre(&self) -> Vec3D {
+        (self.vertices[0] + self.vertices[1] + self.vertices[2]) / 3.0
+    }
+
+    /// Return the distance along the ray at which it hits the triangle, using the Möller-Trumbore algorithm
+    fn ray_distance(&self, ray: Ray) -> Option<f64> {
+        let [corner, b, c] = self.vertices;
+        let (edge1, edge2) = (b - corner, c - corner);
+
+        let perpendicular = ray.direction.cross(edge2);
+        let determinant = edge1.dot(perpendicular);
+        if determinant.abs() < f64::EPSILON {
+            return None;
+        }
+        let inverse = 1.0 / determinant;
+
+        let to_origin = ray.origin - corner;
+        let u = to_origin.dot(perpendicular) * inverse;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let cross = to_origin.cross(edge1);
+        let v = ray.direction.dot(cross) * inverse;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let distance = edge2.dot(cross) * inverse;
+        (distance > f64::EPSILON).then_some(distance)
+    }
+
+    /// Return the point on the triangle closest to the given point
+    fn closest_point(&self, point: Vec3D) -> Vec3D {
+        let [a, b, c] = self.vertices;
+        let (ab, ac, ap) = (b - a, c - a, point - a);
+
+        let (d1, d2) = (ab.dot(ap), ac.dot(ap));
+        if d1 <= 0.0 && d2 <= 0.0 {
+            return a;
+        }
+
+        let bp = point - b;
+        let (d3, d4) = (ab.dot(bp), ac.dot(bp));
+        if d3 >= 0.0 && d4 <= d3 {
+            return b;
+        }
+
+        let edge_c = d1.mul_add(d4, -(d3 * d2));
+        if edge_c <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+            return a + ab * (d1 / (d1 - d3));
+        }
+
+        let cp = point - c;
+        let (d5, d6) = (ab.dot(cp), ac.dot(cp));
+        if d6 >= 0.0 && d5 <= d6 {
+            return c;
+        }
+
+        let edge_b = d5.mul_add(d2, -(d1 * d6));
+        if edge_b <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+            return a + ac * (d2 / (d2 - d6));
+        }
+
+        let edge_a = d3.mul_add(d6, -(d5 * d4));
+        if edge_a <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+            return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+        }
+
+        // The point is over the inside of the triangle
+        let denominator = 1.0 / (edge_a + edge_b + edge_c);
+        a + ab * (edge_b * denominator) + ac * (edge_c * denominator)
+    }
+}
+
+/// A node in a [`Bvh`]'s tree
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BvhNode {
+    /// Holds the triangles in the given range of [`Bvh::triangles`]
+    Leaf {
+        bounds: Aabb,
+        start: usize,
+        end: usize,
+    },
+    /// Splits into two more nodes, given by their index in [`Bvh::nodes`]
+    Branch {
+        bounds: Aabb,
+        left: usize,
+        right: usize,
+    },
+}
+
+impl BvhNode {
+    const fn bounds(&self) -> Aabb {
+        match self {
+            Self::Leaf { bounds, .. } | Self::Branch { bounds, .. } => *bounds,
+        }
+    }
+}
+
+/// A bounding volume hierarchy splits a mesh's triangles into a tree of nested boxes, so that ray and sphere queries only have to test the triangles in the boxes they pass through. Faces with more than three vertices are split into triangles, but every hit reports the index of the face it came from. The `Bvh` is built from the mesh's transformed vertices, so it has to be rebuilt if the mesh moves
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bvh {
+    triangles: Vec<Triangle>,
+    nodes: Vec<BvhNode>,
+}
+
+impl Bvh {
+    /// Build a `Bvh` over the mesh's faces, with the mesh's transform applied
+    #[must_use]
+    pub fn new(mesh: &Mesh3D) -> Self {
+        let vertices = mesh.transform.apply_to(&mesh.vertices);
+        let mut triangles = vec![];
+
+        for (face_index, face) in mesh.faces.iter().enumerate() {
+            let face_vertices = face.index_into(&vertices);
+            if let Some((first, rest)) = face_vertices.split_first() {
+                for pair in rest.windows(2) {
+                    triangles.push(Triangle {
+                        vertices: [*first, pair[0], pair[1]],
+                        face_index,
+                    });
+                }
+            }
+        }
+
+        let mut bvh = Self {
+            triangles,
+            nodes: vec![],
+        };
+        if !bvh.triangles.is_empty() {
+            bvh.build(0, bvh.triangles.len());
+        }
+
+        bvh
+    }
+
+    /// Build the node holding the triangles in the range, returning its index
+    fn build(&mut self, start: usize, end: usize) -> usize {
+        let bounds = self.triangles[start..end]
+            .iter()
+            .fold(Aabb::EMPTY, |bounds, triangle| {
+                bounds.union(triangle.bounds())
+            });
+        let index = self.nodes.len();
+        self.nodes.push(BvhNode::Leaf { bounds, start, end });
+
+        if end - start <= LEAF_SIZE {
+            return index;
+        }
+
+        // Split the triangles in half along the longest axis of their centres
+        let centre_bounds = Aabb::from_points(
+            &self.triangles[start..end]
+                .iter()
+                .map(Triangle::centre)
+                .collect::<Vec<_>>(),
+        );
+        let extent = axes(centre_bounds.max - centre_bounds.min);
+        let axis = (0..3)
+            .max_by(|a, b| extent[*a].total_cmp(&extent[*b]))
+            .unwrap_or(0);
+        self.triangles[start..end]
+            .sort_by(|a, b| axes(a.centre())[axis].total_cmp(&axes(b.centre())[axis]));
+
+        let middle = start + (end - start) / 2;
+        let left = self.build(start, middle);
+        let right = self.build(middle, end);
+        self.nodes[index] = BvhNode::Branch {
+            bounds,
+            left,
+            right,
+        };
+
+        index
+    }
+
+    /// Return the number of triangles in the `Bvh`
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.triangles.len()
+    }
+
+    /// Return true if the `Bvh` has no triangles
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+
+    /// Return the box around every triangle in the `Bvh`, or `None` if it is empty
+    #[must_use]
+    pub fn bounds(&self) -> Option<Aabb> {
+        self.nodes.first().map(BvhNode::bounds)
+    }
+
+    /// Return the closest triangle hit by the ray within `max_distance`, or `None` if the ray doesn't hit anything
+    #[must_use]
+    pub fn raycast(&self, ray: Ray, max_distance: f64) -> Option<RayHit> {
+        let mut closest: Option<(f64, Triangle)> = None;
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+
+        while let Some(index) = stack.pop() {
+            let node = self.nodes[index];
+            let limit = closest.map_or(max_distance, |(distance, _)| distance);
+            if node.bounds().ray_distance(ray, limit).is_none() {
+                continue;
+            }
+
+            match node {
+                BvhNode::Leaf { start, end, .. } => {
+                    for triangle in &self.triangles[start..end] {
+                        if let Some(distance) = triangle.ray_distance(ray) {
+                            if distance <= limit
+                                && closest.is_none_or(|(closest, _)| distance < closest)
+                            {
+                                closest = Some((distance, *triangle));
+                            }
+                        }
+                    }
+                }
+                BvhNode::Branch { left, right, .. } => stack.extend([left, right]),
+            }
+        }
+
+        closest.map(|(distance, triangle)| {
+            let [a, b, c] = triangle.vertices;
+            let normal = (b - a).cross(c - a).normal();
+
+            RayHit {
+                distance,
+                point: ray.at(distance),
+                normal: if normal.dot(ray.direction) > 0.0 {
+                    -normal
+                } else {
+                    normal
+                },
+                face_index: triangle.face_index,
+            }
+        })
+    }
+
+    /// Return every triangle within `radius` of the `centre`, along with the point on each closest to the centre
+    #[must_use]
+    pub fn query_sphere(&self, centre: Vec3D, radius: f64) -> Vec<SphereHit> {
+        let mut hits = vec![];
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+
+        while let Some(index) = stack.pop() {
+            let node = self.nodes[index];
+            let bounds = node.bounds();
+            if (bounds.closest_point(centre) - centre).magnitude() > radius {
+                continue;
+            }
+
+            match node {
+                BvhNode::Leaf { start, end, .. } => {
+                    for triangle in &self.triangles[start..end] {
+                        let point = triangle.closest_point(centre);
+                        let distance = (point - centre).magnitude();
+                        if distance <= radius {
+                            hits.push(SphereHit {
+                                point,
+                                distance,
+                                face_index: triangle.face_index,
+                            });
+                        }
+                    }
+                }
+                BvhNode::Branch { left, right, .. } => stack.extend([left, right]),
+            }
+        }
+
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements3d::Transform3D;
+
+    fn grid_of_cubes() -> Mesh3D {
+        let cube = Mesh3D::default_cube();
+        let mut vertices = vec![];
+        let mut faces = vec![];
+
+        for i in 0..10 {
+            let offset = Vec3D::new(f64::from(i) * 3.0, 0.0, 0.0);
+            let start = vertices.len();
+            vertices.extend(cube.vertices.iter().map(|v| *v + offset));
+            faces.extend(cube.faces.iter().map(|face| {
+                let mut face = face.clone();
+                face.v_indices.iter_mut().for_each(|i| *i += start);
+                face
+            }));
+        }
+
+        Mesh3D::new(Transform3D::DEFAULT, vertices, faces)
+    }
+
+    #[test]
+    fn raycast_finds_closest_face() {
+        let mesh = grid_of_cubes();
+        let bvh = Bvh::new(&mesh);
+        assert_eq!(bvh.len(), 10 * 6 * 2);
+
+        let ray = Ray::new(Vec3D::new(-5.0, 0.5, 0.0), Vec3D::new(1.0, 0.0, 0.0));
+        let hit = bvh
+            .raycast(ray, f64::INFINITY)
+            .expect("ray hits first cube");
+        assert!((hit.distance - 4.0).abs() < 1e-9);
+        assert_eq!(hit.normal, Vec3D::new(-1.0, 0.0, 0.0));
+        assert!(hit.face_index < 6);
+
+        let ray = Ray::new(Vec3D::new(27.0, 5.0, 0.0), Vec3D::new(0.0, -1.0, 0.0));
+        let hit = bvh.raycast(ray, f64::INFINITY).expect("ray hits last cube");
+        assert!(hit.face_index >= 54);
+        assert!(bvh.raycast(ray, 3.0).is_none());
+    }
+
+    #[test]
+    fn sphere_query_returns_touched_faces() {
+        let bvh = Bvh::new(&grid_of_cubes());
+
+        let hits = bvh.query_sphere(Vec3D::new(3.0, 1.5, 0.0), 0.6);
+        assert!(!hits.is_empty());
+        assert!(hits
+            .iter()
+            .all(|hit| (6..12).contains(&hit.face_index) && (hit.distance - 0.5).abs() < 1e-9));
+        assert!(bvh.query_sphere(Vec3D::new(1.5, 5.0, 0.0), 1.0).is_empty());
+    }
+
+    #[test]
+    fn screen_ray_picks_centre_of_view() {
+        use crate::elements::Vec2D;
+        use crate::elements3d::Viewport;
+
+        let viewport = Viewport::new(
+            Transform3D::new_t(Vec3D::new(0.0, 0.0, 5.0)),
+            20.0,
+            Vec2D::new(20, 10),
+        );
+        let bvh = Bvh::new(&Mesh3D::default_cube());
+
+        let hit = bvh.raycast(viewport.screen_ray(Vec2D::new(20, 10)), f64::INFINITY);
+        assert!(hit.is_some_and(|hit| (hit.distance - 4.0).abs() < 1e-9));
+        assert!(bvh
+            .raycast(viewport.screen_ray(Vec2D::new(0, 0)), f64::INFINITY)
+            .is_none());
+    }
+}
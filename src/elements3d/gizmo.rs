@@ -0,0 +1,405 @@
+use super::{bvh::Ray, Transform3D, Vec3D, Viewport};
+use crate::elements::{
+    view::{ColChar, Modifier},
+    Line, Pixel, PixelContainer, Vec2D,
+};
+use std::f64::consts::{PI, TAU};
+
+/// The number of line segments each [`GizmoMode::Rotate`] ring is drawn with
+const RING_SEGMENTS: usize = 32;
+
+/// What a [`Gizmo`] does to the [`Transform3D`] it is dragging
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GizmoMode {
+    /// Move the transform along an axis by dragging one of three arrows
+    #[default]
+    Translate,
+    /// Rotate the transform around an axis by dragging one of three rings
+    Rotate,
+    /// Scale the transform along an axis by dragging one of three handles
+    Scale,
+}
+
+/// One of the three handles of a [`Gizmo`], following the scene's axes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GizmoAxis {
+    /// The X axis, drawn in red
+    X,
+    /// The Y axis, drawn in green
+    Y,
+    /// The Z axis, drawn in blue
+    Z,
+}
+
+impl GizmoAxis {
+    /// Every axis, in order
+    pub const ALL: [Self; 3] = [Self::X, Self::Y, Self::Z];
+
+    /// Return the axis as a unit vector
+    #[must_use]
+    pub const fn direction(self) -> Vec3D {
+        match self {
+            Self::X => Vec3D::new(1.0, 0.0, 0.0),
+            Self::Y => Vec3D::new(0.0, 1.0, 0.0),
+            Self::Z => Vec3D::new(0.0, 0.0, 1.0),
+        }
+    }
+
+    /// Return the two unit vectors spanning the plane perpendicular to the axis, ordered so that a positive rotation around the axis turns the first towards the second
+    const fn plane(self) -> (Vec3D, Vec3D) {
+        match self {
+            Self::X => (Self::Y.direction(), Self::Z.direction()),
+            Self::Y => (Self::X.direction(), Self::Z.direction()),
+            Self::Z => (Self::X.direction(), Self::Y.direction()),
+        }
+    }
+
+    /// Return the component of the vector along the axis
+    const fn component(self, value: &mut Vec3D) -> &mut f64 {
+        match self {
+            Self::X => &mut value.x,
+            Self::Y => &mut value.y,
+            Self::Z => &mut value.z,
+        }
+    }
+
+    /// Return the `Modifier` the axis is drawn with
+    const fn modifier(self) -> Modifier {
+        match self {
+            Self::X => Modifier::RED,
+            Self::Y => Modifier::GREEN,
+            Self::Z => Modifier::BLUE,
+        }
+    }
+}
+
+/// The state of a drag in progress
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Drag {
+    axis: GizmoAxis,
+    start_transform: Transform3D,
+    /// The distance along the axis or the angle around it where the drag started
+    start_value: f64,
+}
+
+/// A `Gizmo` draws handles over an object that can be dragged with the mouse to translate, rotate or scale its [`Transform3D`], the core of an in-engine scene editor. Feed it mouse events with [`press()`](Gizmo::press()), [`drag()`](Gizmo::drag()) and [`release()`](Gizmo::release()), and draw it over the scene with [`render()`](Gizmo::render()). Handles follow the scene's axes rather than the object's rotation, and rotating adds to the matching component of [`Transform3D::rotation`]
+/// ```
+/// use gemini_engine::elements::Vec2D;
+/// use gemini_engine::elements3d::{Gizmo, GizmoMode, Mesh3D, Transform3D, Vec3D, Viewport};
+///
+/// let viewport = Viewport::new(Transform3D::new_t(Vec3D::new(0.0, 0.0, 10.0)), 40.0, Vec2D::new(50, 20));
+/// let mut cube = Mesh3D::default_cube();
+/// let mut gizmo = Gizmo::new(GizmoMode::Translate);
+///
+/// // Grab the end of the X arrow and drag it along
+/// let handle = viewport.world_to_screen(Vec3D::new(2.0, 0.0, 0.0)).unwrap();
+/// if gizmo.press(&viewport, &cube.transform, handle) {
+///     gizmo.drag(&viewport, &mut cube.transform, handle + Vec2D::new(-10, 0));
+///     gizmo.release();
+/// }
+/// assert!(cube.transform.translation.x > 1.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gizmo {
+    /// What dragging the handles does
+    pub mode: GizmoMode,
+    /// The length of the arrows and radius of the rings, in scene units
+    pub size: f64,
+    /// How close to a handle, in cells, the mouse has to be to grab it
+    pub pick_radius: f64,
+    /// The handle under the mouse, drawn highlighted. Updated by [`hover()`](Gizmo::hover())
+    pub hovered: Option<GizmoAxis>,
+    drag: Option<Drag>,
+}
+
+impl Gizmo {
+    /// Create a new `Gizmo` with a size of 2.0 and a pick radius of 1.5 cells
+    #[must_use]
+    pub const fn new(mode: GizmoMode) -> Self {
+        Self {
+            mode,
+            size: 2.0,
+            pick_radius: 1.5,
+            hovered: None,
+            drag: None,
+        }
+    }
+
+    /// Return the `Gizmo` with the modified size
+    #[must_use]
+    pub const fn with_size(mut self, size: f64) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Return the axis being dragged, or `None` if the `Gizmo` isn't being dragged
+    #[must_use]
+    pub fn dragging(&self) -> Option<GizmoAxis> {
+        self.drag.map(|drag| drag.axis)
+    }
+
+    /// Return the points along the handle for the axis, around an object at the `centre`
+    fn handle_points(&self, centre: Vec3D, axis: GizmoAxis) -> Vec<Vec3D> {
+        match self.mode {
+            GizmoMode::Translate | GizmoMode::Scale => {
+                vec![centre, centre + axis.direction() * self.size]
+            }
+            GizmoMode::Rotate => {
+                let (across, up) = axis.plane();
+                (0..=RING_SEGMENTS)
+                    .map(|i| {
+                        let angle = i as f64 / RING_SEGMENTS as f64 * TAU;
+                        centre + (across * angle.cos() + up * angle.sin()) * self.size
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Return the handle closest to the screen position, if any are within the [`pick_radius`](Gizmo::pick_radius)
+    #[must_use]
+    pub fn pick(
+        &self,
+        viewport: &Viewport,
+        transform: &Transform3D,
+        pos: Vec2D,
+    ) -> Option<GizmoAxis> {
+        GizmoAxis::ALL
+            .into_iter()
+            .filter_map(|axis| {
+                let points: Vec<Vec2D> = self
+                    .handle_points(transform.translation, axis)
+                    .into_iter()
+                    .filter_map(|point| viewport.world_to_screen(point))
+                    .collect();
+
+                points
+                    .windows(2)
+                    .map(|pair| distance_to_segment(pos, pair[0], pair[1]))
+                    .min_by(f64::total_cmp)
+                    .map(|distance| (axis, distance))
+            })
+            .filter(|(_, distance)| *distance <= self.pick_radius)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(axis, _)| axis)
+    }
+
+    /// Update the [`hovered`](Gizmo::hovered) handle from the mouse position
+    pub fn hover(&mut self, viewport: &Viewport, transform: &Transform3D, pos: Vec2D) {
+        self.hovered = self.pick(viewport, transform, pos);
+    }
+
+    /// Start dragging the handle at the screen position. Returns true if a handle was grabbed, in which case the mouse shouldn't be used to select anything else
+    pub fn press(&mut self, viewport: &Viewport, transform: &Transform3D, pos: Vec2D) -> bool {
+        let Some(axis) = self.pick(viewport, transform, pos) else {
+            return false;
+        };
+        let Some(start_value) = self.drag_value(viewport.screen_ray(pos), transform, axis) else {
+            return false;
+        };
+
+        self.hovered = Some(axis);
+        self.drag = Some(Drag {
+            axis,
+            start_transform: *transform,
+            start_value,
+        });
+        true
+    }
+
+    /// Continue a drag started by [`press()`](Gizmo::press()), updating the transform for the mouse's new position. Returns true if the transform changed
+    pub fn drag(&mut self, viewport: &Viewport, transform: &mut Transform3D, pos: Vec2D) -> bool {
+        let Some(drag) = self.drag else {
+            return false;
+        };
+        let Some(value) =
+            self.drag_value(viewport.screen_ray(pos), &drag.start_transform, drag.axis)
+        else {
+            return false;
+        };
+
+        let mut new_transform = drag.start_transform;
+        match self.mode {
+            GizmoMode::Translate => {
+                new_transform.translation += drag.axis.direction() * (value - drag.start_value);
+            }
+            GizmoMode::Rotate => {
+                // Keep the change between -π and π so that crossing behind the centre doesn't flip the object
+                let change = (value - drag.start_value + PI).rem_euclid(TAU) - PI;
+                *drag.axis.component(&mut new_transform.rotation) += change;
+            }
+            GizmoMode::Scale => {
+                if drag.start_value.abs() > f64::EPSILON {
+                    let scale = drag.axis.component(&mut new_transform.scale);
+                    *scale = (*scale * value / drag.start_value).max(0.01);
+                }
+            }
+        }
+
+        let changed = new_transform != *transform;
+        *transform = new_transform;
+        changed
+    }
+
+    /// Finish the current drag
+    pub const fn release(&mut self) {
+        self.drag = None;
+    }
+
+    /// Return how far along the axis the ray passes closest to it, or for [`GizmoMode::Rotate`], the angle around the axis where the ray crosses the ring's plane. Returns `None` if the ray runs parallel to the axis or plane
+    fn drag_value(&self, ray: Ray, transform: &Transform3D, axis: GizmoAxis) -> Option<f64> {
+        let centre = transform.translation;
+        let direction = axis.direction();
+
+        match self.mode {
+            GizmoMode::Translate | GizmoMode::Scale => {
+                // The closest point between two lines, with both directions normalised
+                let to_centre = centre - ray.origin;
+                let alignment = direction.dot(ray.direction);
+                let denominator = alignment.mul_add(-alignment, 1.0);
+                if denominator < 1e-6 {
+                    return None;
+                }
+
+                Some(
+                    alignment.mul_add(ray.direction.dot(to_centre), -direction.dot(to_centre))
+                        / denominator,
+                )
+            }
+            GizmoMode::Rotate => {
+                let facing = ray.direction.dot(direction);
+                if facing.abs() < 1e-6 {
+                    return None;
+                }
+                let hit = ray.at((centre - ray.origin).dot(direction) / facing) - centre;
+
+                let (across, up) = axis.plane();
+                Some(hit.dot(up).atan2(hit.dot(across)))
+            }
+        }
+    }
+
+    /// Render the `Gizmo` around an object with the transform, as seen by the `Viewport`. The hovered or dragged handle is drawn in yellow
+    #[must_use]
+    pub fn render(&self, viewport: &Viewport, transform: &Transform3D) -> PixelContainer {
+        let mut canvas = PixelContainer::new();
+        let active = self.dragging().or(self.hovered);
+
+        for axis in GizmoAxis::ALL {
+            let modifier = if active == Some(axis) {
+                Modifier::YELLOW
+            } else {
+                axis.modifier()
+            };
+            let points: Vec<Vec2D> = self
+                .handle_points(transform.translation, axis)
+                .into_iter()
+                .filter_map(|point| viewport.world_to_screen(point))
+                .collect();
+
+            for pair in points.windows(2) {
+                canvas.append_points(
+                    &Line::draw(pair[0], pair[1]),
+                    ColChar::SOLID.with_mod(modifier),
+                );
+            }
+
+            let end_char = match self.mode {
+                GizmoMode::Translate => Some(match axis {
+                    GizmoAxis::X => 'X',
+                    GizmoAxis::Y => 'Y',
+                    GizmoAxis::Z => 'Z',
+                }),
+                GizmoMode::Scale => Some('■'),
+                GizmoMode::Rotate => None,
+            };
+            if let (Some(end_char), Some(end)) = (end_char, points.last()) {
+                canvas.push(Pixel::new(*end, ColChar::new(end_char, modifier)));
+            }
+        }
+
+        canvas
+    }
+}
+
+/// Return the distance between a point and the line segment between `start` and `end`
+fn distance_to_segment(pos: Vec2D, start: Vec2D, end: Vec2D) -> f64 {
+    let to_point = pos - start;
+    let segment = end - start;
+    let (px, py) = (to_point.x as f64, to_point.y as f64);
+    let (sx, sy) = (segment.x as f64, segment.y as f64);
+
+    let length_squared = sx.mul_add(sx, sy * sy);
+    let amount = if length_squared > 0.0 {
+        (px.mul_add(sx, py * sy) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    (sx.mul_add(-amount, px)).hypot(sy.mul_add(-amount, py))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn viewport() -> Viewport {
+        let mut viewport = Viewport::new(
+            Transform3D::new_t(Vec3D::new(0.0, 0.0, 10.0)),
+            40.0,
+            Vec2D::new(50, 25),
+        );
+        viewport.character_width_multiplier = Some(1.0);
+        viewport
+    }
+
+    fn screen(viewport: &Viewport, x: f64, y: f64, z: f64) -> Vec2D {
+        viewport
+            .world_to_screen(Vec3D::new(x, y, z))
+            .expect("point is in front of the viewport")
+    }
+
+    #[test]
+    fn translate_follows_mouse_along_axis() {
+        let viewport = viewport();
+        let mut transform = Transform3D::DEFAULT;
+        let mut gizmo = Gizmo::new(GizmoMode::Translate);
+
+        let start = screen(&viewport, 2.0, 0.0, 0.0);
+        let end = screen(&viewport, 4.0, 0.0, 0.0);
+        assert!(!gizmo.press(&viewport, &transform, Vec2D::new(0, 0)));
+        assert!(gizmo.press(&viewport, &transform, start));
+        assert_eq!(gizmo.dragging(), Some(GizmoAxis::X));
+
+        // Moving off the axis only moves the transform along it
+        assert!(gizmo.drag(&viewport, &mut transform, end + Vec2D::new(0, 3)));
+        assert!((transform.translation.x - 2.0).abs() < 0.2);
+        assert!(transform.translation.y.abs() < f64::EPSILON);
+
+        gizmo.release();
+        assert!(!gizmo.drag(&viewport, &mut transform, start));
+    }
+
+    #[test]
+    fn rotate_and_scale() {
+        let viewport = viewport();
+        let mut transform = Transform3D::DEFAULT;
+
+        let mut gizmo = Gizmo::new(GizmoMode::Rotate);
+        let diagonal = 2.0 * std::f64::consts::FRAC_1_SQRT_2;
+        let start = screen(&viewport, diagonal, diagonal, 0.0);
+        let end = screen(&viewport, -diagonal, diagonal, 0.0);
+        assert!(gizmo.press(&viewport, &transform, start));
+        assert_eq!(gizmo.dragging(), Some(GizmoAxis::Z));
+        gizmo.drag(&viewport, &mut transform, end);
+        assert!((transform.rotation.z - PI / 2.0).abs() < 0.1);
+
+        let mut gizmo = Gizmo::new(GizmoMode::Scale);
+        let start = screen(&viewport, 0.0, 2.0, 0.0);
+        let end = screen(&viewport, 0.0, 4.0, 0.0);
+        assert!(gizmo.press(&viewport, &transform, start));
+        gizmo.drag(&viewport, &mut transform, end);
+        assert!((transform.scale.y - 2.0).abs() < 0.1);
+        assert!((transform.scale.x - 1.0).abs() < f64::EPSILON);
+    }
+}
@@ -1,5 +1,6 @@
 use super::{Face, Transform3D, Vec3D};
 mod mesh3d_presets;
+mod obj_export;
 
 /// The struct for a `Mesh3D` object, containing a position, rotation, collection of vertices and collection of [`Face`]s with indices to the vertex collection.
 #[derive(Debug, Clone)]
@@ -32,4 +33,10 @@ impl Mesh3D {
             faces,
         }
     }
+
+    /// Return a copy of the `Mesh3D` with its transform applied to its vertices and reset to [`Transform3D::DEFAULT`]. When rendering the same scene from several [`Viewport`](super::Viewport)s, baking the meshes first means their transforms are only applied once rather than once per viewport
+    #[must_use]
+    pub fn baked(&self) -> Self {
+        Self::new_at_origin(self.transform.apply_to(&self.vertices), self.faces.clone())
+    }
 }
@@ -0,0 +1,124 @@
+//! Export of [`Mesh3D`]s to the Wavefront OBJ format
+
+use super::Mesh3D;
+use crate::elements3d::Vec3D;
+use std::{fmt::Write, fs, io, path::Path};
+
+impl Mesh3D {
+    /// Return the mesh as the contents of a Wavefront OBJ file, with its transform applied to the vertices. Each vertex is given the average colour of the coloured faces using it (written as `v x y z r g b`, which most tools read as vertex colours) and each face gets its own normal. Faces are written counter-clockwise, as OBJ expects, so they face the same way as they do when rendered
+    #[must_use]
+    pub fn to_obj(&self) -> String {
+        let vertices = self.transform.apply_to(&self.vertices);
+        let mut output = String::from("# Exported by gemini-engine\n");
+
+        let mut colour_sums = vec![(Vec3D::ZERO, 0u32); vertices.len()];
+        for face in &self.faces {
+            if let Some(colour) = face.fill_char.modifier.colour() {
+                let colour = Vec3D::new(
+                    f64::from(colour.r),
+                    f64::from(colour.g),
+                    f64::from(colour.b),
+                );
+                for index in &face.v_indices {
+                    if let Some((sum, count)) = colour_sums.get_mut(*index) {
+                        *sum += colour;
+                        *count += 1;
+                    }
+                }
+            }
+        }
+
+        for (vertex, (colour_sum, count)) in vertices.iter().zip(colour_sums) {
+            let _ = write!(output, "v {} {} {}", vertex.x, vertex.y, vertex.z);
+            if count > 0 {
+                let colour = colour_sum / (f64::from(count) * 255.0);
+                let _ = write!(output, " {:.4} {:.4} {:.4}", colour.x, colour.y, colour.z);
+            }
+            output.push('\n');
+        }
+
+        let mut normal_count = 0;
+        for face in &self.faces {
+            // OBJ indices start at 1
+            let indices: Vec<usize> = face.v_indices.iter().rev().map(|i| i + 1).collect();
+
+            match indices.as_slice() {
+                [] => {}
+                [point] => {
+                    let _ = writeln!(output, "p {point}");
+                }
+                [start, end] => {
+                    let _ = writeln!(output, "l {start} {end}");
+                }
+                [a, b, c, ..] => {
+                    let corners = [a, b, c].map(|i| i.checked_sub(1).and_then(|i| vertices.get(i)));
+                    // A face that refers to vertices that don't exist is written without a normal
+                    let normal_index = if let [Some(&a), Some(&b), Some(&c)] = corners {
+                        // Adding zero turns any `-0` components into `0`
+                        let normal = (b - a).cross(c - a).normal() + Vec3D::ZERO;
+                        let _ = writeln!(output, "vn {} {} {}", normal.x, normal.y, normal.z);
+                        normal_count += 1;
+                        Some(normal_count)
+                    } else {
+                        None
+                    };
+
+                    output.push('f');
+                    for index in &indices {
+                        let _ = match normal_index {
+                            Some(normal_index) => write!(output, " {index}//{normal_index}"),
+                            None => write!(output, " {index}"),
+                        };
+                    }
+                    output.push('\n');
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Write the mesh to a Wavefront OBJ file at the path, as given by [`Mesh3D::to_obj()`], so that it can be opened in other 3D tools
+    ///
+    /// # Errors
+    /// Returns an error if the file couldn't be written
+    pub fn export_obj(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_obj())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        elements::view::ColChar,
+        elements3d::{Face, Mesh3D, Transform3D, Vec3D},
+    };
+
+    #[test]
+    fn exports_transformed_cube() {
+        let mut cube = Mesh3D::default_cube();
+        cube.transform = Transform3D::new_t(Vec3D::new(2.0, 0.0, 0.0));
+        let obj = cube.to_obj();
+        let lines: Vec<&str> = obj.lines().collect();
+
+        assert_eq!(lines.iter().filter(|l| l.starts_with("v ")).count(), 8);
+        assert_eq!(lines.iter().filter(|l| l.starts_with("vn ")).count(), 6);
+        // Shared by a blue and a red face
+        assert_eq!(lines[1], "v 3 1 -1 0.4020 0.0000 0.4667");
+        // The +x face, reversed to counter-clockwise with an outward normal
+        assert_eq!(lines[9], "vn 1 0 0");
+        assert_eq!(lines[10], "f 1//1 2//1 4//1 3//1");
+    }
+
+    #[test]
+    fn faces_with_missing_vertices_have_no_normal() {
+        let mesh = Mesh3D::new_at_origin(
+            vec![Vec3D::ZERO, Vec3D::new(1.0, 0.0, 0.0)],
+            vec![Face::new(vec![0, 1, 5], ColChar::SOLID)],
+        );
+        let obj = mesh.to_obj();
+
+        assert!(!obj.contains("vn "));
+        assert!(obj.ends_with("f 6 2 1\n"));
+    }
+}
@@ -0,0 +1,399 @@
+//! A minimal rigid-body physics step for 3D scenes: gravity, linear and angular velocity, and sphere or box colliders bouncing off static boxes and meshes with restitution and friction. It's aimed at dice rolls, falling crates and bouncing balls rather than accurate simulation. Dynamic bodies don't collide with each other
+//! ```
+//! use gemini_engine::elements3d::{
+//!     physics::{Collider, PhysicsWorld, RigidBody, StaticCollider},
+//!     Mesh3D, Vec3D,
+//! };
+//! use std::time::Duration;
+//!
+//! let mut ball = Mesh3D::default_cube();
+//! let mut world = PhysicsWorld::new(Vec3D::new(0.0, -9.8, 0.0));
+//! world.add_static(StaticCollider::new(Vec3D::new(-10.0, -1.0, -10.0), Vec3D::new(10.0, 0.0, 10.0)));
+//! let body = world.add_body(RigidBody::new(Vec3D::new(0.0, 5.0, 0.0), Collider::Sphere { radius: 1.0 }));
+//!
+//! for _ in 0..60 {
+//!     world.step(Duration::from_millis(16));
+//!     world.bodies[body].apply_to(&mut ball);
+//! }
+//! ```
+
+use super::{bvh::Bvh, Mesh3D, Vec3D};
+use std::time::Duration;
+
+/// The shape a [`RigidBody`] collides with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Collider {
+    /// A sphere around the body's position
+    Sphere {
+        /// The radius of the sphere
+        radius: f64,
+    },
+    /// An axis-aligned box around the body's position. The box doesn't rotate with the body. Against static meshes, it collides as a sphere fitting inside the box
+    Aabb {
+        /// Half of the box's size along each axis
+        half_extents: Vec3D,
+    },
+}
+
+impl Collider {
+    /// Return the radius of the sphere used for collisions with static meshes
+    const fn mesh_radius(self) -> f64 {
+        match self {
+            Self::Sphere { radius } => radius,
+            Self::Aabb { half_extents } => half_extents.x.min(half_extents.y).min(half_extents.z),
+        }
+    }
+}
+
+/// A static, axis-aligned box that [`RigidBody`]s collide with, such as the floor or a wall
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StaticCollider {
+    /// The corner of the box with the lowest coordinates
+    pub min: Vec3D,
+    /// The corner of the box with the highest coordinates
+    pub max: Vec3D,
+}
+
+impl StaticCollider {
+    /// Create a new `StaticCollider` between two opposite corners
+    #[must_use]
+    pub const fn new(corner0: Vec3D, corner1: Vec3D) -> Self {
+        Self {
+            min: Vec3D::new(
+                corner0.x.min(corner1.x),
+                corner0.y.min(corner1.y),
+                corner0.z.min(corner1.z),
+            ),
+            max: Vec3D::new(
+                corner0.x.max(corner1.x),
+                corner0.y.max(corner1.y),
+                corner0.z.max(corner1.z),
+            ),
+        }
+    }
+
+    /// Create a `StaticCollider` covering the bounding box of the mesh's transformed vertices. Returns `None` if the mesh has no vertices
+    #[must_use]
+    pub fn from_mesh(mesh: &Mesh3D) -> Option<Self> {
+        let vertices = mesh.transform.apply_to(&mesh.vertices);
+        let first = *vertices.first()?;
+
+        Some(vertices.iter().fold(Self::new(first, first), |bounds, v| {
+            Self::new(
+                Vec3D::new(
+                    bounds.min.x.min(v.x),
+                    bounds.min.y.min(v.y),
+                    bounds.min.z.min(v.z),
+                ),
+                Vec3D::new(
+                    bounds.max.x.max(v.x),
+                    bounds.max.y.max(v.y),
+                    bounds.max.z.max(v.z),
+                ),
+            )
+        }))
+    }
+
+    /// Return the point in the box closest to the given point
+    #[must_use]
+    pub const fn closest_point(&self, point: Vec3D) -> Vec3D {
+        Vec3D::new(
+            point.x.clamp(self.min.x, self.max.x),
+            point.y.clamp(self.min.y, self.max.y),
+            point.z.clamp(self.min.z, self.max.z),
+        )
+    }
+
+    /// If the collider at the position overlaps the box, return the direction to push it out of the box and how far it must be pushed
+    fn penetration(&self, position: Vec3D, collider: Collider) -> Option<(Vec3D, f64)> {
+        match collider {
+            Collider::Sphere { radius } => {
+                let closest = self.closest_point(position);
+                let offset = position - closest;
+                let distance = offset.magnitude();
+
+                if distance >= radius {
+                    None
+                } else if distance > 0.0 {
+                    Some((offset / distance, radius - distance))
+                } else {
+                    // The centre is inside the box, so push out through the nearest face
+                    let (normal, depth) = self.nearest_face(position, Vec3D::ZERO);
+                    Some((normal, depth + radius))
+                }
+            }
+            Collider::Aabb { half_extents } => {
+                let overlaps = (position.x - half_extents.x) < self.max.x
+                    && (position.x + half_extents.x) > self.min.x
+                    && (position.y - half_extents.y) < self.max.y
+                    && (position.y + half_extents.y) > self.min.y
+                    && (position.z - half_extents.z) < self.max.z
+                    && (position.z + half_extents.z) > self.min.z;
+
+                overlaps.then(|| self.nearest_face(position, half_extents))
+            }
+        }
+    }
+
+    /// Return the outward normal of the face that a box of the given half extents at the position can be pushed out of with the least movement, along with that distance
+    fn nearest_face(&self, position: Vec3D, half_extents: Vec3D) -> (Vec3D, f64) {
+        let candidates = [
+            (
+                Vec3D::new(1.0, 0.0, 0.0),
+                self.max.x - (position.x - half_extents.x),
+            ),
+            (
+                Vec3D::new(-1.0, 0.0, 0.0),
+                (position.x + half_extents.x) - self.min.x,
+            ),
+            (
+                Vec3D::new(0.0, 1.0, 0.0),
+                self.max.y - (position.y - half_extents.y),
+            ),
+            (
+                Vec3D::new(0.0, -1.0, 0.0),
+                (position.y + half_extents.y) - self.min.y,
+            ),
+            (
+                Vec3D::new(0.0, 0.0, 1.0),
+                self.max.z - (position.z - half_extents.z),
+            ),
+            (
+                Vec3D::new(0.0, 0.0, -1.0),
+                (position.z + half_extents.z) - self.min.z,
+            ),
+        ];
+
+        candidates
+            .into_iter()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((Vec3D::new(0.0, 1.0, 0.0), 0.0))
+    }
+}
+
+/// A moving object in a [`PhysicsWorld`]. Call [`apply_to()`](RigidBody::apply_to()) after each step to move a [`Mesh3D`] with it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RigidBody {
+    /// The position of the body's centre
+    pub position: Vec3D,
+    /// The rotation of the body in radians, as in [`Transform3D::rotation`](super::Transform3D::rotation)
+    pub rotation: Vec3D,
+    /// How fast the body is moving, in units per second
+    pub velocity: Vec3D,
+    /// How fast the body is spinning around each axis, in radians per second
+    pub angular_velocity: Vec3D,
+    /// The shape the body collides with
+    pub collider: Collider,
+    /// How bouncy the body is, from 0.0 (no bounce) to 1.0 (a perfectly elastic bounce)
+    pub restitution: f64,
+    /// How much of the body's sliding and spinning is lost in each collision, from 0.0 (frictionless) to 1.0
+    pub friction: f64,
+}
+
+impl RigidBody {
+    /// Create a new, still `RigidBody` at a position with a restitution of 0.5 and friction of 0.2
+    #[must_use]
+    pub const fn new(position: Vec3D, collider: Collider) -> Self {
+        Self {
+            position,
+            rotation: Vec3D::ZERO,
+            velocity: Vec3D::ZERO,
+            angular_velocity: Vec3D::ZERO,
+            collider,
+            restitution: 0.5,
+            friction: 0.2,
+        }
+    }
+
+    /// Return the `RigidBody` with the modified velocity and angular velocity
+    #[must_use]
+    pub const fn with_velocity(mut self, velocity: Vec3D, angular_velocity: Vec3D) -> Self {
+        self.velocity = velocity;
+        self.angular_velocity = angular_velocity;
+        self
+    }
+
+    /// Return the `RigidBody` with the modified restitution and friction
+    #[must_use]
+    pub const fn with_material(mut self, restitution: f64, friction: f64) -> Self {
+        self.restitution = restitution;
+        self.friction = friction;
+        self
+    }
+
+    /// Set the mesh's translation and rotation to match the body
+    pub const fn apply_to(&self, mesh: &mut Mesh3D) {
+        mesh.transform.translation = self.position;
+        mesh.transform.rotation = self.rotation;
+    }
+
+    /// Push the body out of a collision and bounce it off the surface with the given normal
+    fn resolve(&mut self, normal: Vec3D, depth: f64) {
+        self.position += normal * depth;
+
+        let normal_speed = self.velocity.dot(normal);
+        if normal_speed < 0.0 {
+            let friction = self.friction.clamp(0.0, 1.0);
+            let normal_velocity = normal * normal_speed;
+            let tangent_velocity = self.velocity - normal_velocity;
+
+            self.velocity =
+                tangent_velocity * (1.0 - friction) - normal_velocity * self.restitution;
+            self.angular_velocity *= 1.0 - friction;
+        }
+    }
+}
+
+/// A `PhysicsWorld` holds [`RigidBody`]s and the [`StaticCollider`]s they bounce off. Call [`step()`](PhysicsWorld::step()) every frame to move the bodies
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhysicsWorld {
+    /// The acceleration applied to every body, in units per second squared
+    pub gravity: Vec3D,
+    /// The moving bodies in the world
+    pub bodies: Vec<RigidBody>,
+    /// The static boxes bodies collide with
+    pub statics: Vec<StaticCollider>,
+    /// The static meshes bodies collide with, such as terrain
+    pub meshes: Vec<Bvh>,
+}
+
+impl PhysicsWorld {
+    /// Create a new, empty `PhysicsWorld` with the given gravity
+    #[must_use]
+    pub const fn new(gravity: Vec3D) -> Self {
+        Self {
+            gravity,
+            bodies: vec![],
+            statics: vec![],
+            meshes: vec![],
+        }
+    }
+
+    /// Add a body to the world, returning its index in [`bodies`](PhysicsWorld::bodies)
+    pub fn add_body(&mut self, body: RigidBody) -> usize {
+        self.bodies.push(body);
+        self.bodies.len() - 1
+    }
+
+    /// Add a static collider to the world
+    pub fn add_static(&mut self, collider: StaticCollider) {
+        self.statics.push(collider);
+    }
+
+    /// Add a static mesh to the world for bodies to collide with. Its triangles are stored in a [`Bvh`] with the mesh's current transform applied, so moving the mesh afterwards has no effect
+    pub fn add_static_mesh(&mut self, mesh: &Mesh3D) {
+        self.meshes.push(Bvh::new(mesh));
+    }
+
+    /// Move every body forward by `delta`, usually the time since the last frame, and resolve their collisions with the static geometry
+    pub fn step(&mut self, delta: Duration) {
+        let seconds = delta.as_secs_f64();
+
+        for body in &mut self.bodies {
+            body.velocity += self.gravity * seconds;
+            body.position += body.velocity * seconds;
+            body.rotation += body.angular_velocity * seconds;
+
+            for collider in &self.statics {
+                if let Some((normal, depth)) = collider.penetration(body.position, body.collider) {
+                    body.resolve(normal, depth);
+                }
+            }
+
+            let radius = body.collider.mesh_radius();
+            for mesh in &self.meshes {
+                for hit in mesh.query_sphere(body.position, radius) {
+                    // Earlier hits may have already pushed the body away from this one
+                    let offset = body.position - hit.point;
+                    let distance = offset.magnitude();
+                    if distance > 0.0 && distance < radius {
+                        body.resolve(offset / distance, radius - distance);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn floor() -> StaticCollider {
+        StaticCollider::new(Vec3D::new(-10.0, -1.0, -10.0), Vec3D::new(10.0, 0.0, 10.0))
+    }
+
+    #[test]
+    fn ball_bounces_and_settles() {
+        let mut world = PhysicsWorld::new(Vec3D::new(0.0, -10.0, 0.0));
+        world.add_static(floor());
+        let ball = world.add_body(
+            RigidBody::new(Vec3D::new(0.0, 3.0, 0.0), Collider::Sphere { radius: 1.0 })
+                .with_velocity(Vec3D::new(1.0, 0.0, 0.0), Vec3D::new(0.0, 5.0, 0.0)),
+        );
+
+        let mut bounced = false;
+        for _ in 0..600 {
+            world.step(Duration::from_millis(10));
+            bounced |= world.bodies[ball].velocity.y > 0.0;
+        }
+
+        let body = world.bodies[ball];
+        assert!(bounced);
+        assert!((body.position.y - 1.0).abs() < 0.05);
+        assert!(body.velocity.x.abs() < 0.01);
+        assert!(body.angular_velocity.y.abs() < 0.01);
+    }
+
+    #[test]
+    fn box_rests_on_mesh_bounds() {
+        let mut world = PhysicsWorld::new(Vec3D::new(0.0, -10.0, 0.0));
+        let mut platform = Mesh3D::default_cube();
+        platform.transform.translation = Vec3D::new(0.0, -1.0, 0.0);
+        world.add_static(StaticCollider::from_mesh(&platform).expect("cube has vertices"));
+
+        let crate_body = world.add_body(RigidBody::new(
+            Vec3D::new(0.5, 4.0, 0.0),
+            Collider::Aabb {
+                half_extents: Vec3D::new(0.5, 0.5, 0.5),
+            },
+        ));
+        for _ in 0..600 {
+            world.step(Duration::from_millis(10));
+        }
+
+        assert!((world.bodies[crate_body].position.y - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn ball_rolls_down_mesh_slope() {
+        use crate::elements::view::ColChar;
+        use crate::elements3d::Face;
+
+        let slope = Mesh3D::new_at_origin(
+            vec![
+                Vec3D::new(-10.0, 5.0, -10.0),
+                Vec3D::new(10.0, -5.0, -10.0),
+                Vec3D::new(10.0, -5.0, 10.0),
+                Vec3D::new(-10.0, 5.0, 10.0),
+            ],
+            vec![Face::new(vec![0, 1, 2, 3], ColChar::SOLID)],
+        );
+        let mut world = PhysicsWorld::new(Vec3D::new(0.0, -10.0, 0.0));
+        world.add_static_mesh(&slope);
+        let ball = world.add_body(
+            RigidBody::new(Vec3D::new(0.0, 2.0, 0.0), Collider::Sphere { radius: 1.0 })
+                .with_material(0.0, 0.0),
+        );
+
+        for _ in 0..50 {
+            world.step(Duration::from_millis(10));
+        }
+
+        let body = world.bodies[ball];
+        assert!(body.position.x > 0.0);
+        // The ball stays on the surface of the slope rather than falling through it
+        let surface_y = -body.position.x / 2.0;
+        assert!(body.position.y > surface_y);
+    }
+}
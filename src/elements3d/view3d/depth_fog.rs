@@ -0,0 +1,95 @@
+use super::BRIGHTNESS_CHARS;
+use crate::elements::view::{ColChar, Colour, Modifier};
+
+/// How faces fade out as they move into a [`DepthFog`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FogFade {
+    /// Blend the face's colour towards the fog colour. Faces without a [`Modifier::Colour`] are treated as white
+    Colour(Colour),
+    /// Swap the face's character for sparser ones from [`BRIGHTNESS_CHARS`], for scenes without colour
+    Characters,
+}
+
+/// `DepthFog` fades faces out with distance from the [`Viewport`](super::Viewport): faces nearer than [`start`](DepthFog::start) are untouched, faces between `start` and [`end`](DepthFog::end) fade more the further away they are, and faces beyond `end` aren't drawn at all. This greatly helps depth perception at low resolutions and hides objects popping in at the far plane
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepthFog {
+    /// The distance at which faces start to fade
+    pub start: f64,
+    /// The distance at which faces have fully faded and are no longer drawn
+    pub end: f64,
+    /// How faces fade
+    pub fade: FogFade,
+}
+
+impl DepthFog {
+    /// Create a new `DepthFog` between two distances
+    #[must_use]
+    pub const fn new(start: f64, end: f64, fade: FogFade) -> Self {
+        Self { start, end, fade }
+    }
+
+    /// Return how faded something at the distance is, from 0.0 (not at all) to 1.0 (fully faded)
+    #[must_use]
+    pub fn amount(&self, distance: f64) -> f64 {
+        if self.end <= self.start {
+            return if distance >= self.end { 1.0 } else { 0.0 };
+        }
+        ((distance - self.start) / (self.end - self.start)).clamp(0.0, 1.0)
+    }
+
+    /// Returns true if something at the distance is beyond the end of the fog and shouldn't be drawn
+    #[must_use]
+    pub fn is_culled(&self, distance: f64) -> bool {
+        distance > self.end
+    }
+
+    /// Return the `ColChar` faded for the distance
+    #[must_use]
+    pub fn apply(&self, fill_char: ColChar, distance: f64) -> ColChar {
+        let amount = self.amount(distance);
+        if amount <= 0.0 {
+            return fill_char;
+        }
+
+        match self.fade {
+            FogFade::Colour(fog_colour) => {
+                let colour = match fill_char.modifier {
+                    Modifier::Colour(colour) => colour,
+                    _ => Colour::WHITE,
+                };
+                fill_char.with_colour(colour.lerp(fog_colour, amount))
+            }
+            FogFade::Characters => {
+                let ramp: Vec<char> = BRIGHTNESS_CHARS.chars().collect();
+                let densest = ramp
+                    .iter()
+                    .position(|c| *c == fill_char.text_char)
+                    .unwrap_or(ramp.len() - 1);
+                let index = (densest as f64 * (1.0 - amount)).round() as usize;
+
+                fill_char.with_char(ramp[index])
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fades_between_start_and_end() {
+        let fog = DepthFog::new(10.0, 20.0, FogFade::Colour(Colour::BLACK));
+        let white = ColChar::SOLID.with_colour(Colour::WHITE);
+
+        assert_eq!(fog.apply(white, 5.0), white);
+        assert_eq!(
+            fog.apply(white, 15.0),
+            white.with_colour(Colour::greyscale(128))
+        );
+        assert!(fog.is_culled(25.0));
+
+        let sparse = DepthFog::new(0.0, 10.0, FogFade::Characters).apply(ColChar::SOLID, 10.0);
+        assert_eq!(sparse.text_char, '.');
+    }
+}
@@ -1,23 +1,31 @@
 //! This module is home to the [`Viewport`], which handles the projecting of [`Mesh3D`]s to a format then displayable by a [`View`](crate::elements::View)
 
 use crate::elements::{
-    view::{utils, ColChar, Modifier},
+    view::{utils, ColChar, Modifier, ViewElement},
     Line, Pixel, PixelContainer, Polygon, Text, Vec2D,
 };
+mod depth_fog;
 mod display_mode;
+mod portal;
 mod render_helpers;
+mod skybox;
 mod transform3d;
+mod viewport_region;
+pub use depth_fog::{DepthFog, FogFade};
 pub use display_mode::{
     lighting::{Light, LightType, BRIGHTNESS_CHARS},
     DisplayMode,
 };
+pub use portal::Portal;
 pub use render_helpers::Face;
 use render_helpers::ProjectedFace;
+pub use skybox::Skybox;
 pub use transform3d::{Transform3D, Vec3D};
+pub use viewport_region::{render_regions, ViewportRegion};
 
 use self::render_helpers::ProjectedVertex;
 
-use super::Mesh3D;
+use super::{bvh::Ray, Mesh3D};
 
 /// The `Viewport` handles printing 3D objects to a 2D [`View`](crate::elements::View), and also acts as the scene's camera.
 pub struct Viewport {
@@ -27,39 +35,101 @@ pub struct Viewport {
     pub fov: f64,
     /// The center of the view you intend to print to. `View.center()` returns exactly what you need for this
     pub origin: Vec2D,
-    /// Most terminals don't have perfectly square characters. The value you set here is how much the final image will be stretched in the X axis to account for this. The default value is `2.2` but it will be different in most terminals
-    pub character_width_multiplier: f64,
+    /// Most terminals don't have perfectly square characters. The value you set here is how much the final image will be stretched in the X axis to account for this. Defaults to `None`, which uses the engine-wide [`cell_aspect_ratio()`](crate::elements::view::utils::cell_aspect_ratio()) at the time of projecting
+    pub character_width_multiplier: Option<f64>,
     /// Any face with vertices closer to the viewport than this value will be clipped
     pub clipping_distace: f64,
+    /// Fades faces out with distance from the viewport, and culls them beyond its end. Defaults to `None`
+    pub fog: Option<DepthFog>,
 }
 
 impl Viewport {
-    /// Create a new Viewport with a default [`character_width_multiplier`](Viewport::character_width_multiplier) of 2.2
+    /// Create a new Viewport, which follows the engine-wide [`cell_aspect_ratio()`](crate::elements::view::utils::cell_aspect_ratio()) unless given a [`character_width_multiplier`](Viewport::character_width_multiplier)
     #[must_use]
     pub const fn new(transform: Transform3D, fov: f64, screen_origin: Vec2D) -> Self {
         Self {
             transform,
             fov,
             origin: screen_origin,
-            character_width_multiplier: 2.2,
+            character_width_multiplier: None,
             clipping_distace: 0.3,
+            fog: None,
         }
     }
 
-    /// Project the [`Vec3D`] on a flat plane using the `Viewport`'s [fov](Viewport::fov) and [`character_width_multiplier`](Viewport::character_width_multiplier)
+    /// Return the `Viewport` with the modified [`character_width_multiplier`](Viewport::character_width_multiplier), overriding the engine-wide cell aspect ratio
+    #[must_use]
+    pub const fn with_character_width_multiplier(
+        mut self,
+        character_width_multiplier: f64,
+    ) -> Self {
+        self.character_width_multiplier = Some(character_width_multiplier);
+        self
+    }
+
+    /// Return how much the image is stretched in the X axis: the [`character_width_multiplier`](Viewport::character_width_multiplier) if set, otherwise the engine-wide [`cell_aspect_ratio()`](crate::elements::view::utils::cell_aspect_ratio())
+    #[must_use]
+    pub fn width_multiplier(&self) -> f64 {
+        self.character_width_multiplier
+            .unwrap_or_else(crate::utils::cell_aspect_ratio)
+    }
+
+    /// Return the `Viewport` with the modified [`fog`](Viewport::fog)
+    #[must_use]
+    pub const fn with_fog(mut self, fog: Option<DepthFog>) -> Self {
+        self.fog = fog;
+        self
+    }
+
+    /// Return the `ColChar` faded by the `Viewport`'s fog for something at the given distance, or unchanged if there is no fog
+    fn apply_fog(&self, fill_char: ColChar, distance: Option<f64>) -> ColChar {
+        match (self.fog, distance) {
+            (Some(fog), Some(distance)) => fog.apply(fill_char, distance),
+            _ => fill_char,
+        }
+    }
+
+    /// Return the [`Ray`] from the `Viewport` through the given position on the screen, inverting the perspective projection. Cast it into a [`Bvh`](super::bvh::Bvh) to find what is under the mouse
+    #[must_use]
+    pub fn screen_ray(&self, pos: Vec2D) -> Ray {
+        let offset = pos - self.origin;
+        let view_direction = Vec3D::new(
+            -(offset.x as f64) / self.width_multiplier() / self.fov,
+            -(offset.y as f64) / self.fov,
+            -1.0,
+        );
+
+        Ray::new(
+            self.transform.translation,
+            self.transform.viewport_direction_to_world(view_direction),
+        )
+    }
+
+    /// Return where a point in the scene appears on the screen, or `None` if it is behind the `Viewport`'s [`clipping_distace`](Viewport::clipping_distace)
+    #[must_use]
+    pub fn world_to_screen(&self, pos: Vec3D) -> Option<Vec2D> {
+        let pos = self.transform.apply_viewport_transform(&[pos])[0];
+
+        (pos.z < -self.clipping_distace).then(|| self.perspective(pos))
+    }
+
+    /// Project the [`Vec3D`] on a flat plane using the `Viewport`'s [fov](Viewport::fov) and [`width_multiplier()`](Viewport::width_multiplier())
     fn perspective(&self, pos: Vec3D) -> Vec2D {
         let f = self.fov / pos.z;
         let (sx, sy) = (pos.x * f, pos.y * f);
 
         // adjust for non-square pixels
-        let sx = (sx * self.character_width_multiplier).round();
+        let sx = (sx * self.width_multiplier()).round();
         let sy = sy.round();
 
         self.origin + Vec2D::new(sx as isize, sy as isize)
     }
 
-    /// Return the object's vertices, transformed
+    /// Return the object's vertices, transformed. The object's own transform is skipped if it has already been [baked](Mesh3D::baked)
     fn transform_vertices(&self, object: &Mesh3D) -> Vec<Vec3D> {
+        if object.transform.is_default() {
+            return self.transform.apply_viewport_transform(&object.vertices);
+        }
         let obj_transformed = object.transform.apply_to(&object.vertices);
 
         self.transform.apply_viewport_transform(&obj_transformed)
@@ -103,17 +173,19 @@ impl Viewport {
                     continue;
                 }
 
-                let mean_z = if sort_faces {
-                    Some(
-                        face_vertices
-                            .iter()
-                            .map(ProjectedVertex::z_index)
-                            .sum::<f64>()
-                            / face_vertices.len() as f64,
-                    )
-                } else {
-                    None
-                };
+                let mean_z = Some(
+                    face_vertices
+                        .iter()
+                        .map(ProjectedVertex::z_index)
+                        .sum::<f64>()
+                        / face_vertices.len() as f64,
+                );
+
+                if let (Some(fog), Some(distance)) = (self.fog, mean_z) {
+                    if fog.is_culled(distance) {
+                        continue;
+                    }
+                }
 
                 screen_faces.push(ProjectedFace::new(
                     face_screen_points,
@@ -136,20 +208,62 @@ impl Viewport {
     #[must_use]
     pub fn render(&self, objects: Vec<&Mesh3D>, display_mode: DisplayMode) -> PixelContainer {
         let mut canvas = PixelContainer::new();
+        self.render_each(objects, display_mode, |pixel, _| canvas.push(pixel));
+
+        canvas
+    }
+
+    /// Render the [`Mesh3D`]s like [`render()`](Viewport::render()), but return each pixel along with its distance from the `Viewport`. Plot them with [`View::plot_with_depth()`](crate::elements::View::plot_with_depth()) to draw 2D elements in front of or behind parts of the 3D scene regardless of the order they're blit in. Faces use the mean distance of their vertices
+    #[must_use]
+    pub fn render_with_depth(
+        &self,
+        objects: Vec<&Mesh3D>,
+        display_mode: DisplayMode,
+    ) -> Vec<(Pixel, f64)> {
+        let mut pixels = vec![];
+        self.render_each(objects, display_mode, |pixel, depth| {
+            pixels.push((pixel, depth));
+        });
+
+        pixels
+    }
+
+    /// Render the [`Mesh3D`]s, passing every pixel and its distance from the `Viewport` to `emit`
+    fn render_each(
+        &self,
+        objects: Vec<&Mesh3D>,
+        display_mode: DisplayMode,
+        mut emit: impl FnMut(Pixel, f64),
+    ) {
+        engine_span!("rasterise", objects = objects.len());
+        let mut emit_points = |points: &[Vec2D], fill_char: ColChar, depth: f64| {
+            for point in points {
+                emit(Pixel::new(*point, fill_char), depth);
+            }
+        };
 
         match display_mode {
             DisplayMode::Debug => {
                 for object in objects {
                     for (i, vertex) in self.get_vertices_on_screen(object).iter().enumerate() {
                         let index_text = i.to_string();
-                        canvas.blit(&Text::new(vertex.displayed, &index_text, Modifier::None));
+                        for pixel in
+                            Text::new(vertex.displayed, &index_text, Modifier::None).active_pixels()
+                        {
+                            emit_points(&[pixel.pos], pixel.fill_char, vertex.z_index());
+                        }
                     }
                 }
             }
             DisplayMode::Points { fill_char } => {
                 for object in objects {
                     for vertex in self.get_vertices_on_screen(object) {
-                        canvas.push(Pixel::new(vertex.displayed, fill_char));
+                        let distance = vertex.z_index();
+                        if self.fog.is_some_and(|fog| fog.is_culled(distance)) {
+                            continue;
+                        }
+                        let fill_char = self.apply_fog(fill_char, Some(distance));
+                        emit_points(&[vertex.displayed], fill_char, distance);
                     }
                 }
             }
@@ -162,7 +276,11 @@ impl Viewport {
                             face.screen_points[fi],
                             face.screen_points[(fi + 1) % face.screen_points.len()],
                         );
-                        canvas.append_points(&Line::draw(i0, i1), face.fill_char);
+                        emit_points(
+                            &Line::draw(i0, i1),
+                            self.apply_fog(face.fill_char, face.z_index),
+                            face.z_index.unwrap_or(0.0),
+                        );
                     }
                 }
             }
@@ -170,7 +288,11 @@ impl Viewport {
                 let screen_faces = self.project_faces(objects, true, true);
 
                 for face in screen_faces {
-                    canvas.append_points(&Polygon::draw(&face.screen_points), face.fill_char);
+                    emit_points(
+                        &Polygon::draw(&face.screen_points),
+                        self.apply_fog(face.fill_char, face.z_index),
+                        face.z_index.unwrap_or(0.0),
+                    );
                 }
             }
             DisplayMode::Illuminated { lights } => {
@@ -198,11 +320,28 @@ impl Viewport {
                         face.fill_char
                     };
 
-                    canvas.append_points(&Polygon::draw(&face.screen_points), fill_char);
+                    emit_points(
+                        &Polygon::draw(&face.screen_points),
+                        self.apply_fog(fill_char, face.z_index),
+                        face.z_index.unwrap_or(0.0),
+                    );
                 }
             }
         }
+    }
+}
 
-        canvas
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::view::utils::cell_aspect_ratio;
+
+    #[test]
+    fn width_multiplier_follows_the_cell_aspect_ratio() {
+        let viewport = Viewport::new(Transform3D::default(), 95.0, Vec2D::ZERO);
+        assert!((viewport.width_multiplier() - cell_aspect_ratio()).abs() < f64::EPSILON);
+
+        let viewport = viewport.with_character_width_multiplier(1.0);
+        assert!((viewport.width_multiplier() - 1.0).abs() < f64::EPSILON);
     }
 }
@@ -0,0 +1,228 @@
+use super::{DisplayMode, Vec3D, Viewport};
+use crate::{
+    elements::{
+        view::{ColChar, Wrapping},
+        Pixel, PixelContainer, Polygon, Vec2D, View,
+    },
+    elements3d::Mesh3D,
+};
+
+/// A `Portal` is a flat, four-cornered surface in the scene whose face shows what another [`Viewport`] sees, for portals, security monitors and mirrors. The portal camera's view is rendered offscreen with [`Viewport::render_offscreen()`] and then mapped onto the portal's face as seen by the main camera. Portals are drawn over the rest of the scene, so blit them after it
+pub struct Portal {
+    /// The corners of the portal's face in world space as seen from the front: top left, bottom left, bottom right and top right. This is the same winding order as a visible [`Face`](super::Face)
+    pub corners: [Vec3D; 4],
+    /// The camera whose view is shown on the portal's face
+    pub camera: Viewport,
+    /// How the portal camera renders the scene
+    pub display_mode: DisplayMode,
+    /// The width and height of the offscreen render shown on the portal's face
+    pub resolution: Vec2D,
+    /// The character used for parts of the offscreen render with nothing in them
+    pub background_char: ColChar,
+    /// If true, the offscreen render is flipped horizontally, as it would be in a mirror
+    pub mirrored: bool,
+}
+
+impl Portal {
+    /// Create a new `Portal` showing the view from the `camera` on a face with the given corners
+    #[must_use]
+    pub const fn new(
+        corners: [Vec3D; 4],
+        camera: Viewport,
+        display_mode: DisplayMode,
+        resolution: Vec2D,
+    ) -> Self {
+        Self {
+            corners,
+            camera,
+            display_mode,
+            resolution,
+            background_char: ColChar::BACKGROUND,
+            mirrored: false,
+        }
+    }
+
+    /// Return the `Portal` with the modified background character
+    #[must_use]
+    pub const fn with_background_char(mut self, background_char: ColChar) -> Self {
+        self.background_char = background_char;
+        self
+    }
+
+    /// Return the `Portal` with the modified `mirrored` property
+    #[must_use]
+    pub const fn with_mirrored(mut self, mirrored: bool) -> Self {
+        self.mirrored = mirrored;
+        self
+    }
+
+    /// Render the portal's face as seen by the `viewer`, showing the objects from the portal's camera. Returns nothing if the portal is behind the viewer or facing away from it
+    #[must_use]
+    pub fn render(&self, viewer: &Viewport, objects: Vec<&Mesh3D>) -> PixelContainer {
+        let view_space = viewer.transform.apply_viewport_transform(&self.corners);
+        if view_space
+            .iter()
+            .any(|corner| corner.z >= -viewer.clipping_distace)
+        {
+            return PixelContainer::new();
+        }
+        let screen: Vec<Vec2D> = view_space
+            .iter()
+            .map(|corner| viewer.perspective(*corner))
+            .collect();
+        if !crate::elements::view::utils::is_clockwise(&screen) {
+            return PixelContainer::new();
+        }
+
+        let texture = self.camera.render_offscreen(
+            objects,
+            self.display_mode.clone(),
+            self.resolution,
+            self.background_char,
+        );
+        // Texture coordinates of each corner, split into two triangles across the face
+        let triangles = [
+            (
+                [screen[0], screen[1], screen[2]],
+                [(0.0, 0.0), (0.0, 1.0), (1.0, 1.0)],
+            ),
+            (
+                [screen[0], screen[2], screen[3]],
+                [(0.0, 0.0), (1.0, 1.0), (1.0, 0.0)],
+            ),
+        ];
+
+        let pixels = Polygon::draw(&screen)
+            .into_iter()
+            .filter_map(|pos| {
+                let (u, v) = triangles.iter().find_map(|(points, uvs)| {
+                    barycentric(pos, *points).map(|weights| {
+                        (0..3).fold((0.0, 0.0), |(u, v), i| {
+                            (
+                                weights[i].mul_add(uvs[i].0, u),
+                                weights[i].mul_add(uvs[i].1, v),
+                            )
+                        })
+                    })
+                })?;
+                let u = if self.mirrored { 1.0 - u } else { u };
+                let texel = Vec2D::new(
+                    (u * (self.resolution.x - 1) as f64).round() as isize,
+                    (v * (self.resolution.y - 1) as f64).round() as isize,
+                );
+
+                texture
+                    .get(texel)
+                    .map(|fill_char| Pixel::new(pos, fill_char))
+            })
+            .collect();
+
+        PixelContainer { pixels }
+    }
+}
+
+/// Return the barycentric weights of the position in the triangle, or `None` if it lies outside it. A small tolerance stops gaps appearing along the edges
+fn barycentric(pos: Vec2D, [a, b, c]: [Vec2D; 3]) -> Option<[f64; 3]> {
+    let area = (b - a).cross(c - a) as f64;
+    if area == 0.0 {
+        return None;
+    }
+    let weight_b = (pos - a).cross(c - a) as f64 / -area;
+    let weight_c = (b - a).cross(pos - a) as f64 / area;
+    let weight_a = 1.0 - weight_b - weight_c;
+
+    let tolerance = -0.5 / area.abs().sqrt().max(1.0);
+    [weight_a, weight_b, weight_c]
+        .iter()
+        .all(|weight| *weight >= tolerance)
+        .then_some([weight_a, weight_b, weight_c])
+}
+
+impl Viewport {
+    /// Render the objects into a new [`View`] of the given size instead of onto the screen, with the `Viewport` centred in it. Useful for render-to-texture effects such as [`Portal`]s, or for picture-in-picture displays
+    #[must_use]
+    pub fn render_offscreen(
+        &self,
+        objects: Vec<&Mesh3D>,
+        display_mode: DisplayMode,
+        size: Vec2D,
+        background_char: ColChar,
+    ) -> View {
+        let mut view = View::new(
+            size.x.max(0) as usize,
+            size.y.max(0) as usize,
+            background_char,
+        );
+        let shift = size / 2 - self.origin;
+
+        for pixel in self.render(objects, display_mode).pixels {
+            view.plot(pixel.pos + shift, pixel.fill_char, Wrapping::Ignore);
+        }
+
+        view
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{elements::view::Modifier, elements3d::Transform3D};
+
+    fn portal_facing(camera: Viewport) -> Portal {
+        Portal::new(
+            [
+                Vec3D::new(1.0, 1.0, 0.0),
+                Vec3D::new(1.0, -1.0, 0.0),
+                Vec3D::new(-1.0, -1.0, 0.0),
+                Vec3D::new(-1.0, 1.0, 0.0),
+            ],
+            camera,
+            DisplayMode::Solid,
+            Vec2D::new(20, 10),
+        )
+    }
+
+    #[test]
+    fn portal_shows_other_camera() {
+        let cube = Mesh3D::default_cube();
+        let camera = Viewport::new(
+            Transform3D::new_t(Vec3D::new(0.0, 0.0, 6.0)),
+            40.0,
+            Vec2D::ZERO,
+        );
+        let mut portal = portal_facing(camera);
+
+        let texture = portal.camera.render_offscreen(
+            vec![&cube],
+            DisplayMode::Solid,
+            portal.resolution,
+            ColChar::EMPTY,
+        );
+        assert_eq!(
+            texture.get(Vec2D::new(10, 5)),
+            Some(ColChar::SOLID.with_mod(Modifier::None))
+        );
+
+        let viewer = Viewport::new(
+            Transform3D::new_t(Vec3D::new(0.0, 0.0, 4.0)),
+            40.0,
+            Vec2D::ZERO,
+        );
+        let seen = portal.render(&viewer, vec![&cube]).pixels;
+        assert!(!seen.is_empty());
+        assert!(seen.iter().any(|p| p.fill_char.text_char == '█'));
+
+        portal.mirrored = true;
+        assert_eq!(portal.render(&viewer, vec![&cube]).pixels.len(), seen.len());
+
+        let behind = Viewport::new(
+            Transform3D::new_tr(
+                Vec3D::new(0.0, 0.0, -4.0),
+                Vec3D::new(0.0, std::f64::consts::PI, 0.0),
+            ),
+            40.0,
+            Vec2D::ZERO,
+        );
+        assert!(portal.render(&behind, vec![&cube]).pixels.is_empty());
+    }
+}
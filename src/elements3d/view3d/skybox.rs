@@ -0,0 +1,213 @@
+use super::{Vec3D, Viewport};
+use crate::{
+    elements::{
+        view::{ColChar, Colour, Modifier},
+        Pixel, PixelContainer, Sprite, Vec2D,
+    },
+    procgen::random_unit,
+};
+
+/// A `Skybox` draws a background behind a 3D scene that depends on which way the [`Viewport`] is facing, so turning the camera turns the sky with it. Render it with [`Skybox::render()`] and blit it before the scene itself
+#[derive(Debug, Clone)]
+pub enum Skybox {
+    /// A vertical gradient from the colour straight down, through the horizon, to the colour straight up
+    Gradient {
+        /// The colour straight up
+        zenith: Colour,
+        /// The colour at the horizon
+        horizon: Colour,
+        /// The colour straight down
+        nadir: Colour,
+    },
+    /// Stars scattered across the sky
+    Stars {
+        /// The chance of any one cell holding a star, between 0.0 and 1.0
+        density: f64,
+        /// What the stars look like
+        star: ColChar,
+        /// The seed stars are placed with
+        seed: u64,
+    },
+    /// Six [`Sprite`]s wrapped around the scene as the faces of a cube, in the order +X, -X, +Y (up), -Y (down), +Z, -Z. Only each `Sprite`'s texture and modifier are used
+    Cubemap(Box<[Sprite; 6]>),
+}
+
+impl Skybox {
+    /// Return a `Skybox` of sparse, uncoloured stars
+    #[must_use]
+    pub const fn starfield(seed: u64) -> Self {
+        Self::Stars {
+            density: 0.02,
+            star: ColChar::new('.', Modifier::None),
+            seed,
+        }
+    }
+
+    /// Return the sky's `ColChar` in the given direction in the scene, or `None` if the sky is empty there
+    #[must_use]
+    pub fn sample(&self, direction: Vec3D) -> Option<ColChar> {
+        let length = direction.magnitude();
+        if length == 0.0 {
+            return None;
+        }
+        let direction = direction / length;
+
+        match self {
+            Self::Gradient {
+                zenith,
+                horizon,
+                nadir,
+            } => {
+                let colour = if direction.y >= 0.0 {
+                    horizon.lerp(*zenith, direction.y)
+                } else {
+                    horizon.lerp(*nadir, -direction.y)
+                };
+                Some(ColChar::SOLID.with_colour(colour))
+            }
+            Self::Stars {
+                density,
+                star,
+                seed,
+            } => {
+                // Quantise the direction so that each star covers roughly one cell
+                let cell = direction * 60.0;
+                let (x, y, z) = (
+                    cell.x.round() as i64,
+                    cell.y.round() as i64,
+                    cell.z.round() as i64,
+                );
+                let seed = seed.wrapping_add((z as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+                (random_unit(seed, x, y) < *density).then_some(*star)
+            }
+            Self::Cubemap(faces) => {
+                let (face, u, v) = cube_face(direction);
+                let sprite = &faces[face];
+                let rows: Vec<&str> = sprite.texture.lines().collect();
+                let row = rows.get(texel_index(v, rows.len()))?;
+                let columns: Vec<char> = row.chars().collect();
+                let text_char = *columns.get(texel_index(u, columns.len()))?;
+
+                (text_char != ' ').then_some(ColChar::new(text_char, sprite.modifier))
+            }
+        }
+    }
+
+    /// Render the sky as seen by the `Viewport` across a screen of the given size
+    #[must_use]
+    pub fn render(&self, viewport: &Viewport, size: Vec2D) -> PixelContainer {
+        let mut pixels = vec![];
+
+        for y in 0..size.y {
+            for x in 0..size.x {
+                let pos = Vec2D::new(x, y);
+                let direction = viewport.screen_ray(pos).direction;
+
+                if let Some(fill_char) = self.sample(direction) {
+                    pixels.push(Pixel::new(pos, fill_char));
+                }
+            }
+        }
+
+        PixelContainer { pixels }
+    }
+}
+
+impl Default for Skybox {
+    fn default() -> Self {
+        Self::Gradient {
+            zenith: Colour::rgb(20, 40, 120),
+            horizon: Colour::rgb(150, 190, 230),
+            nadir: Colour::rgb(40, 35, 30),
+        }
+    }
+}
+
+/// Return which face of a cube a direction points at, along with the position on that face from 0.0 to 1.0 across and down
+fn cube_face(direction: Vec3D) -> (usize, f64, f64) {
+    let abs = Vec3D::new(direction.x.abs(), direction.y.abs(), direction.z.abs());
+
+    let (face, across, down) = if abs.x >= abs.y && abs.x >= abs.z {
+        if direction.x > 0.0 {
+            (0, -direction.z / abs.x, -direction.y / abs.x)
+        } else {
+            (1, direction.z / abs.x, -direction.y / abs.x)
+        }
+    } else if abs.y >= abs.z {
+        if direction.y > 0.0 {
+            (2, direction.x / abs.y, direction.z / abs.y)
+        } else {
+            (3, direction.x / abs.y, -direction.z / abs.y)
+        }
+    } else if direction.z > 0.0 {
+        (4, direction.x / abs.z, -direction.y / abs.z)
+    } else {
+        (5, -direction.x / abs.z, -direction.y / abs.z)
+    };
+
+    (face, f64::midpoint(across, 1.0), f64::midpoint(down, 1.0))
+}
+
+/// Return the index of the texel at the position from 0.0 to 1.0 across a texture of the given length
+fn texel_index(position: f64, length: usize) -> usize {
+    ((position * length as f64) as usize).min(length.saturating_sub(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements3d::Transform3D;
+
+    #[test]
+    fn gradient_follows_camera_pitch() {
+        let skybox = Skybox::Gradient {
+            zenith: Colour::WHITE,
+            horizon: Colour::greyscale(128),
+            nadir: Colour::BLACK,
+        };
+        assert_eq!(
+            skybox.sample(Vec3D::new(0.0, 1.0, 0.0)),
+            Some(ColChar::SOLID.with_colour(Colour::WHITE))
+        );
+
+        let level = Viewport::new(Transform3D::DEFAULT, 20.0, Vec2D::new(5, 5));
+        let centre = |viewport: &Viewport| {
+            skybox
+                .render(viewport, Vec2D::new(10, 10))
+                .pixels
+                .into_iter()
+                .find(|p| p.pos == Vec2D::new(5, 5))
+                .map(|p| p.fill_char)
+        };
+        assert_eq!(
+            centre(&level),
+            Some(ColChar::SOLID.with_colour(Colour::greyscale(128)))
+        );
+
+        let mut pitched = level;
+        pitched.transform.rotation.x = -1.2;
+        let pitched_one_way = centre(&pitched);
+        pitched.transform.rotation.x = 1.2;
+        assert_ne!(centre(&pitched), pitched_one_way);
+    }
+
+    #[test]
+    fn cubemap_picks_faces() {
+        let faces = ["a", "b", "c", "d", "e", "f"]
+            .map(|texture| Sprite::new(Vec2D::ZERO, texture, Modifier::None));
+        let skybox = Skybox::Cubemap(Box::new(faces));
+
+        assert_eq!(
+            skybox
+                .sample(Vec3D::new(0.0, -2.0, 0.5))
+                .map(|c| c.text_char),
+            Some('d')
+        );
+        assert_eq!(
+            skybox
+                .sample(Vec3D::new(0.0, 0.0, -1.0))
+                .map(|c| c.text_char),
+            Some('f')
+        );
+    }
+}
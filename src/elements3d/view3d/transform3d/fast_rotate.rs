@@ -12,7 +12,7 @@ enum SpatialAxis {
 
 impl SpatialAxis {
     /// Returns the two axes on the plane perpendicular to the `SpatialAxis`' variation
-    pub fn get_perpendicular_plane(self, value: &mut Vec3D) -> (&mut f64, &mut f64) {
+    pub const fn get_perpendicular_plane(self, value: &mut Vec3D) -> (&mut f64, &mut f64) {
         match self {
             Self::X => (&mut value.y, &mut value.z),
             Self::Y => (&mut value.x, &mut value.z),
@@ -72,4 +72,14 @@ impl CachedRotation3D {
 
         rz
     }
+
+    /// Undo [`rotate()`](CachedRotation3D::rotate) for a `CachedRotation3D` created with the negated rotation
+    #[allow(clippy::let_and_return)]
+    pub fn rotate_reverse(&self, rhs: Vec3D) -> Vec3D {
+        let rz = self.z.rotate_one_axis(rhs, SpatialAxis::Z);
+        let rx = self.x.rotate_one_axis(rz, SpatialAxis::X);
+        let ry = self.y.rotate_one_axis(rx, SpatialAxis::Y);
+
+        ry
+    }
 }
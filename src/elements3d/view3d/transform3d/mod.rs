@@ -5,7 +5,7 @@ mod fast_rotate;
 use fast_rotate::CachedRotation3D;
 
 /// The `Transform3D` struct is used to manipulate the position of objects in 3D space
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Transform3D {
     /// The position of the object in 3D space
     pub translation: Vec3D,
@@ -65,6 +65,12 @@ impl Transform3D {
         }
     }
 
+    /// Returns true if the transform has no translation, no rotation and 1x scaling, so applying it would leave vertices unchanged
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        self.translation == Vec3D::ZERO && self.rotation == Vec3D::ZERO && self.scale == Vec3D::ONE
+    }
+
     /// Apply the transform to a slice of vertices
     #[allow(clippy::let_and_return)]
     #[must_use]
@@ -103,6 +109,11 @@ impl Transform3D {
             .collect()
     }
 
+    /// Turn a direction relative to a viewport with this transform back into a direction in the scene, undoing the rotation of [`apply_viewport_transform()`](Transform3D::apply_viewport_transform)
+    pub(crate) fn viewport_direction_to_world(&self, direction: Vec3D) -> Vec3D {
+        CachedRotation3D::new(self.rotation).rotate_reverse(direction)
+    }
+
     /// Rotate the given [`Vec3D`] using the `Transform3D`'s rotation field
     #[must_use]
     pub fn rotate(&self, value: Vec3D) -> Vec3D {
@@ -0,0 +1,119 @@
+use super::{DisplayMode, Viewport};
+use crate::{
+    elements::{PixelContainer, Vec2D},
+    elements3d::Mesh3D,
+};
+
+/// A `ViewportRegion` renders a [`Viewport`] into a rectangular region of the [`View`](crate::elements::View), clipping anything outside of it. Use several to draw different cameras side by side in one frame, such as a main view with a rear mirror or minimap, and render them together with [`render_regions()`]
+pub struct ViewportRegion {
+    /// The camera rendered into the region. Its [`origin`](Viewport::origin) is set to the centre of the region
+    pub viewport: Viewport,
+    /// The position of the top left corner of the region
+    pub pos: Vec2D,
+    /// The width and height of the region
+    pub size: Vec2D,
+    /// How the region renders the scene
+    pub display_mode: DisplayMode,
+}
+
+impl ViewportRegion {
+    /// Create a new `ViewportRegion`, centring the `Viewport` in the region
+    #[must_use]
+    pub fn new(mut viewport: Viewport, pos: Vec2D, size: Vec2D, display_mode: DisplayMode) -> Self {
+        viewport.origin = pos + size / 2;
+        Self {
+            viewport,
+            pos,
+            size,
+            display_mode,
+        }
+    }
+
+    /// Move or resize the region, keeping the `Viewport` centred in it
+    pub fn set_region(&mut self, pos: Vec2D, size: Vec2D) {
+        self.pos = pos;
+        self.size = size;
+        self.viewport.origin = pos + size / 2;
+    }
+
+    /// Returns true if the position is inside the region
+    #[must_use]
+    pub fn contains(&self, pos: Vec2D) -> bool {
+        let relative = pos - self.pos;
+        (0..self.size.x).contains(&relative.x) && (0..self.size.y).contains(&relative.y)
+    }
+
+    /// Render the objects with the region's `Viewport` and [`DisplayMode`], keeping only the pixels inside the region
+    #[must_use]
+    pub fn render(&self, objects: Vec<&Mesh3D>) -> PixelContainer {
+        let rendered = self
+            .viewport
+            .render(objects, self.display_mode.clone())
+            .pixels;
+
+        PixelContainer {
+            pixels: rendered
+                .into_iter()
+                .filter(|p| self.contains(p.pos))
+                .collect(),
+        }
+    }
+}
+
+/// Render the same objects into every [`ViewportRegion`], in order so that later regions are drawn on top of earlier ones. The objects' transforms are applied once and shared by every region, instead of once per region
+#[must_use]
+pub fn render_regions(regions: &[ViewportRegion], objects: &[&Mesh3D]) -> PixelContainer {
+    let baked: Vec<Mesh3D> = objects.iter().map(|object| object.baked()).collect();
+    let mut canvas = PixelContainer::new();
+
+    for region in regions {
+        canvas.blit(&region.render(baked.iter().collect()));
+    }
+
+    canvas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements3d::{Transform3D, Vec3D};
+
+    #[test]
+    fn regions_clip_their_cameras() {
+        let camera = || {
+            Viewport::new(
+                Transform3D::new_t(Vec3D::new(0.0, 0.0, 5.0)),
+                95.0,
+                Vec2D::ZERO,
+            )
+        };
+        let regions = [
+            ViewportRegion::new(
+                camera(),
+                Vec2D::ZERO,
+                Vec2D::new(20, 10),
+                DisplayMode::Solid,
+            ),
+            ViewportRegion::new(
+                camera(),
+                Vec2D::new(20, 0),
+                Vec2D::new(10, 5),
+                DisplayMode::Solid,
+            ),
+        ];
+        let mut cube = Mesh3D::default_cube();
+        cube.transform.rotation.y = 0.5;
+
+        let canvas = render_regions(&regions, &[&cube]);
+        assert!(!canvas.pixels.is_empty());
+        assert!(canvas
+            .pixels
+            .iter()
+            .all(|p| regions.iter().any(|region| region.contains(p.pos))));
+        assert!(canvas.pixels.iter().any(|p| p.pos.x >= 20));
+
+        let unbaked = regions[0].render(vec![&cube]).pixels;
+        let baked = regions[0].render(vec![&cube.baked()]).pixels;
+        assert_eq!(unbaked, baked);
+    }
+}
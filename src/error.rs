@@ -0,0 +1,126 @@
+//! The error type for recoverable failures across the engine. Read the [`EngineError`] documentation for more info
+
+use crate::elements::Vec2D;
+use std::{error::Error, fmt, io};
+
+/// An `EngineError` is returned by the fallible variants of operations that would otherwise panic or silently fix bad input, such as [`View::try_plot()`](crate::elements::View::try_plot()), [`View::try_blit()`](crate::elements::View::try_blit()) and [`View::try_new()`](crate::elements::View::try_new()), so that bad data can be handled gracefully
+/// ```
+/// use gemini_engine::{
+///     elements::{view::{ColChar, Wrapping}, Vec2D, View},
+///     EngineError,
+/// };
+///
+/// let mut view = View::new(10, 5, ColChar::EMPTY);
+///
+/// assert_eq!(
+///     view.try_plot(Vec2D::new(12, 2), ColChar::SOLID, Wrapping::Panic),
+///     Err(EngineError::OutOfBounds { pos: Vec2D::new(12, 2), size: Vec2D::new(10, 5) })
+/// );
+/// assert!(View::try_new(0, 5, ColChar::EMPTY).is_err());
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EngineError {
+    /// A pixel was outside the canvas it was drawn to
+    OutOfBounds {
+        /// The position of the pixel
+        pos: Vec2D,
+        /// The size of the canvas
+        size: Vec2D,
+    },
+    /// A canvas was given a size with no area
+    InvalidSize(Vec2D),
+    /// Input data, such as an asset file, an escape sequence or a command-line argument, was invalid
+    InvalidData(String),
+    /// A generator couldn't produce a result that satisfies every constraint it was given
+    Unsatisfiable(String),
+    /// A replay ended on a different frame than expected
+    HashMismatch {
+        /// The hash of the frame the replay should have ended on
+        expected: u64,
+        /// The hash of the frame it actually ended on
+        found: u64,
+    },
+    /// A file, terminal or other IO operation failed
+    Io(io::Error),
+}
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { pos, size } => {
+                write!(f, "{pos} is out of bounds of a canvas of size {size}")
+            }
+            Self::InvalidSize(size) => write!(f, "{size} is not a valid canvas size"),
+            Self::InvalidData(message) => write!(f, "Invalid data: {message}"),
+            Self::Unsatisfiable(message) => write!(f, "Unsatisfiable constraints: {message}"),
+            Self::HashMismatch { expected, found } => {
+                write!(
+                    f,
+                    "Expected a frame with hash {expected:#018x}, found {found:#018x}"
+                )
+            }
+            Self::Io(err) => write!(f, "IO error: {err}"),
+        }
+    }
+}
+
+impl Error for EngineError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for EngineError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// IO errors are equal if they are of the same [`kind`](io::Error::kind()), as `io::Error` can't be compared itself
+impl PartialEq for EngineError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Self::OutOfBounds { pos, size },
+                Self::OutOfBounds {
+                    pos: other_pos,
+                    size: other_size,
+                },
+            ) => pos == other_pos && size == other_size,
+            (Self::InvalidSize(size), Self::InvalidSize(other)) => size == other,
+            (Self::InvalidData(message), Self::InvalidData(other))
+            | (Self::Unsatisfiable(message), Self::Unsatisfiable(other)) => message == other,
+            (
+                Self::HashMismatch { expected, found },
+                Self::HashMismatch {
+                    expected: other_expected,
+                    found: other_found,
+                },
+            ) => expected == other_expected && found == other_found,
+            (Self::Io(err), Self::Io(other)) => err.kind() == other.kind(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for EngineError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn io_errors_are_kept_as_the_source() {
+        let err = EngineError::from(io::Error::new(io::ErrorKind::NotFound, "missing.obj"));
+        assert_eq!(
+            err.source().map(ToString::to_string),
+            Some(String::from("missing.obj"))
+        );
+        assert_eq!(err, EngineError::Io(io::ErrorKind::NotFound.into()));
+        assert!(EngineError::InvalidSize(Vec2D::ZERO).source().is_none());
+    }
+}
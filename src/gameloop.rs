@@ -26,13 +26,78 @@
 //! Writing your code like this ensures that it wont affect the game's intentional speed too much, and also makes it easy for you to benchmark your game's speed with something like `println!("Elapsed: {:.2?}µs", elapsed.as_micros());` after `let elapsed`.
 //!
 //! You can use the `fps_gameloop!` macro to achieve the same result. Read about how to use it in the [`fps_gameloop!`](crate::fps_gameloop) documentation
+//!
+//! If your game is turn-based and only needs to update when the player does something, use [`TurnBasedRoot`] instead. It waits for input rather than running at a fixed FPS
+//!
+//! Gemini doesn't play audio or read gamepads itself, so that games can use whichever libraries suit them. [`SpatialAudio`] and [`Haptics`] send their output through the [`AudioBackend`] and [`HapticsBackend`] traits, which you implement for a wrapper around those libraries, and [`AudioLevels`] is fed samples from them
 
 pub use std::time::{Duration, Instant};
 
 mod sleep_fps;
 pub use sleep_fps::sleep_fps;
 
+pub mod clock;
+pub use clock::{GameClock, RealTime, SimTime};
+
+pub mod adaptive;
+pub use adaptive::{ActivityFlag, AdaptiveScheduler, CanAnimate};
+
 pub mod with_root;
 pub use with_root::MainLoopRoot;
 
+pub mod pipelined;
+pub use pipelined::{PipelinedRoot, RenderThread};
+
+pub mod turn_based;
+pub use turn_based::TurnBasedRoot;
+
+pub mod headless;
+pub use headless::{Headless, HeadlessRoot};
+
+pub mod golden_replay;
+pub use golden_replay::GoldenReplay;
+
+pub mod world_state;
+pub use world_state::WorldState;
+
+pub mod stats;
+pub use stats::{Achievements, Stats};
+
+pub mod profiler;
+pub use profiler::Profiler;
+
+pub mod engine_args;
+pub use engine_args::{EngineArgs, FrameOutput};
+
+pub mod hotkeys;
+
+pub mod capture;
+#[cfg(feature = "png")]
+pub use capture::AnimationRecorder;
+pub use capture::{CaptureHotkeys, FrameRecorder};
+
+pub mod fixed_timestep;
+pub use fixed_timestep::FixedTimestep;
+
+pub mod frame_step;
+pub use frame_step::FrameStepper;
+
+pub mod tweaks;
+pub use tweaks::TweakFile;
+
+pub mod rewind;
+pub use rewind::Rewind;
+
+pub mod audio_levels;
+pub use audio_levels::AudioLevels;
+
+pub mod haptics;
+pub use haptics::{Haptics, HapticsBackend};
+
+pub mod spatial_audio;
+pub use spatial_audio::{AudioBackend, SpatialAudio};
+
+pub mod camera_rail;
+pub use camera_rail::CameraRail;
+
 mod macros;
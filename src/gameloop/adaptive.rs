@@ -0,0 +1,145 @@
+//! Adaptive frame scheduling, which drops the gameloop to a slower idle frame rate while nothing on screen is changing. Read the [`AdaptiveScheduler`] documentation for more info
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// Implemented by elements that can tell whether they are currently animating, such as [`TextEffects`](crate::elements::ascii::TextEffects). Pass them to [`AdaptiveScheduler::watch()`] every frame to keep the gameloop at full speed while they animate
+pub trait CanAnimate {
+    /// Returns true if the element will look different on the next frame
+    fn is_animating(&self) -> bool;
+}
+
+/// A cheap, cloneable handle that marks an [`AdaptiveScheduler`] as active. Useful for flagging activity from places that don't have access to the scheduler itself, such as an input thread or an element's update function
+#[derive(Debug, Clone, Default)]
+pub struct ActivityFlag(Arc<AtomicBool>);
+
+impl ActivityFlag {
+    /// Mark the scheduler as active, keeping it at full speed for at least another [`idle_after`](AdaptiveScheduler::idle_after)
+    pub fn mark_active(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// `AdaptiveScheduler` is a replacement for [`sleep_fps()`](super::sleep_fps()) that runs at [`active_fps`](AdaptiveScheduler::active_fps) while something is happening, and drops to [`idle_fps`](AdaptiveScheduler::idle_fps) once nothing has been flagged as active for [`idle_after`](AdaptiveScheduler::idle_after). This saves battery while the game is sitting still, and resumes full speed as soon as there's activity
+///
+/// Mark activity with [`mark_active()`](AdaptiveScheduler::mark_active()) (for example whenever an input is received), [`watch()`](AdaptiveScheduler::watch()) for animating elements or an [`ActivityFlag`] from another thread
+/// ```rust,no_run
+/// use gemini_engine::gameloop::{self, AdaptiveScheduler};
+///
+/// let mut scheduler = AdaptiveScheduler::new(60.0, 5.0);
+/// let mut frame_skip = false;
+/// loop {
+///     let now = gameloop::Instant::now();
+///     // --logic, calling scheduler.mark_active() when anything changes--
+///
+///     if frame_skip {
+///         frame_skip = false;
+///     } else {
+///         // --all blitting and rendering goes here--
+///     }
+///
+///     frame_skip = scheduler.sleep(now.elapsed());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AdaptiveScheduler {
+    /// The frame rate used while there is activity
+    pub active_fps: f32,
+    /// The frame rate used while idle
+    pub idle_fps: f32,
+    /// How long after the last activity the scheduler drops to the idle frame rate
+    pub idle_after: Duration,
+    last_activity: Instant,
+    flag: ActivityFlag,
+}
+
+impl AdaptiveScheduler {
+    /// The default value of [`idle_after`](AdaptiveScheduler::idle_after)
+    pub const DEFAULT_IDLE_AFTER: Duration = Duration::from_millis(500);
+
+    /// Create a new `AdaptiveScheduler` with an active and an idle frame rate. The scheduler starts out active
+    #[must_use]
+    pub fn new(active_fps: f32, idle_fps: f32) -> Self {
+        Self {
+            active_fps,
+            idle_fps,
+            idle_after: Self::DEFAULT_IDLE_AFTER,
+            last_activity: Instant::now(),
+            flag: ActivityFlag::default(),
+        }
+    }
+
+    /// Return the `AdaptiveScheduler` with the modified `idle_after` property
+    #[must_use]
+    pub const fn with_idle_after(mut self, idle_after: Duration) -> Self {
+        self.idle_after = idle_after;
+        self
+    }
+
+    /// Return an [`ActivityFlag`] which marks this scheduler as active
+    #[must_use]
+    pub fn activity_flag(&self) -> ActivityFlag {
+        self.flag.clone()
+    }
+
+    /// Mark the scheduler as active, keeping it at full speed for at least another [`idle_after`](AdaptiveScheduler::idle_after)
+    pub fn mark_active(&mut self) {
+        self.last_activity = Instant::now();
+    }
+
+    /// Mark the scheduler as active if the element is animating
+    pub fn watch(&mut self, element: &impl CanAnimate) {
+        if element.is_animating() {
+            self.mark_active();
+        }
+    }
+
+    /// Returns true if nothing has been marked as active for [`idle_after`](AdaptiveScheduler::idle_after)
+    #[must_use]
+    pub fn is_idle(&mut self) -> bool {
+        if self.flag.0.swap(false, Ordering::Relaxed) {
+            self.mark_active();
+        }
+
+        self.last_activity.elapsed() >= self.idle_after
+    }
+
+    /// Return the frame rate the scheduler is currently running at
+    #[must_use]
+    pub fn current_fps(&mut self) -> f32 {
+        if self.is_idle() {
+            self.idle_fps
+        } else {
+            self.active_fps
+        }
+    }
+
+    /// Sleep for a single frame at the current frame rate, subtracting the time spent processing the frame. Returns true if the frame took longer than intended, just like [`sleep_fps()`](super::sleep_fps())
+    pub fn sleep(&mut self, elapsed: Duration) -> bool {
+        let fps = self.current_fps();
+        super::sleep_fps(fps, Some(elapsed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn goes_idle_and_wakes_on_flag() {
+        let mut scheduler = AdaptiveScheduler::new(60.0, 5.0).with_idle_after(Duration::ZERO);
+        assert!(scheduler.is_idle());
+        assert!((scheduler.current_fps() - 5.0).abs() < f32::EPSILON);
+
+        scheduler.idle_after = Duration::from_secs(10);
+        let flag = scheduler.activity_flag();
+        flag.mark_active();
+        assert!(!scheduler.is_idle());
+        assert!((scheduler.current_fps() - 60.0).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,219 @@
+//! Volume and beat analysis for driving visuals from music. Read the [`AudioLevels`] documentation for more info
+
+use std::{fmt, time::Duration};
+
+use crate::elements::view::Colour;
+
+/// How long the average energy that beats are compared against looks back over, in seconds
+const AVERAGE_WINDOW: f64 = 1.0;
+/// How long it takes the loudest recent level to halve once the music gets quieter, in seconds
+const PEAK_HALF_LIFE: f64 = 2.0;
+/// Levels quieter than this never count as a beat, so that silence doesn't trigger beats from noise
+const SILENCE: f64 = 1e-4;
+
+/// `AudioLevels` turns an audio feed into a smoothed volume level and beat events, for music visualisers and other visuals that react to sound. Feed it with [`feed_samples()`](AudioLevels::feed_samples()) from the game's audio library, or with a ready-made amplitude through [`feed_level()`](AudioLevels::feed_level()). The [`drive()`](AudioLevels::drive()) helpers then map the level onto element parameters such as scale, colour or particle rate
+/// ```
+/// use gemini_engine::{elements::view::Colour, gameloop::AudioLevels};
+/// use std::time::Duration;
+///
+/// let frame = Duration::from_millis(33);
+/// let mut audio = AudioLevels::new();
+///
+/// for _ in 0..30 {
+///     audio.feed_level(0.1, frame);
+/// }
+/// audio.feed_level(0.9, frame);
+/// assert!(audio.is_beat());
+///
+/// let radius = audio.drive(2.0, 6.0);
+/// let colour = audio.drive_colour(Colour::BLACK, Colour::rgb(255, 0, 128));
+/// assert!(radius > 4.0 && colour.r > 128);
+/// ```
+pub struct AudioLevels {
+    /// How much of the previous level is kept each update, from 0.0 for no smoothing to just under 1.0 for very slow changes. Defaults to 0.3
+    pub smoothing: f64,
+    /// How many times louder than the recent average the level must be to count as a beat. Defaults to 1.5
+    pub beat_threshold: f64,
+    /// The shortest time between two beats. Defaults to 150ms
+    pub beat_cooldown: Duration,
+    level: f64,
+    average: f64,
+    peak: f64,
+    since_beat: Duration,
+    is_beat: bool,
+    beat_callbacks: Vec<Box<dyn FnMut(f64)>>,
+}
+
+impl AudioLevels {
+    /// Create a new `AudioLevels` with the default settings
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            smoothing: 0.3,
+            beat_threshold: 1.5,
+            beat_cooldown: Duration::from_millis(150),
+            level: 0.0,
+            average: 0.0,
+            peak: 0.0,
+            since_beat: Duration::MAX,
+            is_beat: false,
+            beat_callbacks: vec![],
+        }
+    }
+
+    /// Return the `AudioLevels` with a different smoothing
+    #[must_use]
+    pub const fn with_smoothing(mut self, smoothing: f64) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// Return the `AudioLevels` with a different beat threshold
+    #[must_use]
+    pub const fn with_beat_threshold(mut self, beat_threshold: f64) -> Self {
+        self.beat_threshold = beat_threshold;
+        self
+    }
+
+    /// Return the `AudioLevels` with a callback run on every beat, which is passed the level of the beat
+    #[must_use]
+    pub fn on_beat(mut self, callback: impl FnMut(f64) + 'static) -> Self {
+        self.beat_callbacks.push(Box::new(callback));
+        self
+    }
+
+    /// Feed the samples played since the last update, from -1.0 to 1.0. Their root mean square is used as the amplitude
+    pub fn feed_samples(&mut self, samples: &[f32], delta: Duration) {
+        let sum: f64 = samples.iter().map(|s| f64::from(*s).powi(2)).sum();
+        let rms = if samples.is_empty() {
+            0.0
+        } else {
+            (sum / samples.len() as f64).sqrt()
+        };
+
+        self.feed_level(rms, delta);
+    }
+
+    /// Feed the amplitude of the audio since the last update, such as a volume meter reading from 0.0 to 1.0
+    pub fn feed_level(&mut self, amplitude: f64, delta: Duration) {
+        let seconds = delta.as_secs_f64();
+        let smoothing = self.smoothing.clamp(0.0, 1.0);
+        self.level = smoothing.mul_add(self.level - amplitude, amplitude);
+        self.peak = self
+            .level
+            .max(self.peak * 0.5f64.powf(seconds / PEAK_HALF_LIFE));
+        self.since_beat = self.since_beat.saturating_add(delta);
+        if self.average == 0.0 {
+            // Start the average at the first sound, rather than counting every frame as a beat until it catches up
+            self.average = self.level;
+        }
+
+        self.is_beat = self.level > SILENCE
+            && self.level > self.average * self.beat_threshold
+            && self.since_beat >= self.beat_cooldown;
+        if self.is_beat {
+            self.since_beat = Duration::ZERO;
+            for callback in &mut self.beat_callbacks {
+                callback(self.level);
+            }
+        }
+
+        let blend = (seconds / AVERAGE_WINDOW).min(1.0);
+        self.average += (self.level - self.average) * blend;
+    }
+
+    /// Return the smoothed level of the audio
+    #[must_use]
+    pub const fn level(&self) -> f64 {
+        self.level
+    }
+
+    /// Return the level relative to the loudest recent level, from 0.0 to 1.0, so that visuals react the same to quiet and loud music
+    #[must_use]
+    pub fn normalised(&self) -> f64 {
+        if self.peak > 0.0 {
+            (self.level / self.peak).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns true if there was a beat in the last update
+    #[must_use]
+    pub const fn is_beat(&self) -> bool {
+        self.is_beat
+    }
+
+    /// Return how long it has been since the last beat, for effects that pulse on the beat and then fade
+    #[must_use]
+    pub const fn since_beat(&self) -> Duration {
+        self.since_beat
+    }
+
+    /// Return a value between `min` and `max` following the [`normalised()`](AudioLevels::normalised()) level, such as a scale, radius or particle spawn rate
+    #[must_use]
+    pub fn drive(&self, min: f64, max: f64) -> f64 {
+        (max - min).mul_add(self.normalised(), min)
+    }
+
+    /// Return a colour between `quiet` and `loud` following the [`normalised()`](AudioLevels::normalised()) level
+    #[must_use]
+    pub fn drive_colour(&self, quiet: Colour, loud: Colour) -> Colour {
+        quiet.lerp(loud, self.normalised())
+    }
+}
+
+impl Default for AudioLevels {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for AudioLevels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AudioLevels")
+            .field("smoothing", &self.smoothing)
+            .field("beat_threshold", &self.beat_threshold)
+            .field("beat_cooldown", &self.beat_cooldown)
+            .field("level", &self.level)
+            .field("is_beat", &self.is_beat)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::Cell, rc::Rc};
+
+    #[test]
+    fn samples_use_root_mean_square() {
+        let mut audio = AudioLevels::new().with_smoothing(0.0);
+        audio.feed_samples(&[0.5, -0.5, 0.5, -0.5], Duration::from_millis(10));
+        assert!((audio.level() - 0.5).abs() < 1e-9);
+
+        audio.feed_samples(&[], Duration::from_millis(10));
+        assert!(audio.normalised() < 1e-9);
+    }
+
+    #[test]
+    fn beats_respect_cooldown_and_call_back() {
+        let beats = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&beats);
+        let mut audio = AudioLevels::new()
+            .with_smoothing(0.0)
+            .on_beat(move |_| counter.set(counter.get() + 1));
+
+        let frame = Duration::from_millis(50);
+        for _ in 0..40 {
+            audio.feed_level(0.1, frame);
+        }
+        assert_eq!(beats.get(), 0);
+
+        audio.feed_level(0.8, frame);
+        audio.feed_level(0.9, frame);
+        assert_eq!(beats.get(), 1);
+        assert!(!audio.is_beat());
+        assert_eq!(audio.since_beat(), frame);
+    }
+}
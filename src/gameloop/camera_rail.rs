@@ -0,0 +1,342 @@
+//! Scripted camera movement for cutscenes such as intro sequences and boss reveals. Read the [`CameraRail`] documentation for more info
+
+use super::CanAnimate;
+pub use crate::elements::view::utils::Easing;
+use crate::elements::{Vec2D, View};
+#[cfg(feature = "3D")]
+use crate::elements3d::{Vec3D, Viewport};
+use std::time::Duration;
+
+/// A camera position that a [`CameraRail`] can move between. Implemented for [`Vec2D`], for moving a [`View`]'s camera, and, with the `3D` feature, for `Vec3D`, for moving a `Viewport`
+pub trait RailPoint: Copy {
+    /// Return the point the amount of the way from `self` to `other`, where 0.0 is `self` and 1.0 is `other`
+    #[must_use]
+    fn lerp(self, other: Self, amount: f64) -> Self;
+}
+
+impl RailPoint for Vec2D {
+    fn lerp(self, other: Self, amount: f64) -> Self {
+        let lerp = |a: isize, b: isize| ((b - a) as f64).mul_add(amount, a as f64).round() as isize;
+        Self::new(lerp(self.x, other.x), lerp(self.y, other.y))
+    }
+}
+
+#[cfg(feature = "3D")]
+impl RailPoint for Vec3D {
+    fn lerp(self, other: Self, amount: f64) -> Self {
+        let lerp = |a: f64, b: f64| (b - a).mul_add(amount, a);
+        Self::new(
+            lerp(self.x, other.x),
+            lerp(self.y, other.y),
+            lerp(self.z, other.z),
+        )
+    }
+}
+
+/// A point on a [`CameraRail`] that the camera passes through at a set time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Keyframe<P: RailPoint> {
+    /// When the camera reaches the keyframe, from the start of the rail
+    pub time: Duration,
+    /// Where the camera is at the keyframe
+    pub position: P,
+    /// What the camera is looking at by the keyframe, if anything
+    pub look_at: Option<P>,
+    /// How the camera eases from the previous keyframe into this one
+    pub easing: Easing,
+}
+
+impl<P: RailPoint> Keyframe<P> {
+    /// Create a new `Keyframe` at the time and position, with no look-at target and linear easing
+    #[must_use]
+    pub const fn new(time: Duration, position: P) -> Self {
+        Self {
+            time,
+            position,
+            look_at: None,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Return the `Keyframe` with a look-at target
+    #[must_use]
+    pub const fn with_look_at(mut self, target: P) -> Self {
+        self.look_at = Some(target);
+        self
+    }
+
+    /// Return the `Keyframe` with a different [`easing`](Keyframe::easing)
+    #[must_use]
+    pub const fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// A `CameraRail` moves a camera along a path of [`Keyframe`]s for cutscenes, easing between them and turning to face their look-at targets. Named markers placed along the rail are returned by [`update()`](CameraRail::update()) as the rail passes them, for starting dialogue, sounds or explosions at the right moment
+///
+/// Start the rail with [`play()`](CameraRail::play()), then call [`update()`](CameraRail::update()) and apply it to the camera every frame. While [`is_active()`](CameraRail::is_active()) returns true, the game should ignore the player's input so it doesn't fight the rail for the camera, which [`intercept()`](CameraRail::intercept()) does for you
+/// ```
+/// use gemini_engine::{
+///     elements::{view::ColChar, Vec2D, View},
+///     gameloop::camera_rail::{CameraRail, Easing, Keyframe},
+/// };
+/// use std::time::Duration;
+///
+/// let mut view = View::new(20, 10, ColChar::EMPTY);
+/// let mut intro = CameraRail::new()
+///     .with_keyframe(Keyframe::new(Duration::ZERO, Vec2D::new(0, 0)))
+///     .with_keyframe(Keyframe::new(Duration::from_secs(2), Vec2D::new(40, 0)).with_easing(Easing::EaseInOut))
+///     .with_marker(Duration::from_secs(1), "roar");
+///
+/// intro.play();
+/// while intro.is_active() {
+///     // The player's input is swallowed while the rail is active
+///     assert_eq!(intro.intercept('w'), None);
+///     for marker in intro.update(Duration::from_millis(500)) {
+///         assert_eq!(marker, "roar");
+///     }
+///     intro.apply_to_view(&mut view);
+/// }
+/// assert_eq!(intro.intercept('w'), Some('w'));
+/// assert_eq!(view.camera, Vec2D::new(30, -5));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CameraRail<P: RailPoint> {
+    /// How long the rail has been playing for
+    pub elapsed: Duration,
+    keyframes: Vec<Keyframe<P>>,
+    markers: Vec<(Duration, String)>,
+    next_marker: usize,
+    playing: bool,
+}
+
+impl<P: RailPoint> Default for CameraRail<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: RailPoint> CameraRail<P> {
+    /// Create a new, empty `CameraRail`
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            elapsed: Duration::ZERO,
+            keyframes: vec![],
+            markers: vec![],
+            next_marker: 0,
+            playing: false,
+        }
+    }
+
+    /// Return the `CameraRail` with another keyframe, kept in order of time
+    #[must_use]
+    pub fn with_keyframe(mut self, keyframe: Keyframe<P>) -> Self {
+        let i = self.keyframes.partition_point(|k| k.time <= keyframe.time);
+        self.keyframes.insert(i, keyframe);
+        self
+    }
+
+    /// Return the `CameraRail` with a named marker at the time, kept in order of time
+    #[must_use]
+    pub fn with_marker(mut self, time: Duration, name: impl Into<String>) -> Self {
+        let i = self.markers.partition_point(|(t, _)| *t <= time);
+        self.markers.insert(i, (time, name.into()));
+        self
+    }
+
+    /// Return the keyframes of the rail, in order of time
+    #[must_use]
+    pub fn keyframes(&self) -> &[Keyframe<P>] {
+        &self.keyframes
+    }
+
+    /// Return how long the rail takes to play, which is the time of its last keyframe or marker
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        let last_keyframe = self.keyframes.last().map_or(Duration::ZERO, |k| k.time);
+        let last_marker = self.markers.last().map_or(Duration::ZERO, |(t, _)| *t);
+        last_keyframe.max(last_marker)
+    }
+
+    /// Start playing the rail from the beginning
+    pub const fn play(&mut self) {
+        self.elapsed = Duration::ZERO;
+        self.next_marker = 0;
+        self.playing = true;
+    }
+
+    /// Stop the rail where it is, without passing any more markers
+    pub const fn stop(&mut self) {
+        self.playing = false;
+    }
+
+    /// Jump to the end of the rail, such as when the player skips a cutscene. Returns the names of the markers that were skipped over, so anything they start can still be set up
+    pub fn skip(&mut self) -> Vec<String> {
+        self.elapsed = self.duration();
+        self.playing = false;
+        self.pass_markers()
+    }
+
+    /// Returns true while the rail is playing, during which the player's input should be ignored
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.playing
+    }
+
+    /// Swallow the input while the rail is active, returning `None` so the game never sees it. Otherwise the input is returned unchanged for the game to handle
+    #[must_use]
+    pub fn intercept<K>(&self, input: K) -> Option<K> {
+        (!self.playing).then_some(input)
+    }
+
+    /// Move the rail forward by the time since the last frame, returning the names of the markers passed along the way in order. The rail stops once it reaches the end
+    pub fn update(&mut self, delta: Duration) -> Vec<String> {
+        if !self.playing {
+            return vec![];
+        }
+
+        self.elapsed = (self.elapsed + delta).min(self.duration());
+        if self.elapsed >= self.duration() {
+            self.playing = false;
+        }
+        self.pass_markers()
+    }
+
+    /// Return the names of the markers up to the elapsed time that haven't been passed yet
+    fn pass_markers(&mut self) -> Vec<String> {
+        let start = self.next_marker;
+        while self
+            .markers
+            .get(self.next_marker)
+            .is_some_and(|(time, _)| *time <= self.elapsed)
+        {
+            self.next_marker += 1;
+        }
+
+        self.markers[start..self.next_marker]
+            .iter()
+            .map(|(_, name)| name.clone())
+            .collect()
+    }
+
+    /// Return the keyframes either side of the elapsed time, and how far the camera has eased from the first to the second
+    fn segment(&self) -> Option<(&Keyframe<P>, &Keyframe<P>, f64)> {
+        let next = self.keyframes.partition_point(|k| k.time <= self.elapsed);
+        let Some(to) = self.keyframes.get(next) else {
+            let last = self.keyframes.last()?;
+            return Some((last, last, 1.0));
+        };
+        let Some(from) = next.checked_sub(1).map(|i| &self.keyframes[i]) else {
+            return Some((to, to, 1.0));
+        };
+
+        let span = to.time.saturating_sub(from.time).as_secs_f64();
+        let t = self.elapsed.saturating_sub(from.time).as_secs_f64() / span;
+        Some((from, to, to.easing.apply(t)))
+    }
+
+    /// Return where the camera is at the elapsed time, or `None` if the rail has no keyframes
+    #[must_use]
+    pub fn position(&self) -> Option<P> {
+        let (from, to, t) = self.segment()?;
+        Some(from.position.lerp(to.position, t))
+    }
+
+    /// Return what the camera is looking at at the elapsed time. The camera turns between the look-at targets of neighbouring keyframes, and holds on a target until the next keyframe when only one of them has one
+    #[must_use]
+    pub fn look_at(&self) -> Option<P> {
+        let (from, to, t) = self.segment()?;
+        match (from.look_at, to.look_at) {
+            (Some(a), Some(b)) => Some(a.lerp(b, t)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+impl CameraRail<Vec2D> {
+    /// Move the [`View`]'s [`camera`](View::camera) to the rail's current position, centring it on the look-at target instead while there is one. Does nothing if the rail has no keyframes
+    pub fn apply_to_view(&self, view: &mut View) {
+        if let Some(centre) = self.look_at().or_else(|| self.position()) {
+            view.centre_camera_on(centre);
+        }
+    }
+}
+
+#[cfg(feature = "3D")]
+impl CameraRail<Vec3D> {
+    /// Move the [`Viewport`] to the rail's current position, rotating it to face the look-at target if there is one. Its rotation is left as it is otherwise. Does nothing if the rail has no keyframes. Only available with the `3D` feature
+    pub fn apply_to_viewport(&self, viewport: &mut Viewport) {
+        let Some(position) = self.position() else {
+            return;
+        };
+        viewport.transform.translation = position;
+
+        if let Some(target) = self.look_at() {
+            let direction = target - position;
+            if direction != Vec3D::ZERO {
+                viewport.transform.rotation = Vec3D::new(
+                    direction.y.atan2(direction.x.hypot(direction.z)),
+                    direction.x.atan2(-direction.z),
+                    0.0,
+                );
+            }
+        }
+    }
+}
+
+impl<P: RailPoint> CanAnimate for CameraRail<P> {
+    fn is_animating(&self) -> bool {
+        self.is_active()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eases_between_keyframes_and_passes_markers() {
+        let mut rail = CameraRail::new()
+            .with_keyframe(
+                Keyframe::new(Duration::from_secs(4), Vec2D::new(100, 0))
+                    .with_easing(Easing::EaseIn),
+            )
+            .with_keyframe(Keyframe::new(Duration::ZERO, Vec2D::ZERO))
+            .with_marker(Duration::ZERO, "start")
+            .with_marker(Duration::from_secs(3), "shake");
+        assert!(!rail.is_active());
+        assert!(rail.update(Duration::from_secs(1)).is_empty());
+
+        rail.play();
+        assert_eq!(rail.update(Duration::from_secs(2)), ["start"]);
+        assert_eq!(rail.position(), Some(Vec2D::new(25, 0)));
+        assert!(rail.is_active());
+
+        assert_eq!(rail.intercept("jump"), None);
+
+        assert_eq!(rail.skip(), ["shake"]);
+        assert_eq!(rail.position(), Some(Vec2D::new(100, 0)));
+        assert!(!rail.is_active());
+        assert_eq!(rail.intercept("jump"), Some("jump"));
+    }
+
+    #[cfg(feature = "3D")]
+    #[test]
+    fn viewport_faces_the_look_at_target() {
+        let rail = CameraRail::new().with_keyframe(
+            Keyframe::new(Duration::ZERO, Vec3D::new(0.0, 0.0, 5.0))
+                .with_look_at(Vec3D::new(3.0, 2.0, 5.0)),
+        );
+        let mut viewport =
+            Viewport::new(crate::elements3d::Transform3D::DEFAULT, 90.0, Vec2D::ZERO);
+        rail.apply_to_viewport(&mut viewport);
+
+        let target = viewport
+            .transform
+            .apply_viewport_transform(&[Vec3D::new(3.0, 2.0, 5.0)])[0];
+        assert!(target.x.abs() < 1e-9 && target.y.abs() < 1e-9);
+        assert!(target.z < 0.0);
+    }
+}
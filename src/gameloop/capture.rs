@@ -0,0 +1,467 @@
+//! Engine-level screenshots and frame recording, triggered by hotkeys without any game code. Read the [`CaptureHotkeys`] documentation for more info
+
+#[cfg(feature = "png")]
+use crate::{elements::view::Bitmap, EngineError};
+use crate::{
+    elements::{Vec2D, View},
+    utils::json_string,
+};
+#[cfg(feature = "png")]
+use std::collections::VecDeque;
+use std::{
+    fmt::{self, Write},
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+pub use super::hotkeys::{F11, F12};
+
+/// A `FrameRecorder` records rendered frames of a [`View`] along with when they were rendered, and saves them in the [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/) format so they can be played back in the terminal with `asciinema play`
+/// ```
+/// use gemini_engine::{elements::{view::ColChar, View}, gameloop::capture::FrameRecorder};
+/// use std::time::Duration;
+///
+/// let view = View::new(10, 3, ColChar::BACKGROUND);
+/// let mut recorder = FrameRecorder::new(view.size());
+/// recorder.record_at(Duration::ZERO, &view).unwrap();
+/// recorder.record_at(Duration::from_millis(33), &view).unwrap();
+///
+/// assert_eq!(recorder.len(), 2);
+/// assert!(recorder.to_asciicast().starts_with("{\"version\": 2, \"width\": 10, \"height\": 3}"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrameRecorder {
+    size: Vec2D,
+    started: Instant,
+    frames: Vec<(Duration, String)>,
+}
+
+impl FrameRecorder {
+    /// Create a new `FrameRecorder` for a `View` of the given size, starting its clock now
+    #[must_use]
+    pub fn new(size: Vec2D) -> Self {
+        Self {
+            size,
+            started: Instant::now(),
+            frames: vec![],
+        }
+    }
+
+    /// Record the `View` as a frame rendered now
+    ///
+    /// # Errors
+    /// Returns an error if the `View` could not be rendered to a string
+    pub fn record(&mut self, view: &View) -> fmt::Result {
+        self.record_at(self.started.elapsed(), view)
+    }
+
+    /// Record the `View` as a frame rendered `time` after recording started
+    ///
+    /// # Errors
+    /// Returns an error if the `View` could not be rendered to a string
+    pub fn record_at(&mut self, time: Duration, view: &View) -> fmt::Result {
+        self.frames.push((time, view.to_string()?));
+        Ok(())
+    }
+
+    /// Return the number of frames recorded
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Return true if no frames have been recorded
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Return the recording in the asciicast v2 format
+    #[must_use]
+    pub fn to_asciicast(&self) -> String {
+        let mut cast = format!(
+            "{{\"version\": 2, \"width\": {}, \"height\": {}}}\n",
+            self.size.x, self.size.y
+        );
+        for (time, frame) in &self.frames {
+            let _ = writeln!(
+                cast,
+                "[{:.6}, \"o\", {}]",
+                time.as_secs_f64(),
+                json_string(&format!("\x1b[H{frame}"))
+            );
+        }
+        cast
+    }
+
+    /// Write the recording to a file as returned by [`to_asciicast()`](FrameRecorder::to_asciicast()), replacing the file if it already exists
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be written to
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_asciicast())
+    }
+}
+
+/// An `AnimationRecorder` keeps every `n`th frame of a [`View`] passed to [`capture()`](AnimationRecorder::capture()), and saves them as an animated PNG on demand, for bug reports and store pages. Call `capture()` after every render in the game loop. Frames are only drawn to images when the animation is saved, using the bundled font from [`Bitmap::from_view_with_font()`]. Only available with the `png` feature
+///
+/// Set [`max_frames`](AnimationRecorder::max_frames) to keep only the latest frames, so the recorder can be left running and saved when something interesting happens
+/// ```
+/// use gemini_engine::{elements::{view::ColChar, View}, gameloop::capture::AnimationRecorder};
+///
+/// let view = View::new(10, 3, ColChar::BACKGROUND);
+/// let mut recorder = AnimationRecorder::new(2).with_max_frames(Some(30));
+///
+/// for _ in 0..6 {
+///     // --the game renders here--
+///     recorder.capture(&view);
+/// }
+///
+/// assert_eq!(recorder.len(), 3);
+/// let apng = recorder.to_apng(60.0).unwrap();
+/// assert!(apng.starts_with(b"\x89PNG"));
+/// ```
+#[cfg(feature = "png")]
+#[derive(Debug, Clone)]
+pub struct AnimationRecorder {
+    /// How many frames pass between each one that is kept. 1 keeps every frame
+    pub every: usize,
+    /// The scale the bundled font is drawn at. Defaults to 1
+    pub scale: usize,
+    /// The most frames kept, dropping the oldest when a new one is captured, or `None` for no limit. Defaults to `None`
+    pub max_frames: Option<usize>,
+    seen: usize,
+    frames: VecDeque<View>,
+}
+
+#[cfg(feature = "png")]
+impl AnimationRecorder {
+    /// Create a new `AnimationRecorder` keeping every `every`th frame
+    #[must_use]
+    pub const fn new(every: usize) -> Self {
+        Self {
+            every,
+            scale: 1,
+            max_frames: None,
+            seen: 0,
+            frames: VecDeque::new(),
+        }
+    }
+
+    /// Return the `AnimationRecorder` with a different font scale
+    #[must_use]
+    pub const fn with_scale(mut self, scale: usize) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Return the `AnimationRecorder` with a different limit on the frames kept
+    #[must_use]
+    pub const fn with_max_frames(mut self, max_frames: Option<usize>) -> Self {
+        self.max_frames = max_frames;
+        self
+    }
+
+    /// Pass a rendered frame to the recorder, returning true if it was kept
+    pub fn capture(&mut self, view: &View) -> bool {
+        let keep = self.seen.is_multiple_of(self.every.max(1));
+        self.seen += 1;
+        if !keep {
+            return false;
+        }
+
+        self.frames.push_back(view.clone());
+        if let Some(max_frames) = self.max_frames {
+            while self.frames.len() > max_frames {
+                self.frames.pop_front();
+            }
+        }
+        true
+    }
+
+    /// Return the number of frames kept
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Return true if no frames have been kept
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Drop every frame kept so far
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.seen = 0;
+    }
+
+    /// Return the kept frames as an animated PNG, played back at the speed they were rendered when the game runs at `frame_rate` frames per second
+    ///
+    /// # Errors
+    /// Returns an error if no frames have been kept, the `View` changed size during the recording, or the animation couldn't be encoded
+    pub fn to_apng(&self, frame_rate: f64) -> Result<Vec<u8>, EngineError> {
+        let frames: Vec<Bitmap> = self
+            .frames
+            .iter()
+            .map(|view| Bitmap::from_view_with_font(view, self.scale))
+            .collect();
+        let frame_time = Duration::try_from_secs_f64(self.every.max(1) as f64 / frame_rate)
+            .map_err(|err| EngineError::InvalidData(err.to_string()))?;
+
+        Bitmap::to_apng(&frames, frame_time)
+    }
+
+    /// Write the kept frames to a file as returned by [`to_apng()`](AnimationRecorder::to_apng()), replacing the file if it already exists
+    ///
+    /// # Errors
+    /// Returns an error if the animation couldn't be encoded, or [`EngineError::Io`] if the file couldn't be written to
+    pub fn save(&self, path: impl AsRef<Path>, frame_rate: f64) -> Result<(), EngineError> {
+        Ok(fs::write(path, self.to_apng(frame_rate)?)?)
+    }
+}
+
+/// The format [`CaptureHotkeys`] saves screenshots in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScreenshotFormat {
+    /// A standalone HTML page, as returned by [`View::to_html()`]
+    #[default]
+    Html,
+    /// The raw text and ANSI escape codes the `View` renders as, which can be shown again with `cat`
+    Ansi,
+    /// A PNG image drawn with the bundled font, as returned by [`View::to_png()`] at a scale of 1. Only available with the `png` feature
+    #[cfg(feature = "png")]
+    Png,
+}
+
+impl ScreenshotFormat {
+    /// Return the file extension of the format
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Html => "html",
+            Self::Ansi => "ans",
+            #[cfg(feature = "png")]
+            Self::Png => "png",
+        }
+    }
+}
+
+/// `CaptureHotkeys` adds screenshot and recording hotkeys to a game without any game code. Pass every input through [`intercept()`](CaptureHotkeys::intercept()) before the game handles it, which swallows the hotkeys, then call [`capture()`](CaptureHotkeys::capture()) with the `View` after every render. Files are saved in the [`directory`](CaptureHotkeys::directory), named by the time they were taken
+///
+/// `CaptureHotkeys::default()` takes screenshots with [`F12`] and starts or stops recording with [`F11`]. Read the [`hotkeys`](super::hotkeys) module to use other keys
+/// ```
+/// use gemini_engine::{elements::{view::ColChar, View}, gameloop::capture::{CaptureHotkeys, F12}};
+///
+/// let mut hotkeys = CaptureHotkeys::default().with_directory(std::env::temp_dir());
+/// let view = View::new(10, 3, ColChar::BACKGROUND);
+///
+/// for input in ["w", F12] {
+///     if let Some(input) = hotkeys.intercept(input) {
+///         // --the game handles the input here--
+///         assert_eq!(input, "w");
+///     }
+/// }
+///
+/// // --the game renders here--
+/// let saved = hotkeys.capture(&view).unwrap();
+/// assert!(saved[0].extension().is_some_and(|ext| ext == "html"));
+/// # std::fs::remove_file(&saved[0]).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct CaptureHotkeys<K> {
+    /// The key that saves a screenshot of the next frame
+    pub screenshot_key: K,
+    /// The key that starts recording, or stops and saves the recording
+    pub record_key: K,
+    /// The format screenshots are saved in
+    pub format: ScreenshotFormat,
+    /// The directory screenshots and recordings are saved in
+    pub directory: PathBuf,
+    screenshot_requested: bool,
+    stop_requested: bool,
+    recorder: Option<FrameRecorder>,
+    saved: usize,
+}
+
+impl<K: PartialEq> CaptureHotkeys<K> {
+    /// Create a new `CaptureHotkeys` using the given keys, saving HTML screenshots to the current directory
+    #[must_use]
+    pub fn new(screenshot_key: K, record_key: K) -> Self {
+        Self {
+            screenshot_key,
+            record_key,
+            format: ScreenshotFormat::Html,
+            directory: PathBuf::from("."),
+            screenshot_requested: false,
+            stop_requested: false,
+            recorder: None,
+            saved: 0,
+        }
+    }
+
+    /// Return the `CaptureHotkeys` with the modified screenshot format
+    #[must_use]
+    pub const fn with_format(mut self, format: ScreenshotFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Return the `CaptureHotkeys` with the modified directory
+    #[must_use]
+    pub fn with_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = directory.into();
+        self
+    }
+
+    /// Return true if frames are being recorded
+    #[must_use]
+    pub const fn is_recording(&self) -> bool {
+        self.recorder.is_some() && !self.stop_requested
+    }
+
+    /// Handle the input if it's one of the hotkeys, returning `None` so the game never sees it. Any other input is returned unchanged for the game to handle
+    pub fn intercept(&mut self, input: K) -> Option<K> {
+        if input == self.screenshot_key {
+            self.screenshot_requested = true;
+            None
+        } else if input == self.record_key {
+            if self.recorder.is_some() {
+                self.stop_requested = !self.stop_requested;
+            } else {
+                self.recorder = Some(FrameRecorder::new(Vec2D::ZERO));
+            }
+            None
+        } else {
+            Some(input)
+        }
+    }
+
+    /// Capture the rendered `View`: save a screenshot if one was requested, record the frame if recording, and save the recording if it was stopped. Returns the paths of any files saved
+    ///
+    /// # Errors
+    /// Returns an error if a file could not be written
+    pub fn capture(&mut self, view: &View) -> io::Result<Vec<PathBuf>> {
+        let mut saved = vec![];
+
+        if std::mem::take(&mut self.screenshot_requested) {
+            let path = self.next_path("screenshot", self.format.extension());
+            match self.format {
+                ScreenshotFormat::Html => view.export_html(&path)?,
+                ScreenshotFormat::Ansi => fs::write(&path, render(view)?)?,
+                #[cfg(feature = "png")]
+                ScreenshotFormat::Png => {
+                    fs::write(&path, view.to_png(1).map_err(io::Error::other)?)?;
+                }
+            }
+            saved.push(path);
+        }
+
+        if std::mem::take(&mut self.stop_requested) {
+            if let Some(recorder) = self.recorder.take() {
+                let path = self.next_path("recording", "cast");
+                recorder.save(&path)?;
+                saved.push(path);
+            }
+        } else if let Some(recorder) = &mut self.recorder {
+            if recorder.is_empty() {
+                *recorder = FrameRecorder::new(view.size());
+            }
+            recorder.record(view).map_err(io::Error::other)?;
+        }
+
+        Ok(saved)
+    }
+
+    /// Return a path in the directory for a new file, unique to this `CaptureHotkeys`
+    fn next_path(&mut self, name: &str, extension: &str) -> PathBuf {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        self.saved += 1;
+
+        self.directory
+            .join(format!("{name}-{time}-{}.{extension}", self.saved))
+    }
+}
+
+impl Default for CaptureHotkeys<&'static str> {
+    fn default() -> Self {
+        Self::new(F12, F11)
+    }
+}
+
+/// Render the `View` to a string, as an `io::Result`
+fn render(view: &View) -> io::Result<String> {
+    view.to_string().map_err(io::Error::other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::view::ColChar;
+
+    #[test]
+    fn record_key_toggles_recording() {
+        let directory = std::env::temp_dir();
+        let mut hotkeys = CaptureHotkeys::new('p', 'r')
+            .with_directory(&directory)
+            .with_format(ScreenshotFormat::Ansi);
+        let view = View::new(4, 2, ColChar::BACKGROUND);
+
+        assert_eq!(hotkeys.intercept('r'), None);
+        assert!(hotkeys.is_recording());
+        for _ in 0..3 {
+            assert!(hotkeys.capture(&view).expect("nothing to save").is_empty());
+        }
+        assert_eq!(hotkeys.intercept('x'), Some('x'));
+        hotkeys.intercept('r');
+        assert!(!hotkeys.is_recording());
+
+        let saved = hotkeys.capture(&view).expect("recording saved");
+        let cast = fs::read_to_string(&saved[0]).expect("recording readable");
+        assert!(cast.starts_with("{\"version\": 2, \"width\": 4, \"height\": 2}\n"));
+        assert_eq!(cast.lines().count(), 4);
+        fs::remove_file(&saved[0]).expect("recording removable");
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn saves_png_screenshots() {
+        let mut hotkeys = CaptureHotkeys::new('p', 'r')
+            .with_directory(std::env::temp_dir())
+            .with_format(ScreenshotFormat::Png);
+        let view = View::new(4, 2, ColChar::BACKGROUND);
+
+        hotkeys.intercept('p');
+        let saved = hotkeys.capture(&view).expect("screenshot saved");
+        assert!(saved[0].extension().is_some_and(|ext| ext == "png"));
+        let png = fs::read(&saved[0]).expect("screenshot readable");
+        assert_eq!(png, view.to_png(1).expect("view encodes"));
+        fs::remove_file(&saved[0]).expect("screenshot removable");
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn animation_recorder_keeps_the_latest_frames() {
+        let mut recorder = AnimationRecorder::new(3).with_max_frames(Some(2));
+        let view = View::new(2, 1, ColChar::SOLID);
+        let kept: Vec<bool> = (0..7).map(|_| recorder.capture(&view)).collect();
+        assert_eq!(kept, [true, false, false, true, false, false, true]);
+        assert_eq!(recorder.len(), 2);
+
+        recorder.capture(&View::new(3, 1, ColChar::SOLID));
+        recorder.capture(&View::new(3, 1, ColChar::SOLID));
+        recorder.capture(&View::new(3, 1, ColChar::SOLID));
+        assert!(matches!(
+            recorder.to_apng(30.0),
+            Err(EngineError::InvalidSize(_))
+        ));
+
+        recorder.clear();
+        assert!(recorder.to_apng(30.0).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+//! Separate clocks for real time and simulation time. Read the [`GameClock`] documentation for more info
+
+use std::time::{Duration, Instant};
+
+/// Wall-clock time, which always runs. Use it for anything that should keep moving while the game is paused, such as menus, [`Toasts`](crate::elements::ui::Toasts) and other UI animations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RealTime {
+    /// The time since the last tick
+    pub delta: Duration,
+    /// The total time since the clock started
+    pub elapsed: Duration,
+}
+
+/// Simulation time, which stops while the game is paused and can be slowed down or sped up. Use it for physics, AI and anything else that's part of the game world
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SimTime {
+    /// The simulation time since the last tick, which is zero while paused
+    pub delta: Duration,
+    /// The total simulation time that has passed
+    pub elapsed: Duration,
+    /// How fast the simulation runs compared to real time. 1.0 is normal speed, 0.5 is half speed for slow motion
+    pub scale: f64,
+    /// Whether the simulation is paused
+    pub paused: bool,
+}
+
+/// `GameClock` keeps two clocks: [`RealTime`], which always runs, and [`SimTime`], which can be paused or scaled. Call [`tick()`](GameClock::tick()) once per frame, then pass [`sim.delta`](SimTime::delta) to the game world's updates and [`real.delta`](RealTime::delta) to the UI's, so that a pause menu or slow motion doesn't freeze UI animations. [`MainLoopRoot::main_loop()`](super::MainLoopRoot::main_loop()) and [`fps_gameloop!`](crate::fps_gameloop) tick one for you at the start of every frame, and other gameloops can keep their own
+/// ```
+/// use gemini_engine::gameloop::clock::GameClock;
+/// use std::time::Duration;
+///
+/// let mut clock = GameClock::new();
+/// clock.set_paused(true);
+/// clock.advance(Duration::from_millis(16));
+///
+/// // The pause menu keeps animating while the world stands still
+/// assert_eq!(clock.real.delta, Duration::from_millis(16));
+/// assert_eq!(clock.sim.delta, Duration::ZERO);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameClock {
+    /// The real time clock
+    pub real: RealTime,
+    /// The simulation time clock
+    pub sim: SimTime,
+    last_tick: Option<Instant>,
+}
+
+impl GameClock {
+    /// Create a new `GameClock` with the simulation running at normal speed
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            real: RealTime {
+                delta: Duration::ZERO,
+                elapsed: Duration::ZERO,
+            },
+            sim: SimTime {
+                delta: Duration::ZERO,
+                elapsed: Duration::ZERO,
+                scale: 1.0,
+                paused: false,
+            },
+            last_tick: None,
+        }
+    }
+
+    /// Return the `GameClock` with the simulation running at the given speed
+    #[must_use]
+    pub const fn with_time_scale(mut self, scale: f64) -> Self {
+        self.set_time_scale(scale);
+        self
+    }
+
+    /// Set how fast the simulation runs compared to real time. Negative and NaN values are treated as zero
+    pub const fn set_time_scale(&mut self, scale: f64) {
+        self.sim.scale = scale.max(0.0);
+    }
+
+    /// Pause or resume the simulation
+    pub const fn set_paused(&mut self, paused: bool) {
+        self.sim.paused = paused;
+    }
+
+    /// Advance both clocks by the real time since the last tick. The first tick advances by zero
+    pub fn tick(&mut self) {
+        let now = Instant::now();
+        let delta = self
+            .last_tick
+            .map_or(Duration::ZERO, |last_tick| now - last_tick);
+        self.last_tick = Some(now);
+
+        self.advance(delta);
+    }
+
+    /// Advance both clocks by a fixed amount of real time. Useful for fixed timesteps and tests
+    pub fn advance(&mut self, real_delta: Duration) {
+        self.real.delta = real_delta;
+        self.real.elapsed += real_delta;
+
+        self.sim.delta = if self.sim.paused {
+            Duration::ZERO
+        } else {
+            // A scale that isn't finite could make a delta that doesn't fit in a `Duration`
+            let sim_secs = real_delta.as_secs_f64() * self.sim.scale;
+            Duration::try_from_secs_f64(sim_secs).unwrap_or(if sim_secs > 0.0 {
+                Duration::MAX
+            } else {
+                Duration::ZERO
+            })
+        };
+        self.sim.elapsed = self.sim.elapsed.saturating_add(self.sim.delta);
+    }
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sim_time_scales_and_pauses() {
+        let mut clock = GameClock::new().with_time_scale(0.5);
+        clock.advance(Duration::from_millis(100));
+        assert_eq!(clock.sim.delta, Duration::from_millis(50));
+
+        clock.set_paused(true);
+        clock.advance(Duration::from_millis(100));
+        clock.set_paused(false);
+        clock.set_time_scale(-1.0);
+        clock.advance(Duration::from_millis(100));
+
+        assert_eq!(clock.real.elapsed, Duration::from_millis(300));
+        assert_eq!(clock.sim.elapsed, Duration::from_millis(50));
+    }
+
+    #[test]
+    fn extreme_time_scales_dont_panic() {
+        let mut clock = GameClock::new();
+        clock.sim.scale = f64::NAN;
+        clock.advance(Duration::from_millis(100));
+        assert_eq!(clock.sim.delta, Duration::ZERO);
+
+        clock.sim.scale = f64::INFINITY;
+        clock.advance(Duration::from_millis(100));
+        clock.advance(Duration::from_millis(100));
+        assert_eq!(clock.sim.elapsed, Duration::MAX);
+    }
+
+    #[test]
+    fn first_tick_is_zero() {
+        let mut clock = GameClock::new();
+        clock.tick();
+        assert_eq!(clock.real.delta, Duration::ZERO);
+        std::thread::sleep(Duration::from_millis(5));
+        clock.tick();
+        assert!(clock.real.delta >= Duration::from_millis(5));
+        assert_eq!(clock.sim.elapsed, clock.real.elapsed);
+    }
+}
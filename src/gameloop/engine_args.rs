@@ -0,0 +1,284 @@
+//! Standard command-line options for Gemini games. Read the [`EngineArgs`] documentation for more info
+
+use super::capture::FrameRecorder;
+pub use crate::elements::view::ColourMode;
+use crate::{
+    elements::{view::ColChar, Vec2D, View},
+    EngineError,
+};
+use std::{env, io, path::PathBuf};
+
+/// `EngineArgs` parses the command-line options shared by most Gemini games, so that every game accepts the same flags. Any arguments it doesn't recognise are kept in [`rest`](EngineArgs::rest) for the game to handle itself. The supported flags are listed in [`EngineArgs::USAGE`]
+///
+/// Every option is optional, so use the `_or` methods such as [`fps_or()`](EngineArgs::fps_or()) to fall back on the game's own defaults, [`view()`](EngineArgs::view()) to create a [`View`] of the requested size and [`output()`](EngineArgs::output()) to display frames as `--headless` and `--record` ask
+/// ```
+/// use gemini_engine::{elements::{view::ColChar, Vec2D}, gameloop::engine_args::{ColourMode, EngineArgs}};
+///
+/// let args = EngineArgs::parse(["--fps", "30", "--size=80x24", "--color-mode", "16", "--god-mode"]).unwrap();
+///
+/// assert_eq!(args.fps_or(60.0), 30.0);
+/// assert_eq!(args.colour_mode_or_default(), ColourMode::Ansi16);
+/// assert_eq!(args.view(Vec2D::new(40, 12), ColChar::BACKGROUND).size(), Vec2D::new(80, 24));
+/// assert_eq!(args.rest, vec!["--god-mode"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EngineArgs {
+    /// The frames per second to run at, from `--fps <FPS>`
+    pub fps: Option<f32>,
+    /// The colour mode to draw in, from `--color-mode <MODE>` (or `--colour-mode`)
+    pub colour_mode: Option<ColourMode>,
+    /// The size of the `View`, from `--size <WIDTH>x<HEIGHT>`
+    pub size: Option<Vec2D>,
+    /// A file to record the game's frames to as an asciicast, from `--record <PATH>`. Applied by [`output()`](EngineArgs::output())
+    pub record: Option<PathBuf>,
+    /// Whether to run without drawing to the terminal, such as for tests or servers, from `--headless`. Applied by [`output()`](EngineArgs::output()). See [`Headless`](super::Headless) to also skip rendering
+    pub headless: bool,
+    /// Every argument that isn't one of the engine's, in order
+    pub rest: Vec<String>,
+}
+
+impl EngineArgs {
+    /// A description of the flags `EngineArgs` accepts, for including in a game's `--help` output
+    pub const USAGE: &'static str = "\
+Engine options:
+  --fps <FPS>                 Frames per second to run at
+  --color-mode <MODE>         Colours to draw with: truecolor, 256, 16 or none
+  --size <WIDTH>x<HEIGHT>     Size of the view, such as 80x24
+  --record <PATH>             Record the game's frames to an asciicast file
+  --headless                  Run without drawing to the terminal";
+
+    /// Parse the arguments the program was started with, skipping the program name
+    ///
+    /// # Errors
+    /// Returns an error if one of the engine's flags is missing its value or has an invalid one
+    pub fn from_env() -> Result<Self, EngineError> {
+        Self::parse(env::args().skip(1))
+    }
+
+    /// Parse a list of arguments, not including the program name. Values can be passed either as the next argument (`--fps 30`) or after an equals sign (`--fps=30`)
+    ///
+    /// # Errors
+    /// Returns an error if one of the engine's flags is missing its value or has an invalid one
+    pub fn parse<I>(args: I) -> Result<Self, EngineError>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter().map(Into::into);
+
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg.clone(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| EngineError::InvalidData(format!("{flag} needs a value")))
+            };
+
+            match flag.as_str() {
+                "--fps" => {
+                    let fps = value()?;
+                    parsed.fps = Some(
+                        fps.parse()
+                            .ok()
+                            .filter(|fps: &f32| fps.is_finite() && *fps > 0.0)
+                            .ok_or_else(|| {
+                                EngineError::InvalidData(format!("Invalid FPS: {fps}"))
+                            })?,
+                    );
+                }
+                "--color-mode" | "--colour-mode" => parsed.colour_mode = Some(value()?.parse()?),
+                "--size" => parsed.size = Some(parse_size(&value()?)?),
+                "--record" => parsed.record = Some(PathBuf::from(value()?)),
+                "--headless" if inline_value.is_none() => parsed.headless = true,
+                _ => parsed.rest.push(arg),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Return the requested FPS, or `default` if none was passed
+    #[must_use]
+    pub fn fps_or(&self, default: f32) -> f32 {
+        self.fps.unwrap_or(default)
+    }
+
+    /// Return the requested colour mode, or [`ColourMode::TrueColour`] if none was passed
+    #[must_use]
+    pub fn colour_mode_or_default(&self) -> ColourMode {
+        self.colour_mode.unwrap_or_default()
+    }
+
+    /// Return the requested colour mode, or the terminal's own from [`ColourMode::detect()`] if none was passed
+    #[must_use]
+    pub fn colour_mode_or_detect(&self) -> ColourMode {
+        self.colour_mode.unwrap_or_else(ColourMode::detect)
+    }
+
+    /// Return the requested size, or `default` if none was passed
+    #[must_use]
+    pub fn size_or(&self, default: Vec2D) -> Vec2D {
+        self.size.unwrap_or(default)
+    }
+
+    /// Create a [`View`] of the requested size, or `default_size` if none was passed. The `View` draws in the requested colour mode, or the one detected from the terminal if none was passed
+    #[must_use]
+    pub fn view(&self, default_size: Vec2D, background_char: ColChar) -> View {
+        let size = self.size_or(default_size);
+        View::new(size.x as usize, size.y as usize, background_char)
+            .with_colour_mode(self.colour_mode_or_detect())
+    }
+
+    /// Create a [`FrameOutput`] for a `View` of the given size, which records frames if `--record` was passed and skips drawing to the terminal if `--headless` was passed
+    #[must_use]
+    pub fn output(&self, size: Vec2D) -> FrameOutput {
+        FrameOutput {
+            headless: self.headless,
+            recording: self
+                .record
+                .clone()
+                .map(|path| (path, FrameRecorder::new(size))),
+        }
+    }
+}
+
+/// A `FrameOutput` sends rendered frames wherever the [`EngineArgs`] it was created from asked. Pass every frame to [`display()`](FrameOutput::display()) instead of calling [`View::display_render()`], then call [`finish()`](FrameOutput::finish()) when the game ends to save the recording
+/// ```
+/// use gemini_engine::{elements::{view::ColChar, Vec2D}, gameloop::EngineArgs};
+///
+/// let args = EngineArgs::parse(["--headless"]).unwrap();
+/// let view = args.view(Vec2D::new(20, 5), ColChar::BACKGROUND);
+/// let mut output = args.output(view.size());
+///
+/// // Nothing is drawn to the terminal
+/// output.display(&view).unwrap();
+/// output.finish().unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrameOutput {
+    headless: bool,
+    recording: Option<(PathBuf, FrameRecorder)>,
+}
+
+impl FrameOutput {
+    /// Record the `View` if recording, and display it unless running headless
+    ///
+    /// # Errors
+    /// Returns an error if the `View` couldn't be rendered or written to stdout
+    pub fn display(&mut self, view: &View) -> io::Result<()> {
+        if let Some((_, recorder)) = &mut self.recording {
+            recorder.record(view).map_err(io::Error::other)?;
+        }
+        if self.headless {
+            Ok(())
+        } else {
+            view.display_render()
+        }
+    }
+
+    /// Returns true if frames aren't drawn to the terminal
+    #[must_use]
+    pub const fn is_headless(&self) -> bool {
+        self.headless
+    }
+
+    /// Return the recorder frames are being recorded to, if any
+    #[must_use]
+    pub fn recorder(&self) -> Option<&FrameRecorder> {
+        self.recording.as_ref().map(|(_, recorder)| recorder)
+    }
+
+    /// Save the recording to the `--record` path, if there is one
+    ///
+    /// # Errors
+    /// Returns an error if the recording couldn't be written to the file
+    pub fn finish(self) -> io::Result<()> {
+        match self.recording {
+            Some((path, recorder)) => recorder.save(path),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parse a size written as `<WIDTH>x<HEIGHT>`
+fn parse_size(size: &str) -> Result<Vec2D, EngineError> {
+    let invalid = || {
+        EngineError::InvalidData(format!(
+            "Invalid size {size}, expected <WIDTH>x<HEIGHT> such as 80x24"
+        ))
+    };
+    let (width, height) = size.split_once(['x', 'X']).ok_or_else(invalid)?;
+    let width: usize = width.trim().parse().map_err(|_| invalid())?;
+    let height: usize = height.trim().parse().map_err(|_| invalid())?;
+    if width == 0 || height == 0 {
+        return Err(invalid());
+    }
+
+    Ok(Vec2D::new(width as isize, height as isize))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_flags_and_keeps_the_rest() {
+        let args = EngineArgs::parse([
+            "level2.map",
+            "--headless",
+            "--record",
+            "replay.bin",
+            "--colour-mode=none",
+        ])
+        .expect("valid arguments");
+
+        assert!(args.headless);
+        assert_eq!(args.record, Some(PathBuf::from("replay.bin")));
+        assert_eq!(args.colour_mode, Some(ColourMode::None));
+        assert_eq!(args.fps, None);
+        assert_eq!(args.rest, vec!["level2.map"]);
+    }
+
+    #[test]
+    fn output_records_without_drawing() {
+        let path = env::temp_dir().join("gemini_engine_args_recording.cast");
+        let record = path.to_string_lossy().into_owned();
+        let args = EngineArgs::parse(["--headless", "--record", &record]).expect("valid arguments");
+        let view = args.view(Vec2D::new(4, 2), ColChar::BACKGROUND);
+
+        let mut output = args.output(view.size());
+        assert!(output.is_headless());
+        output
+            .display(&view)
+            .expect("headless output can't fail to draw");
+        output
+            .display(&view)
+            .expect("headless output can't fail to draw");
+        assert_eq!(output.recorder().map(FrameRecorder::len), Some(2));
+        output.finish().expect("the recording should save");
+
+        let saved = std::fs::read_to_string(&path).expect("the recording should exist");
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(saved.lines().count(), 3);
+        assert!(EngineArgs::default()
+            .output(view.size())
+            .recorder()
+            .is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        assert!(EngineArgs::parse(["--fps"]).is_err());
+        assert!(EngineArgs::parse(["--fps", "-5"]).is_err());
+        assert!(EngineArgs::parse(["--size", "80by24"]).is_err());
+        assert!(EngineArgs::parse(["--size", "0x24"]).is_err());
+        assert!(EngineArgs::parse(["--color-mode", "8"]).is_err());
+    }
+}
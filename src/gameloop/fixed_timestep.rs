@@ -0,0 +1,106 @@
+//! Running game logic at a fixed tick rate independent of the frame rate. Read the [`FixedTimestep`] documentation for more info
+
+use std::time::Duration;
+
+/// `FixedTimestep` runs the simulation at a steady tick rate however fast the game renders. Every frame, pass the time since the last frame to [`advance()`](FixedTimestep::advance()) and run as many simulation ticks as it returns, then draw the frame using [`alpha()`](FixedTimestep::alpha()) to place [`Interpolated`](crate::elements::containers::Interpolated) elements between the last two ticks
+/// ```
+/// use gemini_engine::gameloop::{Duration, FixedTimestep};
+///
+/// let mut timestep = FixedTimestep::new(20.0);
+///
+/// // Three 60 FPS frames make up one 20 Hz tick
+/// assert_eq!(timestep.advance(Duration::from_micros(16_667)), 0);
+/// assert_eq!(timestep.advance(Duration::from_micros(16_667)), 0);
+/// assert!((timestep.alpha() - 0.667).abs() < 0.001);
+/// assert_eq!(timestep.advance(Duration::from_micros(16_667)), 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedTimestep {
+    /// The time between simulation ticks
+    pub tick: Duration,
+    /// The most ticks [`advance()`](FixedTimestep::advance()) returns at once. If the game falls further behind than this, such as after a lag spike, the extra time is dropped instead of running a burst of ticks. 0 never runs a tick, pausing the simulation. Defaults to 5
+    pub max_ticks: u32,
+    accumulated: Duration,
+}
+
+impl FixedTimestep {
+    /// Create a new `FixedTimestep` running the given number of ticks per second. A rate that isn't positive gives a zero [`tick`](FixedTimestep::tick), which never runs
+    #[must_use]
+    pub fn new(ticks_per_second: f64) -> Self {
+        Self {
+            tick: Duration::try_from_secs_f64(1.0 / ticks_per_second).unwrap_or(Duration::ZERO),
+            max_ticks: 5,
+            accumulated: Duration::ZERO,
+        }
+    }
+
+    /// Return the `FixedTimestep` with a different limit on ticks per frame
+    #[must_use]
+    pub const fn with_max_ticks(mut self, max_ticks: u32) -> Self {
+        self.max_ticks = max_ticks;
+        self
+    }
+
+    /// Add the time since the last frame and return how many simulation ticks should run this frame
+    pub fn advance(&mut self, delta: Duration) -> u32 {
+        self.accumulated += delta;
+        if self.tick.is_zero() {
+            return 0;
+        }
+
+        let mut ticks = 0;
+        while self.accumulated >= self.tick {
+            if ticks == self.max_ticks {
+                self.accumulated = self.accumulated.min(self.tick);
+                break;
+            }
+            self.accumulated -= self.tick;
+            ticks += 1;
+        }
+
+        ticks
+    }
+
+    /// Return how far the simulation is into the next tick, from 0.0 to 1.0
+    #[must_use]
+    pub fn alpha(&self) -> f64 {
+        if self.tick.is_zero() {
+            return 1.0;
+        }
+
+        (self.accumulated.as_secs_f64() / self.tick.as_secs_f64()).min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lag_spikes_are_capped() {
+        let mut timestep = FixedTimestep::new(10.0).with_max_ticks(3);
+        assert_eq!(timestep.advance(Duration::from_secs(2)), 3);
+        assert_eq!(timestep.advance(Duration::ZERO), 1);
+        assert_eq!(timestep.advance(Duration::from_millis(50)), 0);
+        assert!((timestep.alpha() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn zero_max_ticks_never_ticks() {
+        let mut timestep = FixedTimestep::new(10.0).with_max_ticks(0);
+        assert_eq!(timestep.advance(Duration::from_secs(2)), 0);
+        assert!((timestep.alpha() - 1.0).abs() < 1e-9);
+
+        timestep.max_ticks = 5;
+        assert_eq!(timestep.advance(Duration::ZERO), 1);
+    }
+
+    #[test]
+    fn invalid_rates_never_tick() {
+        for rate in [0.0, -20.0, f64::NAN] {
+            let mut timestep = FixedTimestep::new(rate);
+            assert_eq!(timestep.tick, Duration::ZERO);
+            assert_eq!(timestep.advance(Duration::from_secs(1)), 0);
+        }
+    }
+}
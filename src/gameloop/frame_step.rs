@@ -0,0 +1,178 @@
+//! A debug mode for pausing the simulation and advancing it one tick at a time. Read the [`FrameStepper`] documentation for more info
+
+use crate::elements::{
+    view::{ColChar, Modifier, ViewElement},
+    Pixel, Vec2D,
+};
+
+pub use super::hotkeys::{F10, F9};
+
+/// `FrameStepper` lets you pause the simulation with a hotkey and advance it exactly one update tick at a time with another, while the game keeps rendering. This makes it possible to step through physics or AI bugs frame by frame. Pass every input through [`intercept()`](FrameStepper::intercept()) before the game handles it, and only run the game's update when [`should_update()`](FrameStepper::should_update()) returns true. Blitting the `FrameStepper` shows the current tick count while paused
+///
+/// `FrameStepper::default()` pauses or resumes with [`F9`] and steps with [`F10`]. Read the [`hotkeys`](super::hotkeys) module to use other keys
+/// ```
+/// use gemini_engine::gameloop::frame_step::{FrameStepper, F9, F10};
+///
+/// let mut stepper = FrameStepper::default();
+/// let mut updates = 0;
+///
+/// for input in [None, Some(F9), None, Some(F10), None] {
+///     if let Some(input) = input.and_then(|input| stepper.intercept(input)) {
+///         // --the game handles the input here--
+///     }
+///     if stepper.should_update() {
+///         updates += 1;
+///     }
+///     // --the game renders here, whether or not it updated--
+/// }
+///
+/// // The first frame ran normally, then the simulation was paused and stepped once
+/// assert_eq!(updates, 2);
+/// assert_eq!(stepper.tick(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrameStepper<K> {
+    /// The key that pauses or resumes the simulation
+    pub pause_key: K,
+    /// The key that advances the paused simulation by one tick
+    pub step_key: K,
+    /// Where the tick count is drawn while paused
+    pub pos: Vec2D,
+    /// The `Modifier` the tick count is drawn with
+    pub modifier: Modifier,
+    paused: bool,
+    steps: usize,
+    tick: u64,
+}
+
+impl<K: PartialEq> FrameStepper<K> {
+    /// Create a new, unpaused `FrameStepper` using the given keys
+    #[must_use]
+    pub const fn new(pause_key: K, step_key: K) -> Self {
+        Self {
+            pause_key,
+            step_key,
+            pos: Vec2D::ZERO,
+            modifier: Modifier::YELLOW,
+            paused: false,
+            steps: 0,
+            tick: 0,
+        }
+    }
+
+    /// Return the `FrameStepper` with the tick count drawn at the given position
+    #[must_use]
+    pub const fn with_pos(mut self, pos: Vec2D) -> Self {
+        self.pos = pos;
+        self
+    }
+
+    /// Return true if the simulation is paused
+    #[must_use]
+    pub const fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Pause or resume the simulation. Any steps that haven't run yet are dropped when resuming
+    pub const fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+        self.steps = 0;
+    }
+
+    /// Return the number of update ticks that have run
+    #[must_use]
+    pub const fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Handle the input if it's one of the hotkeys, returning `None` so the game never sees it. Any other input is returned unchanged for the game to handle. The step key is passed through to the game while the simulation isn't paused
+    pub fn intercept(&mut self, input: K) -> Option<K> {
+        if input == self.pause_key {
+            self.set_paused(!self.paused);
+            None
+        } else if self.paused && input == self.step_key {
+            self.steps += 1;
+            None
+        } else {
+            Some(input)
+        }
+    }
+
+    /// Return true if the game should run its update this frame, counting the tick if so. Call this exactly once per frame. While paused, this only returns true once for every press of the step key
+    pub const fn should_update(&mut self) -> bool {
+        if self.paused {
+            if self.steps == 0 {
+                return false;
+            }
+            self.steps -= 1;
+        }
+        self.tick += 1;
+
+        true
+    }
+}
+
+impl Default for FrameStepper<&'static str> {
+    fn default() -> Self {
+        Self::new(F9, F10)
+    }
+}
+
+impl<K> ViewElement for FrameStepper<K> {
+    fn active_pixels(&self) -> Vec<Pixel> {
+        if !self.paused {
+            return vec![];
+        }
+
+        (0..)
+            .zip(format!("PAUSED tick {}", self.tick).chars())
+            .map(|(x, text_char)| {
+                Pixel::new(
+                    self.pos + Vec2D::new(x, 0),
+                    ColChar::new(text_char, self.modifier),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_one_tick_per_press_while_paused() {
+        let mut stepper = FrameStepper::new('p', 's');
+        assert_eq!(stepper.intercept('s'), Some('s'));
+        assert!(stepper.should_update());
+
+        assert_eq!(stepper.intercept('p'), None);
+        assert!(!stepper.should_update());
+        stepper.intercept('s');
+        stepper.intercept('s');
+        assert!(stepper.should_update());
+        assert!(stepper.should_update());
+        assert!(!stepper.should_update());
+        assert_eq!(stepper.tick(), 3);
+
+        stepper.intercept('p');
+        assert!(stepper.should_update());
+        assert_eq!(stepper.tick(), 4);
+    }
+
+    #[test]
+    fn shows_tick_count_while_paused() {
+        let mut stepper = FrameStepper::new('p', 's').with_pos(Vec2D::new(2, 1));
+        stepper.should_update();
+        assert!(stepper.active_pixels().is_empty());
+
+        stepper.intercept('p');
+        let text: String = stepper
+            .active_pixels()
+            .iter()
+            .map(|pixel| pixel.fill_char.text_char)
+            .collect();
+        assert_eq!(text, "PAUSED tick 1");
+        assert_eq!(stepper.active_pixels()[0].pos, Vec2D::new(2, 1));
+    }
+}
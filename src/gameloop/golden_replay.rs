@@ -0,0 +1,218 @@
+//! Regression testing whole games by replaying recorded inputs. Read the [`GoldenReplay`] documentation for more info
+
+use super::headless::{Headless, HeadlessRoot};
+use crate::EngineError;
+
+/// A `GoldenReplay` is a recording of a game's inputs along with the [`canvas_hash()`](crate::elements::View::canvas_hash()) of the frame they should end on. Playing it back through a [`Headless`] simulation of the game and comparing the final frame catches changes anywhere in the stack, from game logic and physics to drawing and colours. Record the inputs with a fixed seed for anything random, run the replay once to get the hash, then keep the replay in a test
+/// ```
+/// use gemini_engine::{
+///     elements::{view::{ColChar, Wrapping}, Pixel, Vec2D, View},
+///     gameloop::{golden_replay::GoldenReplay, headless::{Headless, HeadlessRoot}, MainLoopRoot},
+/// };
+///
+/// struct Game {
+///     player: Vec2D,
+/// }
+///
+/// impl MainLoopRoot for Game {
+///     type InputDataType = isize;
+///
+///     fn frame(&mut self, input_data: Option<isize>) {
+///         self.player.x += input_data.unwrap_or(0);
+///     }
+///
+///     fn render_frame(&mut self) {}
+/// }
+///
+/// impl HeadlessRoot for Game {
+///     fn draw(&self, view: &mut View) {
+///         view.blit(&Pixel::new(self.player, ColChar::SOLID), Wrapping::Wrap);
+///     }
+/// }
+///
+/// let new_game = || Headless::new(Game { player: Vec2D::ZERO }, View::new(8, 1, ColChar::EMPTY));
+/// let mut replay = GoldenReplay::new(vec![Some(1), None, Some(2)], 0);
+///
+/// // The first run records the hash of the final frame
+/// replay.expected_hash = replay.play(&mut new_game());
+///
+/// assert_eq!(replay.verify(&mut new_game()), Ok(()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoldenReplay<I> {
+    /// The input for every tick of the replay, in order
+    pub inputs: Vec<Option<I>>,
+    /// The hash of the frame drawn after the last tick
+    pub expected_hash: u64,
+}
+
+impl<I: Clone> GoldenReplay<I> {
+    /// Create a new `GoldenReplay` from the recorded inputs and the hash of the frame they should end on
+    #[must_use]
+    pub const fn new(inputs: Vec<Option<I>>, expected_hash: u64) -> Self {
+        Self {
+            inputs,
+            expected_hash,
+        }
+    }
+
+    /// Run every input through the simulation, then draw the final frame and return its hash
+    pub fn play<R: HeadlessRoot<InputDataType = I>>(&self, sim: &mut Headless<R>) -> u64 {
+        sim.run(self.inputs.iter().cloned());
+        sim.observe().canvas_hash()
+    }
+
+    /// Play the replay through the simulation and check that it ends on the expected frame
+    ///
+    /// # Errors
+    /// Returns [`EngineError::HashMismatch`] with the hash that was drawn if it doesn't match the expected hash. If the change was intended, use the new hash as the [`expected_hash`](GoldenReplay::expected_hash)
+    pub fn verify<R: HeadlessRoot<InputDataType = I>>(
+        &self,
+        sim: &mut Headless<R>,
+    ) -> Result<(), EngineError> {
+        let hash = self.play(sim);
+        if hash == self.expected_hash {
+            Ok(())
+        } else {
+            Err(EngineError::HashMismatch {
+                expected: self.expected_hash,
+                found: hash,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        elements::{
+            view::{ColChar, Colour, Modifier, Wrapping},
+            Rect, Sprite, Text, Vec2D, View,
+        },
+        gameloop::MainLoopRoot,
+        procgen::Rng,
+    };
+
+    /// The blocks from the `multi-movement` example, each moving at half the speed of the one above
+    struct MultiMovement {
+        blocks: Vec<Vec2D>,
+        tick: u32,
+    }
+
+    impl MainLoopRoot for MultiMovement {
+        type InputDataType = ();
+
+        fn frame(&mut self, _input_data: Option<()>) {
+            self.tick += 1;
+            for (j, block) in (0u32..).zip(self.blocks.iter_mut()) {
+                if self.tick.is_multiple_of(2u32.pow(j)) {
+                    block.x += 1;
+                }
+            }
+        }
+
+        fn render_frame(&mut self) {}
+    }
+
+    impl HeadlessRoot for MultiMovement {
+        fn draw(&self, view: &mut View) {
+            for block in &self.blocks {
+                view.blit(
+                    &Rect::new(*block, Vec2D::new(4, 2), ColChar::SOLID),
+                    Wrapping::Wrap,
+                );
+            }
+        }
+    }
+
+    /// A player steered by input, dodging randomly placed rocks, with a coloured score
+    struct Dodger {
+        player: Vec2D,
+        rocks: Vec<Vec2D>,
+        score: u32,
+        rng: Rng,
+    }
+
+    impl MainLoopRoot for Dodger {
+        type InputDataType = isize;
+
+        fn frame(&mut self, input_data: Option<isize>) {
+            self.player.x = (self.player.x + input_data.unwrap_or(0)).clamp(0, 19);
+            for rock in &mut self.rocks {
+                rock.y += 1;
+                if rock.y > 9 {
+                    *rock = Vec2D::new(self.rng.range(0, 19), 0);
+                    self.score += 1;
+                }
+            }
+            if self.rocks.len() < 4 && self.rng.chance(0.3) {
+                self.rocks.push(Vec2D::new(self.rng.range(0, 19), 0));
+            }
+        }
+
+        fn render_frame(&mut self) {}
+    }
+
+    impl HeadlessRoot for Dodger {
+        fn draw(&self, view: &mut View) {
+            for rock in &self.rocks {
+                view.blit(
+                    &Sprite::new(*rock, "o", Modifier::Colour(Colour::rgb(128, 96, 64))),
+                    Wrapping::Ignore,
+                );
+            }
+            view.blit(
+                &Sprite::new(self.player, "A", Modifier::CYAN),
+                Wrapping::Ignore,
+            );
+            view.blit(
+                &Text::new(
+                    Vec2D::ZERO,
+                    &format!("Score: {}", self.score),
+                    Modifier::YELLOW,
+                ),
+                Wrapping::Ignore,
+            );
+        }
+    }
+
+    #[test]
+    fn multi_movement_replay() {
+        let mut sim = Headless::new(
+            MultiMovement {
+                blocks: (0..6).map(|y| Vec2D::new(0, y * 2)).collect(),
+                tick: 0,
+            },
+            View::new(50, 12, ColChar::BACKGROUND),
+        );
+        let replay = GoldenReplay::new(vec![None; 75], 0xc74f_a851_c1e1_4b73);
+
+        assert_eq!(replay.verify(&mut sim), Ok(()));
+    }
+
+    #[test]
+    fn dodger_replay() {
+        let mut sim = Headless::new(
+            Dodger {
+                player: Vec2D::new(10, 9),
+                rocks: vec![],
+                score: 0,
+                rng: Rng::new(42),
+            },
+            View::new(20, 10, ColChar::EMPTY),
+        );
+        let inputs = [Some(-1), None, Some(1), Some(1), None, Some(-1)]
+            .into_iter()
+            .cycle()
+            .take(60)
+            .collect();
+        let replay = GoldenReplay::new(inputs, 0xba85_9e69_2337_24b8);
+
+        assert_eq!(replay.verify(&mut sim), Ok(()));
+        assert!(sim.root().score > 0);
+
+        // Playing on from the end of the replay draws a different frame
+        assert!(replay.verify(&mut sim).is_err());
+    }
+}
@@ -0,0 +1,204 @@
+//! Rumble and LED feedback for gamepads. Read the [`Haptics`] documentation for more info
+
+use std::time::Duration;
+
+use crate::elements::view::Colour;
+
+/// The gamepad library that [`Haptics`] sends its rumble and LED changes to
+pub trait HapticsBackend {
+    /// Set the strength of the strong (low frequency) and weak (high frequency) rumble motors, from 0.0 to 1.0
+    fn set_rumble(&mut self, strong: f64, weak: f64);
+
+    /// Set the colour of the gamepad's LED. Return false if the gamepad doesn't have one, which is the default
+    fn set_led(&mut self, _colour: Colour) -> bool {
+        false
+    }
+}
+
+/// A rumble that plays for a set time
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rumble {
+    strong: f64,
+    weak: f64,
+    remaining: Duration,
+}
+
+/// `Haptics` is a handle for gamepad feedback beyond the screen: timed rumbles on the strong and weak motors and the LED colour, for gamepads that have them. Overlapping rumbles play together, with each motor running at the strongest of them, and [`update()`](Haptics::update()) only sends the backend a new strength when it changes. For rumbles that follow a curve, such as an engine revving, pass the curve's value to [`hold()`](Haptics::hold()) every frame
+/// ```
+/// use gemini_engine::gameloop::{haptics::{Haptics, HapticsBackend}, Duration};
+///
+/// struct Pad {
+///     motors: (f64, f64),
+/// }
+/// impl HapticsBackend for Pad {
+///     fn set_rumble(&mut self, strong: f64, weak: f64) {
+///         self.motors = (strong, weak);
+///     }
+/// }
+///
+/// let mut haptics = Haptics::new(Pad { motors: (0.0, 0.0) });
+/// haptics.rumble(0.8, Duration::from_millis(200));
+///
+/// haptics.update(Duration::from_millis(100));
+/// assert_eq!(haptics.backend().motors, (0.8, 0.8));
+///
+/// haptics.update(Duration::from_millis(100));
+/// assert_eq!(haptics.backend().motors, (0.0, 0.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Haptics<B: HapticsBackend> {
+    backend: B,
+    rumbles: Vec<Rumble>,
+    held: (f64, f64),
+    sent: (f64, f64),
+    led: Option<Colour>,
+}
+
+impl<B: HapticsBackend> Haptics<B> {
+    /// Create a new `Haptics` that sends its feedback to the backend
+    #[must_use]
+    pub const fn new(backend: B) -> Self {
+        Self {
+            backend,
+            rumbles: vec![],
+            held: (0.0, 0.0),
+            sent: (0.0, 0.0),
+            led: None,
+        }
+    }
+
+    /// Rumble both motors at the strength, from 0.0 to 1.0, for the duration
+    pub fn rumble(&mut self, strength: f64, duration: Duration) {
+        self.rumble_motors(strength, strength, duration);
+    }
+
+    /// Rumble the strong (low frequency) and weak (high frequency) motors at different strengths, from 0.0 to 1.0, for the duration. A heavy impact leans on the strong motor, while footsteps or a ticking dial suit the weak one
+    pub fn rumble_motors(&mut self, strong: f64, weak: f64, duration: Duration) {
+        if !duration.is_zero() {
+            self.rumbles.push(Rumble {
+                strong: strong.clamp(0.0, 1.0),
+                weak: weak.clamp(0.0, 1.0),
+                remaining: duration,
+            });
+        }
+    }
+
+    /// Keep the motors rumbling at least this strongly until `hold` is called again, such as with the current value of an animation curve every frame. Pass 0.0 to let go
+    pub const fn hold(&mut self, strong: f64, weak: f64) {
+        self.held = (strong.clamp(0.0, 1.0), weak.clamp(0.0, 1.0));
+    }
+
+    /// Stop every timed and held rumble. The motors stop on the next [`update()`](Haptics::update())
+    pub fn stop(&mut self) {
+        self.rumbles.clear();
+        self.held = (0.0, 0.0);
+    }
+
+    /// Return the strength of the strong and weak motors as of the last [`update()`](Haptics::update())
+    #[must_use]
+    pub const fn strength(&self) -> (f64, f64) {
+        self.sent
+    }
+
+    /// Set the colour of the gamepad's LED, such as to show the player's health or team. Returns false if the backend doesn't support LEDs
+    pub fn set_led(&mut self, colour: Colour) -> bool {
+        let supported = self.backend.set_led(colour);
+        if supported {
+            self.led = Some(colour);
+        }
+
+        supported
+    }
+
+    /// Return the colour the LED was last set to, or `None` if it hasn't been set or the backend doesn't support LEDs
+    #[must_use]
+    pub const fn led(&self) -> Option<Colour> {
+        self.led
+    }
+
+    /// Advance the timed rumbles by the time since the last update and send the combined strength to the backend if it has changed. Call this once every frame
+    pub fn update(&mut self, delta: Duration) {
+        let mut strength = self.held;
+        for rumble in &mut self.rumbles {
+            if rumble.remaining > delta {
+                strength.0 = strength.0.max(rumble.strong);
+                strength.1 = strength.1.max(rumble.weak);
+            }
+            rumble.remaining = rumble.remaining.saturating_sub(delta);
+        }
+        self.rumbles.retain(|rumble| !rumble.remaining.is_zero());
+
+        if strength != self.sent {
+            self.backend.set_rumble(strength.0, strength.1);
+            self.sent = strength;
+        }
+    }
+
+    /// Return a reference to the backend
+    #[must_use]
+    pub const fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Return a mutable reference to the backend
+    #[must_use]
+    pub const fn backend_mut(&mut self) -> &mut B {
+        &mut self.backend
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Pad {
+        calls: Vec<(f64, f64)>,
+        has_led: bool,
+    }
+
+    impl HapticsBackend for Pad {
+        fn set_rumble(&mut self, strong: f64, weak: f64) {
+            self.calls.push((strong, weak));
+        }
+
+        fn set_led(&mut self, _colour: Colour) -> bool {
+            self.has_led
+        }
+    }
+
+    #[test]
+    fn overlapping_rumbles_use_strongest_motor() {
+        let frame = Duration::from_millis(50);
+        let mut haptics = Haptics::new(Pad::default());
+        haptics.rumble_motors(1.0, 0.0, frame * 2);
+        haptics.rumble_motors(0.2, 0.6, frame * 4);
+
+        haptics.update(frame);
+        haptics.update(frame);
+        assert_eq!(haptics.strength(), (0.2, 0.6));
+        haptics.update(frame);
+        haptics.update(frame);
+        assert_eq!(
+            haptics.backend().calls,
+            vec![(1.0, 0.6), (0.2, 0.6), (0.0, 0.0)]
+        );
+
+        haptics.hold(0.5, 0.5);
+        haptics.rumble(0.9, frame);
+        haptics.stop();
+        haptics.update(frame);
+        assert_eq!(haptics.strength(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn led_only_set_when_supported() {
+        let mut haptics = Haptics::new(Pad::default());
+        assert!(!haptics.set_led(Colour::rgb(255, 0, 0)));
+        assert_eq!(haptics.led(), None);
+
+        haptics.backend_mut().has_led = true;
+        assert!(haptics.set_led(Colour::rgb(255, 0, 0)));
+        assert_eq!(haptics.led(), Some(Colour::rgb(255, 0, 0)));
+    }
+}
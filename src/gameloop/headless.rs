@@ -0,0 +1,197 @@
+//! Running a game's simulation without a terminal, for training bots and running tests in CI. Read the [`Headless`] documentation for more info
+
+use super::{
+    world_state::{EntityState, WorldState},
+    MainLoopRoot,
+};
+use crate::elements::View;
+
+/// A [`MainLoopRoot`] that can draw itself to any [`View`], so it can be observed while running [`Headless`]ly. Call [`draw()`](HeadlessRoot::draw()) from your [`render_frame()`](MainLoopRoot::render_frame()) so the game looks the same either way
+pub trait HeadlessRoot: MainLoopRoot {
+    /// Blit everything in the game to the `View`, which has already been cleared. Don't display the `View` here
+    fn draw(&self, view: &mut View);
+
+    /// Return the entities in the game for a [`WorldState`] snapshot, such as the player and enemies. Returns no entities by default
+    fn entities(&self) -> Vec<EntityState> {
+        vec![]
+    }
+}
+
+/// `Headless` runs a game's update loop as fast as possible with no rendering to the terminal, no sleeping and scripted input. Use [`step()`](Headless::step()) to advance one tick at a time and observe the result through the game's [`View`] canvas, such as when training a bot, or [`run()`](Headless::run()) to simulate many ticks without drawing at all
+/// ```
+/// use gemini_engine::{
+///     elements::{view::{ColChar, Wrapping}, Pixel, Vec2D, View},
+///     gameloop::{headless::{Headless, HeadlessRoot}, MainLoopRoot},
+/// };
+///
+/// struct Game {
+///     player: Vec2D,
+/// }
+///
+/// impl MainLoopRoot for Game {
+///     type InputDataType = isize;
+///
+///     fn frame(&mut self, input_data: Option<isize>) {
+///         self.player.x += input_data.unwrap_or(0);
+///     }
+///
+///     fn render_frame(&mut self) {
+///         // --draw to your own View and display it here--
+///     }
+/// }
+///
+/// impl HeadlessRoot for Game {
+///     fn draw(&self, view: &mut View) {
+///         view.blit(&Pixel::new(self.player, ColChar::SOLID), Wrapping::Ignore);
+///     }
+/// }
+///
+/// let mut sim = Headless::new(Game { player: Vec2D::ZERO }, View::new(5, 1, ColChar::EMPTY));
+/// sim.run([Some(1), None, Some(1)]);
+/// let observation = sim.step(Some(1));
+///
+/// assert_eq!(observation.cells()[3], ColChar::SOLID);
+/// assert_eq!(sim.tick(), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Headless<R: HeadlessRoot> {
+    root: R,
+    view: View,
+    tick: u64,
+}
+
+impl<R: HeadlessRoot> Headless<R> {
+    /// Create a new `Headless` simulation of the game, observed through the `View`
+    #[must_use]
+    pub const fn new(root: R, view: View) -> Self {
+        Self {
+            root,
+            view,
+            tick: 0,
+        }
+    }
+
+    /// Run one tick of the game with the input, then draw and return the game's `View`
+    pub fn step(&mut self, input: Option<R::InputDataType>) -> &View {
+        self.root.frame(input);
+        self.tick += 1;
+        self.observe()
+    }
+
+    /// Run one tick of the game for every input without drawing anything, returning the number of ticks run
+    pub fn run(&mut self, inputs: impl IntoIterator<Item = Option<R::InputDataType>>) -> u64 {
+        let start = self.tick;
+        for input in inputs {
+            self.root.frame(input);
+            self.tick += 1;
+        }
+
+        self.tick - start
+    }
+
+    /// Run ticks of the game with no input until `done` returns true, up to `max_ticks` ticks. Returns the number of ticks run
+    pub fn run_until(&mut self, max_ticks: u64, mut done: impl FnMut(&R) -> bool) -> u64 {
+        let start = self.tick;
+        while self.tick - start < max_ticks && !done(&self.root) {
+            self.root.frame(None);
+            self.tick += 1;
+        }
+
+        self.tick - start
+    }
+
+    /// Draw the game's current state to its `View` without running a tick, and return the `View`
+    pub fn observe(&mut self) -> &View {
+        self.view.clear();
+        self.root.draw(&mut self.view);
+        &self.view
+    }
+
+    /// Draw the game's current state and return it as a [`WorldState`], for sending to an external agent
+    pub fn world_state(&mut self) -> WorldState {
+        self.observe();
+        WorldState::from_view(self.tick, &self.view).with_entities(self.root.entities())
+    }
+
+    /// Return the number of ticks run so far
+    #[must_use]
+    pub const fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Return the game being simulated
+    #[must_use]
+    pub const fn root(&self) -> &R {
+        &self.root
+    }
+
+    /// Return the game being simulated, mutably, such as to reset it between training episodes
+    pub const fn root_mut(&mut self) -> &mut R {
+        &mut self.root
+    }
+
+    /// Return the game being simulated, ending the simulation
+    #[must_use]
+    pub fn into_root(self) -> R {
+        self.root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{
+        view::{ColChar, Wrapping},
+        Pixel, Vec2D,
+    };
+
+    struct Counter {
+        count: usize,
+    }
+
+    impl MainLoopRoot for Counter {
+        type InputDataType = usize;
+
+        fn frame(&mut self, input_data: Option<usize>) {
+            self.count += input_data.unwrap_or(1);
+        }
+
+        fn render_frame(&mut self) {
+            unreachable!("headless simulations never render");
+        }
+    }
+
+    impl HeadlessRoot for Counter {
+        fn draw(&self, view: &mut View) {
+            view.blit(
+                &Pixel::new(Vec2D::new(self.count as isize, 0), ColChar::SOLID),
+                Wrapping::Wrap,
+            );
+        }
+    }
+
+    #[test]
+    fn runs_and_observes_without_rendering() {
+        let mut sim = Headless::new(Counter { count: 0 }, View::new(4, 1, ColChar::EMPTY));
+
+        assert_eq!(sim.run_until(100, |counter| counter.count >= 10), 10);
+        assert_eq!(sim.run([Some(2), None]), 2);
+        assert_eq!(sim.root().count, 13);
+
+        let view = sim.step(None);
+        assert_eq!(view.get(Vec2D::new(2, 0)), Some(ColChar::SOLID));
+        assert_eq!(
+            view.cells()
+                .iter()
+                .filter(|c| **c == ColChar::SOLID)
+                .count(),
+            1
+        );
+        assert_eq!(sim.tick(), 13);
+
+        let state = sim.world_state();
+        assert_eq!(state.tick, 13);
+        assert!(state.entities.is_empty());
+        assert_eq!(state.grid[0][2], (u32::from('█'), 0));
+    }
+}
@@ -0,0 +1,12 @@
+//! The escape sequences most terminals send for the function keys, used as the default hotkeys of engine debugging tools such as [`FrameStepper`](super::FrameStepper) (F9 and F10) and [`CaptureHotkeys`](super::CaptureHotkeys) (F11 and F12)
+//!
+//! Those tools accept keys of any type your input handling uses, so pass your own keys to their `new()` functions if you don't read raw escape sequences, or if the defaults clash with your game's controls
+
+/// The escape sequence most terminals send for F9
+pub const F9: &str = "\x1b[20~";
+/// The escape sequence most terminals send for F10
+pub const F10: &str = "\x1b[21~";
+/// The escape sequence most terminals send for F11
+pub const F11: &str = "\x1b[23~";
+/// The escape sequence most terminals send for F12
+pub const F12: &str = "\x1b[24~";
@@ -59,8 +59,30 @@
 ///         );
 ///     }
 /// );
+/// ```
+/// To use a [`GameClock`](crate::gameloop::GameClock), name it before the logic block. It is ticked at the start of every frame and can be used in all of the blocks, such as to pause the simulation while the UI keeps animating:
+/// ```rust,no_run
+/// # use gemini_engine::fps_gameloop;
+/// let mut x = 0.0;
+/// fps_gameloop!(
+///     clock => {
+///         clock.set_paused(x > 100.0);
+///         x += 20.0 * clock.sim.delta.as_secs_f64();
+///     },
+///     {
+///         println!("{x:.1} after {:.1?}", clock.real.elapsed);
+///     },
+///     30.0
+/// );
 #[macro_export]
 macro_rules! fps_gameloop {
+    ($clock:ident => $logic:block, $render:block, $fps:expr) => {
+        fps_gameloop!($clock => $logic, $render, $fps, |_, _| ());
+    };
+    ($clock:ident => $logic:block, $render:block, $fps:expr, $handle_elapsed:expr) => {
+        let mut $clock = $crate::gameloop::GameClock::new();
+        fps_gameloop!({ $clock.tick(); $logic }, $render, $fps, $handle_elapsed);
+    };
     ($logic:block, $render:block, $fps:expr) => {
         fps_gameloop!($logic, $render, $fps, |_, _| ());
     };
@@ -87,3 +109,19 @@ macro_rules! fps_gameloop {
         }
     };
 }
+
+/// Return the current value of a live-editable tuning variable, registering it with the default value the first time it's used. The value can then be changed while the game runs from a dev console or a watched file without recompiling, as explained in the [`tweaks`](crate::gameloop::tweaks) documentation. The returned value has the same type as the default
+/// ```
+/// use gemini_engine::{gameloop::tweaks, tweak};
+///
+/// assert_eq!(tweak!("player.speed", 12.0), 12.0);
+///
+/// tweaks::apply_command("player.speed 14").unwrap();
+/// assert_eq!(tweak!("player.speed", 12.0), 14.0);
+/// ```
+#[macro_export]
+macro_rules! tweak {
+    ($name:expr, $default:expr) => {
+        $crate::gameloop::tweaks::get_or_register($name, $default)
+    };
+}
@@ -0,0 +1,167 @@
+//! A multi-threaded alternative to [`MainLoopRoot`](super::MainLoopRoot) which rasterises and prints each frame on a separate thread while the next frame is being updated. Read the [`PipelinedRoot`] documentation for more info
+
+use std::{
+    sync::mpsc::{self, SyncSender},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// A thread that renders scenes sent to it with [`RenderThread::submit()`]. The thread holds at most one scene waiting to be rendered alongside the one it is currently rendering, so the thread submitting scenes can stay at most one frame ahead. Dropping the `RenderThread` waits for every submitted scene to finish rendering
+#[derive(Debug)]
+pub struct RenderThread<S: Send + 'static> {
+    sender: Option<SyncSender<S>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl<S: Send + 'static> RenderThread<S> {
+    /// Spawn a new render thread, which calls `render` on every submitted scene in order
+    pub fn spawn<F: FnMut(S) + Send + 'static>(mut render: F) -> Self {
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let handle = thread::spawn(move || {
+            for scene in receiver {
+                engine_span!("render_scene");
+                render(scene);
+            }
+        });
+
+        Self {
+            sender: Some(sender),
+            handle: Some(handle),
+        }
+    }
+
+    /// Send a scene to be rendered. Blocks if there is already a scene waiting to be rendered. Returns false if the render thread has stopped (for example because the render function panicked), in which case the scene is dropped
+    pub fn submit(&self, scene: S) -> bool {
+        self.sender
+            .as_ref()
+            .is_some_and(|sender| sender.send(scene).is_ok())
+    }
+}
+
+impl<S: Send + 'static> Drop for RenderThread<S> {
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// `PipelinedRoot` works like [`MainLoopRoot`](super::MainLoopRoot), except that rendering happens on a separate [`RenderThread`]. Every frame, [`frame()`](PipelinedRoot::frame()) updates the game and [`scene()`](PipelinedRoot::scene()) takes a snapshot of everything that needs to be drawn. The snapshot is then handed off to the render thread, which blits and prints it while the main thread moves on to updating the next frame. For CPU-bound games on multi-core machines this can nearly double throughput
+///
+/// The scene should hold everything needed to draw the frame (for example the game's elements or a [`PixelContainer`](crate::elements::PixelContainer)), while the renderer returned by [`renderer()`](PipelinedRoot::renderer()) owns the [`View`](crate::elements::View) it blits to
+///
+/// # Example
+/// ```rust,no_run
+/// use gemini_engine::elements::{view::{ColChar, Wrapping}, Pixel, Vec2D, View};
+/// use gemini_engine::gameloop::PipelinedRoot;
+///
+/// struct Game {
+///     pixel: Pixel,
+/// }
+///
+/// impl PipelinedRoot for Game {
+///     type InputDataType = bool;
+///     type Scene = Pixel;
+///
+///     fn frame(&mut self, _input_data: Option<bool>) {
+///         self.pixel.pos.x += 1;
+///     }
+///
+///     fn scene(&self) -> Pixel {
+///         self.pixel
+///     }
+///
+///     fn renderer(&mut self) -> Box<dyn FnMut(Pixel) + Send> {
+///         let mut view = View::new(40, 8, ColChar::BACKGROUND);
+///         Box::new(move |pixel| {
+///             view.clear();
+///             view.blit(&pixel, Wrapping::Wrap);
+///             let _ = view.display_render();
+///         })
+///     }
+/// }
+///
+/// let mut game = Game { pixel: Pixel::new(Vec2D::new(10, 5), ColChar::SOLID) };
+/// game.main_loop(30.0);
+/// ```
+pub trait PipelinedRoot {
+    /// This type should be generated by [`PipelinedRoot::sleep_and_get_input_data()`] and will be passed to [`PipelinedRoot::frame()`]
+    type InputDataType;
+    /// A snapshot of everything that needs to be drawn for a frame, sent to the render thread
+    type Scene: Send + 'static;
+
+    /// This is where the main logic of your game - handling input, moving objects, handling collisions, etc.
+    fn frame(&mut self, input_data: Option<Self::InputDataType>);
+
+    /// Return a snapshot of the game for the render thread to draw
+    fn scene(&self) -> Self::Scene;
+
+    /// Return the function which will draw every scene on the render thread. This is called once, at the start of [`main_loop()`](PipelinedRoot::main_loop())
+    fn renderer(&mut self) -> Box<dyn FnMut(Self::Scene) + Send>;
+
+    /// The function used to sleep for the appropriate amount based on the FPS. Uses [`gameloop::sleep_fps`](super::sleep_fps()) by default and will return None for the `InputDataType`. The returned bool value should represent whether or not to skip rendering on the next frame
+    fn sleep_and_get_input_data(
+        &self,
+        fps: f32,
+        elapsed: Duration,
+    ) -> (bool, Option<Self::InputDataType>) {
+        (super::sleep_fps(fps, Some(elapsed)), None)
+    }
+
+    /// The main loop function of the pipelined root. This shouldnt be overriden. Spawns the render thread and then updates the game and submits a scene every frame. Returns if the render thread stops
+    fn main_loop(&mut self, fps: f32) {
+        let render_thread = RenderThread::spawn(self.renderer());
+        let mut elapsed = Duration::ZERO;
+
+        loop {
+            let (frame_skip, input_data) = {
+                engine_span!("input");
+                self.sleep_and_get_input_data(fps, elapsed)
+            };
+            let now = Instant::now();
+            engine_span!("loop", frame_skip);
+
+            {
+                engine_span!("frame");
+                self.frame(input_data);
+            }
+
+            if !frame_skip {
+                engine_span!("submit_scene");
+                if !render_thread.submit(self.scene()) {
+                    break;
+                }
+            }
+
+            elapsed = now.elapsed();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn renders_every_scene_in_order() {
+        let rendered = Arc::new(Mutex::new(vec![]));
+
+        let render_thread = {
+            let rendered = Arc::clone(&rendered);
+            RenderThread::spawn(move |scene: u32| {
+                rendered.lock().expect("Lock poisoned").push(scene);
+            })
+        };
+        for scene in 0..10 {
+            assert!(render_thread.submit(scene));
+        }
+        drop(render_thread);
+
+        assert_eq!(
+            *rendered.lock().expect("Lock poisoned"),
+            (0..10).collect::<Vec<u32>>()
+        );
+    }
+}
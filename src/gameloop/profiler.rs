@@ -0,0 +1,204 @@
+//! Timing the systems that make up each frame and saving them for frame timeline viewers. Read the [`Profiler`] documentation for more info
+
+use crate::utils::json_string;
+use std::{
+    fmt::Write as _,
+    fs, io,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// A timed section of a frame recorded by a [`Profiler`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    /// The name the span was started with
+    pub name: String,
+    /// When the span started, relative to when the `Profiler` was created
+    pub start: Duration,
+    /// How long the span lasted
+    pub duration: Duration,
+    /// How many other spans were open when this one started
+    pub depth: usize,
+}
+
+/// `Profiler` records how long each part of a frame takes as named, nestable spans. Wrap each system of your game in [`begin()`](Profiler::begin()) and [`end()`](Profiler::end()) calls, or in [`time()`](Profiler::time()), then write the spans out with [`save_chrome_trace()`](Profiler::save_chrome_trace()) and open the file in `chrome://tracing`, [Perfetto](https://ui.perfetto.dev) or [Speedscope](https://www.speedscope.app) to find out which system caused a frame spike
+///
+/// Recording can be turned off with [`enabled`](Profiler::enabled), so the calls can stay in release builds at almost no cost
+/// ```
+/// use gemini_engine::gameloop::{profiler::Profiler, Duration};
+///
+/// let mut profiler = Profiler::new();
+///
+/// profiler.begin("frame");
+/// let score = profiler.time("physics", || 40 + 2);
+/// profiler.begin("render");
+/// profiler.end();
+/// profiler.end();
+///
+/// assert_eq!(score, 42);
+/// assert_eq!(profiler.spans().len(), 3);
+/// assert!(profiler.chrome_trace().contains(r#""name":"physics""#));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Profiler {
+    /// Whether spans are being recorded. Defaults to true
+    pub enabled: bool,
+    origin: Instant,
+    open: Vec<(String, Instant)>,
+    spans: Vec<Span>,
+}
+
+impl Profiler {
+    /// Create a new `Profiler` with no spans recorded
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            origin: Instant::now(),
+            open: vec![],
+            spans: vec![],
+        }
+    }
+
+    /// Start a span, which lasts until the matching call to [`end()`](Profiler::end()). Spans started before that one ends are nested inside it
+    pub fn begin(&mut self, name: impl Into<String>) {
+        if self.enabled {
+            self.open.push((name.into(), Instant::now()));
+        }
+    }
+
+    /// End the most recently started span and return how long it lasted, or `None` if no span is open
+    pub fn end(&mut self) -> Option<Duration> {
+        let (name, started) = self.open.pop()?;
+        let duration = started.elapsed();
+        self.record(
+            name,
+            started.duration_since(self.origin),
+            duration,
+            self.open.len(),
+        );
+
+        Some(duration)
+    }
+
+    /// Run the function inside a span with the given name and return its result
+    pub fn time<T>(&mut self, name: impl Into<String>, f: impl FnOnce() -> T) -> T {
+        self.begin(name);
+        let result = f();
+        if self.enabled {
+            self.end();
+        }
+        result
+    }
+
+    /// Record a span that was timed elsewhere, such as on another thread. `start` is relative to when the `Profiler` was created
+    pub fn record(
+        &mut self,
+        name: impl Into<String>,
+        start: Duration,
+        duration: Duration,
+        depth: usize,
+    ) {
+        if self.enabled {
+            self.spans.push(Span {
+                name: name.into(),
+                start,
+                duration,
+                depth,
+            });
+        }
+    }
+
+    /// Return every finished span, in the order they ended
+    #[must_use]
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    /// Remove every finished span, for example after saving them
+    pub fn clear(&mut self) {
+        self.spans.clear();
+    }
+
+    /// Return the finished spans as a Chrome trace event JSON document, with a complete (`"X"`) event for each span
+    #[must_use]
+    pub fn chrome_trace(&self) -> String {
+        let mut json = String::from(r#"{"displayTimeUnit":"ms","traceEvents":["#);
+
+        for (i, span) in self.spans.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            let _ = write!(
+                json,
+                r#"{{"name":{},"cat":"gemini","ph":"X","ts":{},"dur":{},"pid":1,"tid":1,"args":{{"depth":{}}}}}"#,
+                json_string(&span.name),
+                span.start.as_micros(),
+                span.duration.as_micros(),
+                span.depth
+            );
+        }
+        json.push_str("]}");
+
+        json
+    }
+
+    /// Write the finished spans to a file as a Chrome trace, replacing it if it already exists
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be written to
+    pub fn save_chrome_trace(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.chrome_trace())
+    }
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_chrome_trace_events() {
+        let mut profiler = Profiler::new();
+        profiler.record(
+            "draw \"sky\"",
+            Duration::from_micros(1500),
+            Duration::from_micros(250),
+            1,
+        );
+        profiler.record("input", Duration::ZERO, Duration::from_micros(20), 0);
+
+        assert_eq!(
+            profiler.chrome_trace(),
+            concat!(
+                r#"{"displayTimeUnit":"ms","traceEvents":["#,
+                r#"{"name":"draw \"sky\"","cat":"gemini","ph":"X","ts":1500,"dur":250,"pid":1,"tid":1,"args":{"depth":1}},"#,
+                r#"{"name":"input","cat":"gemini","ph":"X","ts":0,"dur":20,"pid":1,"tid":1,"args":{"depth":0}}]}"#
+            )
+        );
+    }
+
+    #[test]
+    fn nests_and_can_be_disabled() {
+        let mut profiler = Profiler::new();
+        profiler.begin("frame");
+        profiler.time("update", || ());
+        assert!(profiler.end().is_some());
+        assert_eq!(profiler.end(), None);
+        assert_eq!(profiler.spans()[0].name, "update");
+        assert_eq!(profiler.spans()[0].depth, 1);
+        assert_eq!(profiler.spans()[1].depth, 0);
+
+        profiler.clear();
+        profiler.enabled = false;
+        profiler.begin("frame");
+        assert_eq!(profiler.time("update", || 5), 5);
+        assert_eq!(profiler.end(), None);
+        assert!(profiler.spans().is_empty());
+    }
+}
@@ -0,0 +1,216 @@
+//! A history of snapshots of game state for rewinding time. Read the [`Rewind`] documentation for more info
+
+use std::{collections::VecDeque, time::Duration};
+
+/// Estimates the memory used by one snapshot, in bytes
+type SizeFn<T> = fn(&T) -> usize;
+
+/// `Rewind` keeps snapshots of chosen game state, taken every few frames, for rewinding time in Braid-style mechanics or undoing moves in puzzle games. Snapshots older than the history length are dropped, and a memory budget can be set to limit how much the snapshots use. Call [`record()`](Rewind::record()) every frame with the state and the time since the last frame, such as the [`SimTime`](super::clock::SimTime) delta
+/// ```
+/// use gemini_engine::gameloop::Rewind;
+/// use std::time::Duration;
+///
+/// let frame = Duration::from_millis(100);
+/// let mut rewind = Rewind::new(1, Duration::from_secs(5));
+///
+/// let mut player_x = 0;
+/// for _ in 0..30 {
+///     player_x += 1;
+///     rewind.record(&player_x, frame);
+/// }
+///
+/// // Roll back one second
+/// player_x = rewind.rewind(Duration::from_secs(1)).expect("there are snapshots");
+/// assert_eq!(player_x, 20);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rewind<T> {
+    /// How many frames pass between snapshots
+    pub interval: u32,
+    /// How much time to keep snapshots for
+    pub history: Duration,
+    snapshots: VecDeque<(Duration, T)>,
+    elapsed: Duration,
+    frame: u64,
+    budget: Option<(usize, SizeFn<T>)>,
+    memory_used: usize,
+}
+
+impl<T: Clone> Rewind<T> {
+    /// Create a new, empty `Rewind` taking a snapshot every `interval` frames and keeping them for `history`. An interval of 0 is treated as 1
+    #[must_use]
+    pub const fn new(interval: u32, history: Duration) -> Self {
+        Self {
+            interval,
+            history,
+            snapshots: VecDeque::new(),
+            elapsed: Duration::ZERO,
+            frame: 0,
+            budget: None,
+            memory_used: 0,
+        }
+    }
+
+    /// Return the `Rewind` limited to `bytes` of snapshots, dropping the oldest snapshots when it would go over. `size_of` estimates the memory used by one snapshot, such as `|state| size_of_val(state) + state.entities.len() * size_of::<Entity>()`
+    #[must_use]
+    pub fn with_memory_budget(mut self, bytes: usize, size_of: fn(&T) -> usize) -> Self {
+        self.budget = Some((bytes, size_of));
+        self.memory_used = self.snapshots.iter().map(|(_, state)| size_of(state)).sum();
+        self.enforce_limits();
+        self
+    }
+
+    /// Advance time by `delta` and take a snapshot of the state if it's time for one. Returns true if a snapshot was taken
+    pub fn record(&mut self, state: &T, delta: Duration) -> bool {
+        self.elapsed += delta;
+        let is_due = self.frame.is_multiple_of(u64::from(self.interval.max(1)));
+        self.frame += 1;
+
+        if is_due {
+            if let Some((_, size_of)) = self.budget {
+                self.memory_used += size_of(state);
+            }
+            self.snapshots.push_back((self.elapsed, state.clone()));
+            self.enforce_limits();
+        }
+
+        is_due
+    }
+
+    /// Roll back by `amount` of time, returning the latest snapshot from at least that long ago and dropping every snapshot after it. If there isn't that much history, the oldest snapshot is returned. Returns `None` if there are no snapshots
+    pub fn rewind(&mut self, amount: Duration) -> Option<T> {
+        let target = self.elapsed.saturating_sub(amount);
+        let keep = self
+            .snapshots
+            .iter()
+            .rposition(|(time, _)| *time <= target)
+            .unwrap_or(0);
+
+        while self.snapshots.len() > keep + 1 {
+            self.pop_back();
+        }
+        self.restore_latest()
+    }
+
+    /// Drop the latest snapshot and return the one before it, for rewinding continuously while a button is held. Returns `None` once only the oldest snapshot is left
+    pub fn step_back(&mut self) -> Option<T> {
+        if self.snapshots.len() < 2 {
+            return None;
+        }
+
+        self.pop_back();
+        self.restore_latest()
+    }
+
+    /// Return the number of snapshots held
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// Returns true if there are no snapshots
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Return how far back the snapshots go from the latest one
+    #[must_use]
+    pub fn available(&self) -> Duration {
+        match (self.snapshots.front(), self.snapshots.back()) {
+            (Some((oldest, _)), Some((latest, _))) => latest.saturating_sub(*oldest),
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Return the estimated memory used by the snapshots, as measured by the function passed to [`with_memory_budget()`](Rewind::with_memory_budget()). Always 0 without a memory budget
+    #[must_use]
+    pub const fn memory_used(&self) -> usize {
+        self.memory_used
+    }
+
+    /// Drop every snapshot
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+        self.memory_used = 0;
+    }
+
+    /// Drop the latest snapshot
+    fn pop_back(&mut self) {
+        if let Some((_, state)) = self.snapshots.pop_back() {
+            self.forget(&state);
+        }
+    }
+
+    /// Move time back to the latest snapshot and return it
+    fn restore_latest(&mut self) -> Option<T> {
+        let (time, state) = self.snapshots.back()?;
+        self.elapsed = *time;
+        self.frame = 0;
+        Some(state.clone())
+    }
+
+    /// Remove the snapshot's size from the memory used
+    fn forget(&mut self, state: &T) {
+        if let Some((_, size_of)) = self.budget {
+            self.memory_used = self.memory_used.saturating_sub(size_of(state));
+        }
+    }
+
+    /// Drop the oldest snapshots until they fit in the history and memory budget. The latest snapshot is always kept
+    fn enforce_limits(&mut self) {
+        let cutoff = self.elapsed.saturating_sub(self.history);
+        while self.snapshots.len() > 1 {
+            let too_old = self
+                .snapshots
+                .front()
+                .is_some_and(|(time, _)| *time < cutoff);
+            let over_budget = self
+                .budget
+                .is_some_and(|(bytes, _)| self.memory_used > bytes);
+            if !too_old && !over_budget {
+                break;
+            }
+
+            if let Some((_, state)) = self.snapshots.pop_front() {
+                self.forget(&state);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_history_and_budget() {
+        let mut rewind = Rewind::new(2, Duration::from_secs(1));
+        for i in 0..40 {
+            rewind.record(&i, Duration::from_millis(100));
+        }
+        // Snapshots every 200ms for a second
+        assert_eq!(rewind.len(), 6);
+        assert_eq!(rewind.available(), Duration::from_secs(1));
+
+        let mut rewind = rewind.with_memory_budget(24, |_| 8);
+        rewind.record(&40, Duration::from_millis(100));
+        assert_eq!(rewind.len(), 3);
+        assert_eq!(rewind.memory_used(), 24);
+    }
+
+    #[test]
+    fn steps_back_through_snapshots() {
+        let mut rewind = Rewind::new(1, Duration::from_secs(10));
+        for i in 0..5 {
+            rewind.record(&i, Duration::from_millis(100));
+        }
+
+        assert_eq!(rewind.step_back(), Some(3));
+        assert_eq!(rewind.rewind(Duration::from_secs(30)), Some(0));
+        assert_eq!(rewind.step_back(), None);
+
+        rewind.record(&7, Duration::from_millis(100));
+        assert_eq!(rewind.step_back(), Some(0));
+    }
+}
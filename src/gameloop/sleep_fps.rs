@@ -25,7 +25,7 @@ pub fn sleep_fps(fps: f32, elapsed: Option<Duration>) -> bool {
     let elapsed = elapsed.unwrap_or(Duration::ZERO);
     let frame_length = Duration::from_secs_f32(1.0 / fps);
     if frame_length > elapsed {
-        sleep(frame_length - elapsed);
+        sleep(frame_length.saturating_sub(elapsed));
         false
     } else {
         true
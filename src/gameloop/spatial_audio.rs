@@ -0,0 +1,282 @@
+//! Volume and panning for sounds placed in the game world. Read the [`SpatialAudio`] documentation for more info
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "3D")]
+use crate::elements3d::{Transform3D, Vec3D};
+use crate::{elements::Vec2D, utils::cell_aspect_ratio};
+
+/// The handle of a sound played through [`SpatialAudio`]
+pub type SoundId = u64;
+
+/// The audio library that [`SpatialAudio`] plays its sounds through. Volumes range from 0.0 to 1.0, and pans from -1.0 (fully left) to 1.0 (fully right)
+pub trait AudioBackend {
+    /// Start playing the named sound, which is identified by `id` from then on
+    fn play(&mut self, id: SoundId, sound: &str, volume: f64, pan: f64);
+
+    /// Change the volume and pan of a playing sound
+    fn set_mix(&mut self, id: SoundId, volume: f64, pan: f64);
+
+    /// Stop a playing sound
+    fn stop(&mut self, id: SoundId);
+}
+
+/// Where a sound is in the game world
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum SoundPosition {
+    /// A position in the 2D world, heard from the [`listener`](SpatialAudio::listener)
+    Flat(Vec2D),
+    /// A position in the 3D scene, heard from the [`listener_3d`](SpatialAudio::listener_3d). Only available with the `3D` feature
+    #[cfg(feature = "3D")]
+    Spatial(Vec3D),
+}
+
+impl From<Vec2D> for SoundPosition {
+    fn from(pos: Vec2D) -> Self {
+        Self::Flat(pos)
+    }
+}
+
+#[cfg(feature = "3D")]
+impl From<Vec3D> for SoundPosition {
+    fn from(pos: Vec3D) -> Self {
+        Self::Spatial(pos)
+    }
+}
+
+/// A sound being played, with the mix last sent to the backend
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PlayingSound {
+    pos: SoundPosition,
+    volume: f64,
+    sent: (f64, f64),
+}
+
+/// `SpatialAudio` plays sounds at positions in the game world, turning each one's distance and direction from the listener into a volume and a pan. Keep the [`listener`](SpatialAudio::listener) on the player or the centre of the camera, and call [`update()`](SpatialAudio::update()) once every frame so sounds fade and pan as the listener and the sounds move. The backend is only sent a new mix when a sound's volume or pan changes
+///
+/// Volume falls off linearly to nothing at the [`range`](SpatialAudio::range), and sounds are panned fully to one side when they're [`pan_width`](SpatialAudio::pan_width) away to that side. In 2D, vertical distances are scaled by the [cell aspect ratio](crate::elements::view::utils::cell_aspect_ratio()), as cells are taller than they are wide
+/// ```
+/// use gemini_engine::{elements::Vec2D, gameloop::spatial_audio::{AudioBackend, SoundId, SpatialAudio}};
+///
+/// #[derive(Default)]
+/// struct Mixer {
+///     mix: (f64, f64),
+/// }
+/// impl AudioBackend for Mixer {
+///     fn play(&mut self, _id: SoundId, _sound: &str, volume: f64, pan: f64) {
+///         self.mix = (volume, pan);
+///     }
+///     fn set_mix(&mut self, _id: SoundId, volume: f64, pan: f64) {
+///         self.mix = (volume, pan);
+///     }
+///     fn stop(&mut self, _id: SoundId) {}
+/// }
+///
+/// let mut audio = SpatialAudio::new(Mixer::default()).with_range(40.0);
+/// audio.play_at("explosion", Vec2D::new(20, 0), 1.0);
+/// assert_eq!(audio.backend().mix, (0.5, 1.0));
+///
+/// // The player walks up to the explosion
+/// audio.listener = Vec2D::new(20, 0);
+/// audio.update();
+/// assert_eq!(audio.backend().mix, (1.0, 0.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpatialAudio<B: AudioBackend> {
+    /// The position sounds are heard from in the 2D world. Defaults to [`Vec2D::ZERO`]
+    pub listener: Vec2D,
+    /// The transform of the camera sounds are heard through in the 3D scene, such as a [`Viewport`](crate::elements3d::Viewport)'s. Only available with the `3D` feature
+    #[cfg(feature = "3D")]
+    pub listener_3d: Transform3D,
+    /// The distance at which sounds fade to silence. Defaults to 60.0
+    pub range: f64,
+    /// How far to one side a sound must be to play from only that side. Defaults to 20.0
+    pub pan_width: f64,
+    backend: B,
+    sounds: BTreeMap<SoundId, PlayingSound>,
+    next_id: SoundId,
+}
+
+impl<B: AudioBackend> SpatialAudio<B> {
+    /// Create a new `SpatialAudio` that plays its sounds through the backend
+    #[must_use]
+    pub const fn new(backend: B) -> Self {
+        Self {
+            listener: Vec2D::ZERO,
+            #[cfg(feature = "3D")]
+            listener_3d: Transform3D::DEFAULT,
+            range: 60.0,
+            pan_width: 20.0,
+            backend,
+            sounds: BTreeMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Return the `SpatialAudio` with a different [`range`](SpatialAudio::range)
+    #[must_use]
+    pub const fn with_range(mut self, range: f64) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// Return the `SpatialAudio` with a different [`pan_width`](SpatialAudio::pan_width)
+    #[must_use]
+    pub const fn with_pan_width(mut self, pan_width: f64) -> Self {
+        self.pan_width = pan_width;
+        self
+    }
+
+    /// Return the volume and pan a sound at the position is heard with, for a sound played at full volume
+    #[must_use]
+    pub fn mix(&self, pos: impl Into<SoundPosition>) -> (f64, f64) {
+        let (sideways, distance) = match pos.into() {
+            SoundPosition::Flat(pos) => {
+                let offset = pos - self.listener;
+                let (x, y) = (offset.x as f64, offset.y as f64 * cell_aspect_ratio());
+                (x, x.hypot(y))
+            }
+            #[cfg(feature = "3D")]
+            SoundPosition::Spatial(pos) => {
+                let offset = self.listener_3d.apply_viewport_transform(&[pos])[0];
+                // The viewport projects positive x to the left of the screen
+                (-offset.x, offset.magnitude())
+            }
+        };
+
+        let volume = if self.range > 0.0 {
+            (1.0 - distance / self.range).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let pan = if self.pan_width > 0.0 {
+            (sideways / self.pan_width).clamp(-1.0, 1.0)
+        } else {
+            0.0
+        };
+
+        (volume, pan)
+    }
+
+    /// Play the named sound at the position, at a volume from 0.0 to 1.0 before it's attenuated by distance. Returns the sound's id, for moving or stopping it later
+    pub fn play_at(&mut self, sound: &str, pos: impl Into<SoundPosition>, volume: f64) -> SoundId {
+        let pos = pos.into();
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let volume = volume.clamp(0.0, 1.0);
+        let (attenuation, pan) = self.mix(pos);
+        let sent = (volume * attenuation, pan);
+        self.backend.play(id, sound, sent.0, sent.1);
+        self.sounds.insert(id, PlayingSound { pos, volume, sent });
+
+        id
+    }
+
+    /// Move a playing sound, such as one following a moving enemy. The mix changes on the next [`update()`](SpatialAudio::update())
+    pub fn move_sound(&mut self, id: SoundId, pos: impl Into<SoundPosition>) {
+        if let Some(sound) = self.sounds.get_mut(&id) {
+            sound.pos = pos.into();
+        }
+    }
+
+    /// Stop a playing sound. Call this when the audio library reports that a sound has finished too, so `SpatialAudio` stops updating it
+    pub fn stop(&mut self, id: SoundId) {
+        if self.sounds.remove(&id).is_some() {
+            self.backend.stop(id);
+        }
+    }
+
+    /// Return the ids of every sound still playing
+    #[must_use]
+    pub fn playing(&self) -> Vec<SoundId> {
+        self.sounds.keys().copied().collect()
+    }
+
+    /// Recalculate the mix of every playing sound from the listener's current position, sending any changes to the backend. Call this once every frame
+    pub fn update(&mut self) {
+        let mixes: Vec<(SoundId, (f64, f64))> = self
+            .sounds
+            .iter()
+            .map(|(id, sound)| {
+                let (attenuation, pan) = self.mix(sound.pos);
+                (*id, (sound.volume * attenuation, pan))
+            })
+            .collect();
+
+        for (id, mix) in mixes {
+            let Some(sound) = self.sounds.get_mut(&id) else {
+                continue;
+            };
+            if sound.sent != mix {
+                self.backend.set_mix(id, mix.0, mix.1);
+                sound.sent = mix;
+            }
+        }
+    }
+
+    /// Return a reference to the backend
+    #[must_use]
+    pub const fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Return a mutable reference to the backend
+    pub const fn backend_mut(&mut self) -> &mut B {
+        &mut self.backend
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Log {
+        events: Vec<String>,
+    }
+
+    impl AudioBackend for Log {
+        fn play(&mut self, id: SoundId, sound: &str, volume: f64, pan: f64) {
+            self.events
+                .push(format!("play {id} {sound} {volume} {pan}"));
+        }
+
+        fn set_mix(&mut self, id: SoundId, volume: f64, pan: f64) {
+            self.events.push(format!("mix {id} {volume} {pan}"));
+        }
+
+        fn stop(&mut self, id: SoundId) {
+            self.events.push(format!("stop {id}"));
+        }
+    }
+
+    #[test]
+    fn only_changed_mixes_are_sent() {
+        let mut audio = SpatialAudio::new(Log::default())
+            .with_range(10.0)
+            .with_pan_width(5.0);
+        let id = audio.play_at("step", Vec2D::new(-5, 0), 0.5);
+        audio.update();
+        audio.move_sound(id, Vec2D::new(-20, 0));
+        audio.update();
+        audio.stop(id);
+        audio.stop(id);
+
+        assert_eq!(
+            audio.backend().events,
+            ["play 0 step 0.25 -1", "mix 0 0 -1", "stop 0"]
+        );
+        assert!(audio.playing().is_empty());
+    }
+
+    #[cfg(feature = "3D")]
+    #[test]
+    fn sounds_in_3d_pan_from_the_camera() {
+        let audio = SpatialAudio::new(Log::default()).with_range(10.0);
+        let (volume, pan) = audio.mix(Vec3D::new(0.0, 0.0, -5.0));
+        assert!((volume - 0.5).abs() < 1e-9);
+        assert!(pan.abs() < 1e-9);
+    }
+}
@@ -0,0 +1,367 @@
+//! Long-term tracking of player statistics and the achievements unlocked by them. Read the [`Stats`] and [`Achievements`] documentation for more info
+
+use crate::elements::ui::{Severity, Toasts};
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fmt, fs, io,
+    path::Path,
+    str::FromStr,
+    time::Duration,
+};
+
+/// `Stats` tracks named statistics over the lifetime of a game: counters (such as enemies defeated), timers (such as time played) and maxima (such as the best score). Stats are saved as plain text with one stat per line, so they can be written to disk with [`save()`](Stats::save()) and read back with [`load()`](Stats::load()) between sessions. Stat names can't contain tabs or line breaks
+/// ```
+/// use gemini_engine::gameloop::stats::Stats;
+/// use std::time::Duration;
+///
+/// let mut stats = Stats::new();
+/// stats.increment("enemies_defeated");
+/// stats.add_time("time_played", Duration::from_secs(90));
+/// stats.record_max("best_score", 1200.0);
+///
+/// let saved = stats.to_string();
+/// assert_eq!(saved.parse::<Stats>(), Ok(stats));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Stats {
+    counters: BTreeMap<String, u64>,
+    timers: BTreeMap<String, Duration>,
+    maxima: BTreeMap<String, f64>,
+}
+
+impl Stats {
+    /// Create a new `Stats` with nothing tracked yet
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            counters: BTreeMap::new(),
+            timers: BTreeMap::new(),
+            maxima: BTreeMap::new(),
+        }
+    }
+
+    /// Add one to the counter
+    pub fn increment(&mut self, name: &str) {
+        self.add(name, 1);
+    }
+
+    /// Add `amount` to the counter
+    pub fn add(&mut self, name: &str, amount: u64) {
+        let counter = self.counters.entry(name.to_string()).or_default();
+        *counter = counter.saturating_add(amount);
+    }
+
+    /// Return the value of the counter, or 0 if it hasn't been counted yet
+    #[must_use]
+    pub fn counter(&self, name: &str) -> u64 {
+        self.counters.get(name).copied().unwrap_or(0)
+    }
+
+    /// Add `delta` to the timer, usually the time since the last frame while whatever is being timed is happening
+    pub fn add_time(&mut self, name: &str, delta: Duration) {
+        let timer = self.timers.entry(name.to_string()).or_default();
+        *timer = timer.saturating_add(delta);
+    }
+
+    /// Return the total time recorded by the timer, or zero if it hasn't been timed yet
+    #[must_use]
+    pub fn time(&self, name: &str) -> Duration {
+        self.timers.get(name).copied().unwrap_or_default()
+    }
+
+    /// Record a value for the maximum, keeping it only if it beats the previous best. Returns true if the value is a new record
+    pub fn record_max(&mut self, name: &str, value: f64) -> bool {
+        match self.maxima.get_mut(name) {
+            Some(best) if *best >= value => false,
+            Some(best) => {
+                *best = value;
+                true
+            }
+            None => {
+                self.maxima.insert(name.to_string(), value);
+                true
+            }
+        }
+    }
+
+    /// Return the highest value recorded for the maximum, if any
+    #[must_use]
+    pub fn max(&self, name: &str) -> Option<f64> {
+        self.maxima.get(name).copied()
+    }
+
+    /// Return true if the `StatCondition` is met
+    #[must_use]
+    pub fn meets(&self, condition: &StatCondition) -> bool {
+        match condition {
+            StatCondition::Counter(name, target) => self.counter(name) >= *target,
+            StatCondition::Time(name, target) => self.time(name) >= *target,
+            StatCondition::Max(name, target) => self.max(name).is_some_and(|max| max >= *target),
+        }
+    }
+
+    /// Write the stats to a file, replacing it if it already exists
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be written to
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Read stats previously written with [`save()`](Stats::save())
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be read, or an [`io::ErrorKind::InvalidData`] error if it isn't a valid stats file
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, value) in &self.counters {
+            writeln!(f, "counter\t{name}\t{value}")?;
+        }
+        for (name, value) in &self.timers {
+            writeln!(f, "time\t{name}\t{}", value.as_secs_f64())?;
+        }
+        for (name, value) in &self.maxima {
+            writeln!(f, "max\t{name}\t{value}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Stats {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut stats = Self::new();
+
+        for (i, line) in s.lines().enumerate().filter(|(_, line)| !line.is_empty()) {
+            let mut parts = line.splitn(3, '\t');
+            let (Some(kind), Some(name), Some(value)) = (parts.next(), parts.next(), parts.next())
+            else {
+                return Err(format!(
+                    "Line {} is missing a stat kind, name or value",
+                    i + 1
+                ));
+            };
+            let invalid = format!("Line {} has an invalid value: {value}", i + 1);
+
+            match kind {
+                "counter" => {
+                    stats.counters.insert(
+                        name.to_string(),
+                        value.parse().map_err(|_| invalid.clone())?,
+                    );
+                }
+                "time" => {
+                    let secs: f64 = value.parse().map_err(|_| invalid.clone())?;
+                    let time = Duration::try_from_secs_f64(secs).map_err(|_| invalid.clone())?;
+                    stats.timers.insert(name.to_string(), time);
+                }
+                "max" => {
+                    stats.maxima.insert(
+                        name.to_string(),
+                        value.parse().map_err(|_| invalid.clone())?,
+                    );
+                }
+                _ => return Err(format!("Line {} has an unknown stat kind: {kind}", i + 1)),
+            }
+        }
+
+        Ok(stats)
+    }
+}
+
+/// A threshold on one of the [`Stats`], which unlocks an [`Achievement`] once met
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatCondition {
+    /// The counter with the given name reaches the value
+    Counter(String, u64),
+    /// The timer with the given name reaches the duration
+    Time(String, Duration),
+    /// The maximum with the given name reaches the value
+    Max(String, f64),
+}
+
+/// An achievement tracked by [`Achievements`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Achievement {
+    /// The unique ID of the achievement, used when saving which achievements are unlocked
+    pub id: String,
+    /// The name shown to the player when the achievement is unlocked
+    pub name: String,
+    /// The condition that unlocks the achievement
+    pub condition: StatCondition,
+}
+
+impl Achievement {
+    /// Create a new `Achievement`
+    #[must_use]
+    pub fn new(id: impl Into<String>, name: impl Into<String>, condition: StatCondition) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            condition,
+        }
+    }
+}
+
+/// `Achievements` unlocks [`Achievement`]s as their conditions on your [`Stats`] are met. Call [`check()`](Achievements::check()) whenever the stats change, then handle the IDs of the newly unlocked achievements from [`events()`](Achievements::events()). Passing a [`Toasts`] to `check()` also announces each unlock on screen
+///
+/// Which achievements are unlocked is saved as plain text with one ID per line, written by the `Display` implementation and read back with [`restore()`](Achievements::restore())
+/// ```
+/// use gemini_engine::{
+///     elements::{ui::Toasts, Vec2D},
+///     gameloop::stats::{Achievement, Achievements, StatCondition, Stats},
+/// };
+///
+/// let mut stats = Stats::new();
+/// let mut toasts = Toasts::new(Vec2D::new(60, 20));
+/// let mut achievements = Achievements::new(vec![Achievement::new(
+///     "first_blood",
+///     "First blood",
+///     StatCondition::Counter(String::from("enemies_defeated"), 1),
+/// )]);
+///
+/// stats.increment("enemies_defeated");
+/// achievements.check(&stats, Some(&mut toasts));
+///
+/// assert_eq!(achievements.events().collect::<Vec<_>>(), vec!["first_blood"]);
+/// assert_eq!(toasts.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Achievements {
+    list: Vec<Achievement>,
+    unlocked: BTreeSet<String>,
+    events: VecDeque<String>,
+}
+
+impl Achievements {
+    /// Create a new `Achievements` with the given achievements, all locked
+    #[must_use]
+    pub const fn new(achievements: Vec<Achievement>) -> Self {
+        Self {
+            list: achievements,
+            unlocked: BTreeSet::new(),
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Return every achievement, locked or not
+    #[must_use]
+    pub fn all(&self) -> &[Achievement] {
+        &self.list
+    }
+
+    /// Return true if the achievement with the ID has been unlocked
+    #[must_use]
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.contains(id)
+    }
+
+    /// Unlock every locked achievement whose condition the stats meet, queueing an event for each and showing a [`Severity::Success`] toast if `toasts` is passed. Returns the number of achievements unlocked
+    pub fn check(&mut self, stats: &Stats, mut toasts: Option<&mut Toasts>) -> usize {
+        let mut unlocked = 0;
+
+        for achievement in &self.list {
+            if self.unlocked.contains(&achievement.id) || !stats.meets(&achievement.condition) {
+                continue;
+            }
+            self.unlocked.insert(achievement.id.clone());
+            self.events.push_back(achievement.id.clone());
+            if let Some(toasts) = toasts.as_deref_mut() {
+                toasts.push(
+                    format!("Achievement unlocked: {}", achievement.name),
+                    Severity::Success,
+                );
+            }
+            unlocked += 1;
+        }
+
+        unlocked
+    }
+
+    /// Drain and return the IDs of the achievements unlocked since this was last called
+    pub fn events(&mut self) -> impl Iterator<Item = String> + '_ {
+        self.events.drain(..)
+    }
+
+    /// Mark the achievements listed in `saved`, one ID per line as written by the `Display` implementation, as unlocked without queueing events. Unknown IDs are ignored
+    pub fn restore(&mut self, saved: &str) {
+        for id in saved.lines() {
+            if self.list.iter().any(|achievement| achievement.id == id) {
+                self.unlocked.insert(id.to_string());
+            }
+        }
+    }
+}
+
+impl fmt::Display for Achievements {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for id in &self.unlocked {
+            writeln!(f, "{id}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_round_trip_and_reject_bad_lines() {
+        let mut stats = Stats::new();
+        stats.add("coins", 40);
+        stats.increment("coins");
+        stats.add_time("time played", Duration::from_millis(2500));
+        assert!(stats.record_max("combo", 12.0));
+        assert!(!stats.record_max("combo", 8.0));
+
+        assert_eq!(stats.counter("coins"), 41);
+        assert_eq!(stats.max("combo"), Some(12.0));
+        assert_eq!(stats.to_string().parse::<Stats>(), Ok(stats));
+
+        assert!("counter\tcoins\tlots".parse::<Stats>().is_err());
+        assert!("level\tcoins\t3".parse::<Stats>().is_err());
+    }
+
+    #[test]
+    fn achievements_unlock_once_and_restore() {
+        let list = vec![
+            Achievement::new(
+                "marathon",
+                "Marathon",
+                StatCondition::Time(String::from("time_played"), Duration::from_mins(1)),
+            ),
+            Achievement::new(
+                "high_score",
+                "High score",
+                StatCondition::Max(String::from("score"), 100.0),
+            ),
+        ];
+        let mut achievements = Achievements::new(list.clone());
+        let mut stats = Stats::new();
+
+        stats.record_max("score", 150.0);
+        assert_eq!(achievements.check(&stats, None), 1);
+        assert_eq!(achievements.check(&stats, None), 0);
+        stats.add_time("time_played", Duration::from_secs(61));
+        assert_eq!(achievements.check(&stats, None), 1);
+        assert_eq!(
+            achievements.events().collect::<Vec<_>>(),
+            vec!["high_score", "marathon"]
+        );
+
+        let mut restored = Achievements::new(list);
+        restored.restore(&achievements.to_string());
+        assert!(restored.is_unlocked("marathon"));
+        assert_eq!(restored.check(&stats, None), 0);
+        assert_eq!(restored.events().count(), 0);
+    }
+}
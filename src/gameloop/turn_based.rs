@@ -0,0 +1,73 @@
+//! A blocking, turn-based alternative to [`MainLoopRoot`](super::MainLoopRoot). Read the [`TurnBasedRoot`] documentation for more info
+
+/// `TurnBasedRoot` is a gameloop for turn-based games such as roguelikes and puzzle games. Rather than running at a fixed FPS, it renders the game once and then blocks until the next input arrives, so the process uses no CPU while waiting for the player
+///
+/// Like [`MainLoopRoot`](super::MainLoopRoot), everything related to the game should be stored in a single struct which implements this trait. Implement [`wait_for_input()`](TurnBasedRoot::wait_for_input()) with a blocking read, such as [`Receiver::recv()`](std::sync::mpsc::Receiver::recv()) on a channel fed by your input handler or a blocking key read from your input library
+///
+/// # Example
+/// ```
+/// use gemini_engine::gameloop::TurnBasedRoot;
+///
+/// struct Game {
+///     inputs: Vec<char>, // stands in for a blocking input source
+///     player_x: isize,
+/// }
+///
+/// impl TurnBasedRoot for Game {
+///     type InputDataType = char;
+///
+///     fn wait_for_input(&mut self) -> Option<char> {
+///         self.inputs.pop()
+///     }
+///
+///     fn turn(&mut self, input: char) {
+///         match input {
+///             'a' => self.player_x -= 1,
+///             'd' => self.player_x += 1,
+///             _ => (),
+///         }
+///     }
+///
+///     fn render_frame(&mut self) {
+///         // --all blitting and rendering goes here--
+///     }
+/// }
+///
+/// let mut game = Game { inputs: vec!['d', 'd', 'a'], player_x: 0 };
+/// game.main_loop();
+/// assert_eq!(game.player_x, 1);
+/// ```
+pub trait TurnBasedRoot {
+    /// The input event returned by [`TurnBasedRoot::wait_for_input()`] and passed to [`TurnBasedRoot::turn()`]
+    type InputDataType;
+
+    /// Block until the next input event arrives and return it. Return `None` to end the [`main_loop()`](TurnBasedRoot::main_loop()), for example when the input source is closed or the player quits
+    fn wait_for_input(&mut self) -> Option<Self::InputDataType>;
+
+    /// Play out a single turn in response to the input: move the player, let enemies act, handle collisions, etc.
+    fn turn(&mut self, input: Self::InputDataType);
+
+    /// All rendering code (blitting, printing to the screen, etc.) should be called in here. This runs once before the first input and again after every turn
+    fn render_frame(&mut self);
+
+    /// The main loop function of the turn-based root. This shouldn't be overriden. Renders the game, then waits for an input and plays out a turn, repeating until [`wait_for_input()`](TurnBasedRoot::wait_for_input()) returns `None`
+    fn main_loop(&mut self) {
+        self.render_frame();
+
+        loop {
+            let input = {
+                engine_span!("input");
+                self.wait_for_input()
+            };
+            let Some(input) = input else {
+                break;
+            };
+            {
+                engine_span!("turn");
+                self.turn(input);
+            }
+            engine_span!("render_frame");
+            self.render_frame();
+        }
+    }
+}
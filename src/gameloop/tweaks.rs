@@ -0,0 +1,288 @@
+//! Live-editable tuning values, so game feel can be adjusted without recompiling. Read the [`tweak!`](crate::tweak) documentation for more info
+//!
+//! Every tweak is stored as an `f64` in a single registry shared by the whole program. Values can be changed while the game runs with [`set()`], from a dev console with [`apply_command()`], or by editing a file watched by a [`TweakFile`]. Call [`subscribe()`] to be notified of every change
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Mutex, MutexGuard, OnceLock,
+    },
+    time::SystemTime,
+};
+
+use crate::EngineError;
+
+/// A change to a tweak's value, sent to every [`subscribe()`]r
+#[derive(Debug, Clone, PartialEq)]
+pub struct TweakChange {
+    /// The name of the tweak
+    pub name: String,
+    /// The new value of the tweak
+    pub value: f64,
+}
+
+#[derive(Debug, Default)]
+struct Registry {
+    values: BTreeMap<String, f64>,
+    subscribers: Vec<Sender<TweakChange>>,
+}
+
+static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+
+/// Lock the registry, recovering it if another thread panicked while holding it
+fn registry() -> MutexGuard<'static, Registry> {
+    REGISTRY
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// A type that can be used as a [`tweak!`](crate::tweak) value. Every tweak is stored as an `f64`, so integers are rounded and booleans are stored as `0.0` or `1.0`
+pub trait TweakValue: Copy {
+    /// Convert the value to how it's stored
+    fn to_tweak(self) -> f64;
+    /// Convert a stored value back
+    fn from_tweak(value: f64) -> Self;
+}
+
+macro_rules! impl_tweak_value {
+    ($to_tweak:expr, $($type:ty),*) => {
+        $(
+            impl TweakValue for $type {
+                fn to_tweak(self) -> f64 {
+                    $to_tweak(self)
+                }
+
+                fn from_tweak(value: f64) -> Self {
+                    value.round() as Self
+                }
+            }
+        )*
+    };
+}
+
+impl_tweak_value!(f64::from, i8, i16, i32, u8, u16, u32);
+impl_tweak_value!(|value| value as f64, i64, isize, u64, usize);
+
+impl TweakValue for f64 {
+    fn to_tweak(self) -> f64 {
+        self
+    }
+
+    fn from_tweak(value: f64) -> Self {
+        value
+    }
+}
+
+impl TweakValue for f32 {
+    fn to_tweak(self) -> f64 {
+        f64::from(self)
+    }
+
+    fn from_tweak(value: f64) -> Self {
+        value as Self
+    }
+}
+
+impl TweakValue for bool {
+    fn to_tweak(self) -> f64 {
+        f64::from(u8::from(self))
+    }
+
+    fn from_tweak(value: f64) -> Self {
+        value != 0.0
+    }
+}
+
+/// Return the tweak's value, registering it with the `default` value if it doesn't exist yet. This is what [`tweak!`](crate::tweak) calls
+pub fn get_or_register<T: TweakValue>(name: &str, default: T) -> T {
+    let value = *registry()
+        .values
+        .entry(name.to_string())
+        .or_insert_with(|| default.to_tweak());
+
+    T::from_tweak(value)
+}
+
+/// Return the tweak's value, or `None` if it hasn't been registered or set
+#[must_use]
+pub fn get(name: &str) -> Option<f64> {
+    registry().values.get(name).copied()
+}
+
+/// Set the tweak's value, notifying subscribers if it changed
+pub fn set(name: &str, value: impl TweakValue) {
+    let value = value.to_tweak();
+    let mut registry = registry();
+    if registry.values.insert(name.to_string(), value) == Some(value) {
+        return;
+    }
+
+    let change = TweakChange {
+        name: name.to_string(),
+        value,
+    };
+    registry
+        .subscribers
+        .retain(|subscriber| subscriber.send(change.clone()).is_ok());
+}
+
+/// Return every tweak and its value, sorted by name
+#[must_use]
+pub fn all() -> Vec<(String, f64)> {
+    registry()
+        .values
+        .iter()
+        .map(|(name, value)| (name.clone(), *value))
+        .collect()
+}
+
+/// Return a `Receiver` that is sent every change to a tweak's value from now on
+#[must_use]
+pub fn subscribe() -> Receiver<TweakChange> {
+    let (sender, receiver) = mpsc::channel();
+    registry().subscribers.push(sender);
+    receiver
+}
+
+/// Apply a dev console command of the form `<name> <value>` or `<name> = <value>`, such as `player.speed 14`. Values can be numbers, `true` or `false`
+///
+/// # Errors
+/// Returns an error if the command isn't in that form or the value isn't a number or boolean
+pub fn apply_command(command: &str) -> Result<(), EngineError> {
+    let command = command.trim();
+    let (name, value) = command
+        .split_once('=')
+        .or_else(|| command.split_once(char::is_whitespace))
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .filter(|(name, value)| !name.is_empty() && !value.is_empty())
+        .ok_or_else(|| {
+            EngineError::InvalidData(format!("Expected <name> <value>, got {command}"))
+        })?;
+
+    let value = match value {
+        "true" => 1.0,
+        "false" => 0.0,
+        _ => value
+            .parse()
+            .map_err(|_| EngineError::InvalidData(format!("Invalid value for {name}: {value}")))?,
+    };
+    set(name, value);
+
+    Ok(())
+}
+
+/// Return every tweak as `<name> = <value>` lines, which can be read back with [`apply_command()`] or a [`TweakFile`]
+#[must_use]
+pub fn to_file_string() -> String {
+    all().iter().fold(String::new(), |mut file, (name, value)| {
+        let _ = writeln!(file, "{name} = {value}");
+        file
+    })
+}
+
+/// A `TweakFile` watches a file of `<name> = <value>` lines, applying them whenever the file changes. Blank lines and lines starting with `#` are ignored. Call [`poll()`](TweakFile::poll()) regularly, such as once a second
+/// ```
+/// use gemini_engine::{gameloop::tweaks::TweakFile, tweak};
+///
+/// let path = std::env::temp_dir().join("gemini_tweak_file_example.txt");
+/// std::fs::write(&path, "# Movement\nexample.jump_height = 4.5\n").unwrap();
+///
+/// let mut file = TweakFile::new(&path);
+/// file.poll().unwrap();
+/// assert_eq!(tweak!("example.jump_height", 3.0), 4.5);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TweakFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl TweakFile {
+    /// Create a new `TweakFile` watching the file at the path. Nothing is read until the first [`poll()`](TweakFile::poll())
+    #[must_use]
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            modified: None,
+        }
+    }
+
+    /// Reload the file if it has been modified since it was last read. Returns true if the file was reloaded. Invalid lines are skipped, so a typo while editing doesn't stop the rest of the file from applying
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be read
+    pub fn poll(&mut self) -> io::Result<bool> {
+        let modified = fs::metadata(&self.path)?.modified()?;
+        if self.modified == Some(modified) {
+            return Ok(false);
+        }
+        self.modified = Some(modified);
+
+        for line in fs::read_to_string(&self.path)?.lines() {
+            let line = line.trim();
+            if !line.is_empty() && !line.starts_with('#') {
+                let _ = apply_command(line);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Write every tweak's current value to the file, so it can be edited from there
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be written to
+    pub fn save(&mut self) -> io::Result<()> {
+        fs::write(&self.path, to_file_string())?;
+        self.modified = Some(fs::metadata(&self.path)?.modified()?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tweaks_register_once_and_convert() {
+        assert_eq!(get_or_register("test.lives", 3_u32), 3);
+        assert_eq!(get_or_register("test.lives", 5_u32), 3);
+        set("test.lives", 4.6);
+        assert_eq!(get_or_register("test.lives", 5_u32), 5);
+        assert!(!get_or_register("test.god_mode", false));
+    }
+
+    #[test]
+    fn commands_notify_subscribers() {
+        let changes = subscribe();
+        apply_command("test.gravity = 9.8").expect("valid command");
+        apply_command("test.gravity 9.8").expect("valid command");
+        apply_command("test.fly true").expect("valid command");
+        assert!(apply_command("test.gravity").is_err());
+        assert!(apply_command("test.gravity fast").is_err());
+
+        let changes: Vec<TweakChange> = changes
+            .try_iter()
+            .filter(|change| change.name.starts_with("test.gravity") || change.name == "test.fly")
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                TweakChange {
+                    name: String::from("test.gravity"),
+                    value: 9.8
+                },
+                TweakChange {
+                    name: String::from("test.fly"),
+                    value: 1.0
+                },
+            ]
+        );
+    }
+}
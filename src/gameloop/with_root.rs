@@ -1,5 +1,6 @@
 //! An object oriented approach to the gameloop, separate from [`fps_gameloop!`](crate::fps_gameloop). Read the [`MainLoopRoot`] documentation for more info
 
+use super::GameClock;
 use std::time::{Duration, Instant};
 
 /// This is an alternative way to handle the gameloop, separate from [`fps_gameloop!`](crate::fps_gameloop). It takes on a more object oriented approach where everything related to the game is stored inside a single struct which implements this trait.
@@ -38,6 +39,26 @@ pub trait MainLoopRoot {
     /// ```
     fn frame(&mut self, input_data: Option<Self::InputDataType>);
 
+    /// Run the game logic with the [`GameClock`] owned by [`main_loop()`](MainLoopRoot::main_loop()), which has just been ticked. Pass [`clock.sim.delta`](super::SimTime::delta) to the game world and [`clock.real.delta`](super::RealTime::delta) to the UI, and pause or slow down the simulation through the clock. Calls [`frame()`](MainLoopRoot::frame()) by default, so only override it if the game needs the clock
+    /// ```
+    /// # use gemini_engine::{elements::Vec2D, gameloop::{GameClock, MainLoopRoot}};
+    /// # struct Game { player: Vec2D, speed: f64, paused: bool }
+    /// # impl MainLoopRoot for Game {
+    /// # type InputDataType = u32;
+    /// # fn frame(&mut self, input_data: Option<Self::InputDataType>) {}
+    /// # fn render_frame(&mut self) {}
+    /// // --inside impl MainLoopRoot for Game--
+    /// fn frame_with_clock(&mut self, input_data: Option<Self::InputDataType>, clock: &mut GameClock) {
+    ///     clock.set_paused(self.paused);
+    ///     self.player.x += (self.speed * clock.sim.delta.as_secs_f64()) as isize;
+    /// }
+    /// # }
+    /// ```
+    fn frame_with_clock(&mut self, input_data: Option<Self::InputDataType>, clock: &mut GameClock) {
+        let _ = clock;
+        self.frame(input_data);
+    }
+
     /// All rendering code (blitting, printing to the screen, etc.) should be called in here. If the bool value returned by [`MainLoopRoot::sleep_and_get_input_data()`] is true, this won't run and nothing should be printed to the screen
     /// # Example
     /// Here's an example of what a `render_frame` trait implementation might look like, assuming your root struct has a `view: View` property for your main view
@@ -69,6 +90,12 @@ pub trait MainLoopRoot {
     /// ```
     fn render_frame(&mut self);
 
+    /// Render the frame with the [`GameClock`] owned by [`main_loop()`](MainLoopRoot::main_loop()), for animations that should use real or simulation time. Calls [`render_frame()`](MainLoopRoot::render_frame()) by default, so only override it if rendering needs the clock
+    fn render_frame_with_clock(&mut self, clock: &GameClock) {
+        let _ = clock;
+        self.render_frame();
+    }
+
     /// The function used to sleep for the appropriate amount based on the FPS. Uses [`gameloop::sleep_fps`](super::sleep_fps()) by default and will return None for the `InputDataType`. The returned bool value should represent whether or not to skip rendering on the next frame
     fn sleep_and_get_input_data(
         &self,
@@ -78,7 +105,7 @@ pub trait MainLoopRoot {
         (super::sleep_fps(fps, Some(elapsed)), None)
     }
 
-    /// The main loop function of the main loop root. This shouldnt be overriden. The `fps` parameter will be passed straight to [`sleep_and_get_input()`](MainLoopRoot::sleep_and_get_input_data()). See the [`MainLoopRoot`] documentation for more info
+    /// The main loop function of the main loop root. This shouldnt be overriden. The `fps` parameter will be passed straight to [`sleep_and_get_input()`](MainLoopRoot::sleep_and_get_input_data()). A [`GameClock`] is ticked at the start of every frame and passed to [`frame_with_clock()`](MainLoopRoot::frame_with_clock()) and [`render_frame_with_clock()`](MainLoopRoot::render_frame_with_clock()). See the [`MainLoopRoot`] documentation for more info
     /// ```rust, no_run
     /// # use gemini_engine::gameloop::MainLoopRoot;
     /// # struct Game {}
@@ -97,15 +124,25 @@ pub trait MainLoopRoot {
     /// ```
     fn main_loop(&mut self, fps: f32) {
         let mut elapsed = Duration::ZERO;
+        let mut clock = GameClock::new();
 
         loop {
-            let (frame_skip, input_data) = self.sleep_and_get_input_data(fps, elapsed);
+            let (frame_skip, input_data) = {
+                engine_span!("input");
+                self.sleep_and_get_input_data(fps, elapsed)
+            };
             let now = Instant::now();
+            clock.tick();
+            engine_span!("loop", frame_skip);
 
-            self.frame(input_data);
+            {
+                engine_span!("frame");
+                self.frame_with_clock(input_data, &mut clock);
+            }
 
             if !frame_skip {
-                self.render_frame();
+                engine_span!("render_frame");
+                self.render_frame_with_clock(&clock);
             }
 
             elapsed = now.elapsed();
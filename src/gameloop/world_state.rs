@@ -0,0 +1,225 @@
+//! A structured, serialisable snapshot of a game for external agents. Read the [`WorldState`] documentation for more info
+
+use crate::{
+    elements::{view::Modifier, Vec2D, View},
+    utils::json_string,
+};
+use std::{fmt::Write as _, io};
+
+/// Something in the game an agent may want to know about, such as the player or an enemy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityState {
+    /// What kind of thing the entity is, such as `"player"` or `"enemy"`
+    pub kind: String,
+    /// The position of the entity on the canvas
+    pub pos: Vec2D,
+}
+
+impl EntityState {
+    /// Create a new `EntityState`
+    #[must_use]
+    pub fn new(kind: impl Into<String>, pos: Vec2D) -> Self {
+        Self {
+            kind: kind.into(),
+            pos,
+        }
+    }
+}
+
+/// A snapshot of a game on a single frame, for external agents such as bots written in Python to observe the game. It holds the game's entities and its canvas as a grid of character and colour IDs, and can be written as a single line of JSON with [`to_json()`](WorldState::to_json()) to send over stdio or a socket
+///
+/// Each cell of the [`grid`](WorldState::grid) is a pair of the character's Unicode code point and an index into the [`modifiers`](WorldState::modifiers) table, so every distinct colour on the canvas gets a small, stable ID. In the JSON, modifiers are written as `null` for [`Modifier::None`], the escape code number for [`Modifier::Coded`], an `"#rrggbb"` string for [`Modifier::Colour`], an object like `{"bg":"#rrggbb"}` for [`Modifier::Background`] and an object with any of `"fg"`, `"bg"` and a `"style"` list of codes for [`Modifier::Set`]
+/// ```
+/// use gemini_engine::{
+///     elements::{view::{ColChar, Modifier, Wrapping}, Vec2D, View},
+///     gameloop::world_state::{EntityState, WorldState},
+/// };
+///
+/// let mut view = View::new(2, 1, ColChar::EMPTY);
+/// view.plot(Vec2D::new(1, 0), ColChar::new('@', Modifier::RED), Wrapping::Panic);
+///
+/// let state = WorldState::from_view(7, &view).with_entity(EntityState::new("player", Vec2D::new(1, 0)));
+/// assert_eq!(
+///     state.to_json(),
+///     r#"{"tick":7,"width":2,"height":1,"entities":[{"kind":"player","x":1,"y":0}],"modifiers":[null,31],"grid":[[[32,0],[64,1]]]}"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorldState {
+    /// The tick the snapshot was taken on
+    pub tick: u64,
+    /// The size of the canvas
+    pub size: Vec2D,
+    /// The entities in the game
+    pub entities: Vec<EntityState>,
+    /// Every distinct `Modifier` on the canvas, in the order they first appear. The grid's colour IDs index into this
+    pub modifiers: Vec<Modifier>,
+    /// The canvas as rows of `(character, colour ID)` pairs
+    pub grid: Vec<Vec<(u32, usize)>>,
+}
+
+impl WorldState {
+    /// Create a new `WorldState` from the `View`'s canvas, with no entities
+    #[must_use]
+    pub fn from_view(tick: u64, view: &View) -> Self {
+        let mut modifiers: Vec<Modifier> = vec![];
+        let grid = view
+            .cells()
+            .chunks(view.width.max(1))
+            .map(|row| {
+                row.iter()
+                    .map(|cell| {
+                        let id = modifiers
+                            .iter()
+                            .position(|modifier| *modifier == cell.modifier)
+                            .unwrap_or_else(|| {
+                                modifiers.push(cell.modifier);
+                                modifiers.len() - 1
+                            });
+                        (u32::from(cell.text_char), id)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            tick,
+            size: view.size(),
+            entities: vec![],
+            modifiers,
+            grid,
+        }
+    }
+
+    /// Return the `WorldState` with another entity
+    #[must_use]
+    pub fn with_entity(mut self, entity: EntityState) -> Self {
+        self.entities.push(entity);
+        self
+    }
+
+    /// Return the `WorldState` with the entities added
+    #[must_use]
+    pub fn with_entities(mut self, entities: impl IntoIterator<Item = EntityState>) -> Self {
+        self.entities.extend(entities);
+        self
+    }
+
+    /// Return the `WorldState` as a single line of JSON
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut json = format!(
+            "{{\"tick\":{},\"width\":{},\"height\":{},\"entities\":[",
+            self.tick, self.size.x, self.size.y
+        );
+
+        for (i, entity) in self.entities.iter().enumerate() {
+            let separator = if i == 0 { "" } else { "," };
+            let _ = write!(
+                json,
+                "{separator}{{\"kind\":{},\"x\":{},\"y\":{}}}",
+                json_string(&entity.kind),
+                entity.pos.x,
+                entity.pos.y
+            );
+        }
+
+        json.push_str("],\"modifiers\":[");
+        for (i, modifier) in self.modifiers.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            match modifier {
+                Modifier::None => json.push_str("null"),
+                Modifier::Coded(code) => {
+                    let _ = write!(json, "{code}");
+                }
+                Modifier::Colour(colour) => {
+                    let _ = write!(
+                        json,
+                        "\"#{:02x}{:02x}{:02x}\"",
+                        colour.r, colour.g, colour.b
+                    );
+                }
+                Modifier::Background(colour) => {
+                    let _ = write!(
+                        json,
+                        "{{\"bg\":\"#{:02x}{:02x}{:02x}\"}}",
+                        colour.r, colour.g, colour.b
+                    );
+                }
+                Modifier::Set(set) => {
+                    let mut fields = vec![];
+                    for (key, colour) in [("fg", set.foreground), ("bg", set.background)] {
+                        if let Some(c) = colour {
+                            fields
+                                .push(format!("\"{key}\":\"#{:02x}{:02x}{:02x}\"", c.r, c.g, c.b));
+                        }
+                    }
+                    if !set.style.is_empty() {
+                        let codes: Vec<String> =
+                            set.style.codes().iter().map(u8::to_string).collect();
+                        fields.push(format!("\"style\":[{}]", codes.join(",")));
+                    }
+                    let _ = write!(json, "{{{}}}", fields.join(","));
+                }
+            }
+        }
+
+        json.push_str("],\"grid\":[");
+        for (y, row) in self.grid.iter().enumerate() {
+            json.push_str(if y == 0 { "[" } else { ",[" });
+            for (x, (text_char, id)) in row.iter().enumerate() {
+                let separator = if x == 0 { "" } else { "," };
+                let _ = write!(json, "{separator}[{text_char},{id}]");
+            }
+            json.push(']');
+        }
+        json.push_str("]}");
+
+        json
+    }
+
+    /// Write the `WorldState` to the writer as a single line of JSON, followed by a line break. Passing `io::stdout()` lets an agent reading the game's output receive one state per line
+    ///
+    /// # Errors
+    /// Returns an error if the writer fails
+    pub fn write_json_line(&self, mut writer: impl io::Write) -> io::Result<()> {
+        writeln!(writer, "{}", self.to_json())?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::view::{ColChar, Colour, Wrapping};
+
+    #[test]
+    fn modifiers_get_stable_ids() {
+        let mut view = View::new(3, 2, ColChar::EMPTY);
+        let orange = ColChar::SOLID.with_colour(Colour::rgb(255, 128, 0));
+        view.plot(Vec2D::new(0, 0), orange, Wrapping::Panic);
+        view.plot(Vec2D::new(2, 1), orange, Wrapping::Panic);
+
+        let state = WorldState::from_view(0, &view);
+        assert_eq!(state.modifiers, vec![orange.modifier, Modifier::None]);
+        assert_eq!(state.grid[1], vec![(32, 1), (32, 1), (u32::from('█'), 0)]);
+        assert!(state.to_json().contains("\"modifiers\":[\"#ff8000\",null]"));
+    }
+
+    #[test]
+    fn writes_json_lines() {
+        let view = View::new(1, 1, ColChar::EMPTY);
+        let state =
+            WorldState::from_view(1, &view).with_entity(EntityState::new("boss\n", Vec2D::ZERO));
+
+        let mut output = vec![];
+        state
+            .write_json_line(&mut output)
+            .expect("written to a Vec");
+        let output = String::from_utf8(output).expect("valid UTF-8");
+        assert!(output.contains(r#"{"kind":"boss\n","x":0,"y":0}"#));
+        assert_eq!(output.lines().count(), 1);
+    }
+}
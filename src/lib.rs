@@ -3,15 +3,22 @@
 //! Go to [`elements`] for a quick start guide.
 //!
 //! ## Crate Structure
-//! This library is made up of three main crates:
+//! This library is made up of five main crates:
 //! - [`gameloop`], which handles the gameloop. See the [`gameloop`] documentation to see how to structure the usual Gemini project.
 //! - [`elements`], which handles the printing of various objects to a [`View`](elements::View), the central object in a Gemini project.
 //! - [`elements3d`], which handles everything 3D-related. Objects that [`elements3d`] converts to a 2d object will then be printed to the screen by a [`View`](elements::View)
+//! - [`procgen`], which generates levels such as dungeons, caves and mazes
+//! - [`algorithms`], which holds algorithms over grids such as Dijkstra maps for game AI
 
 #[macro_use]
 mod utils;
 
+mod error;
+pub use error::EngineError;
+
+pub mod algorithms;
 pub mod elements;
 #[cfg(feature = "3D")]
 pub mod elements3d;
 pub mod gameloop;
+pub mod procgen;
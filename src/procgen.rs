@@ -0,0 +1,190 @@
+//! Procedural generation of levels and worlds. Every generator takes a seed, so the same seed always gives the same result
+//! - [`dungeon`] generates rooms and corridors, caves and mazes as [`TileMap`](crate::elements::tilemap::TileMap)s
+//! - [`wfc`] generates maps that look like a small sample with wave function collapse
+//!
+//! [`Rng`] is a small seeded random number generator, and [`RngStreams`] gives each system of a game its own named `Rng` so replays stay deterministic. [`hash_cell()`] and [`random_unit()`] give random values for a position without any state
+
+pub mod dungeon;
+pub mod wfc;
+
+use std::collections::HashMap;
+
+use crate::utils::{fnv1a, FNV1A_OFFSET_BASIS};
+
+/// Return a well-mixed hash of a seed and cell position. The same inputs always give the same output, so this can be used to decide things like where stars go without storing them
+#[must_use]
+pub const fn hash_cell(seed: u64, x: i64, y: i64) -> u64 {
+    let mut hash = seed
+        .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        .wrapping_add((x as u64).wrapping_mul(0xBF58_476D_1CE4_E5B9))
+        .wrapping_add((y as u64).wrapping_mul(0x94D0_49BB_1331_11EB));
+    hash ^= hash >> 31;
+    hash = hash.wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    hash ^= hash >> 29;
+    hash = hash.wrapping_mul(0x94D0_49BB_1331_11EB);
+    hash ^ (hash >> 32)
+}
+
+/// Return a value between 0.0 and 1.0 derived from [`hash_cell`]
+#[must_use]
+pub fn random_unit(seed: u64, x: i64, y: i64) -> f64 {
+    (hash_cell(seed, x, y) >> 11) as f64 / (1_u64 << 53) as f64
+}
+
+/// A small, seeded random number generator. The same seed always gives the same sequence of numbers, which makes generated levels reproducible
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng(u64);
+
+impl Rng {
+    /// Create a new `Rng` from a seed. The seed is hashed first, so that consecutive seeds give unrelated sequences
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self(hash_cell(seed, 0, 0))
+    }
+
+    /// Return a random `u64`
+    pub const fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(1);
+        hash_cell(self.0, 0, 0)
+    }
+
+    /// Return a random value between 0.0 and 1.0
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Return a random value from 0 up to but not including `max`. Returns 0 if `max` is 0
+    pub const fn below(&mut self, max: usize) -> usize {
+        if max == 0 {
+            0
+        } else {
+            (self.next_u64() % max as u64) as usize
+        }
+    }
+
+    /// Return a random value between `min` and `max`, inclusive. Returns `min` if `max` is less than `min`
+    pub const fn range(&mut self, min: isize, max: isize) -> isize {
+        if max <= min {
+            return min;
+        }
+        match max.abs_diff(min).checked_add(1) {
+            Some(span) => min.wrapping_add(self.below(span) as isize),
+            // The range covers every `isize`
+            None => self.next_u64() as isize,
+        }
+    }
+
+    /// Return true with the given probability, from 0.0 to 1.0
+    pub fn chance(&mut self, probability: f64) -> bool {
+        self.next_f64() < probability
+    }
+
+    /// Shuffle the slice into a random order
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            slice.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// Named [`Rng`] streams all derived from one seed, such as `streams.rng("loot")` and `streams.rng("ai")`. Each stream's sequence depends only on the seed and its name, so adding randomness to one system doesn't change the numbers any other system gets, and a game replayed with the same seed and inputs plays out the same way
+/// ```
+/// use gemini_engine::procgen::RngStreams;
+///
+/// let mut streams = RngStreams::new(42);
+/// let loot = streams.rng("loot").next_u64();
+///
+/// // A new system using randomness doesn't affect the loot stream
+/// let mut streams = RngStreams::new(42);
+/// streams.rng("particles").next_u64();
+/// assert_eq!(streams.rng("loot").next_u64(), loot);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RngStreams {
+    seed: u64,
+    streams: HashMap<String, Rng>,
+}
+
+impl RngStreams {
+    /// Create a new `RngStreams` from the seed every stream is derived from
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            streams: HashMap::new(),
+        }
+    }
+
+    /// Return the seed every stream is derived from, to be saved alongside a replay
+    #[must_use]
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Return the stream with the given name, starting it if it hasn't been used yet
+    pub fn rng(&mut self, name: &str) -> &mut Rng {
+        let seed = self.stream_seed(name);
+        self.streams
+            .entry(name.to_string())
+            .or_insert_with(|| Rng::new(seed))
+    }
+
+    /// Return the seed of the stream with the given name. The same seed and name always give the same stream seed, on every platform
+    #[must_use]
+    pub const fn stream_seed(&self, name: &str) -> u64 {
+        let hash = fnv1a(FNV1A_OFFSET_BASIS, name.as_bytes());
+        hash_cell(self.seed, hash as i64, 0)
+    }
+
+    /// Restart every stream from the beginning, such as when starting a replay
+    pub fn reset(&mut self) {
+        self.streams.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_reproducible_and_in_range() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        assert_eq!(a.next_u64(), b.next_u64());
+
+        for _ in 0..100 {
+            assert!((3..=5).contains(&a.range(3, 5)));
+            assert!(a.below(4) < 4);
+        }
+        assert_eq!(a.below(0), 0);
+
+        assert_ne!(Rng::new(0).next_u64(), Rng::new(1).next_u64());
+        let mut second = Rng::new(0);
+        second.next_u64();
+        assert_ne!(second.next_u64(), Rng::new(1).next_u64());
+
+        // Ranges wider than `isize::MAX` don't overflow
+        a.range(isize::MIN, isize::MAX);
+        assert!(a.range(isize::MIN, isize::MAX - 1) < isize::MAX);
+        assert!(a.range(-1, isize::MAX) >= -1);
+    }
+
+    #[test]
+    fn streams_are_independent() {
+        let mut a = RngStreams::new(1);
+        let mut b = RngStreams::new(1);
+        b.rng("ai").next_u64();
+        b.rng("ai").next_u64();
+
+        assert_eq!(a.rng("loot").next_u64(), b.rng("loot").next_u64());
+        assert_ne!(a.stream_seed("loot"), a.stream_seed("ai"));
+        assert_ne!(
+            a.stream_seed("loot"),
+            RngStreams::new(2).stream_seed("loot")
+        );
+
+        let first = a.rng("ai").next_u64();
+        a.reset();
+        assert_eq!(a.rng("ai").next_u64(), first);
+    }
+}
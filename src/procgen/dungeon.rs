@@ -0,0 +1,458 @@
+//! Dungeon, cave and maze generation for roguelikes. Every generator returns a [`Dungeon`]: a [`TileMap`] of walls and floors with the rooms that were placed and a spawn and exit point
+//! ```
+//! use gemini_engine::{
+//!     elements::Vec2D,
+//!     procgen::dungeon::{Dungeon, Tile},
+//! };
+//!
+//! let dungeon = Dungeon::bsp(Vec2D::new(60, 30), 4, 42);
+//!
+//! assert_eq!(dungeon.map.get(dungeon.spawn), Some(&Tile::Floor));
+//! assert_eq!(dungeon.map.get(dungeon.exit), Some(&Tile::Floor));
+//! ```
+
+use std::collections::VecDeque;
+
+use super::Rng;
+use crate::elements::{
+    tilemap::{TileMap, ORTHOGONAL},
+    view::ColChar,
+    Vec2D,
+};
+
+/// A single tile of a [`Dungeon`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Tile {
+    /// Solid rock that can't be walked through
+    #[default]
+    Wall,
+    /// Open ground
+    Floor,
+}
+
+impl Tile {
+    /// Returns true if the tile can be walked on
+    #[must_use]
+    pub const fn is_floor(self) -> bool {
+        matches!(self, Self::Floor)
+    }
+}
+
+impl From<Tile> for ColChar {
+    fn from(value: Tile) -> Self {
+        match value {
+            Tile::Wall => Self::SOLID,
+            Tile::Floor => Self::EMPTY,
+        }
+    }
+}
+
+/// A rectangular room in a [`Dungeon`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Room {
+    /// The position of the room's top left tile
+    pub pos: Vec2D,
+    /// The width and height of the room
+    pub size: Vec2D,
+}
+
+impl Room {
+    /// Create a new `Room`
+    #[must_use]
+    pub const fn new(pos: Vec2D, size: Vec2D) -> Self {
+        Self { pos, size }
+    }
+
+    /// Return the tile at the centre of the room
+    #[must_use]
+    pub const fn centre(&self) -> Vec2D {
+        Vec2D::new(self.pos.x + self.size.x / 2, self.pos.y + self.size.y / 2)
+    }
+
+    /// Returns true if the position is inside the room
+    #[must_use]
+    pub const fn contains(&self, pos: Vec2D) -> bool {
+        pos.x >= self.pos.x
+            && pos.y >= self.pos.y
+            && pos.x < self.pos.x + self.size.x
+            && pos.y < self.pos.y + self.size.y
+    }
+}
+
+/// The algorithm used by [`Dungeon::maze()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MazeAlgorithm {
+    /// A depth-first search that carves long, winding corridors with few dead ends
+    #[default]
+    RecursiveBacktracker,
+    /// Randomised Prim's algorithm, which grows outwards from the start and makes many short dead ends
+    Prim,
+}
+
+/// A generated level: a map of walls and floors, the rooms placed in it and where the player should start and finish. Every floor tile can be reached from the spawn, and the exit is placed as far from the spawn as possible
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dungeon {
+    /// The map of walls and floors. The edges of the map are always walls
+    pub map: TileMap<Tile>,
+    /// The rooms placed by [`Dungeon::bsp()`]. Caves and mazes have no rooms
+    pub rooms: Vec<Room>,
+    /// Where the player should start
+    pub spawn: Vec2D,
+    /// Where the level's exit should go, the walkable tile furthest from the spawn
+    pub exit: Vec2D,
+}
+
+impl Dungeon {
+    /// Generate rooms connected by corridors by binary space partitioning: the map is split in two again and again until the pieces are too small to split, a room is placed in every piece, and the rooms of each pair of sibling pieces are joined by a corridor. `min_room_size` is the smallest width and height of a room
+    #[must_use]
+    pub fn bsp(size: Vec2D, min_room_size: isize, seed: u64) -> Self {
+        let mut map = walls(size);
+        let mut rooms = vec![];
+        let mut rng = Rng::new(seed);
+        let min_room_size = min_room_size.max(1);
+
+        split(
+            &mut map,
+            &mut rooms,
+            &mut rng,
+            Room::new(Vec2D::new(1, 1), size - Vec2D::new(2, 2)),
+            min_room_size,
+        );
+
+        let spawn = rooms.first().map_or(Vec2D::new(1, 1), Room::centre);
+        Self::finish(map, rooms, spawn)
+    }
+
+    /// Generate natural looking caves with a cellular automaton: tiles start as walls with a probability of `fill`, then for every iteration each tile becomes a wall if most of its neighbours are walls, or a floor if most aren't. Only the largest cave is kept. A `fill` of 0.45 with 5 iterations works well
+    #[must_use]
+    pub fn caves(size: Vec2D, fill: f64, iterations: usize, seed: u64) -> Self {
+        let size = Vec2D::new(size.x.max(0), size.y.max(0));
+        let mut rng = Rng::new(seed);
+        let mut map = TileMap::from_fn(Vec2D::ZERO, size.x as usize, size.y as usize, |pos| {
+            if is_edge(size, pos) || rng.chance(fill) {
+                Tile::Wall
+            } else {
+                Tile::Floor
+            }
+        });
+
+        for _ in 0..iterations {
+            map = TileMap::from_fn(Vec2D::ZERO, size.x as usize, size.y as usize, |pos| {
+                let walls = (-1..=1)
+                    .flat_map(|y| (-1..=1).map(move |x| Vec2D::new(x, y)))
+                    .filter(|offset| *offset != Vec2D::ZERO)
+                    .filter(|offset| !map.get(pos + *offset).is_some_and(|tile| tile.is_floor()))
+                    .count();
+                let tile = map.get(pos).copied().unwrap_or_default();
+
+                match walls {
+                    _ if is_edge(size, pos) => Tile::Wall,
+                    5.. => Tile::Wall,
+                    0..=3 => Tile::Floor,
+                    _ => tile,
+                }
+            });
+        }
+
+        let mut largest: Vec<Vec2D> = vec![];
+        let mut visited = map.map(|_| false);
+        for pos in map.positions() {
+            if map.get(pos).is_some_and(|tile| tile.is_floor()) && visited.get(pos) == Some(&false)
+            {
+                let region = flood(&map, pos);
+                for tile in &region {
+                    visited.set(*tile, true);
+                }
+                if region.len() > largest.len() {
+                    largest = region;
+                }
+            }
+        }
+
+        let mut cave = walls(size);
+        for pos in &largest {
+            cave.set(*pos, Tile::Floor);
+        }
+
+        let spawn = largest
+            .first()
+            .copied()
+            .unwrap_or_else(|| Vec2D::new(size.x / 2, size.y / 2));
+        cave.set(spawn, Tile::Floor);
+        Self::finish(cave, vec![], spawn)
+    }
+
+    /// Generate a perfect maze, where there is exactly one path between any two floor tiles. Corridors and walls are one tile thick, so odd sizes fill the map exactly
+    #[must_use]
+    pub fn maze(size: Vec2D, algorithm: MazeAlgorithm, seed: u64) -> Self {
+        let mut map = walls(size);
+        let mut rng = Rng::new(seed);
+        let start = Vec2D::new(1, 1);
+        let is_cell =
+            |pos: Vec2D| pos.x > 0 && pos.y > 0 && pos.x < size.x - 1 && pos.y < size.y - 1;
+        // The cells two tiles away in each direction, with the wall between
+        let steps = |pos: Vec2D| ORTHOGONAL.map(|direction| (pos + direction * 2, pos + direction));
+
+        if !is_cell(start) {
+            return Self::finish(map, vec![], start);
+        }
+        map.set(start, Tile::Floor);
+
+        match algorithm {
+            MazeAlgorithm::RecursiveBacktracker => {
+                let mut stack = vec![start];
+                while let Some(&pos) = stack.last() {
+                    let unvisited: Vec<(Vec2D, Vec2D)> = steps(pos)
+                        .into_iter()
+                        .filter(|(cell, _)| is_cell(*cell) && map.get(*cell) == Some(&Tile::Wall))
+                        .collect();
+
+                    if unvisited.is_empty() {
+                        stack.pop();
+                    } else {
+                        let (cell, between) = unvisited[rng.below(unvisited.len())];
+                        map.set(between, Tile::Floor);
+                        map.set(cell, Tile::Floor);
+                        stack.push(cell);
+                    }
+                }
+            }
+            MazeAlgorithm::Prim => {
+                let mut frontier: Vec<Vec2D> = vec![];
+                let add_frontier = |map: &TileMap<Tile>, frontier: &mut Vec<Vec2D>, pos| {
+                    for (cell, _) in steps(pos) {
+                        if is_cell(cell)
+                            && map.get(cell) == Some(&Tile::Wall)
+                            && !frontier.contains(&cell)
+                        {
+                            frontier.push(cell);
+                        }
+                    }
+                };
+                add_frontier(&map, &mut frontier, start);
+
+                while !frontier.is_empty() {
+                    let cell = frontier.swap_remove(rng.below(frontier.len()));
+                    let visited: Vec<Vec2D> = steps(cell)
+                        .into_iter()
+                        .filter(|(neighbour, _)| map.get(*neighbour) == Some(&Tile::Floor))
+                        .map(|(_, between)| between)
+                        .collect();
+
+                    map.set(visited[rng.below(visited.len())], Tile::Floor);
+                    map.set(cell, Tile::Floor);
+                    add_frontier(&map, &mut frontier, cell);
+                }
+            }
+        }
+
+        Self::finish(map, vec![], start)
+    }
+
+    /// Build the `Dungeon`, placing the exit on the floor tile furthest from the spawn
+    fn finish(map: TileMap<Tile>, rooms: Vec<Room>, spawn: Vec2D) -> Self {
+        let exit = flood(&map, spawn).last().copied().unwrap_or(spawn);
+
+        Self {
+            map,
+            rooms,
+            spawn,
+            exit,
+        }
+    }
+}
+
+/// Return a map of the size filled with walls
+fn walls(size: Vec2D) -> TileMap<Tile> {
+    TileMap::new(
+        Vec2D::ZERO,
+        size.x.max(0) as usize,
+        size.y.max(0) as usize,
+        Tile::Wall,
+    )
+}
+
+/// Returns true if the position is on the edge of a map of the size
+const fn is_edge(size: Vec2D, pos: Vec2D) -> bool {
+    pos.x == 0 || pos.y == 0 || pos.x == size.x - 1 || pos.y == size.y - 1
+}
+
+/// Return every floor tile reachable from the start, in order of distance from the start. Returns nothing if the start isn't a floor tile
+fn flood(map: &TileMap<Tile>, start: Vec2D) -> Vec<Vec2D> {
+    if !map.get(start).is_some_and(|tile| tile.is_floor()) {
+        return vec![];
+    }
+
+    let mut seen = map.map(|_| false);
+    seen.set(start, true);
+    let mut queue = VecDeque::from([start]);
+    let mut order = vec![];
+
+    while let Some(pos) = queue.pop_front() {
+        order.push(pos);
+        for neighbour in map.neighbours(pos) {
+            if map.get(neighbour) == Some(&Tile::Floor) && seen.get(neighbour) == Some(&false) {
+                seen.set(neighbour, true);
+                queue.push_back(neighbour);
+            }
+        }
+    }
+
+    order
+}
+
+/// Recursively split the area, placing a room in each piece that can't be split any further and joining sibling pieces with corridors. Returns the centre of a room in the area
+fn split(
+    map: &mut TileMap<Tile>,
+    rooms: &mut Vec<Room>,
+    rng: &mut Rng,
+    area: Room,
+    min_room_size: isize,
+) -> Vec2D {
+    // Each piece needs space for a room and a wall on its far side
+    let min_piece = min_room_size + 1;
+    let can_split_x = area.size.x >= min_piece * 2;
+    let can_split_y = area.size.y >= min_piece * 2;
+
+    let split_x = match (can_split_x, can_split_y) {
+        (true, true) => area.size.x >= area.size.y,
+        (true, false) => true,
+        (false, true) => false,
+        (false, false) => {
+            // Rooms keep a wall on their right and bottom, so neighbouring pieces don't merge
+            let size = Vec2D::new(
+                rng.range(min_room_size, area.size.x - 1).max(1),
+                rng.range(min_room_size, area.size.y - 1).max(1),
+            );
+            let pos = area.pos
+                + Vec2D::new(
+                    rng.range(0, area.size.x - 1 - size.x).max(0),
+                    rng.range(0, area.size.y - 1 - size.y).max(0),
+                );
+            let room = Room::new(pos, size);
+            carve_room(map, room);
+            rooms.push(room);
+            return room.centre();
+        }
+    };
+
+    let (first, second) = if split_x {
+        let at = rng.range(min_piece, area.size.x - min_piece);
+        (
+            Room::new(area.pos, Vec2D::new(at, area.size.y)),
+            Room::new(
+                area.pos + Vec2D::new(at, 0),
+                Vec2D::new(area.size.x - at, area.size.y),
+            ),
+        )
+    } else {
+        let at = rng.range(min_piece, area.size.y - min_piece);
+        (
+            Room::new(area.pos, Vec2D::new(area.size.x, at)),
+            Room::new(
+                area.pos + Vec2D::new(0, at),
+                Vec2D::new(area.size.x, area.size.y - at),
+            ),
+        )
+    };
+
+    let a = split(map, rooms, rng, first, min_room_size);
+    let b = split(map, rooms, rng, second, min_room_size);
+    carve_corridor(map, a, b, rng.chance(0.5));
+
+    a
+}
+
+/// Set every tile in the room to floor
+fn carve_room(map: &mut TileMap<Tile>, room: Room) {
+    for y in room.pos.y..room.pos.y + room.size.y {
+        for x in room.pos.x..room.pos.x + room.size.x {
+            map.set(Vec2D::new(x, y), Tile::Floor);
+        }
+    }
+}
+
+/// Carve an L-shaped corridor between two points, going horizontally first if `horizontal_first` is true
+fn carve_corridor(map: &mut TileMap<Tile>, from: Vec2D, to: Vec2D, horizontal_first: bool) {
+    let corner = if horizontal_first {
+        Vec2D::new(to.x, from.y)
+    } else {
+        Vec2D::new(from.x, to.y)
+    };
+
+    for (start, end) in [(from, corner), (corner, to)] {
+        for y in start.y.min(end.y)..=start.y.max(end.y) {
+            for x in start.x.min(end.x)..=start.x.max(end.x) {
+                map.set(Vec2D::new(x, y), Tile::Floor);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Assert that the edges are walls, and that every floor tile is reachable from the spawn
+    fn assert_connected(dungeon: &Dungeon) {
+        let size = dungeon.map.size();
+        let floors: Vec<Vec2D> = dungeon
+            .map
+            .iter()
+            .filter(|(_, tile)| tile.is_floor())
+            .map(|(pos, _)| pos)
+            .collect();
+        for pos in &floors {
+            assert!(!is_edge(size, *pos), "floor on the edge at {pos}");
+        }
+        assert_eq!(flood(&dungeon.map, dungeon.spawn).len(), floors.len());
+    }
+
+    #[test]
+    fn bsp_rooms_are_connected() {
+        let dungeon = Dungeon::bsp(Vec2D::new(50, 30), 4, 1);
+
+        assert!(dungeon.rooms.len() > 4);
+        assert!(dungeon.rooms.iter().all(|room| room.size.x >= 4));
+        assert_ne!(dungeon.spawn, dungeon.exit);
+        assert_connected(&dungeon);
+        assert_eq!(dungeon, Dungeon::bsp(Vec2D::new(50, 30), 4, 1));
+    }
+
+    #[test]
+    fn caves_keep_the_largest_region() {
+        let dungeon = Dungeon::caves(Vec2D::new(60, 30), 0.45, 5, 3);
+
+        assert!(
+            dungeon
+                .map
+                .tiles()
+                .iter()
+                .filter(|tile| tile.is_floor())
+                .count()
+                > 100
+        );
+        assert_connected(&dungeon);
+
+        let empty = Dungeon::caves(Vec2D::new(-5, 10), 0.45, 5, 3);
+        assert!(empty.map.tiles().is_empty());
+    }
+
+    #[test]
+    fn mazes_fill_every_cell() {
+        for algorithm in [MazeAlgorithm::RecursiveBacktracker, MazeAlgorithm::Prim] {
+            let dungeon = Dungeon::maze(Vec2D::new(21, 11), algorithm, 9);
+
+            // Every odd position is a cell of the maze
+            for y in (1..10).step_by(2) {
+                for x in (1..20).step_by(2) {
+                    assert_eq!(dungeon.map.get(Vec2D::new(x, y)), Some(&Tile::Floor));
+                }
+            }
+            // A perfect maze is a tree, so it has one less passage than it has cells
+            let floors = dungeon.map.tiles().iter().filter(|tile| tile.is_floor());
+            assert_eq!(floors.count(), 50 + 49);
+            assert_connected(&dungeon);
+        }
+    }
+}
@@ -0,0 +1,293 @@
+//! Wave function collapse, which generates maps of any size that look locally like a small sample. Read the [`WaveFunctionCollapse`] documentation for more info
+
+use super::Rng;
+use crate::{
+    elements::{
+        tilemap::{TileMap, ORTHOGONAL},
+        Vec2D,
+    },
+    EngineError,
+};
+
+/// How many times [`WaveFunctionCollapse::generate()`] starts over after running into a contradiction before giving up
+const MAX_ATTEMPTS: u64 = 20;
+
+/// `WaveFunctionCollapse` learns which tiles can be next to each other, and how common each tile is, from a sample [`TileMap`] (or a [`Sprite`](crate::elements::Sprite) converted to one), then generates larger maps where every pair of neighbouring tiles also appears in the sample. Generation can be constrained with a fixed border and pre-placed tiles, such as an entrance that must be at a certain position
+/// ```
+/// use gemini_engine::{
+///     elements::{tilemap::TileMap, view::{ColChar, Modifier}, Sprite, Vec2D},
+///     procgen::wfc::WaveFunctionCollapse,
+/// };
+///
+/// let sample = TileMap::from(&Sprite::new(Vec2D::ZERO, "#####\n#...#\n#.#.#\n#...#\n#####", Modifier::None));
+/// let wall = ColChar::new('#', Modifier::None);
+/// let floor = ColChar::new('.', Modifier::None);
+///
+/// let map = WaveFunctionCollapse::learn(&sample)
+///     .with_border(wall)
+///     .with_fixed(Vec2D::new(1, 1), floor)
+///     .generate(20, 10, 42)
+///     .expect("the sample tiles fit together");
+///
+/// assert_eq!(map.get(Vec2D::new(0, 5)), Some(&wall));
+/// assert_eq!(map.get(Vec2D::new(1, 1)), Some(&floor));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaveFunctionCollapse<T> {
+    tiles: Vec<T>,
+    weights: Vec<usize>,
+    /// `allowed[a][direction][b]` is true if tile `b` was seen next to tile `a` in the direction, in the same order as [`ORTHOGONAL`]
+    allowed: Vec<[Vec<bool>; 4]>,
+    border: Option<T>,
+    fixed: Vec<(Vec2D, T)>,
+}
+
+impl<T: Clone + PartialEq> WaveFunctionCollapse<T> {
+    /// Learn the tiles, how often they appear and which tiles can be next to each other from the sample
+    #[must_use]
+    pub fn learn(sample: &TileMap<T>) -> Self {
+        let mut tiles: Vec<T> = vec![];
+        let mut weights: Vec<usize> = vec![];
+        let ids = sample.map(|tile| {
+            let id = tiles.iter().position(|t| t == tile).unwrap_or_else(|| {
+                tiles.push(tile.clone());
+                weights.push(0);
+                tiles.len() - 1
+            });
+            weights[id] += 1;
+            id
+        });
+
+        let mut allowed = vec![[(); 4].map(|()| vec![false; tiles.len()]); tiles.len()];
+        for (pos, id) in ids.iter() {
+            for (direction, offset) in ORTHOGONAL.into_iter().enumerate() {
+                if let Some(neighbour) = ids.get(pos + offset) {
+                    allowed[*id][direction][*neighbour] = true;
+                }
+            }
+        }
+
+        Self {
+            tiles,
+            weights,
+            allowed,
+            border: None,
+            fixed: vec![],
+        }
+    }
+
+    /// Return the `WaveFunctionCollapse` with every tile on the edge of generated maps fixed to `tile`
+    #[must_use]
+    pub fn with_border(mut self, tile: T) -> Self {
+        self.border = Some(tile);
+        self
+    }
+
+    /// Return the `WaveFunctionCollapse` with the tile at the position fixed to `tile` in generated maps. Fixed tiles take priority over the border
+    #[must_use]
+    pub fn with_fixed(mut self, pos: Vec2D, tile: T) -> Self {
+        self.fixed.push((pos, tile));
+        self
+    }
+
+    /// Return every distinct tile learned from the sample
+    #[must_use]
+    pub fn tiles(&self) -> &[T] {
+        &self.tiles
+    }
+
+    /// Generate a new map of the given size. The same seed always generates the same map
+    ///
+    /// # Errors
+    /// Returns [`EngineError::InvalidData`] if the sample has no tiles or a border or fixed tile doesn't appear in the sample, or [`EngineError::Unsatisfiable`] if no map could be generated that satisfies every constraint
+    pub fn generate(
+        &self,
+        width: usize,
+        height: usize,
+        seed: u64,
+    ) -> Result<TileMap<T>, EngineError> {
+        if self.tiles.is_empty() {
+            return Err(EngineError::InvalidData(String::from(
+                "The sample has no tiles to generate from",
+            )));
+        }
+        let find = |tile: &T| {
+            self.tiles.iter().position(|t| t == tile).ok_or_else(|| {
+                EngineError::InvalidData(String::from(
+                    "Constrained tile doesn't appear in the sample",
+                ))
+            })
+        };
+        let border = self.border.as_ref().map(find).transpose()?;
+        let fixed = self
+            .fixed
+            .iter()
+            .map(|(pos, tile)| find(tile).map(|id| (*pos, id)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for attempt in 0..MAX_ATTEMPTS {
+            let mut rng = Rng::new(seed.wrapping_add(attempt.wrapping_mul(0x9E37_79B9)));
+            let mut wave = TileMap::new(Vec2D::ZERO, width, height, vec![true; self.tiles.len()]);
+
+            let size = wave.size();
+            let mut constraints: Vec<(Vec2D, usize)> = vec![];
+            if let Some(border) = border {
+                constraints.extend(
+                    wave.positions()
+                        .filter(|pos| {
+                            (pos.x == 0 || pos.y == 0 || pos.x == size.x - 1 || pos.y == size.y - 1)
+                                && !fixed.iter().any(|(fixed_pos, _)| fixed_pos == pos)
+                        })
+                        .map(|pos| (pos, border)),
+                );
+            }
+            constraints.extend(fixed.iter().filter(|(pos, _)| wave.contains(*pos)));
+
+            if constraints
+                .into_iter()
+                .all(|(pos, id)| self.collapse(&mut wave, pos, id))
+                && self.run(&mut wave, &mut rng)
+            {
+                return Ok(wave.map(|options| {
+                    let id = options.iter().position(|o| *o).unwrap_or_default();
+                    self.tiles[id].clone()
+                }));
+            }
+        }
+
+        Err(EngineError::Unsatisfiable(format!(
+            "Couldn't generate a map without contradictions in {MAX_ATTEMPTS} attempts"
+        )))
+    }
+
+    /// Collapse cells until every cell has one option left, always picking the cell with the fewest options. Returns false on a contradiction
+    fn run(&self, wave: &mut TileMap<Vec<bool>>, rng: &mut Rng) -> bool {
+        loop {
+            let mut fewest = usize::MAX;
+            let mut candidates = vec![];
+            for (pos, options) in wave.iter() {
+                let count = options.iter().filter(|o| **o).count();
+                if count > 1 {
+                    if count < fewest {
+                        fewest = count;
+                        candidates.clear();
+                    }
+                    if count == fewest {
+                        candidates.push(pos);
+                    }
+                }
+            }
+
+            let Some(&pos) = candidates.get(rng.below(candidates.len())) else {
+                return true;
+            };
+
+            let options = wave.get(pos).cloned().unwrap_or_default();
+            let total: usize = (0..self.tiles.len())
+                .filter(|id| options[*id])
+                .map(|id| self.weights[id])
+                .sum();
+            let mut pick = rng.below(total);
+            let id = (0..self.tiles.len())
+                .filter(|id| options[*id])
+                .find(|id| {
+                    let found = pick < self.weights[*id];
+                    pick = pick.saturating_sub(self.weights[*id]);
+                    found
+                })
+                .unwrap_or_default();
+
+            if !self.collapse(wave, pos, id) {
+                return false;
+            }
+        }
+    }
+
+    /// Remove every option but `id` from the cell and propagate the change to its neighbours. Returns false on a contradiction
+    fn collapse(&self, wave: &mut TileMap<Vec<bool>>, pos: Vec2D, id: usize) -> bool {
+        let Some(options) = wave.get_mut(pos) else {
+            return true;
+        };
+        if !options[id] {
+            return false;
+        }
+        options
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, o)| *o = i == id);
+
+        let mut stack = vec![pos];
+        while let Some(pos) = stack.pop() {
+            let options = wave.get(pos).cloned().unwrap_or_default();
+
+            for (direction, offset) in ORTHOGONAL.into_iter().enumerate() {
+                let neighbour = pos + offset;
+                let Some(neighbour_options) = wave.get_mut(neighbour) else {
+                    continue;
+                };
+
+                let mut changed = false;
+                for (b, possible) in neighbour_options.iter_mut().enumerate() {
+                    let supported = options
+                        .iter()
+                        .enumerate()
+                        .any(|(a, o)| *o && self.allowed[a][direction][b]);
+                    if *possible && !supported {
+                        *possible = false;
+                        changed = true;
+                    }
+                }
+
+                if changed {
+                    if !neighbour_options.contains(&true) {
+                        return false;
+                    }
+                    stack.push(neighbour);
+                }
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follows_learned_adjacency() {
+        let checkerboard = TileMap::from_fn(Vec2D::ZERO, 4, 4, |pos| (pos.x + pos.y) % 2);
+        let map = WaveFunctionCollapse::learn(&checkerboard)
+            .with_fixed(Vec2D::ZERO, 1)
+            .generate(9, 7, 1)
+            .expect("checkerboards never contradict");
+
+        for (pos, tile) in map.iter() {
+            assert_eq!(*tile, (pos.x + pos.y + 1) % 2, "at {pos}");
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_and_impossible_constraints() {
+        let sample = TileMap::from_fn(Vec2D::ZERO, 2, 2, |pos| pos.x);
+        let wfc = WaveFunctionCollapse::learn(&sample);
+
+        assert_eq!(wfc.tiles(), &[0, 1]);
+        assert!(wfc.clone().with_border(5).generate(4, 4, 0).is_err());
+        // 0 is never seen to the right of 1
+        assert!(wfc
+            .with_fixed(Vec2D::ZERO, 1)
+            .with_fixed(Vec2D::new(1, 0), 0)
+            .generate(4, 4, 0)
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_empty_samples() {
+        let sample = TileMap::from_fn(Vec2D::ZERO, 0, 0, |_| 0);
+        assert!(WaveFunctionCollapse::learn(&sample)
+            .generate(4, 4, 0)
+            .is_err());
+    }
+}
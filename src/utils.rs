@@ -1,5 +1,13 @@
 use crate::elements::Vec2D;
-use std::{fmt, sync::OnceLock};
+use std::{
+    env,
+    fmt::{self, Write},
+    io,
+    sync::{
+        atomic::{AtomicU64, AtomicU8, Ordering},
+        OnceLock,
+    },
+};
 use terminal_size::terminal_size;
 
 #[macro_use]
@@ -7,6 +15,111 @@ pub mod macros;
 
 static TERMINAL_PREPARED: OnceLock<bool> = OnceLock::new();
 
+/// The default ratio of a terminal cell's height to its width, used until [`set_cell_aspect_ratio()`] is called
+pub const DEFAULT_CELL_ASPECT_RATIO: f64 = 2.2;
+
+static CELL_ASPECT_RATIO: AtomicU64 = AtomicU64::new(DEFAULT_CELL_ASPECT_RATIO.to_bits());
+
+/// Returns how many times taller than it is wide a terminal cell is. Geometry that should look round or rotate without shearing, such as [`Circle`](crate::elements::geometry::Circle), [`Polygon::rotate()`](crate::elements::Polygon::rotate()) and the [`Iris`](crate::elements::transition::TransitionEffect::Iris) transition, uses this to correct for non-square cells
+#[must_use]
+pub fn cell_aspect_ratio() -> f64 {
+    f64::from_bits(CELL_ASPECT_RATIO.load(Ordering::Relaxed))
+}
+
+/// Set the engine-wide cell aspect ratio returned by [`cell_aspect_ratio()`]. Most terminal fonts are around twice as tall as they are wide. Non-positive or non-finite values are ignored
+pub fn set_cell_aspect_ratio(ratio: f64) {
+    if ratio.is_finite() && ratio > 0.0 {
+        CELL_ASPECT_RATIO.store(ratio.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Start a synchronized update (mode 2026), after which the terminal holds off drawing until the update ends
+pub const BEGIN_SYNCHRONIZED_UPDATE: &str = "\x1b[?2026h";
+/// End a synchronized update (mode 2026), drawing everything written since it began at once
+pub const END_SYNCHRONIZED_UPDATE: &str = "\x1b[?2026l";
+
+/// Whether frames are synchronized: 0 to detect it from the terminal, 1 to always and 2 to never
+static SYNCHRONIZED_OUTPUT: AtomicU8 = AtomicU8::new(0);
+static SYNCHRONIZED_OUTPUT_DETECTED: OnceLock<bool> = OnceLock::new();
+
+/// Returns true if frames written to stdout are wrapped in synchronized update escape sequences, so the terminal never shows a partially written frame. Unless changed with [`set_synchronized_output()`], this is detected from the terminal's environment variables by [`detect_synchronized_output_from()`]
+#[must_use]
+pub fn synchronized_output() -> bool {
+    match SYNCHRONIZED_OUTPUT.load(Ordering::Relaxed) {
+        1 => true,
+        2 => false,
+        _ => *SYNCHRONIZED_OUTPUT_DETECTED.get_or_init(|| {
+            detect_synchronized_output_from(
+                env::var("TERM").ok().as_deref(),
+                env::var("TERM_PROGRAM").ok().as_deref(),
+                env::var_os("WT_SESSION").is_some(),
+            )
+        }),
+    }
+}
+
+/// Turn the engine-wide synchronized output returned by [`synchronized_output()`] on or off, or pass `None` to go back to detecting it from the terminal
+pub fn set_synchronized_output(enabled: Option<bool>) {
+    let value = match enabled {
+        None => 0,
+        Some(true) => 1,
+        Some(false) => 2,
+    };
+    SYNCHRONIZED_OUTPUT.store(value, Ordering::Relaxed);
+}
+
+/// Work out whether the terminal supports synchronized updates from the values of the `TERM` and `TERM_PROGRAM` environment variables and whether `WT_SESSION` is set, which Windows Terminal sets. Turn it on with [`set_synchronized_output()`] for terminals that aren't detected
+#[must_use]
+pub fn detect_synchronized_output_from(
+    term: Option<&str>,
+    term_program: Option<&str>,
+    windows_terminal: bool,
+) -> bool {
+    windows_terminal
+        || is_terminal(
+            term,
+            term_program,
+            &[
+                "xterm-kitty",
+                "xterm-ghostty",
+                "alacritty",
+                "foot",
+                "contour",
+                "wezterm",
+            ],
+            &["WezTerm", "ghostty", "iTerm.app", "mintty"],
+        )
+}
+
+/// Returns true if `TERM` starts with one of the `terms` or `TERM_PROGRAM` is one of the `programs`, for detecting terminal features from the environment. Terminals that only advertise a feature when queried can't be detected this way
+#[must_use]
+pub fn is_terminal(
+    term: Option<&str>,
+    term_program: Option<&str>,
+    terms: &[&str],
+    programs: &[&str],
+) -> bool {
+    term.is_some_and(|term| terms.iter().any(|name| term.starts_with(name)))
+        || term_program.is_some_and(|program| programs.contains(&program))
+}
+
+/// Write a frame with `write_frame` and flush the writer, wrapping the frame in synchronized update escape sequences if [`synchronized_output()`] is on. The update is ended even if writing the frame fails, so the terminal doesn't stop drawing, and the first error is returned
+pub fn write_synchronized<W: io::Write>(
+    out: &mut W,
+    write_frame: impl FnOnce(&mut W) -> io::Result<()>,
+) -> io::Result<()> {
+    if synchronized_output() {
+        out.write_all(BEGIN_SYNCHRONIZED_UPDATE.as_bytes())?;
+        let written = write_frame(out);
+        let ended = out.write_all(END_SYNCHRONIZED_UPDATE.as_bytes());
+        written.and(ended)?;
+    } else {
+        write_frame(out)?;
+    }
+
+    out.flush()
+}
+
 /// Returns the size of the terminal as a `Vec2D`, using [`terminal_size::terminal_size()`]
 ///
 /// # Panics
@@ -52,3 +165,131 @@ pub fn prepare_terminal(f: &mut fmt::Formatter<'_>) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Returns true if the character takes up no space when printed, such as a zero-width space or joiner
+pub const fn is_zero_width(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}' | '\u{200B}'..='\u{200F}' | '\u{2060}'..='\u{2064}' | '\u{FEFF}'
+    )
+}
+
+/// Returns true if the character takes up two cells when printed, such as most CJK characters and emoji
+pub const fn is_wide(c: char) -> bool {
+    matches!(
+        c,
+        '\u{1100}'..='\u{115F}'
+            | '\u{2E80}'..='\u{303E}'
+            | '\u{3041}'..='\u{33FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{A000}'..='\u{A4CF}'
+            | '\u{AC00}'..='\u{D7A3}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{FE30}'..='\u{FE4F}'
+            | '\u{FF00}'..='\u{FF60}'
+            | '\u{FFE0}'..='\u{FFE6}'
+            | '\u{1F300}'..='\u{1F64F}'
+            | '\u{1F680}'..='\u{1F6FF}'
+            | '\u{1F900}'..='\u{1F9FF}'
+            | '\u{1FA70}'..='\u{1FAFF}'
+            | '\u{20000}'..='\u{2FFFD}'
+            | '\u{30000}'..='\u{3FFFD}'
+    )
+}
+
+/// The starting hash for [`fnv1a()`]
+pub const FNV1A_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Continue an FNV-1a hash with the bytes, starting from [`FNV1A_OFFSET_BASIS`]. Unlike std's hashers, it's guaranteed to give the same hash on every platform and release
+pub const fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+        i += 1;
+    }
+    hash
+}
+
+/// Return the string as a quoted JSON string
+pub fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if u32::from(c) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", u32::from(c));
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// The alphabet used to base64 encode data for escape sequences such as OSC 52
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode the bytes as padded base64
+pub fn encode_base64(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, byte)| {
+            group | u32::from(*byte) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                output.push(BASE64[(group >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+
+    output
+}
+
+/// Decode padded or unpadded base64, returning `None` if it contains other characters
+pub fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = vec![];
+    let mut group = 0u32;
+    let mut bits = 0;
+    for c in text.trim_end_matches('=').bytes() {
+        let value = BASE64.iter().position(|b| *b == c)? as u32;
+        group = group << 6 | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((group >> bits) as u8);
+        }
+    }
+
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnv1a_matches_reference_hashes() {
+        assert_eq!(fnv1a(FNV1A_OFFSET_BASIS, b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(FNV1A_OFFSET_BASIS, b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(
+            fnv1a(fnv1a(FNV1A_OFFSET_BASIS, b"foo"), b"bar"),
+            fnv1a(FNV1A_OFFSET_BASIS, b"foobar")
+        );
+    }
+
+    #[test]
+    fn json_strings_are_escaped() {
+        assert_eq!(json_string("boss"), r#""boss""#);
+        assert_eq!(json_string("\"a\\b\"\n\t\x1b"), r#""\"a\\b\"\n\t\u001b""#);
+    }
+}
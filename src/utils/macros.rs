@@ -1,4 +1,4 @@
-//! Macros for near-identical implementations in `Vec2D` and `Vec3D`
+//! Macros for near-identical implementations in `Vec2D` and `Vec3D`, and for instrumenting the engine with `tracing`
 
 /// Ignore all parameters after the first and just return the first
 macro_rules! ignore_rest {
@@ -232,3 +232,17 @@ macro_rules! impl_vec_rem {
         }
     };
 }
+
+/// Enter a trace level `tracing` span for the rest of the enclosing block if the `tracing` feature is enabled, and do nothing otherwise. Takes the same arguments as [`tracing::trace_span!`]
+#[cfg(feature = "tracing")]
+macro_rules! engine_span {
+    ($($arg:tt)+) => {
+        let _span = tracing::trace_span!($($arg)+).entered();
+    };
+}
+
+/// Enter a trace level `tracing` span for the rest of the enclosing block if the `tracing` feature is enabled, and do nothing otherwise
+#[cfg(not(feature = "tracing"))]
+macro_rules! engine_span {
+    ($($arg:tt)+) => {};
+}